@@ -184,6 +184,29 @@ pub struct ZBufReader<'a> {
     cursor: ZBufPos,
 }
 
+impl<'a> ZBufReader<'a> {
+    /// The bytes remaining in the current slice, empty if everything has been read.
+    pub fn chunk(&self) -> &'a [u8] {
+        match self.inner.slices.get(self.cursor.slice) {
+            // SAFETY: validity of self.cursor.byte is ensured by the read logic.
+            Some(slice) => crate::unsafe_slice!(slice.as_slice(), self.cursor.byte..),
+            None => &[],
+        }
+    }
+
+    /// Read `len` bytes without copy if they are contiguous in the current slice,
+    /// otherwise leave the reader untouched and return `None`.
+    pub fn read_borrowed(&mut self, len: usize) -> Option<&'a [u8]> {
+        let chunk = self.chunk();
+        if chunk.len() < len {
+            return None;
+        }
+        self.skip(len).ok()?;
+        // SAFETY: len has been checked against chunk length.
+        Some(crate::unsafe_slice!(chunk, ..len))
+    }
+}
+
 impl<'a> HasReader for &'a ZBuf {
     type Reader = ZBufReader<'a>;
 
@@ -374,9 +397,8 @@ impl<'a> AdvanceableReader for ZBufReader<'a> {
     fn backtrack(&mut self, offset: usize) -> Result<(), DidntRead> {
         let mut remaining_offset = offset;
         while remaining_offset > 0 {
-            let backtrack = remaining_offset.min(self.cursor.byte);
-            remaining_offset -= backtrack;
-            self.cursor.byte -= backtrack;
+            // Only move to the previous slice when there is still something to backtrack, so that
+            // the cursor never stays at the end of a slice
             if self.cursor.byte == 0 {
                 if self.cursor.slice == 0 {
                     break;
@@ -389,6 +411,9 @@ impl<'a> AdvanceableReader for ZBufReader<'a> {
                     .ok_or(DidntRead)?
                     .len();
             }
+            let backtrack = remaining_offset.min(self.cursor.byte);
+            remaining_offset -= backtrack;
+            self.cursor.byte -= backtrack;
         }
         if remaining_offset == 0 {
             Ok(())
@@ -668,4 +693,51 @@ mod tests {
         assert_eq!(reader.seek(std::io::SeekFrom::Start(10)).unwrap(), 10);
         reader.seek(std::io::SeekFrom::Current(-100)).unwrap_err();
     }
+
+    #[test]
+    fn zbuf_read_borrowed() {
+        use super::{HasReader, ZBuf};
+
+        let mut buf = ZBuf::empty();
+        buf.push_zslice([0u8, 1u8, 2u8, 3u8].into());
+        buf.push_zslice([4u8, 5u8, 6u8].into());
+        let mut reader = buf.reader();
+
+        assert_eq!(reader.read_borrowed(3).unwrap(), &[0, 1, 2]);
+        assert_eq!(reader.chunk(), &[3]);
+        // Bytes spanning two slices can't be borrowed
+        assert!(reader.read_borrowed(2).is_none());
+        assert_eq!(reader.read_borrowed(1).unwrap(), &[3]);
+        assert_eq!(reader.chunk(), &[4, 5, 6]);
+        assert_eq!(reader.read_borrowed(3).unwrap(), &[4, 5, 6]);
+        assert!(reader.chunk().is_empty());
+        assert_eq!(reader.read_borrowed(0).unwrap(), &[] as &[u8]);
+        assert!(reader.read_borrowed(1).is_none());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn zbuf_seek_chunk() {
+        use std::io::Seek;
+
+        use super::{HasReader, ZBuf};
+        use crate::reader::Reader;
+
+        let mut buf = ZBuf::empty();
+        buf.push_zslice([1u8, 2u8, 3u8].into());
+        buf.push_zslice([4u8, 5u8].into());
+        let mut reader = buf.reader();
+
+        // Seeking back onto a slice boundary leaves the cursor at the start of the next slice
+        assert_eq!(reader.seek(std::io::SeekFrom::End(0)).unwrap(), 5);
+        assert_eq!(reader.seek(std::io::SeekFrom::Current(-2)).unwrap(), 3);
+        assert_eq!(reader.remaining(), 2);
+        assert_eq!(reader.chunk(), &[4, 5]);
+        assert_eq!(reader.read_borrowed(2).unwrap(), &[4, 5]);
+
+        assert_eq!(reader.seek(std::io::SeekFrom::Current(-3)).unwrap(), 2);
+        assert_eq!(reader.chunk(), &[3]);
+        assert_eq!(reader.read_u8().unwrap(), 3);
+        assert_eq!(reader.chunk(), &[4, 5]);
+    }
 }
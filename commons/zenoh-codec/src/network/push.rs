@@ -28,6 +28,38 @@ use zenoh_protocol::{
 
 use crate::{common::extension, RCodec, WCodec, Zenoh080, Zenoh080Condition, Zenoh080Header};
 
+// Redundancy
+impl<W> WCodec<(&ext::RedundancyType, bool), &mut W> for Zenoh080
+where
+    W: Writer,
+{
+    type Output = Result<(), DidntWrite>;
+
+    fn write(self, writer: &mut W, x: (&ext::RedundancyType, bool)) -> Self::Output {
+        let (x, more) = x;
+
+        let v = (x.sn << 1) | (x.alternate as u64);
+        let ext = ext::Redundancy::new(v);
+        self.write(&mut *writer, (&ext, more))
+    }
+}
+
+impl<R> RCodec<(ext::RedundancyType, bool), &mut R> for Zenoh080Header
+where
+    R: Reader,
+{
+    type Error = DidntRead;
+
+    fn read(self, reader: &mut R) -> Result<(ext::RedundancyType, bool), Self::Error> {
+        let (ext, more): (ext::Redundancy, bool) = self.read(&mut *reader)?;
+        let rd = ext::RedundancyType {
+            sn: ext.value >> 1,
+            alternate: ext.value & 1 != 0,
+        };
+        Ok((rd, more))
+    }
+}
+
 impl<W> WCodec<&Push, &mut W> for Zenoh080
 where
     W: Writer,
@@ -40,6 +72,7 @@ where
             ext_qos,
             ext_tstamp,
             ext_nodeid,
+            ext_redundancy,
             payload,
         } = x;
 
@@ -47,7 +80,8 @@ where
         let mut header = id::PUSH;
         let mut n_exts = ((ext_qos != &ext::QoSType::DEFAULT) as u8)
             + (ext_tstamp.is_some() as u8)
-            + ((ext_nodeid != &ext::NodeIdType::DEFAULT) as u8);
+            + ((ext_nodeid != &ext::NodeIdType::DEFAULT) as u8)
+            + (ext_redundancy.is_some() as u8);
         if n_exts != 0 {
             header |= flag::Z;
         }
@@ -75,6 +109,10 @@ where
             n_exts -= 1;
             self.write(&mut *writer, (*ext_nodeid, n_exts != 0))?;
         }
+        if let Some(rd) = ext_redundancy.as_ref() {
+            n_exts -= 1;
+            self.write(&mut *writer, (rd, n_exts != 0))?;
+        }
 
         // Payload
         self.write(&mut *writer, payload)?;
@@ -120,6 +158,7 @@ where
         let mut ext_qos = ext::QoSType::DEFAULT;
         let mut ext_tstamp = None;
         let mut ext_nodeid = ext::NodeIdType::DEFAULT;
+        let mut ext_redundancy = None;
 
         let mut has_ext = imsg::has_flag(self.header, flag::Z);
         while has_ext {
@@ -141,6 +180,11 @@ where
                     ext_nodeid = nid;
                     has_ext = ext;
                 }
+                ext::Redundancy::ID => {
+                    let (rd, ext): (ext::RedundancyType, bool) = eodec.read(&mut *reader)?;
+                    ext_redundancy = Some(rd);
+                    has_ext = ext;
+                }
                 _ => {
                     has_ext = extension::skip(reader, "Push", ext)?;
                 }
@@ -156,6 +200,7 @@ where
             ext_qos,
             ext_tstamp,
             ext_nodeid,
+            ext_redundancy,
         })
     }
 }
@@ -12,6 +12,7 @@
 //   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
 //
 
+use rand::Rng;
 use serde::{Deserialize, Serialize};
 use zenoh_core::zparse_default;
 use zenoh_protocol::core::{EndPoint, WhatAmI};
@@ -26,6 +27,8 @@ pub struct ConnectionRetryModeDependentConf {
     pub period_max_ms: Option<ModeDependentValue<i64>>,
     // increase factor for the next timeout until next try
     pub period_increase_factor: Option<ModeDependentValue<f64>>,
+    // random variation applied to each timeout until next try, as a fraction of it (0 to 1)
+    pub period_jitter: Option<ModeDependentValue<f64>>,
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
@@ -34,6 +37,7 @@ pub struct ConnectionRetryConf {
     pub period_init_ms: i64,
     pub period_max_ms: i64,
     pub period_increase_factor: f64,
+    pub period_jitter: f64,
 }
 
 impl ConnectionRetryConf {
@@ -57,6 +61,10 @@ impl ConnectionRetryConf {
                 .period_increase_factor
                 .get(whatami)
                 .unwrap_or(default_retry.period_increase_factor.get(whatami).unwrap()),
+            period_jitter: *retry
+                .period_jitter
+                .get(whatami)
+                .unwrap_or(default_retry.period_jitter.get(whatami).unwrap()),
         }
     }
 
@@ -95,7 +103,11 @@ impl ConnectionRetryPeriod {
     }
 
     pub fn next_duration(&mut self) -> std::time::Duration {
-        let res = self.duration();
+        let mut res = self.duration();
+        let jitter = self.conf.period_jitter.clamp(0., 1.);
+        if jitter > 0. && res != std::time::Duration::MAX {
+            res = res.mul_f64(1. + jitter * rand::thread_rng().gen_range(-1.0..=1.0));
+        }
 
         self.delay = (self.delay as f64 * self.conf.period_increase_factor) as i64;
         if self.conf.period_max_ms > 0 && self.delay > self.conf.period_max_ms {
@@ -188,6 +200,9 @@ pub fn get_retry_config(
         if let Some(val) = config.get("retry_period_increase_factor") {
             res.period_increase_factor = zparse_default!(val, res.period_increase_factor);
         }
+        if let Some(val) = config.get("retry_period_jitter") {
+            res.period_jitter = zparse_default!(val, res.period_jitter);
+        }
     }
     res
 }
@@ -55,6 +55,7 @@ pub mod listen {
 
     pub const timeout_ms: ModeDependentValue<i64> = ModeDependentValue::Unique(0);
     pub const exit_on_failure: ModeDependentValue<bool> = ModeDependentValue::Unique(true);
+    pub const interfaces_check_period_ms: u64 = 1000;
 }
 
 #[allow(non_upper_case_globals)]
@@ -128,10 +129,22 @@ pub const queries_default_timeout: u64 = 10000;
 pub mod routing {
     pub mod router {
         pub const peers_failover_brokering: bool = true;
+        pub mod snapshot {
+            pub const period: u64 = 10000;
+            pub const reconciliation_delay: u64 = 10000;
+        }
     }
     pub mod peer {
         pub const mode: &str = "peer_to_peer";
     }
+    pub mod linkstate {
+        pub const rtt_weights: bool = false;
+        pub const rtt_period: u64 = 10000;
+        pub const weight_hysteresis: f64 = 0.1;
+    }
+    pub mod queries {
+        pub const load_balancing: &str = "best_matching";
+    }
 }
 
 impl Default for ListenConfig {
@@ -146,6 +159,7 @@ impl Default for ListenConfig {
             }),
             exit_on_failure: None,
             retry: None,
+            interfaces_check_period_ms: None,
         }
     }
 }
@@ -158,6 +172,8 @@ impl Default for ConnectConfig {
             endpoints: ModeDependentValue::Unique(vec![]),
             exit_on_failure: None,
             retry: None,
+            failover: None,
+            disconnected_buffer_size: None,
         }
     }
 }
@@ -309,6 +325,7 @@ impl Default for ConnectionRetryModeDependentConf {
             period_init_ms: Some(ModeDependentValue::Unique(1000)),
             period_max_ms: Some(ModeDependentValue::Unique(4000)),
             period_increase_factor: Some(ModeDependentValue::Unique(2.)),
+            period_jitter: Some(ModeDependentValue::Unique(0.)),
         }
     }
 }
@@ -26,7 +26,14 @@ pub mod wrappers;
 #[allow(unused_imports)]
 use std::convert::TryFrom; // This is a false positive from the rust analyser
 use std::{
-    any::Any, collections::HashSet, fmt, io::Read, net::SocketAddr, ops, path::Path, sync::Weak,
+    any::Any,
+    collections::HashSet,
+    fmt,
+    io::Read,
+    net::{IpAddr, SocketAddr},
+    ops,
+    path::Path,
+    sync::Weak,
 };
 
 use include::recursive_include;
@@ -75,13 +82,26 @@ impl Zeroize for SecretString {
 
 pub type SecretValue = Secret<SecretString>;
 
-#[derive(Debug, Deserialize, Serialize, Clone, Copy)]
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq)]
 #[serde(rename_all = "lowercase")]
 pub enum InterceptorFlow {
     Egress,
     Ingress,
 }
 
+/// The order in which a client tries its connect endpoints when (re)connecting.
+#[derive(Debug, Default, Deserialize, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ConnectFailover {
+    /// Always start from the first endpoint, so that the client gets back to it when possible.
+    #[default]
+    Ordered,
+    /// Start from the endpoint following the last one the client was connected through.
+    RoundRobin,
+    /// Try the endpoints in a random order.
+    Random,
+}
+
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct DownsamplingRuleConf {
     /// A list of key-expressions to which the downsampling will be applied.
@@ -102,6 +122,44 @@ pub struct DownsamplingItemConf {
     pub flow: InterceptorFlow,
 }
 
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct RemappingRuleConf {
+    /// The key expression prefix as known on this side of the router. Must not contain wildcards.
+    pub local: OwnedKeyExpr,
+    /// The key expression prefix it is remapped to on the remote side. Must not contain wildcards.
+    pub remote: OwnedKeyExpr,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct RemappingItemConf {
+    /// A list of interfaces on which the key expressions will be remapped
+    /// Key expressions will be remapped on all interfaces if the parameter is None
+    pub interfaces: Option<Vec<String>>,
+    /// A list of remote zids for which the key expressions will be remapped
+    /// Key expressions will be remapped for all remotes if the parameter is None
+    pub zids: Option<Vec<ZenohId>>,
+    /// A list of remapping rules, the first matching rule applies.
+    pub rules: Vec<RemappingRuleConf>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct MirrorItemConf {
+    /// A list of interfaces on which the messages will be mirrored
+    /// Messages will be mirrored on all interfaces if the parameter is None
+    pub interfaces: Option<Vec<String>>,
+    /// Mirrored flow directions: egress, ingress
+    /// Both directions will be mirrored if the parameter is None
+    pub flows: Option<Vec<InterceptorFlow>>,
+    /// A list of key-expressions of the messages to mirror.
+    pub key_exprs: Vec<OwnedKeyExpr>,
+    /// Whether the messages dropped by the other interceptors are mirrored too (default true)
+    pub dropped: Option<bool>,
+    /// The key space the mirrored messages are published to, as `<key_space>/<flow>/<zid>/<reason>`
+    pub key_space: Option<OwnedKeyExpr>,
+    /// The capture file the mirrored messages are written to
+    pub file: Option<String>,
+}
+
 #[derive(Serialize, Debug, Deserialize, Clone)]
 pub struct AclConfigRule {
     pub id: String,
@@ -146,6 +204,15 @@ impl std::fmt::Display for Username {
     }
 }
 
+#[derive(Serialize, Debug, Deserialize, Clone)]
+pub struct NamespaceBindingConf {
+    /// The namespace the sessions authenticated with one of the usernames or certificate
+    /// common names are confined to. Must not contain wildcards.
+    pub namespace: OwnedKeyExpr,
+    pub usernames: Option<Vec<Username>>,
+    pub cert_common_names: Option<Vec<CertCommonName>>,
+}
+
 #[derive(Serialize, Debug, Deserialize, Clone, PartialEq, Eq, Hash)]
 pub struct AclConfigPolicyEntry {
     pub rules: Vec<String>,
@@ -241,6 +308,9 @@ validated_struct::validator! {
         metadata: Value,
         /// The node's mode ("router" (default value in `zenohd`), "peer" or "client").
         mode: Option<whatami::WhatAmI>,
+        /// The namespace prefixed to the key expressions sent by the sessions of this instance
+        /// and stripped from the key expressions they receive. Must not contain wildcards.
+        namespace: Option<OwnedKeyExpr>,
         /// Which zenoh nodes to connect to.
         pub connect:
         ConnectConfig {
@@ -251,6 +321,11 @@ validated_struct::validator! {
             /// if connection timeout exceed, exit from application
             pub exit_on_failure: Option<ModeDependentValue<bool>>,
             pub retry: Option<connection_retry::ConnectionRetryModeDependentConf>,
+            /// The order in which a client tries its endpoints when (re)connecting
+            pub failover: Option<ConnectFailover>,
+            /// The maximum number of publications with a blocking congestion control
+            /// buffered while the session is disconnected (0: disabled)
+            pub disconnected_buffer_size: Option<usize>,
         },
         /// Which endpoints to listen on.
         pub listen:
@@ -262,6 +337,9 @@ validated_struct::validator! {
             /// if connection timeout exceed, exit from application
             pub exit_on_failure: Option<ModeDependentValue<bool>>,
             pub retry: Option<connection_retry::ConnectionRetryModeDependentConf>,
+            /// The period in milliseconds to check the addresses of the network interfaces,
+            /// re-binding the listeners and re-advertising the locators when they change (0: disabled)
+            pub interfaces_check_period_ms: Option<u64>,
         },
         /// Configure the session open behavior.
         pub open: #[derive(Default)]
@@ -294,6 +372,13 @@ validated_struct::validator! {
                 interface: Option<String>,
                 /// The time-to-live on multicast scouting packets. (default: 1)
                 pub ttl: Option<u32>,
+                /// The source addresses of a source-specific multicast (SSM) join. If set, scouting messages
+                /// are only received from these sources. Only supported for IPv4 multicast addresses.
+                sources: Option<Vec<IpAddr>>,
+                /// The IPv6 interface index (scope id) used to join and send on the multicast group.
+                scope_id: Option<u32>,
+                /// Whether sent multicast scouting packets are looped back to the local host.
+                loopback: Option<bool>,
                 /// Which type of Zenoh instances to automatically establish sessions with upon discovery through UDP multicast.
                 autoconnect: Option<ModeDependentValue<WhatAmIMatcher>>,
                 /// Whether or not to listen for scout messages on UDP multicast and reply to them.
@@ -340,6 +425,27 @@ validated_struct::validator! {
                 /// connected to each other.
                 /// The failover brokering only works if gossip discovery is enabled.
                 peers_failover_brokering: Option<bool>,
+                /// The key expressions of the data that routers forward on two disjoint routes,
+                /// dropping the duplicates before delivery, so that it survives a single link failure.
+                redundant_key_exprs: Vec<OwnedKeyExpr>,
+                /// The routing region of the router. Routers only exchange their linkstate and
+                /// declarations within their region. The routers connected to routers of another
+                /// region re-declare the declarations of their region to them, aggregating the
+                /// subscribers according to the `aggregation` configuration.
+                region: Option<String>,
+                /// The persistence of the routing state of the router across restarts.
+                pub snapshot: #[derive(Default)]
+                RouterSnapshotConf {
+                    /// The file the linkstate graph and the declarations of the other routers are
+                    /// saved to, and restored from on start. Disabled if not set.
+                    path: Option<String>,
+                    /// The period in milliseconds at which the snapshot is saved.
+                    /// It is also saved when the router closes.
+                    period: Option<u64>,
+                    /// The delay in milliseconds after start after which the restored declarations
+                    /// that the other routers did not declare again are removed.
+                    reconciliation_delay: Option<u64>,
+                },
             },
             /// The routing strategy to use in peers and it's configuration.
             pub peer: #[derive(Default)]
@@ -347,6 +453,33 @@ validated_struct::validator! {
                 /// The routing strategy to use in peers. ("peer_to_peer" or "linkstate").
                 mode: Option<String>,
             },
+            /// The weights of the links used to compute the routes in routers and "linkstate" peers.
+            pub linkstate: #[derive(Default)]
+            LinkstateRoutingConf {
+                /// When set to true, the links without a configured `cost` get a weight derived
+                /// from their round-trip time, sampled when they are established and then every
+                /// `rtt_period` milliseconds (TCP and TLS links on Linux only).
+                rtt_weights: Option<bool>,
+                /// The period in milliseconds at which the round-trip time of the links is sampled.
+                rtt_period: Option<u64>,
+                /// The relative change of a link weight under which the routes are not recomputed.
+                weight_hysteresis: Option<f64>,
+            },
+            /// The selection of the queryables that receive the queries with a `BestMatching` target.
+            pub queries: #[derive(Default)]
+            QueriesRoutingConf {
+                /// How a query is routed when several complete queryables match it:
+                /// "best_matching" (the nearest one), "round_robin" or "least_outstanding"
+                /// (the one behind the face with the fewest pending queries).
+                load_balancing: Option<String>,
+                /// When set, a load-balanced query that doesn't get its final reply from a queryable
+                /// within this time in milliseconds is forwarded to the next complete queryable.
+                failover_timeout: Option<u64>,
+                /// When set, a query that got no reply from its queryable within this delay in
+                /// milliseconds is also sent to the next complete queryable. Only the replies of
+                /// the first queryable to respond reach the querier.
+                hedge_delay: Option<u64>,
+            },
         },
 
         /// The declarations aggregation strategy.
@@ -557,6 +690,12 @@ validated_struct::validator! {
         /// Configuration of the downsampling.
         downsampling: Vec<DownsamplingItemConf>,
 
+        /// Configuration of the traffic mirroring.
+        mirror: Vec<MirrorItemConf>,
+
+        /// Configuration of the key expressions remapping.
+        remapping: Vec<RemappingItemConf>,
+
         ///Configuration of the access control (ACL)
         pub access_control: AclConfig {
             pub enabled: bool,
@@ -566,6 +705,10 @@ validated_struct::validator! {
             pub policies: Option<Vec<AclConfigPolicyEntry>>,
         },
 
+        /// The namespaces the authenticated sessions are confined to: the messages they send
+        /// or would receive outside of their namespace are dropped.
+        namespace_bindings: Vec<NamespaceBindingConf>,
+
         /// A list of directories where plugins may be searched for if no `__path__` was specified for them.
         /// The executable's current directory will be added to the search paths.
         pub plugins_loading: #[derive(Default)]
@@ -555,6 +555,37 @@ pub fn register_param(input: proc_macro::TokenStream) -> proc_macro::TokenStream
         .into()
 }
 
+mod zenoh_serialization_derive;
+use zenoh_serialization_derive::{derive_deserialize, derive_serialize};
+
+/// Derive `zenoh_ext::Serialize`, serializing the fields in declaration order,
+/// like a tuple of these fields.
+///
+/// Enum variants are serialized as their `u32` index, followed by their fields.
+/// ```rust,ignore
+/// #[derive(zenoh_ext::Serialize, zenoh_ext::Deserialize)]
+/// struct Point {
+///    x: f64,
+///    y: f64,
+/// }
+/// ```
+#[proc_macro_derive(Serialize)]
+pub fn serialize(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input: DeriveInput = syn::parse_macro_input!(input);
+    derive_serialize(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Derive `zenoh_ext::Deserialize`, the counterpart of [`macro@Serialize`].
+#[proc_macro_derive(Deserialize)]
+pub fn deserialize(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input: DeriveInput = syn::parse_macro_input!(input);
+    derive_deserialize(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
 /// Macro `#[internal_trait]` should precede
 /// `impl Trait for Struct { ... }`
 ///
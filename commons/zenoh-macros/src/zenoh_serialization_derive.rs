@@ -0,0 +1,150 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, quote};
+use syn::{parse_quote, Data, DeriveInput, Fields, Generics, Index, Path};
+
+// Add the bound `T: #bound` to each type parameter `T`
+fn add_bounds(mut generics: Generics, bound: &Path) -> Generics {
+    for param in generics.type_params_mut() {
+        param.bounds.push(parse_quote!(#bound));
+    }
+    generics
+}
+
+// The bindings of the fields, named `f0`, `f1`, ... and the pattern destructuring them
+fn fields_pattern(fields: &Fields) -> (Vec<syn::Ident>, TokenStream) {
+    let bindings: Vec<_> = (0..fields.len()).map(|i| format_ident!("f{i}")).collect();
+    let pattern = match fields {
+        Fields::Named(named) => {
+            let names = named.named.iter().map(|f| f.ident.as_ref().unwrap());
+            quote!({ #(#names: #bindings),* })
+        }
+        Fields::Unnamed(_) => quote!(( #(#bindings),* )),
+        Fields::Unit => quote!(),
+    };
+    (bindings, pattern)
+}
+
+// The expression building the fields, each one being deserialized in order
+fn fields_constructor(fields: &Fields) -> TokenStream {
+    let deserialize = quote!(::zenoh_ext::Deserialize::deserialize(deserializer)?);
+    match fields {
+        Fields::Named(named) => {
+            let names = named.named.iter().map(|f| f.ident.as_ref().unwrap());
+            quote!({ #(#names: #deserialize),* })
+        }
+        Fields::Unnamed(unnamed) => {
+            let fields = unnamed.unnamed.iter().map(|_| &deserialize);
+            quote!(( #(#fields),* ))
+        }
+        Fields::Unit => quote!(),
+    }
+}
+
+pub(crate) fn derive_serialize(input: DeriveInput) -> syn::Result<TokenStream> {
+    let name = &input.ident;
+    let generics = add_bounds(
+        input.generics.clone(),
+        &parse_quote!(::zenoh_ext::Serialize),
+    );
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let body = match &input.data {
+        Data::Struct(data) => {
+            let fields = data.fields.iter().enumerate().map(|(i, f)| match &f.ident {
+                Some(ident) => quote!(self.#ident),
+                None => {
+                    let index = Index::from(i);
+                    quote!(self.#index)
+                }
+            });
+            quote!(#(::zenoh_ext::Serialize::serialize(&#fields, serializer);)*)
+        }
+        Data::Enum(data) => {
+            let arms = data.variants.iter().enumerate().map(|(i, variant)| {
+                let ident = &variant.ident;
+                let index = i as u32;
+                let (bindings, pattern) = fields_pattern(&variant.fields);
+                quote! {
+                    Self::#ident #pattern => {
+                        serializer.serialize(#index);
+                        #(::zenoh_ext::Serialize::serialize(#bindings, serializer);)*
+                    }
+                }
+            });
+            quote! {
+                match self {
+                    #(#arms)*
+                }
+            }
+        }
+        Data::Union(_) => {
+            return Err(syn::Error::new(
+                Span::call_site(),
+                "Serialize cannot be derived for unions",
+            ))
+        }
+    };
+    Ok(quote! {
+        impl #impl_generics ::zenoh_ext::Serialize for #name #ty_generics #where_clause {
+            fn serialize(&self, serializer: &mut ::zenoh_ext::ZSerializer) {
+                #body
+            }
+        }
+    })
+}
+
+pub(crate) fn derive_deserialize(input: DeriveInput) -> syn::Result<TokenStream> {
+    let name = &input.ident;
+    let generics = add_bounds(
+        input.generics.clone(),
+        &parse_quote!(::zenoh_ext::Deserialize),
+    );
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let body = match &input.data {
+        Data::Struct(data) => {
+            let constructor = fields_constructor(&data.fields);
+            quote!(Ok(Self #constructor))
+        }
+        Data::Enum(data) => {
+            let arms = data.variants.iter().enumerate().map(|(i, variant)| {
+                let ident = &variant.ident;
+                let index = i as u32;
+                let constructor = fields_constructor(&variant.fields);
+                quote!(#index => Ok(Self::#ident #constructor),)
+            });
+            quote! {
+                match deserializer.deserialize::<u32>()? {
+                    #(#arms)*
+                    _ => Err(::zenoh_ext::ZDeserializeError),
+                }
+            }
+        }
+        Data::Union(_) => {
+            return Err(syn::Error::new(
+                Span::call_site(),
+                "Deserialize cannot be derived for unions",
+            ))
+        }
+    };
+    Ok(quote! {
+        impl #impl_generics ::zenoh_ext::Deserialize for #name #ty_generics #where_clause {
+            fn deserialize(
+                deserializer: &mut ::zenoh_ext::ZDeserializer,
+            ) -> ::core::result::Result<Self, ::zenoh_ext::ZDeserializeError> {
+                #body
+            }
+        }
+    })
+}
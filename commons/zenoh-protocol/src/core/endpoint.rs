@@ -644,6 +644,14 @@ impl TryFrom<String> for EndPoint {
     }
 }
 
+impl TryFrom<&str> for EndPoint {
+    type Error = ZError;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        Self::try_from(s.to_owned())
+    }
+}
+
 impl FromStr for EndPoint {
     type Err = ZError;
 
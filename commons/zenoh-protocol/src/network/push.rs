@@ -44,6 +44,7 @@ pub struct Push {
     pub ext_qos: ext::QoSType,
     pub ext_tstamp: Option<ext::TimestampType>,
     pub ext_nodeid: ext::NodeIdType,
+    pub ext_redundancy: Option<ext::RedundancyType>,
     pub payload: PushBody,
 }
 
@@ -61,6 +62,36 @@ pub mod ext {
 
     pub type NodeId = zextz64!(0x3, true);
     pub type NodeIdType = crate::network::ext::NodeIdType<{ NodeId::ID }>;
+
+    pub type Redundancy = zextz64!(0x4, false);
+    // ```text
+    // - Redundancy (0x04)
+    //  7 6 5 4 3 2 1 0
+    // +-+-+-+-+-+-+-+-+
+    // %    sn     |A  %
+    // +---------------+
+    // ```
+    // Set by routers on the messages forwarded on redundant routes: the sequence number
+    // assigned by the source router and the alternate flag (A) telling if the message
+    // follows the alternate routing tree.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct RedundancyType {
+        pub sn: u64,
+        pub alternate: bool,
+    }
+
+    impl RedundancyType {
+        #[cfg(feature = "test")]
+        pub fn rand() -> Self {
+            use rand::Rng;
+            let mut rng = rand::thread_rng();
+
+            Self {
+                sn: rng.gen::<u64>() >> 1,
+                alternate: rng.gen_bool(0.5),
+            }
+        }
+    }
 }
 
 impl Push {
@@ -74,6 +105,7 @@ impl Push {
         let ext_qos = ext::QoSType::rand();
         let ext_tstamp = rng.gen_bool(0.5).then(ext::TimestampType::rand);
         let ext_nodeid = ext::NodeIdType::rand();
+        let ext_redundancy = rng.gen_bool(0.5).then(ext::RedundancyType::rand);
 
         Self {
             wire_expr,
@@ -81,6 +113,7 @@ impl Push {
             ext_tstamp,
             ext_qos,
             ext_nodeid,
+            ext_redundancy,
         }
     }
 }
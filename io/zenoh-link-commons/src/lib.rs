@@ -21,17 +21,24 @@ extern crate alloc;
 
 mod listener;
 mod multicast;
+mod sim;
+pub mod tcp;
 #[cfg(feature = "tls")]
 pub mod tls;
 mod unicast;
 
 use alloc::{borrow::ToOwned, boxed::Box, string::String, vec, vec::Vec};
-use core::{cmp::PartialEq, fmt, hash::Hash};
+use core::{
+    cmp::PartialEq,
+    fmt,
+    hash::{Hash, Hasher},
+};
 
 use async_trait::async_trait;
 pub use listener::*;
 pub use multicast::*;
 use serde::Serialize;
+pub use sim::*;
 pub use unicast::*;
 use zenoh_protocol::{
     core::{Locator, Metadata, PriorityRange, Reliability},
@@ -56,6 +63,32 @@ pub struct Link {
     pub auth_identifier: LinkAuthId,
     pub priorities: Option<PriorityRange>,
     pub reliability: Option<Reliability>,
+    #[serde(skip_serializing_if = "LinkTcpInfo::is_none")]
+    tcp: LinkTcpInfo,
+}
+
+// The TCP socket info is a snapshot of the socket state, not part of the link identity:
+// it's ignored when comparing and hashing links
+#[derive(Clone, Debug, Serialize)]
+#[serde(transparent)]
+struct LinkTcpInfo(Option<tcp::TcpSocketInfo>);
+
+impl LinkTcpInfo {
+    fn is_none(&self) -> bool {
+        self.0.is_none()
+    }
+}
+
+impl PartialEq for LinkTcpInfo {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+impl Eq for LinkTcpInfo {}
+
+impl Hash for LinkTcpInfo {
+    fn hash<H: Hasher>(&self, _state: &mut H) {}
 }
 
 #[async_trait]
@@ -91,6 +124,7 @@ impl Link {
             auth_identifier: link.get_auth_id().clone(),
             priorities,
             reliability,
+            tcp: LinkTcpInfo(link.get_tcp_info().cloned()),
         }
     }
 
@@ -105,9 +139,15 @@ impl Link {
             auth_identifier: LinkAuthId::default(),
             priorities: None,
             reliability: None,
+            tcp: LinkTcpInfo(None),
         }
     }
 
+    /// The effective TCP socket options of the link, for TCP and TLS links.
+    pub fn tcp_info(&self) -> Option<&tcp::TcpSocketInfo> {
+        self.tcp.0.as_ref()
+    }
+
     /// Updates the metadata of the `locator` with `priorities` and `reliability`.
     fn to_patched_locator(
         locator: &Locator,
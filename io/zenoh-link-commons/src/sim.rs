@@ -0,0 +1,436 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+use alloc::{boxed::Box, string::String, sync::Arc, vec::Vec};
+use std::{
+    net::SocketAddr,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use async_trait::async_trait;
+use rand::Rng;
+use tokio_util::sync::CancellationToken;
+use zenoh_core::zlock;
+use zenoh_protocol::{
+    core::{EndPoint, Locator, Metadata},
+    transport::BatchSize,
+};
+use zenoh_result::{bail, zerror, ZResult};
+
+use crate::{tcp::TcpSocketInfo, LinkAuthId, LinkUnicast, LinkUnicastTrait};
+
+/// Fixed latency added to every write, e.g. `sim_delay=50ms`.
+pub const SIM_DELAY: &str = "sim_delay";
+/// Random latency added on top of [`SIM_DELAY`], uniformly drawn in `[0, sim_jitter]`.
+pub const SIM_JITTER: &str = "sim_jitter";
+/// Probability of dropping a write, e.g. `sim_loss=2%`. Only honoured on unreliable links.
+pub const SIM_LOSS: &str = "sim_loss";
+/// Outgoing bandwidth cap, e.g. `sim_bw=1Mbps`.
+pub const SIM_BANDWIDTH: &str = "sim_bw";
+/// Time after which the link is forcibly disconnected, e.g. `sim_disconnect=30s`.
+pub const SIM_DISCONNECT: &str = "sim_disconnect";
+
+pub const SIM_KEYS: &[&str] = &[
+    SIM_DELAY,
+    SIM_JITTER,
+    SIM_LOSS,
+    SIM_BANDWIDTH,
+    SIM_DISCONNECT,
+];
+
+// Maximum number of writes buffered by the impairment scheduler before applying backpressure
+const SIM_QUEUE_SIZE: usize = 256;
+
+/*************************************/
+/*              CONFIG               */
+/*************************************/
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct LinkSimConfig {
+    pub delay: Duration,
+    pub jitter: Duration,
+    /// Loss probability in `[0, 1]`.
+    pub loss: f64,
+    /// Bandwidth in bits per second.
+    pub bandwidth: Option<u64>,
+    pub disconnect: Option<Duration>,
+}
+
+impl LinkSimConfig {
+    /// Parses the impairment parameters from the `metadata` of an endpoint.
+    /// Returns `None` if no impairment is requested.
+    pub fn from_metadata(metadata: &Metadata) -> ZResult<Option<Self>> {
+        if !SIM_KEYS.iter().any(|k| metadata.get(k).is_some()) {
+            return Ok(None);
+        }
+
+        let mut config = LinkSimConfig::default();
+        if let Some(s) = metadata.get(SIM_DELAY) {
+            config.delay = parse_duration(SIM_DELAY, s)?;
+        }
+        if let Some(s) = metadata.get(SIM_JITTER) {
+            config.jitter = parse_duration(SIM_JITTER, s)?;
+        }
+        if let Some(s) = metadata.get(SIM_LOSS) {
+            config.loss = parse_loss(s)?;
+        }
+        if let Some(s) = metadata.get(SIM_BANDWIDTH) {
+            config.bandwidth = Some(parse_bandwidth(s)?);
+        }
+        if let Some(s) = metadata.get(SIM_DISCONNECT) {
+            config.disconnect = Some(parse_duration(SIM_DISCONNECT, s)?);
+        }
+        Ok(Some(config))
+    }
+
+    /// Extracts the impairment parameters from `endpoint` and removes them from its metadata,
+    /// so that they are neither interpreted by the link nor advertised to remote peers.
+    pub fn take_from_endpoint(endpoint: &mut EndPoint) -> ZResult<Option<Self>> {
+        let config = Self::from_metadata(&endpoint.metadata())?;
+        if config.is_some() {
+            let mut metadata = endpoint.metadata_mut();
+            for k in SIM_KEYS {
+                metadata.remove(*k)?;
+            }
+        }
+        Ok(config)
+    }
+
+    fn is_scheduled(&self) -> bool {
+        !self.delay.is_zero() || !self.jitter.is_zero() || self.bandwidth.is_some()
+    }
+}
+
+fn parse_duration(key: &str, s: &str) -> ZResult<Duration> {
+    humantime::parse_duration(s).map_err(|e| zerror!("Invalid {} value '{}': {}", key, s, e).into())
+}
+
+fn parse_loss(s: &str) -> ZResult<f64> {
+    let loss = match s.strip_suffix('%') {
+        Some(p) => p.trim().parse::<f64>().map(|p| p / 100.0),
+        None => s.trim().parse::<f64>(),
+    }
+    .map_err(|e| zerror!("Invalid {} value '{}': {}", SIM_LOSS, s, e))?;
+    if !(0.0..=1.0).contains(&loss) {
+        bail!("Invalid {} value '{}': must be in [0%, 100%]", SIM_LOSS, s);
+    }
+    Ok(loss)
+}
+
+fn parse_bandwidth(s: &str) -> ZResult<u64> {
+    let s = s.trim();
+    let Some(value) = s.strip_suffix("bps") else {
+        bail!(
+            "Invalid {} value '{}': expected a value in bps, kbps, Mbps or Gbps",
+            SIM_BANDWIDTH,
+            s
+        );
+    };
+    let (value, factor) = match value.chars().last() {
+        Some('k' | 'K') => (&value[..value.len() - 1], 1_000.0),
+        Some('M') => (&value[..value.len() - 1], 1_000_000.0),
+        Some('G') => (&value[..value.len() - 1], 1_000_000_000.0),
+        _ => (value, 1.0),
+    };
+    let bw = value
+        .trim()
+        .parse::<f64>()
+        .map_err(|e| zerror!("Invalid {} value '{}': {}", SIM_BANDWIDTH, s, e))?
+        * factor;
+    if bw < 1.0 {
+        bail!(
+            "Invalid {} value '{}': must be at least 1bps",
+            SIM_BANDWIDTH,
+            s
+        );
+    }
+    Ok(bw as u64)
+}
+
+/// Returns `true` if a link whose local locator is `src` has been accepted by the `listener`.
+/// Listeners bound to an unspecified address match on protocol and port only.
+pub fn is_accepted_by(listener: &Locator, src: &Locator) -> bool {
+    if listener.protocol() != src.protocol() {
+        return false;
+    }
+    match (
+        listener.address().as_str().parse::<SocketAddr>(),
+        src.address().as_str().parse::<SocketAddr>(),
+    ) {
+        (Ok(l), Ok(s)) if l.ip().is_unspecified() => l.port() == s.port(),
+        (Ok(l), Ok(s)) => l == s,
+        _ => listener.address() == src.address(),
+    }
+}
+
+/*************************************/
+/*              LINK                 */
+/*************************************/
+enum SimWrite {
+    // The time the write was queued at, its deadline and its content
+    Data(Instant, Instant, Vec<u8>),
+    // Acknowledged once all the writes queued before it have been performed
+    Flush(flume::Sender<()>),
+}
+
+/// A [`LinkUnicastTrait`] decorator injecting delay, jitter, loss, bandwidth limitation and
+/// scheduled disconnection on the outgoing direction of any unicast link.
+pub struct LinkUnicastSim {
+    inner: LinkUnicast,
+    config: LinkSimConfig,
+    tx: Option<flume::Sender<SimWrite>>,
+    next_deadline: Mutex<Instant>,
+    // The time the last performed write spent in the simulator
+    latency: Arc<Mutex<Duration>>,
+    token: CancellationToken,
+}
+
+impl LinkUnicastSim {
+    pub fn new(inner: LinkUnicast, mut config: LinkSimConfig) -> Self {
+        if config.loss > 0.0 && inner.is_reliable() {
+            tracing::warn!(
+                "Ignoring {} on reliable link {}: losses can only be simulated on unreliable links",
+                SIM_LOSS,
+                inner
+            );
+            config.loss = 0.0;
+        }
+
+        let token = CancellationToken::new();
+        let latency = Arc::new(Mutex::new(Duration::ZERO));
+        let tx = config.is_scheduled().then(|| {
+            let (tx, rx) = flume::bounded(SIM_QUEUE_SIZE);
+            zenoh_runtime::ZRuntime::TX.spawn(sim_tx_task(
+                inner.clone(),
+                rx,
+                config.bandwidth,
+                latency.clone(),
+                token.clone(),
+            ));
+            tx
+        });
+
+        if let Some(after) = config.disconnect {
+            let c_inner = inner.clone();
+            let c_token = token.clone();
+            zenoh_runtime::ZRuntime::TX.spawn(async move {
+                tokio::select! {
+                    _ = tokio::time::sleep(after) => {
+                        tracing::debug!("Simulating disconnection of link {}", c_inner);
+                        c_token.cancel();
+                        let _ = c_inner.close().await;
+                    }
+                    _ = c_token.cancelled() => {}
+                }
+            });
+        }
+
+        Self {
+            inner,
+            config,
+            tx,
+            next_deadline: Mutex::new(Instant::now()),
+            latency,
+            token,
+        }
+    }
+
+    /// Wraps `link` if `config` requires any impairment, returns it untouched otherwise.
+    pub fn wrap(link: LinkUnicast, config: Option<LinkSimConfig>) -> LinkUnicast {
+        match config {
+            Some(config) => {
+                tracing::debug!("Simulating {:?} on link {}", config, link);
+                LinkUnicast(Arc::new(LinkUnicastSim::new(link, config)))
+            }
+            None => link,
+        }
+    }
+
+    fn deadline(&self) -> Instant {
+        let mut deadline = Instant::now() + self.config.delay;
+        if !self.config.jitter.is_zero() {
+            deadline += self
+                .config
+                .jitter
+                .mul_f64(rand::thread_rng().gen_range(0.0..=1.0));
+        }
+        // Never reorder writes: a streamed link would be corrupted otherwise
+        let mut guard = zlock!(self.next_deadline);
+        *guard = (*guard).max(deadline);
+        *guard
+    }
+
+    fn is_lost(&self) -> bool {
+        self.config.loss > 0.0 && rand::thread_rng().gen_bool(self.config.loss)
+    }
+
+    fn check_connected(&self) -> ZResult<()> {
+        if self.token.is_cancelled() {
+            bail!("Link {} has been disconnected by the simulator", self.inner);
+        }
+        Ok(())
+    }
+}
+
+async fn sim_tx_task(
+    link: LinkUnicast,
+    rx: flume::Receiver<SimWrite>,
+    bandwidth: Option<u64>,
+    latency: Arc<Mutex<Duration>>,
+    token: CancellationToken,
+) {
+    let process = async {
+        while let Ok(write) = rx.recv_async().await {
+            match write {
+                SimWrite::Data(queued, deadline, buffer) => {
+                    tokio::time::sleep_until(deadline.into()).await;
+                    link.write_all(&buffer).await?;
+                    *zlock!(latency) = queued.elapsed();
+                    if let Some(bw) = bandwidth {
+                        let secs = (buffer.len() * 8) as f64 / bw as f64;
+                        tokio::time::sleep(Duration::from_secs_f64(secs)).await;
+                    }
+                }
+                SimWrite::Flush(ack) => {
+                    let _ = ack.send(());
+                }
+            }
+        }
+        ZResult::Ok(())
+    };
+
+    tokio::select! {
+        res = process => {
+            if let Err(e) = res {
+                tracing::debug!("Simulated link {} failed to write: {}", link, e);
+                token.cancel();
+            }
+        }
+        _ = token.cancelled() => {}
+    }
+}
+
+#[async_trait]
+impl LinkUnicastTrait for LinkUnicastSim {
+    #[inline(always)]
+    fn get_mtu(&self) -> BatchSize {
+        self.inner.get_mtu()
+    }
+
+    #[inline(always)]
+    fn get_src(&self) -> &Locator {
+        self.inner.get_src()
+    }
+
+    #[inline(always)]
+    fn get_dst(&self) -> &Locator {
+        self.inner.get_dst()
+    }
+
+    #[inline(always)]
+    fn is_reliable(&self) -> bool {
+        self.inner.is_reliable()
+    }
+
+    #[inline(always)]
+    fn is_streamed(&self) -> bool {
+        self.inner.is_streamed()
+    }
+
+    #[inline(always)]
+    fn get_interface_names(&self) -> Vec<String> {
+        self.inner.get_interface_names()
+    }
+
+    #[inline(always)]
+    fn get_auth_id(&self) -> &LinkAuthId {
+        self.inner.get_auth_id()
+    }
+
+    #[inline(always)]
+    fn get_tcp_info(&self) -> Option<&TcpSocketInfo> {
+        self.inner.get_tcp_info()
+    }
+
+    // The time the writes spend in the simulator adds up to the round-trip time of the inner link
+    fn get_rtt(&self) -> Option<Duration> {
+        self.inner.get_rtt().map(|rtt| rtt + *zlock!(self.latency))
+    }
+
+    async fn write(&self, buffer: &[u8]) -> ZResult<usize> {
+        self.write_all(buffer).await?;
+        Ok(buffer.len())
+    }
+
+    async fn write_all(&self, buffer: &[u8]) -> ZResult<()> {
+        self.check_connected()?;
+        if self.is_lost() {
+            tracing::trace!(
+                "Simulating loss of {} bytes on link {}",
+                buffer.len(),
+                self.inner
+            );
+            return Ok(());
+        }
+        match self.tx.as_ref() {
+            Some(tx) => tx
+                .send_async(SimWrite::Data(
+                    Instant::now(),
+                    self.deadline(),
+                    buffer.to_vec(),
+                ))
+                .await
+                .map_err(|_| {
+                    zerror!("Link {} has been disconnected by the simulator", self.inner).into()
+                }),
+            None => self.inner.write_all(buffer).await,
+        }
+    }
+
+    async fn read(&self, buffer: &mut [u8]) -> ZResult<usize> {
+        tokio::select! {
+            res = self.inner.read(buffer) => res,
+            _ = self.token.cancelled() => {
+                bail!("Link {} has been disconnected by the simulator", self.inner)
+            }
+        }
+    }
+
+    async fn read_exact(&self, buffer: &mut [u8]) -> ZResult<()> {
+        tokio::select! {
+            res = self.inner.read_exact(buffer) => res,
+            _ = self.token.cancelled() => {
+                bail!("Link {} has been disconnected by the simulator", self.inner)
+            }
+        }
+    }
+
+    async fn close(&self) -> ZResult<()> {
+        // Perform the queued writes first, e.g. the Close message of the transport. The ack is
+        // dropped without being sent if the link gets disconnected in the meantime.
+        if let Some(tx) = self.tx.as_ref() {
+            let (ack_tx, ack_rx) = flume::bounded(1);
+            if tx.send_async(SimWrite::Flush(ack_tx)).await.is_ok() {
+                let _ = ack_rx.recv_async().await;
+            }
+        }
+        self.token.cancel();
+        self.inner.close().await
+    }
+}
+
+impl Drop for LinkUnicastSim {
+    fn drop(&mut self) {
+        self.token.cancel();
+    }
+}
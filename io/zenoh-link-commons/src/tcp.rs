@@ -0,0 +1,350 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+use alloc::string::{String, ToString};
+use std::{net::SocketAddr, time::Duration};
+
+use serde::Serialize;
+use socket2::{SockRef, TcpKeepalive};
+use tokio::net::{TcpListener, TcpSocket, TcpStream};
+use zenoh_protocol::core::Config;
+use zenoh_result::{zerror, ZResult};
+
+/// Size in bytes of the socket send buffer (`SO_SNDBUF`).
+pub const TCP_SO_SND_BUF: &str = "so_sndbuf";
+/// Size in bytes of the socket receive buffer (`SO_RCVBUF`).
+pub const TCP_SO_RCV_BUF: &str = "so_rcvbuf";
+/// Idle time before the first keepalive probe is sent, e.g. `keepalive_time=30s`.
+/// Setting any of the keepalive keys enables `SO_KEEPALIVE`.
+pub const TCP_KEEPALIVE_TIME: &str = "keepalive_time";
+/// Time between two keepalive probes, e.g. `keepalive_interval=5s`.
+pub const TCP_KEEPALIVE_INTERVAL: &str = "keepalive_interval";
+/// Number of unacknowledged keepalive probes before the connection is dropped.
+pub const TCP_KEEPALIVE_RETRIES: &str = "keepalive_retries";
+/// Maximum time transmitted data may remain unacknowledged (`TCP_USER_TIMEOUT`, Linux only).
+pub const TCP_USER_TIMEOUT: &str = "user_timeout";
+/// Congestion control algorithm (`TCP_CONGESTION`, Linux only), e.g. `congestion=bbr`.
+pub const TCP_CONGESTION: &str = "congestion";
+
+/// TCP socket options configured from the endpoint configuration.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TcpSocketConfig {
+    pub tx_buffer_size: Option<u32>,
+    pub rx_buffer_size: Option<u32>,
+    pub keepalive_time: Option<Duration>,
+    pub keepalive_interval: Option<Duration>,
+    pub keepalive_retries: Option<u32>,
+    pub user_timeout: Option<Duration>,
+    pub congestion: Option<String>,
+}
+
+impl TcpSocketConfig {
+    pub fn new(config: &Config) -> ZResult<Self> {
+        Ok(Self {
+            tx_buffer_size: parse_number(config, TCP_SO_SND_BUF)?,
+            rx_buffer_size: parse_number(config, TCP_SO_RCV_BUF)?,
+            keepalive_time: parse_duration(config, TCP_KEEPALIVE_TIME)?,
+            keepalive_interval: parse_duration(config, TCP_KEEPALIVE_INTERVAL)?,
+            keepalive_retries: parse_number(config, TCP_KEEPALIVE_RETRIES)?,
+            user_timeout: parse_duration(config, TCP_USER_TIMEOUT)?,
+            congestion: config.get(TCP_CONGESTION).map(ToString::to_string),
+        })
+    }
+
+    fn is_keepalive(&self) -> bool {
+        self.keepalive_time.is_some()
+            || self.keepalive_interval.is_some()
+            || self.keepalive_retries.is_some()
+    }
+
+    /// Creates a socket bound to `addr` (and optionally to `iface`) and listens on it.
+    /// Accepted sockets inherit the configured options.
+    ///
+    /// `SO_REUSEADDR` is set on the socket, so that a listener can be restarted on the same
+    /// address while the connections of the previous one are still in `TIME_WAIT`.
+    pub fn new_listener(
+        &self,
+        addr: &SocketAddr,
+        iface: Option<&str>,
+    ) -> ZResult<(TcpListener, SocketAddr)> {
+        let socket = self.socket(addr, iface)?;
+
+        // Build a TcpListener from TcpSocket
+        // https://docs.rs/tokio/latest/tokio/net/struct.TcpSocket.html
+        socket.set_reuseaddr(true)?;
+        socket.bind(*addr).map_err(|e| zerror!("{}: {}", addr, e))?;
+        // backlog (the maximum number of pending connections are queued): 1024
+        let listener = socket
+            .listen(1024)
+            .map_err(|e| zerror!("{}: {}", addr, e))?;
+
+        let local_addr = listener
+            .local_addr()
+            .map_err(|e| zerror!("{}: {}", addr, e))?;
+
+        Ok((listener, local_addr))
+    }
+
+    /// Connects a new socket (optionally bound to `iface`) to `dst_addr`.
+    /// Returns the stream together with its source and destination addresses.
+    pub async fn new_link(
+        &self,
+        dst_addr: &SocketAddr,
+        iface: Option<&str>,
+    ) -> ZResult<(TcpStream, SocketAddr, SocketAddr)> {
+        let socket = self.socket(dst_addr, iface)?;
+
+        // Build a TcpStream from TcpSocket
+        // https://docs.rs/tokio/latest/tokio/net/struct.TcpSocket.html
+        let stream = socket
+            .connect(*dst_addr)
+            .await
+            .map_err(|e| zerror!("{}: {}", dst_addr, e))?;
+
+        let src_addr = stream
+            .local_addr()
+            .map_err(|e| zerror!("{}: {}", dst_addr, e))?;
+
+        let dst_addr = stream
+            .peer_addr()
+            .map_err(|e| zerror!("{}: {}", dst_addr, e))?;
+
+        Ok((stream, src_addr, dst_addr))
+    }
+
+    fn socket(&self, addr: &SocketAddr, iface: Option<&str>) -> ZResult<TcpSocket> {
+        let socket = match addr {
+            SocketAddr::V4(_) => TcpSocket::new_v4(),
+            SocketAddr::V6(_) => TcpSocket::new_v6(),
+        }?;
+
+        if let Some(iface) = iface {
+            zenoh_util::net::set_bind_to_device_tcp_socket(&socket, iface)?;
+        }
+
+        // Buffer sizes must be set before connecting or listening for the TCP window scale
+        // option to be negotiated accordingly
+        if let Some(size) = self.tx_buffer_size {
+            socket.set_send_buffer_size(size)?;
+        }
+        if let Some(size) = self.rx_buffer_size {
+            socket.set_recv_buffer_size(size)?;
+        }
+        self.apply(SockRef::from(&socket))?;
+
+        Ok(socket)
+    }
+
+    fn apply(&self, socket: SockRef) -> ZResult<()> {
+        if self.is_keepalive() {
+            let mut keepalive = TcpKeepalive::new();
+            if let Some(time) = self.keepalive_time {
+                keepalive = keepalive.with_time(time);
+            }
+            #[cfg(not(any(target_os = "openbsd", target_os = "redox", target_os = "solaris")))]
+            if let Some(interval) = self.keepalive_interval {
+                keepalive = keepalive.with_interval(interval);
+            }
+            #[cfg(not(any(
+                target_os = "openbsd",
+                target_os = "redox",
+                target_os = "solaris",
+                target_os = "windows"
+            )))]
+            if let Some(retries) = self.keepalive_retries {
+                keepalive = keepalive.with_retries(retries);
+            }
+            socket
+                .set_tcp_keepalive(&keepalive)
+                .map_err(|e| zerror!("Unable to set TCP keepalive: {}", e))?;
+        }
+
+        #[cfg(any(target_os = "android", target_os = "linux"))]
+        if let Some(timeout) = self.user_timeout {
+            socket
+                .set_tcp_user_timeout(Some(timeout))
+                .map_err(|e| zerror!("Unable to set {}: {}", TCP_USER_TIMEOUT, e))?;
+        }
+        #[cfg(not(any(target_os = "android", target_os = "linux")))]
+        if self.user_timeout.is_some() {
+            tracing::warn!("{} is only supported on Linux", TCP_USER_TIMEOUT);
+        }
+
+        #[cfg(target_os = "linux")]
+        if let Some(congestion) = self.congestion.as_ref() {
+            socket
+                .set_tcp_congestion(congestion.as_bytes())
+                .map_err(|e| zerror!("Unable to set {} '{}': {}", TCP_CONGESTION, congestion, e))?;
+        }
+        #[cfg(not(target_os = "linux"))]
+        if self.congestion.is_some() {
+            tracing::warn!("{} is only supported on Linux", TCP_CONGESTION);
+        }
+
+        Ok(())
+    }
+}
+
+fn parse_number<T: core::str::FromStr>(config: &Config, key: &str) -> ZResult<Option<T>>
+where
+    T::Err: core::fmt::Display,
+{
+    config
+        .get(key)
+        .map(|s| {
+            s.parse::<T>()
+                .map_err(|e| zerror!("Invalid {} value '{}': {}", key, s, e).into())
+        })
+        .transpose()
+}
+
+fn parse_duration(config: &Config, key: &str) -> ZResult<Option<Duration>> {
+    config
+        .get(key)
+        .map(|s| {
+            humantime::parse_duration(s)
+                .map_err(|e| zerror!("Invalid {} value '{}': {}", key, s, e).into())
+        })
+        .transpose()
+}
+
+/// The effective TCP socket options of a link, as reported by the operating system.
+#[derive(Clone, Debug, Default, Serialize, Hash, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct TcpSocketInfo {
+    pub so_sndbuf: Option<usize>,
+    pub so_rcvbuf: Option<usize>,
+    pub keepalive: Option<bool>,
+    pub keepalive_time: Option<Duration>,
+    pub keepalive_interval: Option<Duration>,
+    pub keepalive_retries: Option<u32>,
+    pub user_timeout: Option<Duration>,
+    pub congestion: Option<String>,
+    /// The smoothed round-trip time sampled when the link was established (Linux only). It is not
+    /// refreshed afterwards, see [`LinkUnicastTrait::get_rtt`](crate::LinkUnicastTrait::get_rtt)
+    /// for the current value.
+    pub rtt: Option<Duration>,
+}
+
+impl TcpSocketInfo {
+    pub fn new(stream: &TcpStream) -> Self {
+        let socket = SockRef::from(stream);
+        #[allow(unused_mut)]
+        let mut info = TcpSocketInfo {
+            so_sndbuf: socket.send_buffer_size().ok(),
+            so_rcvbuf: socket.recv_buffer_size().ok(),
+            keepalive: socket.keepalive().ok(),
+            ..Default::default()
+        };
+        #[cfg(not(any(target_os = "haiku", target_os = "openbsd", target_os = "windows")))]
+        if info.keepalive == Some(true) {
+            info.keepalive_time = socket.keepalive_time().ok();
+            info.keepalive_interval = socket.keepalive_interval().ok();
+            info.keepalive_retries = socket.keepalive_retries().ok();
+        }
+        #[cfg(any(target_os = "android", target_os = "linux"))]
+        {
+            info.user_timeout = socket.tcp_user_timeout().ok().flatten();
+        }
+        #[cfg(target_os = "linux")]
+        {
+            info.congestion = socket.tcp_congestion().ok().map(|ca| {
+                String::from_utf8_lossy(&ca)
+                    .trim_end_matches('\0')
+                    .to_string()
+            });
+            info.rtt = tcp_rtt(stream);
+        }
+        info
+    }
+
+    /// Applies the options to an accepted `stream` and returns the effective values.
+    pub fn from_accepted(stream: &TcpStream, config: &TcpSocketConfig) -> Self {
+        if let Err(e) = config.apply(SockRef::from(stream)) {
+            tracing::warn!("Unable to configure accepted TCP socket: {}", e);
+        }
+        Self::new(stream)
+    }
+}
+
+/// Returns the smoothed round-trip time currently measured by the kernel on `stream`.
+/// Only available on Linux, returns `None` on the other platforms.
+pub fn tcp_rtt(stream: &TcpStream) -> Option<Duration> {
+    #[cfg(target_os = "linux")]
+    {
+        linux::tcp_rtt(stream)
+    }
+    #[cfg(not(target_os = "linux"))]
+    {
+        let _ = stream;
+        None
+    }
+}
+
+#[cfg(target_os = "linux")]
+mod linux {
+    use std::{os::fd::AsRawFd, time::Duration};
+
+    use tokio::net::TcpStream;
+
+    // The beginning of the Linux `struct tcp_info`, which libc doesn't expose
+    #[repr(C)]
+    #[derive(Default)]
+    struct TcpInfo {
+        tcpi_state: u8,
+        tcpi_ca_state: u8,
+        tcpi_retransmits: u8,
+        tcpi_probes: u8,
+        tcpi_backoff: u8,
+        tcpi_options: u8,
+        tcpi_wscale: u8,
+        tcpi_flags: u8,
+        tcpi_rto: u32,
+        tcpi_ato: u32,
+        tcpi_snd_mss: u32,
+        tcpi_rcv_mss: u32,
+        tcpi_unacked: u32,
+        tcpi_sacked: u32,
+        tcpi_lost: u32,
+        tcpi_retrans: u32,
+        tcpi_fackets: u32,
+        tcpi_last_data_sent: u32,
+        tcpi_last_ack_sent: u32,
+        tcpi_last_data_recv: u32,
+        tcpi_last_ack_recv: u32,
+        tcpi_pmtu: u32,
+        tcpi_rcv_ssthresh: u32,
+        tcpi_rtt: u32,
+        tcpi_rttvar: u32,
+    }
+
+    pub(super) fn tcp_rtt(stream: &TcpStream) -> Option<Duration> {
+        let mut info = TcpInfo::default();
+        let mut len = core::mem::size_of::<TcpInfo>() as libc::socklen_t;
+        // SAFETY: the kernel writes at most `len` bytes of its `struct tcp_info` in `info`, whose
+        // layout matches the beginning of it
+        let ret = unsafe {
+            libc::getsockopt(
+                stream.as_raw_fd(),
+                libc::IPPROTO_TCP,
+                libc::TCP_INFO,
+                (&mut info as *mut TcpInfo).cast(),
+                &mut len,
+            )
+        };
+        if ret != 0 || (len as usize) < core::mem::size_of::<TcpInfo>() {
+            return None;
+        }
+        Some(Duration::from_micros(info.tcpi_rtt.into()))
+    }
+}
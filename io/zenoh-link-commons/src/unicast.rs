@@ -17,7 +17,7 @@ use core::{
     hash::{Hash, Hasher},
     ops::Deref,
 };
-use std::net::SocketAddr;
+use std::{net::SocketAddr, time::Duration};
 
 use async_trait::async_trait;
 use serde::Serialize;
@@ -27,6 +27,8 @@ use zenoh_protocol::{
 };
 use zenoh_result::ZResult;
 
+use crate::tcp::TcpSocketInfo;
+
 pub type LinkManagerUnicast = Arc<dyn LinkManagerUnicastTrait>;
 #[async_trait]
 pub trait LinkManagerUnicastTrait: Send + Sync {
@@ -54,6 +56,14 @@ pub trait LinkUnicastTrait: Send + Sync {
     fn is_streamed(&self) -> bool;
     fn get_interface_names(&self) -> Vec<String>;
     fn get_auth_id(&self) -> &LinkAuthId;
+    /// The effective socket options of TCP-based links.
+    fn get_tcp_info(&self) -> Option<&TcpSocketInfo> {
+        None
+    }
+    /// The round-trip time currently measured on the link, if the link measures it.
+    fn get_rtt(&self) -> Option<Duration> {
+        None
+    }
     async fn write(&self, buffer: &[u8]) -> ZResult<usize>;
     async fn write_all(&self, buffer: &[u8]) -> ZResult<()>;
     async fn read(&self, buffer: &mut [u8]) -> ZResult<usize>;
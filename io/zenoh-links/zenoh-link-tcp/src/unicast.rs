@@ -16,12 +16,14 @@ use std::{cell::UnsafeCell, convert::TryInto, fmt, net::SocketAddr, sync::Arc, t
 use async_trait::async_trait;
 use tokio::{
     io::{AsyncReadExt, AsyncWriteExt},
-    net::{TcpListener, TcpSocket, TcpStream},
+    net::{TcpListener, TcpStream},
 };
 use tokio_util::sync::CancellationToken;
 use zenoh_link_commons::{
-    get_ip_interface_names, LinkAuthId, LinkManagerUnicastTrait, LinkUnicast, LinkUnicastTrait,
-    ListenersUnicastIP, NewLinkChannelSender, BIND_INTERFACE,
+    get_ip_interface_names,
+    tcp::{tcp_rtt, TcpSocketConfig, TcpSocketInfo},
+    LinkAuthId, LinkManagerUnicastTrait, LinkUnicast, LinkUnicastTrait, ListenersUnicastIP,
+    NewLinkChannelSender, BIND_INTERFACE,
 };
 use zenoh_protocol::{
     core::{EndPoint, Locator},
@@ -45,12 +47,19 @@ pub struct LinkUnicastTcp {
     dst_locator: Locator,
     // The computed mtu
     mtu: BatchSize,
+    // The effective socket options
+    tcp_info: TcpSocketInfo,
 }
 
 unsafe impl Sync for LinkUnicastTcp {}
 
 impl LinkUnicastTcp {
-    fn new(socket: TcpStream, src_addr: SocketAddr, dst_addr: SocketAddr) -> LinkUnicastTcp {
+    fn new(
+        socket: TcpStream,
+        src_addr: SocketAddr,
+        dst_addr: SocketAddr,
+        tcp_info: TcpSocketInfo,
+    ) -> LinkUnicastTcp {
         // Set the TCP nodelay option
         if let Err(err) = socket.set_nodelay(true) {
             tracing::warn!(
@@ -104,6 +113,7 @@ impl LinkUnicastTcp {
             dst_addr,
             dst_locator: Locator::new(TCP_LOCATOR_PREFIX, dst_addr.to_string(), "").unwrap(),
             mtu,
+            tcp_info,
         }
     }
 
@@ -111,6 +121,10 @@ impl LinkUnicastTcp {
     fn get_mut_socket(&self) -> &mut TcpStream {
         unsafe { &mut *self.socket.get() }
     }
+
+    fn get_socket(&self) -> &TcpStream {
+        unsafe { &*self.socket.get() }
+    }
 }
 
 #[async_trait]
@@ -196,6 +210,16 @@ impl LinkUnicastTrait for LinkUnicastTcp {
     fn get_auth_id(&self) -> &LinkAuthId {
         &LinkAuthId::NONE
     }
+
+    #[inline(always)]
+    fn get_tcp_info(&self) -> Option<&TcpSocketInfo> {
+        Some(&self.tcp_info)
+    }
+
+    #[inline(always)]
+    fn get_rtt(&self) -> Option<Duration> {
+        tcp_rtt(self.get_socket())
+    }
 }
 
 // // WARN: This sometimes causes timeout in routing test
@@ -241,82 +265,20 @@ impl LinkManagerUnicastTcp {
     }
 }
 
-impl LinkManagerUnicastTcp {
-    async fn new_link_inner(
-        &self,
-        dst_addr: &SocketAddr,
-        iface: Option<&str>,
-    ) -> ZResult<(TcpStream, SocketAddr, SocketAddr)> {
-        let socket = match dst_addr {
-            SocketAddr::V4(_) => TcpSocket::new_v4(),
-            SocketAddr::V6(_) => TcpSocket::new_v6(),
-        }?;
-
-        if let Some(iface) = iface {
-            zenoh_util::net::set_bind_to_device_tcp_socket(&socket, iface)?;
-        }
-
-        // Build a TcpStream from TcpSocket
-        // https://docs.rs/tokio/latest/tokio/net/struct.TcpSocket.html
-        let stream = socket
-            .connect(*dst_addr)
-            .await
-            .map_err(|e| zerror!("{}: {}", dst_addr, e))?;
-
-        let src_addr = stream
-            .local_addr()
-            .map_err(|e| zerror!("{}: {}", dst_addr, e))?;
-
-        let dst_addr = stream
-            .peer_addr()
-            .map_err(|e| zerror!("{}: {}", dst_addr, e))?;
-
-        Ok((stream, src_addr, dst_addr))
-    }
-
-    async fn new_listener_inner(
-        &self,
-        addr: &SocketAddr,
-        iface: Option<&str>,
-    ) -> ZResult<(TcpListener, SocketAddr)> {
-        let socket = match addr {
-            SocketAddr::V4(_) => TcpSocket::new_v4(),
-            SocketAddr::V6(_) => TcpSocket::new_v6(),
-        }?;
-
-        if let Some(iface) = iface {
-            zenoh_util::net::set_bind_to_device_tcp_socket(&socket, iface)?;
-        }
-
-        // Build a TcpListener from TcpSocket
-        // https://docs.rs/tokio/latest/tokio/net/struct.TcpSocket.html
-        socket.set_reuseaddr(true)?;
-        socket.bind(*addr).map_err(|e| zerror!("{}: {}", addr, e))?;
-        // backlog (the maximum number of pending connections are queued): 1024
-        let listener = socket
-            .listen(1024)
-            .map_err(|e| zerror!("{}: {}", addr, e))?;
-
-        let local_addr = listener
-            .local_addr()
-            .map_err(|e| zerror!("{}: {}", addr, e))?;
-
-        Ok((listener, local_addr))
-    }
-}
-
 #[async_trait]
 impl LinkManagerUnicastTrait for LinkManagerUnicastTcp {
     async fn new_link(&self, endpoint: EndPoint) -> ZResult<LinkUnicast> {
         let dst_addrs = get_tcp_addrs(endpoint.address()).await?;
         let config = endpoint.config();
         let iface = config.get(BIND_INTERFACE);
+        let socket_config = TcpSocketConfig::new(&config)?;
 
         let mut errs: Vec<ZError> = vec![];
         for da in dst_addrs {
-            match self.new_link_inner(&da, iface).await {
+            match socket_config.new_link(&da, iface).await {
                 Ok((stream, src_addr, dst_addr)) => {
-                    let link = Arc::new(LinkUnicastTcp::new(stream, src_addr, dst_addr));
+                    let tcp_info = TcpSocketInfo::new(&stream);
+                    let link = Arc::new(LinkUnicastTcp::new(stream, src_addr, dst_addr, tcp_info));
                     return Ok(LinkUnicast(link));
                 }
                 Err(e) => {
@@ -340,10 +302,11 @@ impl LinkManagerUnicastTrait for LinkManagerUnicastTcp {
         let addrs = get_tcp_addrs(endpoint.address()).await?;
         let config = endpoint.config();
         let iface = config.get(BIND_INTERFACE);
+        let socket_config = TcpSocketConfig::new(&config)?;
 
         let mut errs: Vec<ZError> = vec![];
         for da in addrs {
-            match self.new_listener_inner(&da, iface).await {
+            match socket_config.new_listener(&da, iface) {
                 Ok((socket, local_addr)) => {
                     // Update the endpoint locator address
                     endpoint = EndPoint::new(
@@ -358,8 +321,9 @@ impl LinkManagerUnicastTrait for LinkManagerUnicastTcp {
                     let task = {
                         let token = token.clone();
                         let manager = self.manager.clone();
+                        let socket_config = socket_config.clone();
 
-                        async move { accept_task(socket, token, manager).await }
+                        async move { accept_task(socket, socket_config, token, manager).await }
                     };
 
                     let locator = endpoint.to_locator();
@@ -425,6 +389,7 @@ impl LinkManagerUnicastTrait for LinkManagerUnicastTcp {
 
 async fn accept_task(
     socket: TcpListener,
+    socket_config: TcpSocketConfig,
     token: CancellationToken,
     manager: NewLinkChannelSender,
 ) -> ZResult<()> {
@@ -457,7 +422,8 @@ async fn accept_task(
 
                         tracing::debug!("Accepted TCP connection on {:?}: {:?}", src_addr, dst_addr);
                         // Create the new link object
-                        let link = Arc::new(LinkUnicastTcp::new(stream, src_addr, dst_addr));
+                        let tcp_info = TcpSocketInfo::from_accepted(&stream, &socket_config);
+                        let link = Arc::new(LinkUnicastTcp::new(stream, src_addr, dst_addr, tcp_info));
 
                         // Communicate the new link to the initial transport manager
                         if let Err(e) = manager.send_async(LinkUnicast(link)).await {
@@ -24,8 +24,10 @@ use tokio_util::sync::CancellationToken;
 use x509_parser::prelude::*;
 use zenoh_core::zasynclock;
 use zenoh_link_commons::{
-    get_ip_interface_names, LinkAuthId, LinkAuthType, LinkManagerUnicastTrait, LinkUnicast,
-    LinkUnicastTrait, ListenersUnicastIP, NewLinkChannelSender,
+    get_ip_interface_names,
+    tcp::{tcp_rtt, TcpSocketConfig, TcpSocketInfo},
+    LinkAuthId, LinkAuthType, LinkManagerUnicastTrait, LinkUnicast, LinkUnicastTrait,
+    ListenersUnicastIP, NewLinkChannelSender,
 };
 use zenoh_protocol::{
     core::{EndPoint, Locator},
@@ -62,6 +64,7 @@ pub struct LinkUnicastTls {
     read_mtx: AsyncMutex<()>,
     auth_identifier: LinkAuthId,
     mtu: BatchSize,
+    tcp_info: TcpSocketInfo,
 }
 
 unsafe impl Send for LinkUnicastTls {}
@@ -73,6 +76,7 @@ impl LinkUnicastTls {
         src_addr: SocketAddr,
         dst_addr: SocketAddr,
         auth_identifier: LinkAuthId,
+        tcp_info: TcpSocketInfo,
     ) -> LinkUnicastTls {
         let (tcp_stream, _) = socket.get_ref();
         // Set the TLS nodelay option
@@ -131,6 +135,7 @@ impl LinkUnicastTls {
             read_mtx: AsyncMutex::new(()),
             auth_identifier,
             mtu,
+            tcp_info,
         }
     }
 
@@ -141,6 +146,10 @@ impl LinkUnicastTls {
     fn get_mut_socket(&self) -> &mut TlsStream<TcpStream> {
         unsafe { &mut *self.inner.get() }
     }
+
+    fn get_socket(&self) -> &TlsStream<TcpStream> {
+        unsafe { &*self.inner.get() }
+    }
 }
 
 #[async_trait]
@@ -230,6 +239,17 @@ impl LinkUnicastTrait for LinkUnicastTls {
     fn get_auth_id(&self) -> &LinkAuthId {
         &self.auth_identifier
     }
+
+    #[inline(always)]
+    fn get_tcp_info(&self) -> Option<&TcpSocketInfo> {
+        Some(&self.tcp_info)
+    }
+
+    #[inline(always)]
+    fn get_rtt(&self) -> Option<Duration> {
+        let (tcp_stream, _) = self.get_socket().get_ref();
+        tcp_rtt(tcp_stream)
+    }
 }
 
 impl Drop for LinkUnicastTls {
@@ -288,29 +308,16 @@ impl LinkManagerUnicastTrait for LinkManagerUnicastTls {
         let connector = TlsConnector::from(config);
 
         // Initialize the TcpStream
-        let tcp_stream = TcpStream::connect(addr).await.map_err(|e| {
-            zerror!(
-                "Can not create a new TLS link bound to {:?}: {}",
-                server_name,
-                e
-            )
-        })?;
-
-        let src_addr = tcp_stream.local_addr().map_err(|e| {
-            zerror!(
-                "Can not create a new TLS link bound to {:?}: {}",
-                server_name,
-                e
-            )
-        })?;
-
-        let dst_addr = tcp_stream.peer_addr().map_err(|e| {
-            zerror!(
-                "Can not create a new TLS link bound to {:?}: {}",
-                server_name,
-                e
-            )
-        })?;
+        let socket_config = TcpSocketConfig::new(&epconf)?;
+        let (tcp_stream, src_addr, dst_addr) =
+            socket_config.new_link(&addr, None).await.map_err(|e| {
+                zerror!(
+                    "Can not create a new TLS link bound to {:?}: {}",
+                    server_name,
+                    e
+                )
+            })?;
+        let tcp_info = TcpSocketInfo::new(&tcp_stream);
 
         // Initialize the TlsStream
         let tls_stream = connector
@@ -334,6 +341,7 @@ impl LinkManagerUnicastTrait for LinkManagerUnicastTls {
             src_addr,
             dst_addr,
             auth_identifier.into(),
+            tcp_info,
         ));
 
         Ok(LinkUnicast(link))
@@ -351,13 +359,10 @@ impl LinkManagerUnicastTrait for LinkManagerUnicastTls {
             .await
             .map_err(|e| zerror!("Cannot create a new TLS listener on {addr}. {e}"))?;
 
-        // Initialize the TcpListener
-        let socket = TcpListener::bind(addr)
-            .await
-            .map_err(|e| zerror!("Can not create a new TLS listener on {}: {}", addr, e))?;
-
-        let local_addr = socket
-            .local_addr()
+        // Initialize the TcpListener, with SO_REUSEADDR set like for the TCP listeners
+        let socket_config = TcpSocketConfig::new(&epconf)?;
+        let (socket, local_addr) = socket_config
+            .new_listener(&addr, None)
             .map_err(|e| zerror!("Can not create a new TLS listener on {}: {}", addr, e))?;
         let local_port = local_addr.port();
 
@@ -372,6 +377,7 @@ impl LinkManagerUnicastTrait for LinkManagerUnicastTls {
             async move {
                 accept_task(
                     socket,
+                    socket_config,
                     acceptor,
                     token,
                     manager,
@@ -412,6 +418,7 @@ impl LinkManagerUnicastTrait for LinkManagerUnicastTls {
 
 async fn accept_task(
     socket: TcpListener,
+    socket_config: TcpSocketConfig,
     acceptor: TlsAcceptor,
     token: CancellationToken,
     manager: NewLinkChannelSender,
@@ -444,6 +451,7 @@ async fn accept_task(
                             }
                         };
                         let auth_identifier = get_client_cert_common_name(tls_conn)?;
+                        let tcp_info = TcpSocketInfo::from_accepted(tcp_stream, &socket_config);
 
                         tracing::debug!("Accepted TLS connection on {:?}: {:?}", src_addr, dst_addr);
                         // Create the new link object
@@ -452,6 +460,7 @@ async fn accept_task(
                             src_addr,
                             dst_addr,
                             auth_identifier.into(),
+                            tcp_info,
                         ));
 
                         // Communicate the new link to the initial transport manager
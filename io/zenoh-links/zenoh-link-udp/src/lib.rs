@@ -107,6 +107,14 @@ impl LocatorInspector for UdpLocatorInspector {
 pub mod config {
     pub const UDP_MULTICAST_IFACE: &str = "iface";
     pub const UDP_MULTICAST_JOIN: &str = "join";
+    /// Source addresses for a source-specific multicast (SSM) join, separated by `|`.
+    pub const UDP_MULTICAST_SOURCE: &str = "source";
+    /// IPv6 interface index (or interface name) used to join and send on the multicast group.
+    pub const UDP_MULTICAST_SCOPE_ID: &str = "scope_id";
+    /// Time-to-live (IPv4) or hop limit (IPv6) of the sent multicast packets.
+    pub const UDP_MULTICAST_TTL: &str = "ttl";
+    /// Whether sent multicast packets are looped back to the local host.
+    pub const UDP_MULTICAST_LOOPBACK: &str = "loopback";
 }
 
 pub async fn get_udp_addrs(address: Address<'_>) -> ZResult<impl Iterator<Item = SocketAddr>> {
@@ -42,6 +42,10 @@ pub struct LinkMulticastUdp {
     multicast_locator: Locator,
     // The multicast UDP socket used for read operations
     mcast_sock: UdpSocket,
+    // The sources of a source-specific multicast join, if any
+    sources: Vec<IpAddr>,
+    // The IPv6 interface index the multicast group has been joined on
+    scope_id: u32,
 }
 
 impl LinkMulticastUdp {
@@ -50,6 +54,8 @@ impl LinkMulticastUdp {
         unicast_socket: UdpSocket,
         multicast_addr: SocketAddr,
         mcast_sock: UdpSocket,
+        sources: Vec<IpAddr>,
+        scope_id: u32,
     ) -> LinkMulticastUdp {
         LinkMulticastUdp {
             unicast_locator: socket_addr_to_udp_locator(&unicast_addr),
@@ -58,6 +64,8 @@ impl LinkMulticastUdp {
             unicast_socket,
             multicast_addr,
             mcast_sock,
+            sources,
+            scope_id,
         }
     }
 }
@@ -67,11 +75,18 @@ impl LinkMulticastTrait for LinkMulticastUdp {
     async fn close(&self) -> ZResult<()> {
         tracing::trace!("Closing UDP link: {}", self);
         match self.multicast_addr.ip() {
-            IpAddr::V4(dst_ip4) => match self.multicast_addr.ip() {
+            IpAddr::V4(dst_ip4) => match self.unicast_addr.ip() {
+                IpAddr::V4(src_ip4) if !self.sources.is_empty() => {
+                    let sock = socket2::SockRef::from(&self.mcast_sock);
+                    self.sources.iter().try_for_each(|source| match source {
+                        IpAddr::V4(source) => sock.leave_ssm_v4(source, &dst_ip4, &src_ip4),
+                        IpAddr::V6(_) => unreachable!(),
+                    })
+                }
                 IpAddr::V4(src_ip4) => self.mcast_sock.leave_multicast_v4(dst_ip4, src_ip4),
                 IpAddr::V6(_) => unreachable!(),
             },
-            IpAddr::V6(dst_ip6) => self.mcast_sock.leave_multicast_v6(&dst_ip6, 0),
+            IpAddr::V6(dst_ip6) => self.mcast_sock.leave_multicast_v6(&dst_ip6, self.scope_id),
         }
         .map_err(|e| {
             let e = zerror!("Close error on UDP link {}: {}", self, e);
@@ -181,7 +196,7 @@ impl LinkManagerMulticastUdp {
         &self,
         mcast_addr: &SocketAddr,
         config: Config<'_>,
-    ) -> ZResult<(UdpSocket, UdpSocket, SocketAddr)> {
+    ) -> ZResult<(UdpSocket, UdpSocket, SocketAddr, Vec<IpAddr>, u32)> {
         let domain = match mcast_addr.ip() {
             IpAddr::V4(_) => Domain::IPV4,
             IpAddr::V6(_) => Domain::IPV6,
@@ -233,6 +248,35 @@ impl LinkManagerMulticastUdp {
             }
         };
 
+        // Get the explicit IPv6 scope, either from the configuration or from the address itself
+        let scope_id = match config.get(UDP_MULTICAST_SCOPE_ID) {
+            Some(s) => Some(parse_scope_id(s).map_err(|e| zerror!("{}: {}", mcast_addr, e))?),
+            None => match mcast_addr {
+                SocketAddr::V6(addr) if addr.scope_id() != 0 => Some(addr.scope_id()),
+                _ => None,
+            },
+        };
+
+        // Get the sources of a source-specific multicast join
+        let sources = config
+            .values(UDP_MULTICAST_SOURCE)
+            .map(|s| {
+                s.parse::<IpAddr>()
+                    .map_err(|e| zerror!("{}: invalid source '{}': {}", mcast_addr, s, e))
+            })
+            .collect::<Result<Vec<IpAddr>, _>>()?;
+        if !sources.is_empty() {
+            if mcast_addr.is_ipv6() {
+                bail!(
+                    "{}: source-specific multicast is only supported on IPv4 groups",
+                    mcast_addr
+                );
+            }
+            if let Some(s) = sources.iter().find(|s| s.is_ipv6()) {
+                bail!("{}: unexpected IPv6 source address {}", mcast_addr, s);
+            }
+        }
+
         // Establish a unicast UDP socket
         let ucast_sock = Socket::new(domain, Type::DGRAM, Some(Protocol::UDP))
             .map_err(|e| zerror!("{}: {}", mcast_addr, e))?;
@@ -242,7 +286,10 @@ impl LinkManagerMulticastUdp {
                     .set_multicast_if_v4(addr)
                     .map_err(|e| zerror!("{}: {}", mcast_addr, e))?;
             }
-            IpAddr::V6(_) => match zenoh_util::net::get_index_of_interface(local_addr) {
+            IpAddr::V6(_) => match scope_id
+                .map(Ok)
+                .unwrap_or_else(|| zenoh_util::net::get_index_of_interface(local_addr))
+            {
                 Ok(idx) => ucast_sock
                     .set_multicast_if_v6(idx)
                     .map_err(|e| zerror!("{}: {}", mcast_addr, e))?,
@@ -250,6 +297,40 @@ impl LinkManagerMulticastUdp {
             },
         }
 
+        if let Some(ttl) = config.get(UDP_MULTICAST_TTL) {
+            let ttl: u32 = ttl.parse().map_err(|e| {
+                zerror!(
+                    "{}: invalid {} '{}': {}",
+                    mcast_addr,
+                    UDP_MULTICAST_TTL,
+                    ttl,
+                    e
+                )
+            })?;
+            match mcast_addr.ip() {
+                IpAddr::V4(_) => ucast_sock.set_multicast_ttl_v4(ttl),
+                IpAddr::V6(_) => ucast_sock.set_multicast_hops_v6(ttl),
+            }
+            .map_err(|e| zerror!("{}: {}", mcast_addr, e))?;
+        }
+
+        if let Some(lo) = config.get(UDP_MULTICAST_LOOPBACK) {
+            let lo: bool = lo.parse().map_err(|e| {
+                zerror!(
+                    "{}: invalid {} '{}': {}",
+                    mcast_addr,
+                    UDP_MULTICAST_LOOPBACK,
+                    lo,
+                    e
+                )
+            })?;
+            match mcast_addr.ip() {
+                IpAddr::V4(_) => ucast_sock.set_multicast_loop_v4(lo),
+                IpAddr::V6(_) => ucast_sock.set_multicast_loop_v6(lo),
+            }
+            .map_err(|e| zerror!("{}: {}", mcast_addr, e))?;
+        }
+
         ucast_sock
             .bind(&SocketAddr::new(local_addr, 0).into())
             .map_err(|e| zerror!("{}: {}", mcast_addr, e))?;
@@ -287,31 +368,39 @@ impl LinkManagerMulticastUdp {
         match mcast_addr.ip() {
             IpAddr::V4(dst_ip4) => match local_addr {
                 IpAddr::V4(src_ip4) => {
+                    let join_v4 = |g: &Ipv4Addr| -> ZResult<()> {
+                        if sources.is_empty() {
+                            mcast_sock.join_multicast_v4(g, &src_ip4)
+                        } else {
+                            sources.iter().try_for_each(|s| match s {
+                                IpAddr::V4(s) => mcast_sock.join_ssm_v4(s, g, &src_ip4),
+                                IpAddr::V6(_) => unreachable!(),
+                            })
+                        }
+                        .map_err(|e| zerror!("{}: {}", mcast_addr, e).into())
+                    };
                     // Join default multicast group
-                    mcast_sock
-                        .join_multicast_v4(&dst_ip4, &src_ip4)
-                        .map_err(|e| zerror!("{}: {}", mcast_addr, e))?;
+                    join_v4(&dst_ip4)?;
                     // Join any additional multicast group
                     for g in join {
                         let g: Ipv4Addr =
                             g.parse().map_err(|e| zerror!("{}: {}", mcast_addr, e))?;
-                        mcast_sock
-                            .join_multicast_v4(&g, &src_ip4)
-                            .map_err(|e| zerror!("{}: {}", mcast_addr, e))?;
+                        join_v4(&g)?;
                     }
                 }
                 IpAddr::V6(src_ip6) => bail!("{}: unexpected IPv6 source address", src_ip6),
             },
             IpAddr::V6(dst_ip6) => {
+                let idx = scope_id.unwrap_or(0);
                 // Join default multicast group
                 mcast_sock
-                    .join_multicast_v6(&dst_ip6, 0)
+                    .join_multicast_v6(&dst_ip6, idx)
                     .map_err(|e| zerror!("{}: {}", mcast_addr, e))?;
                 // Join any additional multicast group
                 for g in join {
                     let g: Ipv6Addr = g.parse().map_err(|e| zerror!("{}: {}", mcast_addr, e))?;
                     mcast_sock
-                        .join_multicast_v6(&g, 0)
+                        .join_multicast_v6(&g, idx)
                         .map_err(|e| zerror!("{}: {}", mcast_addr, e))?;
                 }
             }
@@ -329,8 +418,32 @@ impl LinkManagerMulticastUdp {
             .map_err(|e| zerror!("{}: {}", mcast_addr, e))?;
         assert_eq!(ucast_addr.ip(), local_addr);
 
-        Ok((mcast_sock, ucast_sock, ucast_addr))
+        Ok((
+            mcast_sock,
+            ucast_sock,
+            ucast_addr,
+            sources,
+            scope_id.unwrap_or(0),
+        ))
+    }
+}
+
+fn parse_scope_id(s: &str) -> ZResult<u32> {
+    if let Ok(idx) = s.parse::<u32>() {
+        return Ok(idx);
     }
+    // Resolve the index of the interface by name
+    let addr = zenoh_util::net::get_unicast_addresses_of_interface(s)?
+        .into_iter()
+        .next()
+        .ok_or_else(|| {
+            zerror!(
+                "invalid {} '{}': no such interface",
+                UDP_MULTICAST_SCOPE_ID,
+                s
+            )
+        })?;
+    zenoh_util::net::get_index_of_interface(addr)
 }
 
 #[async_trait]
@@ -344,9 +457,9 @@ impl LinkManagerMulticastTrait for LinkManagerMulticastUdp {
         let mut errs: Vec<ZError> = vec![];
         for maddr in mcast_addrs {
             match self.new_link_inner(&maddr, endpoint.config()).await {
-                Ok((mcast_sock, ucast_sock, ucast_addr)) => {
+                Ok((mcast_sock, ucast_sock, ucast_addr, sources, scope_id)) => {
                     let link = Arc::new(LinkMulticastUdp::new(
-                        ucast_addr, ucast_sock, maddr, mcast_sock,
+                        ucast_addr, ucast_sock, maddr, mcast_sock, sources, scope_id,
                     ));
 
                     return Ok(LinkMulticast(link));
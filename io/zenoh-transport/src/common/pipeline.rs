@@ -884,6 +884,7 @@ mod tests {
                 ext_qos: ext::QoSType::new(Priority::Control, CongestionControl::Block, false),
                 ext_tstamp: None,
                 ext_nodeid: ext::NodeIdType::DEFAULT,
+                ext_redundancy: None,
                 payload: PushBody::Put(Put {
                     timestamp: None,
                     encoding: Encoding::empty(),
@@ -1012,6 +1013,7 @@ mod tests {
                 ext_qos: ext::QoSType::new(Priority::Control, CongestionControl::Block, false),
                 ext_tstamp: None,
                 ext_nodeid: ext::NodeIdType::DEFAULT,
+                ext_redundancy: None,
                 payload: PushBody::Put(Put {
                     timestamp: None,
                     encoding: Encoding::empty(),
@@ -1129,6 +1131,7 @@ mod tests {
                         ),
                         ext_tstamp: None,
                         ext_nodeid: ext::NodeIdType::DEFAULT,
+                        ext_redundancy: None,
                         payload: PushBody::Put(Put {
                             timestamp: None,
                             encoding: Encoding::empty(),
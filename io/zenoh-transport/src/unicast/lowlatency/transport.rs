@@ -183,6 +183,13 @@ impl TransportUnicastTrait for TransportUnicastLowlatency {
         self.config.zid
     }
 
+    fn get_rtt(&self) -> Option<Duration> {
+        let handle = tokio::runtime::Handle::current();
+        let guard =
+            tokio::task::block_in_place(|| handle.block_on(async { zasyncread!(self.link) }));
+        guard.as_ref().and_then(|val| val.link.get_rtt())
+    }
+
     fn get_auth_ids(&self) -> Vec<AuthId> {
         // Convert LinkUnicast auth id to AuthId
         let mut auth_ids: Vec<AuthId> = vec![];
@@ -78,6 +78,8 @@ pub struct TransportManagerStateUnicast {
     pub(super) incoming: Arc<AtomicUsize>,
     // Established listeners
     pub(super) protocols: Arc<AsyncMutex<HashMap<String, LinkManagerUnicast>>>,
+    // Listeners with simulated link impairments
+    pub(super) sim_listeners: Arc<AsyncMutex<Vec<(Locator, LinkSimConfig)>>>,
     // Established transports
     pub(super) transports: Arc<AsyncMutex<HashMap<ZenohIdProto, Arc<dyn TransportUnicastTrait>>>>,
     // Multilink
@@ -241,6 +243,7 @@ impl TransportManagerBuilderUnicast {
         let state = TransportManagerStateUnicast {
             incoming: Arc::new(AtomicUsize::new(0)),
             protocols: Arc::new(AsyncMutex::new(HashMap::new())),
+            sim_listeners: Arc::new(AsyncMutex::new(Vec::new())),
             transports: Arc::new(AsyncMutex::new(HashMap::new())),
             #[cfg(feature = "transport_multilink")]
             multilink: Arc::new(MultiLink::make(prng, config.max_links > 1)?),
@@ -389,7 +392,12 @@ impl TransportManager {
                 .config_mut()
                 .extend_from_iter(parameters::iter(config))?;
         };
-        manager.new_listener(endpoint).await
+        let sim = LinkSimConfig::take_from_endpoint(&mut endpoint)?;
+        let locator = manager.new_listener(endpoint).await?;
+        if let Some(sim) = sim {
+            zasynclock!(self.state.unicast.sim_listeners).push((locator.clone(), sim));
+        }
+        Ok(locator)
     }
 
     pub async fn del_listener_unicast(&self, endpoint: &EndPoint) -> ZResult<()> {
@@ -397,7 +405,17 @@ impl TransportManager {
             .get_link_manager_unicast(endpoint.protocol().as_str())
             .await?;
         lm.del_listener(endpoint).await?;
-        if lm.get_listeners().await.is_empty() {
+        // The simulation configs are registered with the bound locators, which may differ from
+        // the endpoint (e.g. with port 0): keep the ones of the remaining listeners
+        let listeners: Vec<Locator> = lm
+            .get_listeners()
+            .await
+            .iter()
+            .map(EndPoint::to_locator)
+            .collect();
+        zasynclock!(self.state.unicast.sim_listeners)
+            .retain(|(l, _)| l.protocol() != endpoint.protocol() || listeners.contains(l));
+        if listeners.is_empty() {
             self.del_link_manager_unicast(endpoint.protocol().as_str())
                 .await?;
         }
@@ -709,9 +727,10 @@ impl TransportManager {
                 .config_mut()
                 .extend_from_iter(parameters::iter(config))?;
         };
+        let sim = LinkSimConfig::take_from_endpoint(&mut endpoint)?;
 
         // Create a new link associated by calling the Link Manager
-        let link = manager.new_link(endpoint.clone()).await?;
+        let link = LinkUnicastSim::wrap(manager.new_link(endpoint.clone()).await?, sim);
         // Open the link
         super::establishment::open::open_link(endpoint, link, self).await
     }
@@ -753,6 +772,13 @@ impl TransportManager {
             return;
         }
 
+        // Apply the simulated impairments of the listener, if any
+        let sim = zasynclock!(self.state.unicast.sim_listeners)
+            .iter()
+            .find(|(l, _)| is_accepted_by(l, link.get_src()))
+            .map(|(_, s)| s.clone());
+        let link = LinkUnicastSim::wrap(link, sim);
+
         // A new link is available
         tracing::trace!("Accepting link... {}", link);
         self.state.unicast.incoming.fetch_add(1, SeqCst);
@@ -24,6 +24,7 @@ pub(crate) mod universal;
 use std::{
     fmt,
     sync::{Arc, Weak},
+    time::Duration,
 };
 
 #[cfg(feature = "transport_multilink")]
@@ -97,6 +98,18 @@ impl TransportUnicast {
         Ok(transport.is_shm())
     }
 
+    #[inline(always)]
+    pub fn is_qos(&self) -> ZResult<bool> {
+        let transport = self.get_inner()?;
+        Ok(transport.is_qos())
+    }
+
+    #[inline(always)]
+    pub fn is_lowlatency(&self) -> ZResult<bool> {
+        let transport = self.get_inner()?;
+        Ok(transport.get_config().is_lowlatency)
+    }
+
     #[inline(always)]
     pub fn get_callback(&self) -> ZResult<Option<Arc<dyn TransportPeerEventHandler>>> {
         let transport = self.get_inner()?;
@@ -122,6 +135,13 @@ impl TransportUnicast {
         Ok(transport.get_links())
     }
 
+    /// Returns the smallest round-trip time currently measured on the links of the transport.
+    #[inline(always)]
+    pub fn get_rtt(&self) -> ZResult<Option<Duration>> {
+        let transport = self.get_inner()?;
+        Ok(transport.get_rtt())
+    }
+
     pub fn get_auth_ids(&self) -> ZResult<Vec<AuthId>> {
         let transport = self.get_inner()?;
         Ok(transport.get_auth_ids())
@@ -381,6 +381,13 @@ impl TransportUnicastTrait for TransportUnicastUniversal {
         zread!(self.links).iter().map(|l| l.link.link()).collect()
     }
 
+    fn get_rtt(&self) -> Option<Duration> {
+        zread!(self.links)
+            .iter()
+            .filter_map(|l| l.link.link.get_rtt())
+            .min()
+    }
+
     fn get_auth_ids(&self) -> Vec<AuthId> {
         // Convert LinkUnicast auth ids to AuthId
         #[allow(unused_mut)]
@@ -262,6 +262,7 @@ mod tests {
             ext_qos: QoSType::new(channel.priority, CongestionControl::Block, false),
             ext_tstamp: None,
             ext_nodeid: NodeIdType::DEFAULT,
+            ext_redundancy: None,
             payload: Put {
                 payload: vec![0u8; msg_size].into(),
                 timestamp: None,
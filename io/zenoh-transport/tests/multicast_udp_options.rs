@@ -0,0 +1,131 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+#![cfg(all(target_os = "linux", feature = "transport_udp"))]
+use std::time::Duration;
+
+use zenoh_core::ztimeout;
+use zenoh_link::{LinkManagerBuilderMulticast, LinkMulticast};
+use zenoh_protocol::core::EndPoint;
+use zenoh_result::ZResult;
+
+const TIMEOUT: Duration = Duration::from_secs(60);
+const NO_DATA: Duration = Duration::from_millis(500);
+
+async fn new_link(endpoint: &str) -> ZResult<LinkMulticast> {
+    let endpoint: EndPoint = endpoint.parse().unwrap();
+    let manager = LinkManagerBuilderMulticast::make(endpoint.protocol().as_str())?;
+    manager.new_link(&endpoint).await
+}
+
+// Whether `dst` receives the data written by `src` on their multicast group
+async fn receives(src: &LinkMulticast, dst: &LinkMulticast) -> bool {
+    src.write_all(b"zenoh").await.unwrap();
+    let mut buffer = [0u8; 16];
+    match tokio::time::timeout(NO_DATA, dst.read(&mut buffer)).await {
+        Ok(res) => {
+            let (n, _) = res.unwrap();
+            &buffer[..n] == b"zenoh"
+        }
+        Err(_) => false,
+    }
+}
+
+fn assert_error(res: ZResult<LinkMulticast>, expected: &str) {
+    match res {
+        Ok(_) => panic!("link creation should fail with '{expected}'"),
+        Err(e) => assert!(
+            e.to_string().contains(expected),
+            "'{e}' doesn't contain '{expected}'"
+        ),
+    }
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn udp_multicast_invalid_options() {
+    zenoh_util::init_log_from_env_or("error");
+    for (endpoint, expected) in [
+        ("udp/224.0.0.224:17520#iface=lo;ttl=-1", "invalid ttl"),
+        ("udp/224.0.0.224:17520#iface=lo;ttl=often", "invalid ttl"),
+        (
+            "udp/224.0.0.224:17520#iface=lo;loopback=1",
+            "invalid loopback",
+        ),
+        (
+            "udp/224.0.0.224:17520#iface=lo;source=host",
+            "invalid source",
+        ),
+        (
+            "udp/232.1.1.1:17520#iface=lo;source=127.0.0.1|::1",
+            "unexpected IPv6 source",
+        ),
+        (
+            "udp/[ff02::1:7]:17520#source=127.0.0.1",
+            "only supported on IPv4 groups",
+        ),
+        (
+            "udp/[ff02::1:7]:17520#scope_id=no-such-iface0",
+            "no-such-iface0",
+        ),
+    ] {
+        assert_error(ztimeout!(new_link(endpoint)), expected);
+    }
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn udp_multicast_ttl_loopback() {
+    zenoh_util::init_log_from_env_or("error");
+    // Multicast packets are only received by the other local sockets when looped back. The packets
+    // sent on the loopback interface are always received, so the default interface is used.
+    let dst = ztimeout!(new_link("udp/224.0.0.225:17521")).unwrap();
+    let src = ztimeout!(new_link("udp/224.0.0.225:17521#ttl=4;loopback=true")).unwrap();
+    assert!(receives(&src, &dst).await);
+    ztimeout!(src.close()).unwrap();
+
+    let src = ztimeout!(new_link("udp/224.0.0.225:17521#ttl=4;loopback=false")).unwrap();
+    assert!(!receives(&src, &dst).await);
+    ztimeout!(src.close()).unwrap();
+    ztimeout!(dst.close()).unwrap();
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn udp_multicast_source_specific() {
+    zenoh_util::init_log_from_env_or("error");
+    let src = ztimeout!(new_link("udp/232.1.1.2:17522#iface=lo")).unwrap();
+    // Only the packets sent from the joined sources are received
+    let dst = ztimeout!(new_link("udp/232.1.1.2:17522#iface=lo;source=127.0.0.1")).unwrap();
+    let other = ztimeout!(new_link(
+        "udp/232.1.1.2:17522#iface=lo;source=127.0.0.2|127.0.0.3"
+    ))
+    .unwrap();
+    assert!(receives(&src, &dst).await);
+    assert!(!receives(&src, &other).await);
+
+    ztimeout!(src.close()).unwrap();
+    ztimeout!(dst.close()).unwrap();
+    ztimeout!(other.close()).unwrap();
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn udp_multicast_scope_id() {
+    zenoh_util::init_log_from_env_or("error");
+    // The scope can be given by interface index or name, and defaults to the one of the address
+    for endpoint in [
+        "udp/[ff02::1:8]:17523#iface=::1;scope_id=1",
+        "udp/[ff02::1:8]:17523#iface=::1;scope_id=lo",
+        "udp/[ff02::1:8%1]:17523#iface=::1",
+    ] {
+        let link = ztimeout!(new_link(endpoint)).unwrap();
+        ztimeout!(link.close()).unwrap();
+    }
+}
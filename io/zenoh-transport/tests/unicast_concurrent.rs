@@ -190,6 +190,7 @@ async fn transport_concurrent(endpoint01: Vec<EndPoint>, endpoint02: Vec<EndPoin
             ext_qos: QoSType::new(Priority::DEFAULT, CongestionControl::Block, false),
             ext_tstamp: None,
             ext_nodeid: NodeIdType::DEFAULT,
+            ext_redundancy: None,
             payload: Put {
                 payload: vec![0u8; MSG_SIZE].into(),
                 timestamp: None,
@@ -292,6 +293,7 @@ async fn transport_concurrent(endpoint01: Vec<EndPoint>, endpoint02: Vec<EndPoin
             ext_qos: QoSType::new(Priority::DEFAULT, CongestionControl::Block, false),
             ext_tstamp: None,
             ext_nodeid: NodeIdType::DEFAULT,
+            ext_redundancy: None,
             payload: Put {
                 payload: vec![0u8; MSG_SIZE].into(),
                 timestamp: None,
@@ -307,6 +307,7 @@ async fn transport_intermittent(endpoint: &EndPoint, lowlatency_transport: bool)
             ext_qos: QoSType::new(Priority::DEFAULT, CongestionControl::Block, false),
             ext_tstamp: None,
             ext_nodeid: NodeIdType::DEFAULT,
+            ext_redundancy: None,
             payload: Put {
                 payload: vec![0u8; MSG_SIZE].into(),
                 timestamp: None,
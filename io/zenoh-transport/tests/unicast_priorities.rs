@@ -289,6 +289,7 @@ async fn single_run(router_handler: Arc<SHRouter>, client_transport: TransportUn
                 ext_qos: QoSType::new(*p, CongestionControl::Block, false),
                 ext_tstamp: None,
                 ext_nodeid: NodeIdType::DEFAULT,
+                ext_redundancy: None,
                 payload: Put {
                     payload: vec![0u8; *ms].into(),
                     timestamp: None,
@@ -252,6 +252,7 @@ mod tests {
                 ext_qos: QoSType::new(Priority::DEFAULT, CongestionControl::Block, false),
                 ext_tstamp: None,
                 ext_nodeid: NodeIdType::DEFAULT,
+                ext_redundancy: None,
                 payload: Put {
                     payload: sbuf.into(),
                     timestamp: None,
@@ -293,6 +294,7 @@ mod tests {
                 ext_qos: QoSType::new(Priority::DEFAULT, CongestionControl::Block, false),
                 ext_tstamp: None,
                 ext_nodeid: NodeIdType::DEFAULT,
+                ext_redundancy: None,
                 payload: Put {
                     payload: sbuf.into(),
                     timestamp: None,
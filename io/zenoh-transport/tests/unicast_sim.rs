@@ -0,0 +1,430 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+#![cfg(feature = "transport_tcp")]
+use std::{
+    any::Any,
+    convert::TryFrom,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use zenoh_core::ztimeout;
+use zenoh_link::{EndPoint, Link};
+use zenoh_protocol::{
+    core::{CongestionControl, Encoding, Priority, WhatAmI, ZenohIdProto},
+    network::{
+        push::ext::{NodeIdType, QoSType},
+        NetworkBody, NetworkMessage, Push,
+    },
+    zenoh::Put,
+};
+use zenoh_result::ZResult;
+use zenoh_transport::{
+    multicast::TransportMulticast, unicast::TransportUnicast, TransportEventHandler,
+    TransportManager, TransportMulticastEventHandler, TransportPeer, TransportPeerEventHandler,
+};
+
+const TIMEOUT: Duration = Duration::from_secs(60);
+const SLEEP: Duration = Duration::from_millis(100);
+const SIM_DELAY: Duration = Duration::from_millis(200);
+const SIM_DISCONNECT: Duration = Duration::from_secs(1);
+
+// Transport handler recording the key expressions of the received messages
+#[derive(Default)]
+struct SHSim {
+    received: Arc<Mutex<Vec<String>>>,
+}
+
+impl SHSim {
+    fn received(&self) -> Vec<String> {
+        self.received.lock().unwrap().clone()
+    }
+}
+
+impl TransportEventHandler for SHSim {
+    fn new_unicast(
+        &self,
+        _peer: TransportPeer,
+        _transport: TransportUnicast,
+    ) -> ZResult<Arc<dyn TransportPeerEventHandler>> {
+        Ok(Arc::new(SCSim {
+            received: self.received.clone(),
+        }))
+    }
+
+    fn new_multicast(
+        &self,
+        _transport: TransportMulticast,
+    ) -> ZResult<Arc<dyn TransportMulticastEventHandler>> {
+        panic!();
+    }
+}
+
+struct SCSim {
+    received: Arc<Mutex<Vec<String>>>,
+}
+
+impl TransportPeerEventHandler for SCSim {
+    fn handle_message(&self, message: NetworkMessage) -> ZResult<()> {
+        if let NetworkBody::Push(push) = message.body {
+            self.received
+                .lock()
+                .unwrap()
+                .push(push.wire_expr.suffix.to_string());
+        }
+        Ok(())
+    }
+
+    fn new_link(&self, _link: Link) {}
+    fn del_link(&self, _link: Link) {}
+    fn closed(&self) {}
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+fn make_manager(id: u8, whatami: WhatAmI) -> TransportManager {
+    make_manager_with_handler(id, whatami, Arc::new(SHSim::default()))
+}
+
+fn make_manager_with_handler(id: u8, whatami: WhatAmI, handler: Arc<SHSim>) -> TransportManager {
+    TransportManager::builder()
+        .whatami(whatami)
+        .zid(ZenohIdProto::try_from([id]).unwrap())
+        .build(handler)
+        .unwrap()
+}
+
+fn message(key_expr: String, size: usize) -> NetworkMessage {
+    Push {
+        wire_expr: key_expr.into(),
+        ext_qos: QoSType::new(Priority::DEFAULT, CongestionControl::Block, false),
+        ext_tstamp: None,
+        ext_nodeid: NodeIdType::DEFAULT,
+        ext_redundancy: None,
+        payload: Put {
+            payload: vec![0u8; size].into(),
+            timestamp: None,
+            encoding: Encoding::empty(),
+            ext_sinfo: None,
+            #[cfg(feature = "shared-memory")]
+            ext_shm: None,
+            ext_attachment: None,
+            ext_unknown: vec![],
+        }
+        .into(),
+    }
+    .into()
+}
+
+async fn wait_received(handler: &SHSim, count: usize) {
+    ztimeout!(async {
+        while handler.received().len() < count {
+            tokio::time::sleep(SLEEP).await;
+        }
+    });
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn transport_unicast_sim_tcp() {
+    zenoh_util::init_log_from_env_or("error");
+
+    let listen: EndPoint = "tcp/127.0.0.1:17460".parse().unwrap();
+    let connect: EndPoint = format!(
+        "tcp/127.0.0.1:17460?sim_delay={}ms;sim_disconnect={}ms",
+        SIM_DELAY.as_millis(),
+        SIM_DISCONNECT.as_millis()
+    )
+    .parse()
+    .unwrap();
+
+    let router_manager = make_manager(1, WhatAmI::Router);
+    let client_manager = make_manager(2, WhatAmI::Client);
+
+    let locator = ztimeout!(router_manager.add_listener(listen.clone())).unwrap();
+    // The impairment parameters are never advertised
+    assert!(locator.metadata().is_empty());
+
+    // The opener writes at least two messages (InitSyn and OpenSyn) during the establishment
+    let now = Instant::now();
+    let transport = ztimeout!(client_manager.open_transport_unicast(connect)).unwrap();
+    assert!(now.elapsed() >= 2 * SIM_DELAY);
+    assert_eq!(transport.get_links().unwrap().len(), 1);
+
+    // The link is closed by the simulator after the scheduled disconnection
+    ztimeout!(async {
+        while !client_manager.get_transports_unicast().await.is_empty() {
+            tokio::time::sleep(SLEEP).await;
+        }
+    });
+    assert!(now.elapsed() >= SIM_DISCONNECT);
+
+    ztimeout!(router_manager.del_listener(&listen)).unwrap();
+    ztimeout!(router_manager.close());
+    ztimeout!(client_manager.close());
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn transport_unicast_sim_tcp_listener() {
+    zenoh_util::init_log_from_env_or("error");
+
+    let listen: EndPoint = format!("tcp/127.0.0.1:17461?sim_delay={}ms", SIM_DELAY.as_millis())
+        .parse()
+        .unwrap();
+    let connect: EndPoint = "tcp/127.0.0.1:17461".parse().unwrap();
+
+    let router_manager = make_manager(1, WhatAmI::Router);
+    let client_manager = make_manager(2, WhatAmI::Client);
+
+    let locator = ztimeout!(router_manager.add_listener(listen.clone())).unwrap();
+    assert!(locator.metadata().is_empty());
+
+    // The acceptor writes InitAck and OpenAck during the establishment
+    let now = Instant::now();
+    let _transport = ztimeout!(client_manager.open_transport_unicast(connect)).unwrap();
+    assert!(now.elapsed() >= 2 * SIM_DELAY);
+
+    ztimeout!(router_manager.del_listener(&listen)).unwrap();
+    ztimeout!(router_manager.close());
+    ztimeout!(client_manager.close());
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn transport_unicast_sim_tcp_jitter() {
+    zenoh_util::init_log_from_env_or("error");
+    const MSG_COUNT: usize = 20;
+
+    let listen: EndPoint = "tcp/127.0.0.1:17462".parse().unwrap();
+    let connect: EndPoint = format!(
+        "tcp/127.0.0.1:17462?sim_delay={}ms;sim_jitter={}ms",
+        SIM_DELAY.as_millis(),
+        SIM_DELAY.as_millis()
+    )
+    .parse()
+    .unwrap();
+
+    let router_handler = Arc::new(SHSim::default());
+    let router_manager = make_manager_with_handler(1, WhatAmI::Router, router_handler.clone());
+    let client_manager = make_manager(2, WhatAmI::Client);
+    ztimeout!(router_manager.add_listener(listen.clone())).unwrap();
+
+    let now = Instant::now();
+    let transport = ztimeout!(client_manager.open_transport_unicast(connect)).unwrap();
+    assert!(now.elapsed() >= 2 * SIM_DELAY);
+
+    // The jitter never reorders the writes, which would corrupt the stream
+    for i in 0..MSG_COUNT {
+        transport.schedule(message(format!("test/{i}"), 8)).unwrap();
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+    wait_received(&router_handler, MSG_COUNT).await;
+    let expected: Vec<String> = (0..MSG_COUNT).map(|i| format!("test/{i}")).collect();
+    assert_eq!(router_handler.received(), expected);
+
+    ztimeout!(router_manager.del_listener(&listen)).unwrap();
+    ztimeout!(router_manager.close());
+    ztimeout!(client_manager.close());
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn transport_unicast_sim_tcp_bandwidth() {
+    zenoh_util::init_log_from_env_or("error");
+    const MSG_COUNT: usize = 5;
+    const MSG_SIZE: usize = 4_000;
+    const BANDWIDTH: u64 = 80_000;
+
+    let listen: EndPoint = "tcp/127.0.0.1:17463".parse().unwrap();
+    let connect: EndPoint = format!("tcp/127.0.0.1:17463?sim_bw={}kbps", BANDWIDTH / 1_000)
+        .parse()
+        .unwrap();
+
+    let router_handler = Arc::new(SHSim::default());
+    let router_manager = make_manager_with_handler(1, WhatAmI::Router, router_handler.clone());
+    let client_manager = make_manager(2, WhatAmI::Client);
+    ztimeout!(router_manager.add_listener(listen.clone())).unwrap();
+    let transport = ztimeout!(client_manager.open_transport_unicast(connect)).unwrap();
+
+    // Each message is written once the previous one has been received, so that they are not
+    // batched: every write then waits for the time the previous one takes at the given bandwidth
+    let now = Instant::now();
+    for i in 0..MSG_COUNT {
+        transport
+            .schedule(message(format!("test/{i}"), MSG_SIZE))
+            .unwrap();
+        wait_received(&router_handler, i + 1).await;
+    }
+    let min = Duration::from_secs_f64(((MSG_COUNT - 1) * MSG_SIZE * 8) as f64 / BANDWIDTH as f64);
+    assert!(now.elapsed() >= min);
+
+    ztimeout!(router_manager.del_listener(&listen)).unwrap();
+    ztimeout!(router_manager.close());
+    ztimeout!(client_manager.close());
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn transport_unicast_sim_tcp_loss() {
+    zenoh_util::init_log_from_env_or("error");
+    const MSG_COUNT: usize = 10;
+
+    let listen: EndPoint = "tcp/127.0.0.1:17464".parse().unwrap();
+    let connect: EndPoint = "tcp/127.0.0.1:17464?sim_loss=100%".parse().unwrap();
+
+    let router_handler = Arc::new(SHSim::default());
+    let router_manager = make_manager_with_handler(1, WhatAmI::Router, router_handler.clone());
+    let client_manager = make_manager(2, WhatAmI::Client);
+    ztimeout!(router_manager.add_listener(listen.clone())).unwrap();
+
+    // Losses are ignored on reliable links
+    let transport = ztimeout!(client_manager.open_transport_unicast(connect)).unwrap();
+    for i in 0..MSG_COUNT {
+        transport.schedule(message(format!("test/{i}"), 8)).unwrap();
+    }
+    wait_received(&router_handler, MSG_COUNT).await;
+
+    ztimeout!(router_manager.del_listener(&listen)).unwrap();
+    ztimeout!(router_manager.close());
+    ztimeout!(client_manager.close());
+}
+
+#[cfg(feature = "transport_udp")]
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn transport_unicast_sim_udp_loss() {
+    zenoh_util::init_log_from_env_or("error");
+
+    let listen: EndPoint = "udp/127.0.0.1:17465".parse().unwrap();
+    let connect: EndPoint = "udp/127.0.0.1:17465?sim_loss=100%".parse().unwrap();
+
+    let router_manager = make_manager(1, WhatAmI::Router);
+    let client_manager = make_manager(2, WhatAmI::Client);
+    ztimeout!(router_manager.add_listener(listen.clone())).unwrap();
+
+    // All the writes are lost on unreliable links, so the transport is never established
+    assert!(tokio::time::timeout(
+        Duration::from_secs(2),
+        client_manager.open_transport_unicast(connect)
+    )
+    .await
+    .is_err());
+    assert!(router_manager.get_transports_unicast().await.is_empty());
+
+    ztimeout!(router_manager.del_listener(&listen)).unwrap();
+    ztimeout!(router_manager.close());
+    ztimeout!(client_manager.close());
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn transport_unicast_sim_tcp_del_listener() {
+    zenoh_util::init_log_from_env_or("error");
+    const DELAY: Duration = Duration::from_secs(1);
+
+    let router_manager = make_manager(1, WhatAmI::Router);
+    let client_manager = make_manager(2, WhatAmI::Client);
+
+    // The listener is deleted with another endpoint than the bound locator
+    let listen: EndPoint = format!("tcp/127.0.0.1:0?sim_delay={}ms", DELAY.as_millis())
+        .parse()
+        .unwrap();
+    let locator = ztimeout!(router_manager.add_listener(listen)).unwrap();
+    let port = locator
+        .address()
+        .as_str()
+        .rsplit(':')
+        .next()
+        .unwrap()
+        .to_string();
+    let hostname: EndPoint = format!("tcp/localhost:{port}").parse().unwrap();
+    ztimeout!(router_manager.del_listener(&hostname)).unwrap();
+
+    // A new listener on the same address doesn't inherit the impairments of the deleted one
+    let listen: EndPoint = format!("tcp/127.0.0.1:{port}").parse().unwrap();
+    ztimeout!(router_manager.add_listener(listen.clone())).unwrap();
+    let now = Instant::now();
+    let _transport = ztimeout!(client_manager.open_transport_unicast(listen.clone())).unwrap();
+    assert!(now.elapsed() < 2 * DELAY);
+
+    ztimeout!(router_manager.del_listener(&listen)).unwrap();
+    ztimeout!(router_manager.close());
+    ztimeout!(client_manager.close());
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn transport_unicast_sim_tcp_close() {
+    zenoh_util::init_log_from_env_or("error");
+    const MSG_COUNT: usize = 10;
+
+    let listen: EndPoint = "tcp/127.0.0.1:17466".parse().unwrap();
+    let connect: EndPoint = format!("tcp/127.0.0.1:17466?sim_delay={}ms", SIM_DELAY.as_millis())
+        .parse()
+        .unwrap();
+
+    let router_handler = Arc::new(SHSim::default());
+    let router_manager = make_manager_with_handler(1, WhatAmI::Router, router_handler.clone());
+    let client_manager = make_manager(2, WhatAmI::Client);
+    ztimeout!(router_manager.add_listener(listen.clone())).unwrap();
+    let transport = ztimeout!(client_manager.open_transport_unicast(connect)).unwrap();
+
+    // The writes still queued by the simulator are performed before the link is closed
+    for i in 0..MSG_COUNT {
+        transport.schedule(message(format!("test/{i}"), 8)).unwrap();
+    }
+    ztimeout!(transport.close()).unwrap();
+    wait_received(&router_handler, MSG_COUNT).await;
+    ztimeout!(async {
+        while !router_manager.get_transports_unicast().await.is_empty() {
+            tokio::time::sleep(SLEEP).await;
+        }
+    });
+
+    ztimeout!(router_manager.del_listener(&listen)).unwrap();
+    ztimeout!(router_manager.close());
+    ztimeout!(client_manager.close());
+}
+
+#[cfg(feature = "transport_udp")]
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn transport_unicast_sim_udp_close() {
+    zenoh_util::init_log_from_env_or("error");
+    const LEASE: Duration = Duration::from_secs(10);
+
+    let listen: EndPoint = "udp/127.0.0.1:17467".parse().unwrap();
+    let connect: EndPoint = format!("udp/127.0.0.1:17467?sim_delay={}ms", SIM_DELAY.as_millis())
+        .parse()
+        .unwrap();
+
+    let router_manager = TransportManager::builder()
+        .whatami(WhatAmI::Router)
+        .zid(ZenohIdProto::try_from([1]).unwrap())
+        .unicast(TransportManager::config_unicast().lease(LEASE))
+        .build(Arc::new(SHSim::default()))
+        .unwrap();
+    let client_manager = make_manager(2, WhatAmI::Client);
+    ztimeout!(router_manager.add_listener(listen.clone())).unwrap();
+    let transport = ztimeout!(client_manager.open_transport_unicast(connect)).unwrap();
+
+    // Nothing else than the Close message tells the remote that the transport is closed: it
+    // would otherwise wait for the expiration of the lease
+    let now = Instant::now();
+    ztimeout!(transport.close()).unwrap();
+    ztimeout!(async {
+        while !router_manager.get_transports_unicast().await.is_empty() {
+            tokio::time::sleep(SLEEP).await;
+        }
+    });
+    assert!(now.elapsed() < LEASE / 2);
+
+    ztimeout!(router_manager.del_listener(&listen)).unwrap();
+    ztimeout!(router_manager.close());
+    ztimeout!(client_manager.close());
+}
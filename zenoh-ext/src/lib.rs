@@ -14,14 +14,22 @@
 #[cfg(feature = "unstable")]
 pub mod group;
 #[cfg(feature = "unstable")]
+mod offline_publisher;
+#[cfg(feature = "unstable")]
 mod publication_cache;
 #[cfg(feature = "unstable")]
 mod querying_subscriber;
+#[cfg(feature = "unstable")]
+mod schema_registry;
+#[cfg(feature = "unstable")]
+mod serde_bridge;
 mod serialization;
 #[cfg(feature = "unstable")]
 mod session_ext;
 #[cfg(feature = "unstable")]
 mod subscriber_ext;
+#[cfg(feature = "unstable")]
+mod typed;
 
 #[cfg(feature = "internal")]
 pub use crate::serialization::VarInt;
@@ -31,11 +39,26 @@ pub use crate::serialization::{
 };
 #[cfg(feature = "unstable")]
 pub use crate::{
+    offline_publisher::{
+        OfflinePublicationBuilder, OfflinePublisher, OfflinePublisherBuilder, OverflowPolicy,
+    },
     publication_cache::{PublicationCache, PublicationCacheBuilder},
     querying_subscriber::{
         ExtractSample, FetchingSubscriber, FetchingSubscriberBuilder, KeySpace, LivelinessSpace,
         QueryingSubscriberBuilder, UserSpace,
     },
+    schema_registry::{
+        check_compatibility, Compatibility, Schema, SchemaFormat, SchemaRegistry,
+        SchemaRegistryBuilder, SchemaRegistryClient,
+    },
+    serde_bridge::{z_deserialize_serde, z_serialize_serde, ZSerdeError},
     session_ext::SessionExt,
     subscriber_ext::{SubscriberBuilderExt, SubscriberForward},
+    typed::{
+        Codec, JsonCodec, TypedError, TypedGetBuilder, TypedPublicationBuilder, TypedPublisher,
+        TypedPublisherBuilder, TypedQuery, TypedQueryable, TypedQueryableBuilder, TypedReply,
+        TypedSample, TypedSubscriber, TypedSubscriberBuilder, ZSerializeCodec,
+    },
 };
+#[cfg(feature = "unstable")]
+pub use zenoh_macros::{Deserialize, Serialize};
@@ -0,0 +1,571 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+use std::{
+    collections::VecDeque,
+    fs::{File, OpenOptions},
+    future::{IntoFuture, Ready},
+    io::{Read, Write},
+    path::{Path, PathBuf},
+    str::FromStr,
+    sync::{Arc, Mutex, Weak},
+    time::{Duration, SystemTime},
+};
+
+use zenoh::{
+    bytes::{Encoding, ZBytes},
+    internal::{bail, runtime::ZRuntime, zerror},
+    key_expr::KeyExpr,
+    pubsub::{MatchingStatus, Publisher},
+    qos::CongestionControl,
+    sample::SampleKind,
+    time::Timestamp,
+    Resolvable, Result as ZResult, Session, Wait,
+};
+
+use crate::{z_deserialize, z_serialize};
+
+/// The policy applied when a sample is published while the queue of an [`OfflinePublisher`] is full.
+#[zenoh_macros::unstable]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum OverflowPolicy {
+    /// Drop the oldest queued sample to make room for the new one.
+    #[default]
+    DropOldest,
+    /// Drop the new sample.
+    DropNewest,
+    /// Drop the new sample and return an error to the publication.
+    Reject,
+}
+
+/// The builder of OfflinePublisher, allowing to configure it.
+#[zenoh_macros::unstable]
+#[must_use = "Resolvables do nothing unless you resolve them using `.await` or `zenoh::Wait::wait`"]
+pub struct OfflinePublisherBuilder<'a, 'b> {
+    session: &'a Session,
+    key_expr: ZResult<KeyExpr<'b>>,
+    path: Option<PathBuf>,
+    max_samples: usize,
+    max_bytes: usize,
+    max_age: Option<Duration>,
+    overflow: OverflowPolicy,
+}
+
+impl<'a, 'b> OfflinePublisherBuilder<'a, 'b> {
+    pub(crate) fn new(
+        session: &'a Session,
+        key_expr: ZResult<KeyExpr<'b>>,
+    ) -> OfflinePublisherBuilder<'a, 'b> {
+        OfflinePublisherBuilder {
+            session,
+            key_expr,
+            path: None,
+            max_samples: 1024,
+            max_bytes: usize::MAX,
+            max_age: None,
+            overflow: OverflowPolicy::default(),
+        }
+    }
+
+    /// Persist the queued samples in the given append-only file.
+    ///
+    /// The samples found in this file when the [`OfflinePublisher`] is declared are queued
+    /// again, so that they survive a restart of the application.
+    pub fn path<P: AsRef<Path>>(mut self, path: P) -> Self {
+        self.path = Some(path.as_ref().to_path_buf());
+        self
+    }
+
+    /// Change the maximum number of queued samples, which must not be 0.
+    pub fn max_samples(mut self, max_samples: usize) -> Self {
+        self.max_samples = max_samples;
+        self
+    }
+
+    /// Change the maximum total size of the payloads of the queued samples.
+    pub fn max_bytes(mut self, max_bytes: usize) -> Self {
+        self.max_bytes = max_bytes;
+        self
+    }
+
+    /// Drop the queued samples older than the given age instead of replaying them.
+    pub fn max_age(mut self, max_age: Duration) -> Self {
+        self.max_age = Some(max_age);
+        self
+    }
+
+    /// Change the policy applied when the queue is full.
+    pub fn overflow(mut self, overflow: OverflowPolicy) -> Self {
+        self.overflow = overflow;
+        self
+    }
+}
+
+impl Resolvable for OfflinePublisherBuilder<'_, '_> {
+    type To = ZResult<OfflinePublisher>;
+}
+
+impl Wait for OfflinePublisherBuilder<'_, '_> {
+    fn wait(self) -> <Self as Resolvable>::To {
+        OfflinePublisher::new(self)
+    }
+}
+
+impl IntoFuture for OfflinePublisherBuilder<'_, '_> {
+    type Output = <Self as Resolvable>::To;
+    type IntoFuture = Ready<<Self as Resolvable>::To>;
+
+    fn into_future(self) -> Self::IntoFuture {
+        std::future::ready(self.wait())
+    }
+}
+
+#[derive(Clone)]
+struct QueuedSample {
+    kind: SampleKind,
+    payload: ZBytes,
+    encoding: Encoding,
+    timestamp: Timestamp,
+    attachment: Option<ZBytes>,
+}
+
+impl QueuedSample {
+    fn to_record(&self) -> Vec<u8> {
+        let kind: u8 = match self.kind {
+            SampleKind::Put => 0,
+            SampleKind::Delete => 1,
+        };
+        let record = z_serialize(&(
+            kind,
+            self.payload.to_bytes(),
+            self.encoding.to_string(),
+            self.timestamp.to_string(),
+            self.attachment.is_some(),
+            self.attachment
+                .as_ref()
+                .map(|a| a.to_bytes())
+                .unwrap_or_default(),
+        ))
+        .to_bytes()
+        .into_owned();
+        let mut bytes = (record.len() as u32).to_le_bytes().to_vec();
+        bytes.extend_from_slice(&record);
+        bytes
+    }
+
+    fn from_record(record: &[u8]) -> ZResult<Self> {
+        let (kind, payload, encoding, timestamp, has_attachment, attachment) =
+            z_deserialize::<(u8, Vec<u8>, String, String, bool, Vec<u8>)>(&record.into())?;
+        Ok(QueuedSample {
+            kind: match kind {
+                0 => SampleKind::Put,
+                1 => SampleKind::Delete,
+                k => bail!("Invalid sample kind {}", k),
+            },
+            payload: payload.into(),
+            encoding: Encoding::from(encoding),
+            timestamp: Timestamp::from_str(&timestamp)
+                .map_err(|e| zerror!("Invalid timestamp {}: {:?}", timestamp, e))?,
+            attachment: has_attachment.then(|| attachment.into()),
+        })
+    }
+}
+
+struct OfflineQueue {
+    matching: bool,
+    replaying: bool,
+    samples: VecDeque<QueuedSample>,
+    bytes: usize,
+    path: Option<PathBuf>,
+    file: Option<File>,
+    max_samples: usize,
+    max_bytes: usize,
+    max_age: Option<Duration>,
+    overflow: OverflowPolicy,
+}
+
+impl OfflineQueue {
+    // Reads the samples persisted in the file, ignoring a truncated last record
+    fn load(&mut self) -> ZResult<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        if path.exists() {
+            let mut bytes = vec![];
+            File::open(path)?.read_to_end(&mut bytes)?;
+            let mut records = bytes.as_slice();
+            while records.len() >= 4 {
+                let len = u32::from_le_bytes(records[..4].try_into().unwrap()) as usize;
+                let Some(record) = records.get(4..4 + len) else {
+                    tracing::warn!("Ignoring truncated record at the end of {:?}", path);
+                    break;
+                };
+                let sample = QueuedSample::from_record(record)?;
+                self.bytes += sample.payload.len();
+                self.samples.push_back(sample);
+                records = &records[4 + len..];
+            }
+        }
+        self.drop_expired();
+        self.rewrite()
+    }
+
+    fn is_expired(&self, sample: &QueuedSample) -> bool {
+        self.max_age.is_some_and(|max_age| {
+            SystemTime::now()
+                .duration_since(sample.timestamp.get_time().to_system_time())
+                .is_ok_and(|age| age > max_age)
+        })
+    }
+
+    fn drop_expired(&mut self) {
+        while self.samples.front().is_some_and(|s| self.is_expired(s)) {
+            self.pop_front();
+        }
+    }
+
+    fn pop_front(&mut self) -> Option<QueuedSample> {
+        let sample = self.samples.pop_front()?;
+        self.bytes -= sample.payload.len();
+        Some(sample)
+    }
+
+    fn push_front(&mut self, sample: QueuedSample) {
+        self.bytes += sample.payload.len();
+        self.samples.push_front(sample);
+    }
+
+    fn is_full(&self, sample: &QueuedSample) -> bool {
+        self.samples.len() >= self.max_samples
+            || self.bytes.saturating_add(sample.payload.len()) > self.max_bytes
+    }
+
+    fn push(&mut self, sample: QueuedSample) -> ZResult<()> {
+        let dropped_before = self.samples.len();
+        self.drop_expired();
+        if sample.payload.len() > self.max_bytes {
+            bail!("Sample too large to be queued");
+        }
+        while self.is_full(&sample) {
+            match self.overflow {
+                OverflowPolicy::DropOldest => {
+                    tracing::debug!("Offline queue full: dropping oldest sample");
+                    self.pop_front();
+                }
+                OverflowPolicy::DropNewest => {
+                    tracing::debug!("Offline queue full: dropping new sample");
+                    return self.sync(dropped_before);
+                }
+                OverflowPolicy::Reject => {
+                    self.sync(dropped_before)?;
+                    bail!("Offline queue full");
+                }
+            }
+        }
+        self.bytes += sample.payload.len();
+        self.samples.push_back(sample);
+        // Samples are appended to the file unless some were dropped from its head
+        if self.samples.len() == dropped_before + 1 {
+            if let Some(file) = &mut self.file {
+                file.write_all(&self.samples.back().unwrap().to_record())?;
+            }
+            Ok(())
+        } else {
+            self.rewrite()
+        }
+    }
+
+    fn sync(&mut self, len_before: usize) -> ZResult<()> {
+        if self.samples.len() != len_before {
+            self.rewrite()?;
+        }
+        Ok(())
+    }
+
+    // Rewrites the file with the currently queued samples
+    fn rewrite(&mut self) -> ZResult<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        let mut tmp = path.clone().into_os_string();
+        tmp.push(".tmp");
+        let mut file = File::create(&tmp)?;
+        for sample in &self.samples {
+            file.write_all(&sample.to_record())?;
+        }
+        file.sync_all()?;
+        std::fs::rename(&tmp, path)?;
+        self.file = Some(OpenOptions::new().append(true).open(path)?);
+        Ok(())
+    }
+}
+
+struct OfflinePublisherInner {
+    session: Session,
+    publisher: Publisher<'static>,
+    queue: Mutex<OfflineQueue>,
+}
+
+impl OfflinePublisherInner {
+    fn publish(&self, sample: QueuedSample, timestamp: bool) -> ZResult<()> {
+        let QueuedSample {
+            kind,
+            payload,
+            encoding,
+            timestamp: ts,
+            attachment,
+        } = sample;
+        let ts = timestamp.then_some(ts);
+        match kind {
+            SampleKind::Put => self
+                .publisher
+                .put(payload)
+                .encoding(encoding)
+                .timestamp(ts)
+                .attachment(attachment)
+                .wait(),
+            SampleKind::Delete => self
+                .publisher
+                .delete()
+                .timestamp(ts)
+                .attachment(attachment)
+                .wait(),
+        }
+    }
+
+    fn send(&self, sample: QueuedSample) -> ZResult<()> {
+        let mut queue = self.queue.lock().unwrap();
+        if queue.matching && !queue.replaying && queue.samples.is_empty() {
+            self.publish(sample, false)
+        } else {
+            queue.push(sample)
+        }
+    }
+
+    // Replays the queued samples in order while there are matching subscribers. The queue is not
+    // locked while a sample is published, as it may block, and the sample is queued again if its
+    // publication fails.
+    fn replay(&self) {
+        let mut queue = self.queue.lock().unwrap();
+        let len = queue.samples.len();
+        queue.drop_expired();
+        let mut replayed = queue.samples.len() != len;
+        while queue.matching {
+            let Some(sample) = queue.pop_front() else {
+                break;
+            };
+            drop(queue);
+            let result = self.publish(sample.clone(), true);
+            queue = self.queue.lock().unwrap();
+            if let Err(e) = result {
+                tracing::warn!(
+                    "Unable to replay queued sample on {}: {}",
+                    self.publisher.key_expr(),
+                    e
+                );
+                queue.push_front(sample);
+                break;
+            }
+            replayed = true;
+        }
+        queue.replaying = false;
+        // The replayed samples were at the head of the file, which has to be rewritten
+        if replayed {
+            if let Err(e) = queue.rewrite() {
+                tracing::warn!(
+                    "Unable to persist offline queue of {}: {}",
+                    self.publisher.key_expr(),
+                    e
+                );
+            }
+        }
+    }
+
+    fn on_matching_status(this: &Weak<Self>, status: MatchingStatus) {
+        let Some(inner) = this.upgrade() else {
+            return;
+        };
+        let mut queue = inner.queue.lock().unwrap();
+        queue.matching = status.matching_subscribers();
+        if queue.matching && !queue.replaying && !queue.samples.is_empty() {
+            queue.replaying = true;
+            drop(queue);
+            ZRuntime::Application.spawn(async move { inner.replay() });
+        }
+    }
+}
+
+/// A publisher that queues its publications while they have no matching subscriber, and replays
+/// them in order with their original timestamps as soon as a matching subscriber is reachable.
+///
+/// Typically used by intermittently connected clients that must not lose their publications
+/// while disconnected. The queue can be persisted in a file to survive a restart.
+/// The publications are sent with a blocking congestion control so that the replayed samples
+/// are not dropped.
+///
+/// # Examples
+/// ```no_run
+/// # #[tokio::main]
+/// # async fn main() {
+/// use std::time::Duration;
+/// use zenoh_ext::SessionExt;
+///
+/// let session = zenoh::open(zenoh::Config::default()).await.unwrap();
+/// let publisher = session
+///     .declare_offline_publisher("telemetry/temperature")
+///     .path("/var/lib/telemetry/temperature.queue")
+///     .max_age(Duration::from_secs(3600))
+///     .await
+///     .unwrap();
+/// publisher.put("21.5").await.unwrap();
+/// # }
+/// ```
+#[zenoh_macros::unstable]
+pub struct OfflinePublisher {
+    inner: Arc<OfflinePublisherInner>,
+}
+
+impl OfflinePublisher {
+    fn new(conf: OfflinePublisherBuilder<'_, '_>) -> ZResult<OfflinePublisher> {
+        let key_expr = conf.key_expr?.into_owned();
+        if conf.max_samples == 0 {
+            bail!("The maximum number of queued samples of an OfflinePublisher must not be 0");
+        }
+        tracing::debug!(
+            "Create OfflinePublisher on {} with path={:?} max_samples={} max_bytes={} max_age={:?}",
+            &key_expr,
+            conf.path,
+            conf.max_samples,
+            conf.max_bytes,
+            conf.max_age
+        );
+        let mut queue = OfflineQueue {
+            matching: false,
+            replaying: false,
+            samples: VecDeque::new(),
+            bytes: 0,
+            path: conf.path,
+            file: None,
+            max_samples: conf.max_samples,
+            max_bytes: conf.max_bytes,
+            max_age: conf.max_age,
+            overflow: conf.overflow,
+        };
+        queue.load()?;
+        let publisher = conf
+            .session
+            .declare_publisher(key_expr)
+            .congestion_control(CongestionControl::Block)
+            .wait()?;
+        let inner = Arc::new(OfflinePublisherInner {
+            session: conf.session.clone(),
+            publisher,
+            queue: Mutex::new(queue),
+        });
+        let weak = Arc::downgrade(&inner);
+        inner
+            .publisher
+            .matching_listener()
+            .callback(move |status| OfflinePublisherInner::on_matching_status(&weak, status))
+            .background()
+            .wait()?;
+        let status = inner.publisher.matching_status().wait()?;
+        OfflinePublisherInner::on_matching_status(&Arc::downgrade(&inner), status);
+        Ok(OfflinePublisher { inner })
+    }
+
+    /// Put data, or queue it if there is no matching subscriber.
+    pub fn put<IntoZBytes>(&self, payload: IntoZBytes) -> OfflinePublicationBuilder<'_>
+    where
+        IntoZBytes: Into<ZBytes>,
+    {
+        OfflinePublicationBuilder {
+            publisher: self,
+            kind: SampleKind::Put,
+            payload: payload.into(),
+            encoding: Encoding::default(),
+            attachment: None,
+        }
+    }
+
+    /// Delete data, or queue the deletion if there is no matching subscriber.
+    pub fn delete(&self) -> OfflinePublicationBuilder<'_> {
+        OfflinePublicationBuilder {
+            publisher: self,
+            kind: SampleKind::Delete,
+            payload: ZBytes::new(),
+            encoding: Encoding::default(),
+            attachment: None,
+        }
+    }
+
+    /// Returns the number of queued samples.
+    pub fn queued(&self) -> usize {
+        self.inner.queue.lock().unwrap().samples.len()
+    }
+
+    pub fn key_expr(&self) -> &KeyExpr<'static> {
+        self.inner.publisher.key_expr()
+    }
+}
+
+/// A publication of an [`OfflinePublisher`].
+#[zenoh_macros::unstable]
+#[must_use = "Resolvables do nothing unless you resolve them using `.await` or `zenoh::Wait::wait`"]
+pub struct OfflinePublicationBuilder<'a> {
+    publisher: &'a OfflinePublisher,
+    kind: SampleKind,
+    payload: ZBytes,
+    encoding: Encoding,
+    attachment: Option<ZBytes>,
+}
+
+impl OfflinePublicationBuilder<'_> {
+    /// Set the [`Encoding`] of the published data.
+    pub fn encoding<T: Into<Encoding>>(mut self, encoding: T) -> Self {
+        self.encoding = encoding.into();
+        self
+    }
+
+    /// Set the attachment of the published data.
+    pub fn attachment<T: Into<ZBytes>>(mut self, attachment: T) -> Self {
+        self.attachment = Some(attachment.into());
+        self
+    }
+}
+
+impl Resolvable for OfflinePublicationBuilder<'_> {
+    type To = ZResult<()>;
+}
+
+impl Wait for OfflinePublicationBuilder<'_> {
+    fn wait(self) -> <Self as Resolvable>::To {
+        let inner = &self.publisher.inner;
+        inner.send(QueuedSample {
+            kind: self.kind,
+            payload: self.payload,
+            encoding: self.encoding,
+            timestamp: inner.session.new_timestamp(),
+            attachment: self.attachment,
+        })
+    }
+}
+
+impl IntoFuture for OfflinePublicationBuilder<'_> {
+    type Output = <Self as Resolvable>::To;
+    type IntoFuture = Ready<<Self as Resolvable>::To>;
+
+    fn into_future(self) -> Self::IntoFuture {
+        std::future::ready(self.wait())
+    }
+}
@@ -0,0 +1,802 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+
+//! A registry of the schemas of the published data, rejecting incompatible schema changes.
+//!
+//! The [`SchemaRegistry`] is a queryable answering under its prefix:
+//! - queries on `<prefix>/<id>` with a payload register a new version of the schema `<id>`,
+//!   and are replied with the registered version, or with an error if the schema is
+//!   incompatible with the previous version;
+//! - queries on `<prefix>/<id>` without payload resolve the latest version of the schema `<id>`,
+//!   or the version given by the `version` parameter.
+//!
+//! The [`SchemaRegistryClient`] wraps these queries, and identifies a schema version in the
+//! [`Encoding`] of the publications as `<id>@<version>` schema suffix.
+use std::{
+    collections::HashMap,
+    fs::{File, OpenOptions},
+    future::{IntoFuture, Ready},
+    io::{Read, Write},
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use prost::Message;
+use prost_types::{field_descriptor_proto::Label, DescriptorProto, FileDescriptorSet};
+use serde_json::Value;
+use zenoh::{
+    bytes::Encoding,
+    internal::{bail, zerror, zlock, ResolveFuture},
+    key_expr::KeyExpr,
+    pubsub::Publisher,
+    query::{Query, Queryable},
+    Resolvable, Resolve, Result as ZResult, Session, Wait,
+};
+
+use crate::{z_deserialize, z_serialize};
+
+/// The format of a schema definition.
+#[zenoh_macros::unstable]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SchemaFormat {
+    /// A [JSON Schema](https://json-schema.org/) document.
+    JsonSchema,
+    /// A protobuf `FileDescriptorSet`, as generated by `protoc --descriptor_set_out`.
+    Protobuf,
+}
+
+impl SchemaFormat {
+    /// The encoding of the data described by schemas of this format.
+    pub fn encoding(&self) -> Encoding {
+        match self {
+            SchemaFormat::JsonSchema => Encoding::APPLICATION_JSON,
+            SchemaFormat::Protobuf => Encoding::APPLICATION_PROTOBUF,
+        }
+    }
+
+    fn to_u8(self) -> u8 {
+        match self {
+            SchemaFormat::JsonSchema => 0,
+            SchemaFormat::Protobuf => 1,
+        }
+    }
+
+    fn from_u8(format: u8) -> ZResult<Self> {
+        match format {
+            0 => Ok(SchemaFormat::JsonSchema),
+            1 => Ok(SchemaFormat::Protobuf),
+            f => bail!("Invalid schema format {}", f),
+        }
+    }
+}
+
+/// The compatibility enforced between consecutive versions of a schema.
+#[zenoh_macros::unstable]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Compatibility {
+    /// Any change is accepted.
+    None,
+    /// The consumers using the new version can read the data produced with the previous one.
+    #[default]
+    Backward,
+    /// The consumers using the previous version can read the data produced with the new one.
+    Forward,
+    /// Both backward and forward compatibility.
+    Full,
+}
+
+/// A registered version of a schema.
+#[zenoh_macros::unstable]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Schema {
+    id: String,
+    version: u32,
+    format: SchemaFormat,
+    definition: Vec<u8>,
+}
+
+impl Schema {
+    pub fn new<I: Into<String>, D: Into<Vec<u8>>>(
+        id: I,
+        version: u32,
+        format: SchemaFormat,
+        definition: D,
+    ) -> Self {
+        Schema {
+            id: id.into(),
+            version,
+            format,
+            definition: definition.into(),
+        }
+    }
+
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    pub fn version(&self) -> u32 {
+        self.version
+    }
+
+    pub fn format(&self) -> SchemaFormat {
+        self.format
+    }
+
+    pub fn definition(&self) -> &[u8] {
+        &self.definition
+    }
+
+    /// The encoding of the data described by this schema, identifying its version.
+    pub fn encoding(&self) -> Encoding {
+        self.format
+            .encoding()
+            .with_schema(format!("{}@{}", self.id, self.version))
+    }
+
+    fn to_record(&self) -> Vec<u8> {
+        z_serialize(&(
+            &self.id,
+            self.version,
+            self.format.to_u8(),
+            &self.definition,
+        ))
+        .to_bytes()
+        .into_owned()
+    }
+
+    fn from_record(record: &[u8]) -> ZResult<Self> {
+        let (id, version, format, definition) =
+            z_deserialize::<(String, u32, u8, Vec<u8>)>(&record.into())?;
+        Ok(Schema {
+            id,
+            version,
+            format: SchemaFormat::from_u8(format)?,
+            definition,
+        })
+    }
+
+    fn validate(&self) -> ZResult<()> {
+        match self.format {
+            SchemaFormat::JsonSchema => {
+                serde_json::from_slice::<Value>(&self.definition)
+                    .map_err(|e| zerror!("Invalid JSON schema: {}", e))?;
+            }
+            SchemaFormat::Protobuf => {
+                FileDescriptorSet::decode(self.definition.as_slice())
+                    .map_err(|e| zerror!("Invalid protobuf descriptor set: {}", e))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Check that `schema` is a compatible evolution of `previous`.
+#[zenoh_macros::unstable]
+pub fn check_compatibility(
+    previous: &Schema,
+    schema: &Schema,
+    compatibility: Compatibility,
+) -> ZResult<()> {
+    if compatibility == Compatibility::None {
+        return Ok(());
+    }
+    if previous.format != schema.format {
+        bail!(
+            "Schema format changed from {:?} to {:?}",
+            previous.format,
+            schema.format
+        );
+    }
+    let check = |reader: &Schema, writer: &Schema| -> ZResult<()> {
+        let result = match schema.format {
+            SchemaFormat::JsonSchema => check_json_schema(
+                &serde_json::from_slice(&reader.definition)?,
+                &serde_json::from_slice(&writer.definition)?,
+                "#",
+            ),
+            SchemaFormat::Protobuf => check_protobuf(
+                &FileDescriptorSet::decode(reader.definition.as_slice())?,
+                &FileDescriptorSet::decode(writer.definition.as_slice())?,
+            ),
+        };
+        result.map_err(|e| {
+            zerror!(
+                "Version {} of {} can't read data of version {}: {}",
+                reader.version,
+                schema.id,
+                writer.version,
+                e
+            )
+            .into()
+        })
+    };
+    if matches!(compatibility, Compatibility::Backward | Compatibility::Full) {
+        check(schema, previous)?;
+    }
+    if matches!(compatibility, Compatibility::Forward | Compatibility::Full) {
+        check(previous, schema)?;
+    }
+    Ok(())
+}
+
+fn json_types(schema: &Value) -> Option<Vec<&str>> {
+    match schema.get("type")? {
+        Value::String(t) => Some(vec![t.as_str()]),
+        Value::Array(types) => Some(types.iter().filter_map(Value::as_str).collect()),
+        _ => None,
+    }
+}
+
+fn json_required(schema: &Value) -> Vec<&str> {
+    schema
+        .get("required")
+        .and_then(Value::as_array)
+        .map(|required| required.iter().filter_map(Value::as_str).collect())
+        .unwrap_or_default()
+}
+
+// Checks that the data valid for the `writer` schema is valid for the `reader` schema
+fn check_json_schema(reader: &Value, writer: &Value, path: &str) -> Result<(), String> {
+    if let Some(reader_types) = json_types(reader) {
+        let Some(writer_types) = json_types(writer) else {
+            return Err(format!("{path}: type is constrained by the reader only"));
+        };
+        for t in writer_types {
+            let widened = t == "integer" && reader_types.contains(&"number");
+            if !reader_types.contains(&t) && !widened {
+                return Err(format!("{path}: type `{t}` is not accepted by the reader"));
+            }
+        }
+    }
+    if let Some(reader_enum) = reader.get("enum").and_then(Value::as_array) {
+        let Some(writer_enum) = writer.get("enum").and_then(Value::as_array) else {
+            return Err(format!("{path}: values are restricted by the reader only"));
+        };
+        if let Some(value) = writer_enum.iter().find(|v| !reader_enum.contains(v)) {
+            return Err(format!(
+                "{path}: value {value} is not accepted by the reader"
+            ));
+        }
+    }
+    let writer_required = json_required(writer);
+    if let Some(name) = json_required(reader)
+        .into_iter()
+        .find(|name| !writer_required.contains(name))
+    {
+        return Err(format!(
+            "{path}: property `{name}` is required by the reader but not by the writer"
+        ));
+    }
+    let empty = serde_json::Map::new();
+    let reader_properties = reader
+        .get("properties")
+        .and_then(Value::as_object)
+        .unwrap_or(&empty);
+    let writer_properties = writer
+        .get("properties")
+        .and_then(Value::as_object)
+        .unwrap_or(&empty);
+    if reader.get("additionalProperties") == Some(&Value::Bool(false)) {
+        if let Some(name) = writer_properties
+            .keys()
+            .find(|name| !reader_properties.contains_key(*name))
+        {
+            return Err(format!(
+                "{path}: property `{name}` is not allowed by the reader"
+            ));
+        }
+    }
+    for (name, reader_property) in reader_properties {
+        if let Some(writer_property) = writer_properties.get(name) {
+            check_json_schema(reader_property, writer_property, &format!("{path}/{name}"))?;
+        }
+    }
+    if let (Some(reader_items), Some(writer_items)) = (reader.get("items"), writer.get("items")) {
+        check_json_schema(reader_items, writer_items, &format!("{path}/items"))?;
+    }
+    Ok(())
+}
+
+// Indexes the messages, including the nested ones, by their fully qualified name
+fn protobuf_messages<'a>(
+    prefix: &str,
+    messages: &'a [DescriptorProto],
+    index: &mut HashMap<String, &'a DescriptorProto>,
+) {
+    for message in messages {
+        let name = format!("{prefix}.{}", message.name());
+        protobuf_messages(&name, &message.nested_type, index);
+        index.insert(name, message);
+    }
+}
+
+fn protobuf_index(set: &FileDescriptorSet) -> HashMap<String, &DescriptorProto> {
+    let mut index = HashMap::new();
+    for file in &set.file {
+        let prefix = match file.package() {
+            "" => String::new(),
+            package => format!(".{package}"),
+        };
+        protobuf_messages(&prefix, &file.message_type, &mut index);
+    }
+    index
+}
+
+// Checks that the messages encoded with the `writer` descriptors can be decoded with the `reader` ones
+fn check_protobuf(reader: &FileDescriptorSet, writer: &FileDescriptorSet) -> Result<(), String> {
+    let writer_messages = protobuf_index(writer);
+    for (name, reader_message) in protobuf_index(reader) {
+        let Some(writer_message) = writer_messages.get(&name) else {
+            continue;
+        };
+        for reader_field in &reader_message.field {
+            let writer_field = writer_message
+                .field
+                .iter()
+                .find(|f| f.number == reader_field.number);
+            let required = reader_field.label() == Label::Required;
+            match writer_field {
+                Some(writer_field) => {
+                    if writer_field.r#type != reader_field.r#type
+                        || writer_field.type_name != reader_field.type_name
+                        || (writer_field.label() == Label::Repeated)
+                            != (reader_field.label() == Label::Repeated)
+                    {
+                        return Err(format!(
+                            "{name}: field {} changed from `{}` to `{}`",
+                            reader_field.number(),
+                            writer_field.name(),
+                            reader_field.name()
+                        ));
+                    }
+                    if required && writer_field.label() != Label::Required {
+                        return Err(format!(
+                            "{name}: field `{}` is required by the reader but not by the writer",
+                            reader_field.name()
+                        ));
+                    }
+                }
+                None if required => {
+                    return Err(format!(
+                        "{name}: required field `{}` is missing in the writer",
+                        reader_field.name()
+                    ))
+                }
+                None => {}
+            }
+        }
+    }
+    Ok(())
+}
+
+/// The builder of [`SchemaRegistry`], allowing to configure it.
+#[zenoh_macros::unstable]
+#[must_use = "Resolvables do nothing unless you resolve them using `.await` or `zenoh::Wait::wait`"]
+pub struct SchemaRegistryBuilder<'a, 'b> {
+    session: &'a Session,
+    prefix: ZResult<KeyExpr<'b>>,
+    compatibility: Compatibility,
+    path: Option<PathBuf>,
+}
+
+impl<'a, 'b> SchemaRegistryBuilder<'a, 'b> {
+    pub(crate) fn new(session: &'a Session, prefix: ZResult<KeyExpr<'b>>) -> Self {
+        Self {
+            session,
+            prefix,
+            compatibility: Compatibility::default(),
+            path: None,
+        }
+    }
+
+    /// Change the compatibility enforced between consecutive versions of a schema,
+    /// [`Compatibility::Backward`] by default.
+    pub fn compatibility(mut self, compatibility: Compatibility) -> Self {
+        self.compatibility = compatibility;
+        self
+    }
+
+    /// Persist the registered schemas in the file at the given path,
+    /// reloading them when the registry is declared again.
+    pub fn path<P: AsRef<Path>>(mut self, path: P) -> Self {
+        self.path = Some(path.as_ref().to_path_buf());
+        self
+    }
+}
+
+impl Resolvable for SchemaRegistryBuilder<'_, '_> {
+    type To = ZResult<SchemaRegistry>;
+}
+
+impl Wait for SchemaRegistryBuilder<'_, '_> {
+    fn wait(self) -> <Self as Resolvable>::To {
+        SchemaRegistry::new(self)
+    }
+}
+
+impl IntoFuture for SchemaRegistryBuilder<'_, '_> {
+    type Output = <Self as Resolvable>::To;
+    type IntoFuture = Ready<<Self as Resolvable>::To>;
+
+    fn into_future(self) -> Self::IntoFuture {
+        std::future::ready(self.wait())
+    }
+}
+
+struct SchemaStore {
+    schemas: HashMap<String, Vec<Schema>>,
+    file: Option<File>,
+}
+
+impl SchemaStore {
+    // Reads the schemas persisted in the file, ignoring a truncated last record
+    fn load(path: Option<&Path>) -> ZResult<Self> {
+        let mut store = SchemaStore {
+            schemas: HashMap::new(),
+            file: None,
+        };
+        let Some(path) = path else {
+            return Ok(store);
+        };
+        if path.exists() {
+            let mut bytes = vec![];
+            File::open(path)?.read_to_end(&mut bytes)?;
+            let mut records = bytes.as_slice();
+            while records.len() >= 4 {
+                let len = u32::from_le_bytes(records[..4].try_into().unwrap()) as usize;
+                let Some(record) = records.get(4..4 + len) else {
+                    tracing::warn!("Ignoring truncated record at the end of {:?}", path);
+                    break;
+                };
+                let schema = Schema::from_record(record)?;
+                store
+                    .schemas
+                    .entry(schema.id.clone())
+                    .or_default()
+                    .push(schema);
+                records = &records[4 + len..];
+            }
+        }
+        store.file = Some(OpenOptions::new().create(true).append(true).open(path)?);
+        Ok(store)
+    }
+
+    fn insert(&mut self, schema: Schema) -> ZResult<()> {
+        if let Some(file) = &mut self.file {
+            let record = schema.to_record();
+            let mut bytes = (record.len() as u32).to_le_bytes().to_vec();
+            bytes.extend_from_slice(&record);
+            file.write_all(&bytes)?;
+            file.sync_data()?;
+        }
+        self.schemas
+            .entry(schema.id.clone())
+            .or_default()
+            .push(schema);
+        Ok(())
+    }
+}
+
+struct SchemaRegistryInner {
+    prefix: KeyExpr<'static>,
+    compatibility: Compatibility,
+    store: Mutex<SchemaStore>,
+}
+
+impl SchemaRegistryInner {
+    fn register(&self, mut schema: Schema) -> ZResult<u32> {
+        schema.validate()?;
+        let mut store = zlock!(self.store);
+        let previous = store.schemas.get(&schema.id).and_then(|v| v.last());
+        if let Some(previous) = previous {
+            if previous.format == schema.format && previous.definition == schema.definition {
+                return Ok(previous.version);
+            }
+        }
+        schema.version = previous.map_or(1, |p| p.version + 1);
+        if let Some(previous) = previous {
+            check_compatibility(previous, &schema, self.compatibility)?;
+        }
+        let version = schema.version;
+        tracing::debug!("Registering version {} of schema {}", version, schema.id);
+        store.insert(schema)?;
+        Ok(version)
+    }
+
+    fn handle_query(&self, query: Query) {
+        if let Err(e) = self.reply(&query) {
+            if let Err(e) = query.reply_err(e.to_string()).wait() {
+                tracing::warn!("Unable to reply to schema registry query: {}", e);
+            }
+        }
+    }
+
+    fn reply(&self, query: &Query) -> ZResult<()> {
+        let id = query
+            .key_expr()
+            .as_str()
+            .strip_prefix(self.prefix.as_str())
+            .and_then(|id| id.strip_prefix('/'))
+            .ok_or_else(|| zerror!("Query outside of the registry: {}", query.key_expr()))?;
+        if let Some(payload) = query.payload() {
+            if id.contains('*') {
+                bail!("Invalid schema id {}", id);
+            }
+            let (format, definition) = z_deserialize::<(u8, Vec<u8>)>(payload)?;
+            let version = self.register(Schema {
+                id: id.to_string(),
+                version: 0,
+                format: SchemaFormat::from_u8(format)?,
+                definition,
+            })?;
+            return query.reply(query.key_expr(), z_serialize(&version)).wait();
+        }
+        let version = match query.parameters().get("version") {
+            Some(version) => Some(
+                version
+                    .parse::<u32>()
+                    .map_err(|e| zerror!("Invalid version {}: {}", version, e))?,
+            ),
+            None => None,
+        };
+        let store = zlock!(self.store);
+        let mut found = false;
+        for (id, versions) in &store.schemas {
+            let key_expr = self.prefix.join(id)?;
+            if !key_expr.intersects(query.key_expr()) {
+                continue;
+            }
+            let schema = match version {
+                Some(version) => versions.iter().find(|s| s.version == version),
+                None => versions.last(),
+            };
+            if let Some(schema) = schema {
+                found = true;
+                query.reply(key_expr, schema.to_record()).wait()?;
+            }
+        }
+        if !found {
+            bail!("Unknown schema {}", id);
+        }
+        Ok(())
+    }
+}
+
+/// A schema registry, registering and resolving schemas under its prefix.
+///
+/// # Examples
+/// ```no_run
+/// # #[tokio::main]
+/// # async fn main() {
+/// use zenoh_ext::{Compatibility, SessionExt};
+///
+/// let session = zenoh::open(zenoh::Config::default()).await.unwrap();
+/// let registry = session
+///     .declare_schema_registry("schemas")
+///     .compatibility(Compatibility::Full)
+///     .path("schemas.db")
+///     .await
+///     .unwrap();
+/// # }
+/// ```
+#[zenoh_macros::unstable]
+pub struct SchemaRegistry {
+    inner: Arc<SchemaRegistryInner>,
+    queryable: Queryable<()>,
+}
+
+impl SchemaRegistry {
+    fn new(conf: SchemaRegistryBuilder<'_, '_>) -> ZResult<Self> {
+        let prefix = conf.prefix?.into_owned();
+        let inner = Arc::new(SchemaRegistryInner {
+            store: Mutex::new(SchemaStore::load(conf.path.as_deref())?),
+            prefix: prefix.clone(),
+            compatibility: conf.compatibility,
+        });
+        let handler = inner.clone();
+        let queryable = conf
+            .session
+            .declare_queryable(prefix.join("**")?)
+            .complete(true)
+            .callback(move |query| handler.handle_query(query))
+            .wait()?;
+        Ok(SchemaRegistry { inner, queryable })
+    }
+
+    pub fn prefix(&self) -> &KeyExpr<'static> {
+        &self.inner.prefix
+    }
+
+    /// The registered versions of the given schema, from the oldest to the latest.
+    pub fn versions(&self, id: &str) -> Vec<Schema> {
+        self.inner
+            .store
+            .lock()
+            .unwrap()
+            .schemas
+            .get(id)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    pub fn undeclare(self) -> impl Resolve<ZResult<()>> {
+        self.queryable.undeclare()
+    }
+}
+
+/// A client of a [`SchemaRegistry`], registering the schemas of publishers and resolving the
+/// schemas of the received data.
+///
+/// # Examples
+/// ```no_run
+/// # #[tokio::main]
+/// # async fn main() {
+/// use zenoh_ext::{SchemaFormat, SessionExt};
+///
+/// let session = zenoh::open(zenoh::Config::default()).await.unwrap();
+/// let registry = session.schema_registry_client("schemas").unwrap();
+/// let schema = r#"{"type": "object", "properties": {"value": {"type": "number"}}}"#;
+/// // Fails if the schema is incompatible with its previous version
+/// let publisher = registry
+///     .declare_publisher("sensors/temperature", "temperature", SchemaFormat::JsonSchema, schema)
+///     .await
+///     .unwrap();
+/// publisher.put(r#"{"value": 21.5}"#).await.unwrap();
+///
+/// let subscriber = session.declare_subscriber("sensors/**").await.unwrap();
+/// while let Ok(sample) = subscriber.recv_async().await {
+///     let schema = registry.resolve_encoding(sample.encoding()).await.unwrap();
+///     println!("Received data of schema {} version {}", schema.id(), schema.version());
+/// }
+/// # }
+/// ```
+#[zenoh_macros::unstable]
+#[derive(Clone)]
+pub struct SchemaRegistryClient {
+    session: Session,
+    prefix: KeyExpr<'static>,
+    timeout: Duration,
+}
+
+impl SchemaRegistryClient {
+    pub(crate) fn new(session: &Session, prefix: KeyExpr<'static>) -> Self {
+        SchemaRegistryClient {
+            session: session.clone(),
+            prefix,
+            timeout: Duration::from_secs(10),
+        }
+    }
+
+    /// Change the timeout of the queries to the registry, 10 seconds by default.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Register a new version of a schema, failing if it is incompatible with the previous one.
+    ///
+    /// Registering the latest version again returns it unchanged.
+    pub fn register<D: Into<Vec<u8>>>(
+        &self,
+        id: &str,
+        format: SchemaFormat,
+        definition: D,
+    ) -> impl Resolve<ZResult<Schema>> {
+        let client = self.clone();
+        let id = id.to_string();
+        let definition = definition.into();
+        ResolveFuture::new(async move {
+            let payload = z_serialize(&(format.to_u8(), &definition));
+            let replies = client
+                .session
+                .get(client.prefix.join(&id)?)
+                .payload(payload)
+                .timeout(client.timeout)
+                .await?;
+            let reply = replies
+                .recv_async()
+                .await
+                .map_err(|_| zerror!("No schema registry replied on {}", client.prefix))?;
+            match reply.result() {
+                Ok(sample) => Ok(Schema {
+                    id,
+                    version: z_deserialize(sample.payload())?,
+                    format,
+                    definition,
+                }),
+                Err(e) => bail!(
+                    "Unable to register schema {}: {}",
+                    id,
+                    e.payload().try_to_string().unwrap_or_default()
+                ),
+            }
+        })
+    }
+
+    /// Resolve the given version of a schema, or its latest version if `None`.
+    pub fn resolve(&self, id: &str, version: Option<u32>) -> impl Resolve<ZResult<Schema>> {
+        let client = self.clone();
+        let id = id.to_string();
+        ResolveFuture::new(async move {
+            let key_expr = client.prefix.join(&id)?;
+            let replies = match version {
+                Some(version) => {
+                    client
+                        .session
+                        .get((key_expr, format!("version={version}").as_str()))
+                        .timeout(client.timeout)
+                        .await?
+                }
+                None => client.session.get(key_expr).timeout(client.timeout).await?,
+            };
+            let reply = replies
+                .recv_async()
+                .await
+                .map_err(|_| zerror!("No schema registry replied on {}", client.prefix))?;
+            match reply.result() {
+                Ok(sample) => Schema::from_record(&sample.payload().to_bytes()),
+                Err(e) => bail!(
+                    "Unable to resolve schema {}: {}",
+                    id,
+                    e.payload().try_to_string().unwrap_or_default()
+                ),
+            }
+        })
+    }
+
+    /// Resolve the schema identified in the schema suffix of an [`Encoding`], see [`Schema::encoding`].
+    pub fn resolve_encoding(&self, encoding: &Encoding) -> impl Resolve<ZResult<Schema>> {
+        let client = self.clone();
+        let encoding = encoding.to_string();
+        ResolveFuture::new(async move {
+            let (id, version) = encoding
+                .split_once(';')
+                .and_then(|(_, schema)| schema.rsplit_once('@'))
+                .ok_or_else(|| zerror!("No schema version in encoding {}", encoding))?;
+            let version = version
+                .parse::<u32>()
+                .map_err(|e| zerror!("Invalid schema version in encoding {}: {}", encoding, e))?;
+            client.resolve(id, Some(version)).await
+        })
+    }
+
+    /// Register a schema and declare a [`Publisher`] with its encoding,
+    /// failing if the schema is incompatible with its previous version.
+    pub fn declare_publisher<TryIntoKeyExpr, D>(
+        &self,
+        key_expr: TryIntoKeyExpr,
+        id: &str,
+        format: SchemaFormat,
+        definition: D,
+    ) -> impl Resolve<ZResult<Publisher<'static>>>
+    where
+        TryIntoKeyExpr: TryInto<KeyExpr<'static>>,
+        <TryIntoKeyExpr as TryInto<KeyExpr<'static>>>::Error: Into<zenoh::Error>,
+        D: Into<Vec<u8>>,
+    {
+        let client = self.clone();
+        let key_expr = key_expr.try_into().map_err(Into::into);
+        let register = self.register(id, format, definition);
+        ResolveFuture::new(async move {
+            let key_expr = key_expr?;
+            let schema = register.await?;
+            client
+                .session
+                .declare_publisher(key_expr)
+                .encoding(schema.encoding())
+                .await
+        })
+    }
+}
@@ -0,0 +1,745 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+
+//! [`serde`] support for the [Zenoh serialization format][1].
+//!
+//! [`ZSerializer`] and [`ZDeserializer`] implement [`serde::Serializer`] and [`serde::Deserializer`],
+//! so that the types deriving the serde traits are serialized exactly as their equivalent
+//! Rust tuples and collections with [`z_serialize`](crate::z_serialize).
+//!
+//! The format being not self-describing, the data model is mapped as follows:
+//! - structs, tuples and tuple structs are serialized as tuples, i.e. their fields one after the other;
+//! - newtype structs are serialized as their inner value, unit and unit structs as nothing;
+//! - sequences and maps are serialized with their length as prefix, which must be known beforehand;
+//! - `char` is serialized as its `u32` code point;
+//! - `Option` is serialized as a `bool` followed by the value if it's `Some`;
+//! - enum variants are serialized as their `u32` index followed by their fields.
+//!
+//! `Option` and enums are thus serialized like with their [`Serialize`](crate::Serialize)
+//! implementation, provided for `Option` and derived for enums.
+//!
+//! Deserialization of self-describing formats features, like `deserialize_any` or
+//! `#[serde(skip_serializing_if = ...)]`, is not supported.
+//!
+//! Strings and bytes can be deserialized without copy with `&str`/`&[u8]` fields, or `Cow` fields
+//! marked with `#[serde(borrow)]`. They are borrowed from the [`ZBytes`] only if stored contiguously,
+//! so `&str`/`&[u8]` fields may fail to deserialize from fragmented payloads; see [`ZBytes::contiguous`].
+//!
+//! [1]: https://github.com/eclipse-zenoh/roadmap/blob/main/rfcs/ALL/Serialization.md
+use std::{borrow::Cow, fmt};
+
+use serde::{
+    de::{self, DeserializeSeed, IntoDeserializer, Visitor},
+    ser,
+};
+use zenoh::bytes::ZBytes;
+
+use crate::{serialization::VarInt, ZDeserializeError, ZDeserializer, ZSerializer};
+
+/// Error occurring in serialization or deserialization of serde types.
+#[zenoh_macros::unstable]
+#[derive(Debug)]
+pub struct ZSerdeError(String);
+
+impl fmt::Display for ZSerdeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for ZSerdeError {}
+
+impl ser::Error for ZSerdeError {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        ZSerdeError(msg.to_string())
+    }
+}
+
+impl de::Error for ZSerdeError {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        ZSerdeError(msg.to_string())
+    }
+}
+
+impl From<ZDeserializeError> for ZSerdeError {
+    fn from(value: ZDeserializeError) -> Self {
+        ZSerdeError(value.to_string())
+    }
+}
+
+/// Serialize a [`serde::Serialize`] object according to the [Zenoh serialization format][1].
+///
+/// # Examples
+///
+/// ```rust
+/// use zenoh_ext::*;
+///
+/// #[derive(serde::Serialize, serde::Deserialize, Debug, PartialEq)]
+/// struct Point {
+///     x: i32,
+///     y: i32,
+/// }
+///
+/// let zbytes = z_serialize_serde(&Point { x: 1, y: 2 }).unwrap();
+/// assert_eq!(z_deserialize::<(i32, i32)>(&zbytes).unwrap(), (1, 2));
+/// assert_eq!(z_deserialize_serde::<Point>(&zbytes).unwrap(), Point { x: 1, y: 2 });
+/// ```
+///
+/// [1]: https://github.com/eclipse-zenoh/roadmap/blob/main/rfcs/ALL/Serialization.md
+#[zenoh_macros::unstable]
+pub fn z_serialize_serde<T: serde::Serialize + ?Sized>(t: &T) -> Result<ZBytes, ZSerdeError> {
+    let mut serializer = ZSerializer::new();
+    t.serialize(&mut serializer)?;
+    Ok(serializer.finish())
+}
+
+/// Deserialize a [`serde::Deserialize`] object according to the [Zenoh serialization format][1].
+///
+/// See [`z_serialize_serde`].
+///
+/// [1]: https://github.com/eclipse-zenoh/roadmap/blob/main/rfcs/ALL/Serialization.md
+#[zenoh_macros::unstable]
+pub fn z_deserialize_serde<'a, T: serde::Deserialize<'a>>(
+    zbytes: &'a ZBytes,
+) -> Result<T, ZSerdeError> {
+    let mut deserializer = ZDeserializer::new(zbytes);
+    let t = T::deserialize(&mut deserializer)?;
+    if !deserializer.done() {
+        return Err(ZDeserializeError.into());
+    }
+    Ok(t)
+}
+
+fn serialize_variant_index(serializer: &mut ZSerializer, index: u32) {
+    serializer.serialize(index);
+}
+
+impl ser::Serializer for &mut ZSerializer {
+    type Ok = ();
+    type Error = ZSerdeError;
+    type SerializeSeq = Self;
+    type SerializeTuple = Self;
+    type SerializeTupleStruct = Self;
+    type SerializeTupleVariant = Self;
+    type SerializeMap = Self;
+    type SerializeStruct = Self;
+    type SerializeStructVariant = Self;
+
+    fn serialize_bool(self, v: bool) -> Result<(), ZSerdeError> {
+        self.serialize(v);
+        Ok(())
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<(), ZSerdeError> {
+        self.serialize(v);
+        Ok(())
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<(), ZSerdeError> {
+        self.serialize(v);
+        Ok(())
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<(), ZSerdeError> {
+        self.serialize(v);
+        Ok(())
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<(), ZSerdeError> {
+        self.serialize(v);
+        Ok(())
+    }
+
+    fn serialize_i128(self, v: i128) -> Result<(), ZSerdeError> {
+        self.serialize(v);
+        Ok(())
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<(), ZSerdeError> {
+        self.serialize(v);
+        Ok(())
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<(), ZSerdeError> {
+        self.serialize(v);
+        Ok(())
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<(), ZSerdeError> {
+        self.serialize(v);
+        Ok(())
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<(), ZSerdeError> {
+        self.serialize(v);
+        Ok(())
+    }
+
+    fn serialize_u128(self, v: u128) -> Result<(), ZSerdeError> {
+        self.serialize(v);
+        Ok(())
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<(), ZSerdeError> {
+        self.serialize(v);
+        Ok(())
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<(), ZSerdeError> {
+        self.serialize(v);
+        Ok(())
+    }
+
+    fn serialize_char(self, v: char) -> Result<(), ZSerdeError> {
+        self.serialize(v as u32);
+        Ok(())
+    }
+
+    fn serialize_str(self, v: &str) -> Result<(), ZSerdeError> {
+        self.serialize(v);
+        Ok(())
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<(), ZSerdeError> {
+        self.serialize(v);
+        Ok(())
+    }
+
+    fn serialize_none(self) -> Result<(), ZSerdeError> {
+        self.serialize(false);
+        Ok(())
+    }
+
+    fn serialize_some<T: serde::Serialize + ?Sized>(self, value: &T) -> Result<(), ZSerdeError> {
+        self.serialize(true);
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<(), ZSerdeError> {
+        Ok(())
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<(), ZSerdeError> {
+        Ok(())
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        variant_index: u32,
+        _variant: &'static str,
+    ) -> Result<(), ZSerdeError> {
+        serialize_variant_index(self, variant_index);
+        Ok(())
+    }
+
+    fn serialize_newtype_struct<T: serde::Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<(), ZSerdeError> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: serde::Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        variant_index: u32,
+        _variant: &'static str,
+        value: &T,
+    ) -> Result<(), ZSerdeError> {
+        serialize_variant_index(self, variant_index);
+        value.serialize(self)
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<Self, ZSerdeError> {
+        let len = len.ok_or_else(|| ZSerdeError("sequence length is required".into()))?;
+        self.serialize(VarInt(len));
+        Ok(self)
+    }
+
+    fn serialize_tuple(self, _len: usize) -> Result<Self, ZSerdeError> {
+        Ok(self)
+    }
+
+    fn serialize_tuple_struct(self, _name: &'static str, _len: usize) -> Result<Self, ZSerdeError> {
+        Ok(self)
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self, ZSerdeError> {
+        serialize_variant_index(self, variant_index);
+        Ok(self)
+    }
+
+    fn serialize_map(self, len: Option<usize>) -> Result<Self, ZSerdeError> {
+        let len = len.ok_or_else(|| ZSerdeError("map length is required".into()))?;
+        self.serialize(VarInt(len));
+        Ok(self)
+    }
+
+    fn serialize_struct(self, _name: &'static str, _len: usize) -> Result<Self, ZSerdeError> {
+        Ok(self)
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self, ZSerdeError> {
+        serialize_variant_index(self, variant_index);
+        Ok(self)
+    }
+
+    fn is_human_readable(&self) -> bool {
+        false
+    }
+}
+
+macro_rules! impl_serialize_compound {
+    ($($trait:ident::$method:ident),* $(,)?) => {$(
+        impl ser::$trait for &mut ZSerializer {
+            type Ok = ();
+            type Error = ZSerdeError;
+
+            fn $method<T: serde::Serialize + ?Sized>(&mut self, value: &T) -> Result<(), ZSerdeError> {
+                value.serialize(&mut **self)
+            }
+
+            fn end(self) -> Result<(), ZSerdeError> {
+                Ok(())
+            }
+        }
+    )*};
+}
+impl_serialize_compound!(
+    SerializeSeq::serialize_element,
+    SerializeTuple::serialize_element,
+    SerializeTupleStruct::serialize_field,
+    SerializeTupleVariant::serialize_field,
+);
+
+impl ser::SerializeMap for &mut ZSerializer {
+    type Ok = ();
+    type Error = ZSerdeError;
+
+    fn serialize_key<T: serde::Serialize + ?Sized>(&mut self, key: &T) -> Result<(), ZSerdeError> {
+        key.serialize(&mut **self)
+    }
+
+    fn serialize_value<T: serde::Serialize + ?Sized>(
+        &mut self,
+        value: &T,
+    ) -> Result<(), ZSerdeError> {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<(), ZSerdeError> {
+        Ok(())
+    }
+}
+
+impl ser::SerializeStruct for &mut ZSerializer {
+    type Ok = ();
+    type Error = ZSerdeError;
+
+    fn serialize_field<T: serde::Serialize + ?Sized>(
+        &mut self,
+        _key: &'static str,
+        value: &T,
+    ) -> Result<(), ZSerdeError> {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<(), ZSerdeError> {
+        Ok(())
+    }
+}
+
+impl ser::SerializeStructVariant for &mut ZSerializer {
+    type Ok = ();
+    type Error = ZSerdeError;
+
+    fn serialize_field<T: serde::Serialize + ?Sized>(
+        &mut self,
+        _key: &'static str,
+        value: &T,
+    ) -> Result<(), ZSerdeError> {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<(), ZSerdeError> {
+        Ok(())
+    }
+}
+
+// Access to a fixed number of elements, the length prefix having already been read if any
+struct Access<'a, 'b> {
+    deserializer: &'a mut ZDeserializer<'b>,
+    len: usize,
+}
+
+impl<'de> de::SeqAccess<'de> for Access<'_, 'de> {
+    type Error = ZSerdeError;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, ZSerdeError> {
+        if self.len == 0 {
+            return Ok(None);
+        }
+        self.len -= 1;
+        seed.deserialize(&mut *self.deserializer).map(Some)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.len)
+    }
+}
+
+impl<'de> de::MapAccess<'de> for Access<'_, 'de> {
+    type Error = ZSerdeError;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, ZSerdeError> {
+        if self.len == 0 {
+            return Ok(None);
+        }
+        self.len -= 1;
+        seed.deserialize(&mut *self.deserializer).map(Some)
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(
+        &mut self,
+        seed: V,
+    ) -> Result<V::Value, ZSerdeError> {
+        seed.deserialize(&mut *self.deserializer)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.len)
+    }
+}
+
+impl<'a, 'b> Access<'a, 'b> {
+    fn new(deserializer: &'a mut ZDeserializer<'b>, len: usize) -> Self {
+        Self { deserializer, len }
+    }
+
+    fn with_prefix(deserializer: &'a mut ZDeserializer<'b>) -> Result<Self, ZSerdeError> {
+        let len = deserializer.deserialize::<VarInt<usize>>()?.0;
+        Ok(Self::new(deserializer, len))
+    }
+}
+
+impl<'de> de::EnumAccess<'de> for &mut ZDeserializer<'de> {
+    type Error = ZSerdeError;
+    type Variant = Self;
+
+    fn variant_seed<V: DeserializeSeed<'de>>(
+        self,
+        seed: V,
+    ) -> Result<(V::Value, Self), ZSerdeError> {
+        let index = self.deserialize::<u32>()?;
+        let value = seed.deserialize(IntoDeserializer::<ZSerdeError>::into_deserializer(index))?;
+        Ok((value, self))
+    }
+}
+
+impl<'de> de::VariantAccess<'de> for &mut ZDeserializer<'de> {
+    type Error = ZSerdeError;
+
+    fn unit_variant(self) -> Result<(), ZSerdeError> {
+        Ok(())
+    }
+
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(
+        self,
+        seed: T,
+    ) -> Result<T::Value, ZSerdeError> {
+        seed.deserialize(self)
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(
+        self,
+        len: usize,
+        visitor: V,
+    ) -> Result<V::Value, ZSerdeError> {
+        visitor.visit_seq(Access::new(self, len))
+    }
+
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, ZSerdeError> {
+        visitor.visit_seq(Access::new(self, fields.len()))
+    }
+}
+
+macro_rules! impl_deserialize_primitive {
+    ($($method:ident => $visit:ident($ty:ty)),* $(,)?) => {$(
+        fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, ZSerdeError> {
+            visitor.$visit(self.deserialize::<$ty>()?)
+        }
+    )*};
+}
+
+impl<'de> de::Deserializer<'de> for &mut ZDeserializer<'de> {
+    type Error = ZSerdeError;
+
+    impl_deserialize_primitive!(
+        deserialize_bool => visit_bool(bool),
+        deserialize_i8 => visit_i8(i8),
+        deserialize_i16 => visit_i16(i16),
+        deserialize_i32 => visit_i32(i32),
+        deserialize_i64 => visit_i64(i64),
+        deserialize_i128 => visit_i128(i128),
+        deserialize_u8 => visit_u8(u8),
+        deserialize_u16 => visit_u16(u16),
+        deserialize_u32 => visit_u32(u32),
+        deserialize_u64 => visit_u64(u64),
+        deserialize_u128 => visit_u128(u128),
+        deserialize_f32 => visit_f32(f32),
+        deserialize_f64 => visit_f64(f64),
+        deserialize_string => visit_string(String),
+        deserialize_byte_buf => visit_byte_buf(Vec<u8>),
+        deserialize_identifier => visit_u32(u32),
+    );
+
+    fn deserialize_any<V: Visitor<'de>>(self, _visitor: V) -> Result<V::Value, ZSerdeError> {
+        Err(ZSerdeError(
+            "zenoh serialization format is not self-describing".into(),
+        ))
+    }
+
+    fn deserialize_str<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, ZSerdeError> {
+        match self.deserialize_borrowed_str()? {
+            Cow::Borrowed(s) => visitor.visit_borrowed_str(s),
+            Cow::Owned(s) => visitor.visit_string(s),
+        }
+    }
+
+    fn deserialize_bytes<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, ZSerdeError> {
+        match self.deserialize_borrowed_bytes()? {
+            Cow::Borrowed(bytes) => visitor.visit_borrowed_bytes(bytes),
+            Cow::Owned(bytes) => visitor.visit_byte_buf(bytes),
+        }
+    }
+
+    fn deserialize_char<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, ZSerdeError> {
+        let c = char::from_u32(self.deserialize::<u32>()?).ok_or(ZDeserializeError)?;
+        visitor.visit_char(c)
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, ZSerdeError> {
+        if self.deserialize::<bool>()? {
+            visitor.visit_some(self)
+        } else {
+            visitor.visit_none()
+        }
+    }
+
+    fn deserialize_unit<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, ZSerdeError> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_unit_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, ZSerdeError> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, ZSerdeError> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, ZSerdeError> {
+        visitor.visit_seq(Access::with_prefix(self)?)
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(
+        self,
+        len: usize,
+        visitor: V,
+    ) -> Result<V::Value, ZSerdeError> {
+        visitor.visit_seq(Access::new(self, len))
+    }
+
+    fn deserialize_tuple_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        len: usize,
+        visitor: V,
+    ) -> Result<V::Value, ZSerdeError> {
+        visitor.visit_seq(Access::new(self, len))
+    }
+
+    fn deserialize_map<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, ZSerdeError> {
+        visitor.visit_map(Access::with_prefix(self)?)
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, ZSerdeError> {
+        visitor.visit_seq(Access::new(self, fields.len()))
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, ZSerdeError> {
+        visitor.visit_enum(self)
+    }
+
+    fn deserialize_ignored_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, ZSerdeError> {
+        self.deserialize_any(visitor)
+    }
+
+    fn is_human_readable(&self) -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::{z_deserialize, z_serialize};
+
+    #[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+    struct Message {
+        id: u32,
+        name: String,
+        values: Vec<f64>,
+        tags: HashMap<String, i64>,
+        flag: bool,
+        location: (f32, f32),
+        payload: Option<Vec<u8>>,
+        kind: Kind,
+    }
+
+    #[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+    enum Kind {
+        Empty,
+        Value(i16),
+        Pair(u8, String),
+        Named { x: i32, c: char },
+    }
+
+    #[test]
+    fn serde_roundtrip() {
+        for kind in [
+            Kind::Empty,
+            Kind::Value(-3),
+            Kind::Pair(7, "seven".into()),
+            Kind::Named { x: 42, c: 'z' },
+        ] {
+            let message = Message {
+                id: 1,
+                name: "message".into(),
+                values: vec![0.5, 1.5],
+                tags: HashMap::from([("tag".to_string(), -1)]),
+                flag: true,
+                location: (1.0, 2.0),
+                payload: Some(vec![1, 2, 3]),
+                kind,
+            };
+            let zbytes = z_serialize_serde(&message).unwrap();
+            assert_eq!(z_deserialize_serde::<Message>(&zbytes).unwrap(), message);
+        }
+    }
+
+    #[test]
+    fn serde_binary_format() {
+        #[derive(serde::Serialize, serde::Deserialize)]
+        struct Point(u16, f32, String);
+        let serde = z_serialize_serde(&Point(500, 1234.0, "test".into())).unwrap();
+        let native = z_serialize(&(500u16, 1234.0f32, "test"));
+        assert_eq!(serde.to_bytes(), native.to_bytes());
+
+        let vp = vec![("s1".to_string(), 10i16), ("s2".to_string(), -10000)];
+        let serde = z_serialize_serde(&vp).unwrap();
+        assert_eq!(serde.to_bytes(), z_serialize(&vp).to_bytes());
+        assert_eq!(z_deserialize::<Vec<(String, i16)>>(&serde).unwrap(), vp);
+
+        let map = HashMap::from([("hello".to_string(), "world".to_string())]);
+        let serde = z_serialize_serde(&map).unwrap();
+        assert_eq!(serde.to_bytes(), z_serialize(&map).to_bytes());
+    }
+
+    #[test]
+    fn serde_borrowed() {
+        #[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+        struct Frame<'a> {
+            id: u32,
+            label: &'a str,
+            data: &'a [u8],
+            #[serde(borrow)]
+            extra: Cow<'a, [u8]>,
+        }
+        let frame = Frame {
+            id: 1,
+            label: "frame",
+            data: &[1, 2, 3],
+            extra: Cow::Borrowed(&[4, 5]),
+        };
+        let zbytes = z_serialize_serde(&frame).unwrap();
+        let deserialized = z_deserialize_serde::<Frame>(&zbytes).unwrap();
+        assert_eq!(deserialized, frame);
+        assert!(matches!(deserialized.extra, Cow::Borrowed(_)));
+
+        // Fragmented payloads can only be deserialized into owned data
+        let mut writer = ZBytes::writer();
+        writer.append(zbytes.to_bytes()[..6].to_vec().into());
+        writer.append(zbytes.to_bytes()[6..].to_vec().into());
+        let fragmented = writer.finish();
+        assert!(z_deserialize_serde::<Frame>(&fragmented).is_err());
+        let owned = z_deserialize_serde::<(u32, String, Vec<u8>, Cow<[u8]>)>(&fragmented).unwrap();
+        assert_eq!(
+            owned,
+            (1, "frame".into(), vec![1, 2, 3], Cow::Owned(vec![4, 5]))
+        );
+    }
+
+    #[test]
+    fn serde_errors() {
+        let zbytes = z_serialize(&(1u32, 2u32));
+        assert!(z_deserialize_serde::<u32>(&zbytes).is_err());
+        assert!(z_deserialize_serde::<(u32, u32, u32)>(&zbytes).is_err());
+        assert!(z_deserialize_serde::<serde_json::Value>(&zbytes).is_err());
+    }
+}
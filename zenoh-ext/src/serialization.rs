@@ -251,6 +251,48 @@ impl<'a> ZDeserializer<'a> {
         T::deserialize(self)
     }
 
+    /// Deserialize a byte sequence, borrowed from the [`ZBytes`] if it's stored contiguously.
+    ///
+    /// It's the zero-copy equivalent of deserializing a `Vec<u8>`; if the bytes span several
+    /// slices of the [`ZBytes`], they are copied. See [`ZBytes::contiguous`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::borrow::Cow;
+    /// use zenoh_ext::*;
+    /// let zbytes = z_serialize(&(vec![1u8, 2, 3], "text"));
+    /// let mut deserializer = ZDeserializer::new(&zbytes);
+    /// assert!(matches!(deserializer.deserialize_borrowed_bytes().unwrap(), Cow::Borrowed(&[1, 2, 3])));
+    /// assert!(matches!(deserializer.deserialize_borrowed_str().unwrap(), Cow::Borrowed("text")));
+    /// ```
+    #[zenoh_macros::unstable]
+    pub fn deserialize_borrowed_bytes(&mut self) -> Result<Cow<'a, [u8]>, ZDeserializeError> {
+        let len = <VarInt<usize>>::deserialize(self)?.0;
+        if let Some(bytes) = self.0.read_borrowed(len) {
+            return Ok(Cow::Borrowed(bytes));
+        }
+        if len > self.0.remaining() {
+            return Err(ZDeserializeError);
+        }
+        let mut bytes = vec![0; len];
+        self.0.read_exact(&mut bytes).or(Err(ZDeserializeError))?;
+        Ok(Cow::Owned(bytes))
+    }
+
+    /// Deserialize a string, borrowed from the [`ZBytes`] if it's stored contiguously.
+    ///
+    /// See [`ZDeserializer::deserialize_borrowed_bytes`].
+    #[zenoh_macros::unstable]
+    pub fn deserialize_borrowed_str(&mut self) -> Result<Cow<'a, str>, ZDeserializeError> {
+        Ok(match self.deserialize_borrowed_bytes()? {
+            Cow::Borrowed(bytes) => {
+                Cow::Borrowed(std::str::from_utf8(bytes).or(Err(ZDeserializeError))?)
+            }
+            Cow::Owned(bytes) => Cow::Owned(String::from_utf8(bytes).or(Err(ZDeserializeError))?),
+        })
+    }
+
     /// Deserialize an iterator into a [`ZDeserializer`].
     ///
     /// Sequence deserialized with this method may have been serialized with [`ZSerializer::serialize_iter`].
@@ -500,6 +542,24 @@ impl Deserialize for String {
     }
 }
 
+impl<T: Serialize> Serialize for Option<T> {
+    fn serialize(&self, serializer: &mut ZSerializer) {
+        self.is_some().serialize(serializer);
+        if let Some(t) = self {
+            t.serialize(serializer);
+        }
+    }
+}
+impl<T: Deserialize> Deserialize for Option<T> {
+    fn deserialize(deserializer: &mut ZDeserializer) -> Result<Self, ZDeserializeError> {
+        if bool::deserialize(deserializer)? {
+            Ok(Some(T::deserialize(deserializer)?))
+        } else {
+            Ok(None)
+        }
+    }
+}
+
 macro_rules! impl_tuple {
     ($($ty:ident/$i:tt),* $(,)?) => {
         impl_tuple!(@;$($ty/$i),*);
@@ -628,6 +688,13 @@ mod tests {
         assert_eq!(s, z_deserialize::<String>(&payload).unwrap())
     }
 
+    #[test]
+    fn option_serialization() {
+        serialize_deserialize!(Option<String>, Some("option".to_string()));
+        serialize_deserialize!(Option<String>, None);
+        serialize_deserialize!(Vec<Option<u8>>, vec![Some(1), None]);
+    }
+
     #[test]
     fn tuple_serialization() {
         serialize_deserialize!(
@@ -670,5 +737,7 @@ mod tests {
         );
         let vp: Vec<(&str, i16)> = vec![("s1", 10), ("s2", -10000)];
         check_binary_format!(vp, vec![2, 2, 115, 49, 10, 0, 2, 115, 50, 240, 216]);
+        let o: (Option<u16>, Option<u16>) = (Some(500), None);
+        check_binary_format!(o, vec![1, 244, 1, 0]);
     }
 }
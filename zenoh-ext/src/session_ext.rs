@@ -12,9 +12,12 @@
 //   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
 //
 
-use zenoh::{key_expr::KeyExpr, session::Session, Error};
+use zenoh::{key_expr::KeyExpr, query::Selector, session::Session, Error, Result as ZResult};
 
-use super::PublicationCacheBuilder;
+use super::{
+    OfflinePublisherBuilder, PublicationCacheBuilder, SchemaRegistryBuilder, SchemaRegistryClient,
+    TypedGetBuilder, TypedPublisherBuilder, TypedQueryableBuilder, TypedSubscriberBuilder,
+};
 
 /// Some extensions to the [`zenoh::Session`](zenoh::Session)
 #[zenoh_macros::unstable]
@@ -44,6 +47,116 @@ pub trait SessionExt {
     where
         TryIntoKeyExpr: TryInto<KeyExpr<'b>>,
         <TryIntoKeyExpr as TryInto<KeyExpr<'b>>>::Error: Into<Error>;
+
+    /// Declare an [`OfflinePublisher`](crate::OfflinePublisher), queuing its publications
+    /// while they have no matching subscriber.
+    ///
+    /// Examples:
+    /// ```no_run
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// use zenoh_ext::SessionExt;
+    ///
+    /// let session = zenoh::open(zenoh::Config::default()).await.unwrap();
+    /// let publisher = session
+    ///     .declare_offline_publisher("key/expression")
+    ///     .path("key_expression.queue")
+    ///     .await
+    ///     .unwrap();
+    /// publisher.put("value").await.unwrap();
+    /// # }
+    /// ```
+    fn declare_offline_publisher<'a, 'b, TryIntoKeyExpr>(
+        &'a self,
+        key_expr: TryIntoKeyExpr,
+    ) -> OfflinePublisherBuilder<'a, 'b>
+    where
+        TryIntoKeyExpr: TryInto<KeyExpr<'b>>,
+        <TryIntoKeyExpr as TryInto<KeyExpr<'b>>>::Error: Into<Error>;
+
+    /// Declare a [`TypedPublisher`](crate::TypedPublisher), publishing values of type `T`
+    /// serialized with a [`Codec`](crate::Codec), [`ZSerializeCodec`](crate::ZSerializeCodec) by default.
+    ///
+    /// The schema identifies the type of the values and is added to the encoding of the codec.
+    fn declare_typed_publisher<'a, 'b, T, TryIntoKeyExpr>(
+        &'a self,
+        key_expr: TryIntoKeyExpr,
+        schema: &str,
+    ) -> TypedPublisherBuilder<'a, 'b, T>
+    where
+        TryIntoKeyExpr: TryInto<KeyExpr<'b>>,
+        <TryIntoKeyExpr as TryInto<KeyExpr<'b>>>::Error: Into<Error>;
+
+    /// Declare a [`TypedSubscriber`](crate::TypedSubscriber), receiving values of type `T`
+    /// deserialized with a [`Codec`](crate::Codec), [`ZSerializeCodec`](crate::ZSerializeCodec) by default.
+    ///
+    /// The samples whose encoding doesn't carry the given schema are received as errors.
+    fn declare_typed_subscriber<'a, 'b, T, TryIntoKeyExpr>(
+        &'a self,
+        key_expr: TryIntoKeyExpr,
+        schema: &str,
+    ) -> TypedSubscriberBuilder<'a, 'b, T>
+    where
+        TryIntoKeyExpr: TryInto<KeyExpr<'b>>,
+        <TryIntoKeyExpr as TryInto<KeyExpr<'b>>>::Error: Into<Error>;
+
+    /// Declare a [`TypedQueryable`](crate::TypedQueryable), replying values of type `T`
+    /// serialized with a [`Codec`](crate::Codec), [`ZSerializeCodec`](crate::ZSerializeCodec) by default.
+    fn declare_typed_queryable<'a, 'b, T, TryIntoKeyExpr>(
+        &'a self,
+        key_expr: TryIntoKeyExpr,
+        schema: &str,
+    ) -> TypedQueryableBuilder<'a, 'b, T>
+    where
+        TryIntoKeyExpr: TryInto<KeyExpr<'b>>,
+        <TryIntoKeyExpr as TryInto<KeyExpr<'b>>>::Error: Into<Error>;
+
+    /// Query data, receiving the replied values of type `T` deserialized with a
+    /// [`Codec`](crate::Codec), [`ZSerializeCodec`](crate::ZSerializeCodec) by default.
+    ///
+    /// Examples:
+    /// ```no_run
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// use zenoh_ext::SessionExt;
+    ///
+    /// let session = zenoh::open(zenoh::Config::default()).await.unwrap();
+    /// let replies = session
+    ///     .typed_get::<f64, _>("key/expression", "example/Temperature")
+    ///     .await
+    ///     .unwrap();
+    /// while let Ok(reply) = replies.recv_async().await {
+    ///     println!(">> Received {:?}", reply.result());
+    /// }
+    /// # }
+    /// ```
+    fn typed_get<'a, 'b: 'a, T, IntoSelector>(
+        &'a self,
+        selector: IntoSelector,
+        schema: &str,
+    ) -> TypedGetBuilder<'a, 'b, T>
+    where
+        IntoSelector: TryInto<Selector<'b>>,
+        <IntoSelector as TryInto<Selector<'b>>>::Error: Into<Error>;
+
+    /// Declare a [`SchemaRegistry`](crate::SchemaRegistry), registering and resolving
+    /// schemas under the given prefix.
+    fn declare_schema_registry<'a, 'b, TryIntoKeyExpr>(
+        &'a self,
+        prefix: TryIntoKeyExpr,
+    ) -> SchemaRegistryBuilder<'a, 'b>
+    where
+        TryIntoKeyExpr: TryInto<KeyExpr<'b>>,
+        <TryIntoKeyExpr as TryInto<KeyExpr<'b>>>::Error: Into<Error>;
+
+    /// Create a [`SchemaRegistryClient`] of the schema registry declared on the given prefix.
+    fn schema_registry_client<TryIntoKeyExpr>(
+        &self,
+        prefix: TryIntoKeyExpr,
+    ) -> ZResult<SchemaRegistryClient>
+    where
+        TryIntoKeyExpr: TryInto<KeyExpr<'static>>,
+        <TryIntoKeyExpr as TryInto<KeyExpr<'static>>>::Error: Into<Error>;
 }
 
 impl SessionExt for Session {
@@ -57,4 +170,96 @@ impl SessionExt for Session {
     {
         PublicationCacheBuilder::new(self, pub_key_expr.try_into().map_err(Into::into))
     }
+
+    fn declare_offline_publisher<'a, 'b, TryIntoKeyExpr>(
+        &'a self,
+        key_expr: TryIntoKeyExpr,
+    ) -> OfflinePublisherBuilder<'a, 'b>
+    where
+        TryIntoKeyExpr: TryInto<KeyExpr<'b>>,
+        <TryIntoKeyExpr as TryInto<KeyExpr<'b>>>::Error: Into<Error>,
+    {
+        OfflinePublisherBuilder::new(self, key_expr.try_into().map_err(Into::into))
+    }
+
+    fn declare_typed_publisher<'a, 'b, T, TryIntoKeyExpr>(
+        &'a self,
+        key_expr: TryIntoKeyExpr,
+        schema: &str,
+    ) -> TypedPublisherBuilder<'a, 'b, T>
+    where
+        TryIntoKeyExpr: TryInto<KeyExpr<'b>>,
+        <TryIntoKeyExpr as TryInto<KeyExpr<'b>>>::Error: Into<Error>,
+    {
+        TypedPublisherBuilder::new(self.declare_publisher(key_expr), schema.to_string())
+    }
+
+    fn declare_typed_subscriber<'a, 'b, T, TryIntoKeyExpr>(
+        &'a self,
+        key_expr: TryIntoKeyExpr,
+        schema: &str,
+    ) -> TypedSubscriberBuilder<'a, 'b, T>
+    where
+        TryIntoKeyExpr: TryInto<KeyExpr<'b>>,
+        <TryIntoKeyExpr as TryInto<KeyExpr<'b>>>::Error: Into<Error>,
+    {
+        TypedSubscriberBuilder::new(
+            self,
+            key_expr.try_into().map_err(Into::into),
+            schema.to_string(),
+        )
+    }
+
+    fn declare_typed_queryable<'a, 'b, T, TryIntoKeyExpr>(
+        &'a self,
+        key_expr: TryIntoKeyExpr,
+        schema: &str,
+    ) -> TypedQueryableBuilder<'a, 'b, T>
+    where
+        TryIntoKeyExpr: TryInto<KeyExpr<'b>>,
+        <TryIntoKeyExpr as TryInto<KeyExpr<'b>>>::Error: Into<Error>,
+    {
+        TypedQueryableBuilder::new(
+            self,
+            key_expr.try_into().map_err(Into::into),
+            schema.to_string(),
+        )
+    }
+
+    fn typed_get<'a, 'b: 'a, T, IntoSelector>(
+        &'a self,
+        selector: IntoSelector,
+        schema: &str,
+    ) -> TypedGetBuilder<'a, 'b, T>
+    where
+        IntoSelector: TryInto<Selector<'b>>,
+        <IntoSelector as TryInto<Selector<'b>>>::Error: Into<Error>,
+    {
+        TypedGetBuilder::new(self.get(selector), schema.to_string())
+    }
+
+    fn declare_schema_registry<'a, 'b, TryIntoKeyExpr>(
+        &'a self,
+        prefix: TryIntoKeyExpr,
+    ) -> SchemaRegistryBuilder<'a, 'b>
+    where
+        TryIntoKeyExpr: TryInto<KeyExpr<'b>>,
+        <TryIntoKeyExpr as TryInto<KeyExpr<'b>>>::Error: Into<Error>,
+    {
+        SchemaRegistryBuilder::new(self, prefix.try_into().map_err(Into::into))
+    }
+
+    fn schema_registry_client<TryIntoKeyExpr>(
+        &self,
+        prefix: TryIntoKeyExpr,
+    ) -> ZResult<SchemaRegistryClient>
+    where
+        TryIntoKeyExpr: TryInto<KeyExpr<'static>>,
+        <TryIntoKeyExpr as TryInto<KeyExpr<'static>>>::Error: Into<Error>,
+    {
+        Ok(SchemaRegistryClient::new(
+            self,
+            prefix.try_into().map_err(Into::into)?,
+        ))
+    }
 }
@@ -0,0 +1,932 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+
+//! Typed publishers, subscribers, queryables and queries.
+//!
+//! The values are serialized with a [`Codec`] and published with the encoding of the codec,
+//! completed with a schema identifying their type. The schema is given when declaring the typed
+//! entities and must be the same on both sides, so it should be a stable name of the type
+//! rather than a Rust type name, which may change between compiler versions.
+//! The received samples whose encoding or payload doesn't match the expected type are
+//! surfaced with a [`TypedError`] instead of being silently misinterpreted.
+use std::{
+    fmt,
+    future::{IntoFuture, Ready},
+    marker::PhantomData,
+    sync::Arc,
+    time::Duration,
+};
+
+use zenoh::{
+    bytes::{Encoding, OptionZBytes, ZBytes},
+    handlers::{locked, Callback, DefaultHandler, IntoHandler},
+    internal::zerror,
+    key_expr::KeyExpr,
+    pubsub::{
+        Publisher, PublisherBuilder, PublisherDeleteBuilder, PublisherPutBuilder, Subscriber,
+    },
+    qos::{CongestionControl, Priority},
+    query::{Query, QueryTarget, Queryable, Reply, ReplyBuilder, ReplyBuilderPut, ReplyError},
+    sample::{Sample, SampleKind},
+    session::SessionGetBuilder,
+    time::Timestamp,
+    Error, Resolvable, Resolve, Result as ZResult, Session, Wait,
+};
+
+use crate::{z_deserialize, z_serialize, Deserialize, Serialize};
+
+/// A codec serializing values of type `T` to payloads of a given [`Encoding`].
+///
+/// [`ZSerializeCodec`] and [`JsonCodec`] are provided, other formats (CBOR, protobuf, ...)
+/// can be plugged in by implementing this trait.
+#[zenoh_macros::unstable]
+pub trait Codec<T>: Send + Sync + 'static {
+    /// The encoding of the serialized values, before the schema is added.
+    fn encoding(&self) -> Encoding;
+
+    /// Serialize a value into a payload.
+    fn encode(&self, value: &T) -> ZResult<ZBytes>;
+
+    /// Deserialize a value from a payload.
+    fn decode(&self, payload: &ZBytes) -> ZResult<T>;
+}
+
+/// A [`Codec`] using the zenoh-ext serialization format, see [`z_serialize`].
+#[zenoh_macros::unstable]
+#[derive(Debug, Default, Clone, Copy)]
+pub struct ZSerializeCodec;
+
+impl<T: Serialize + Deserialize> Codec<T> for ZSerializeCodec {
+    fn encoding(&self) -> Encoding {
+        Encoding::ZENOH_SERIALIZED
+    }
+
+    fn encode(&self, value: &T) -> ZResult<ZBytes> {
+        Ok(z_serialize(value))
+    }
+
+    fn decode(&self, payload: &ZBytes) -> ZResult<T> {
+        Ok(z_deserialize(payload)?)
+    }
+}
+
+/// A [`Codec`] using the JSON format for the types implementing the serde traits.
+#[zenoh_macros::unstable]
+#[derive(Debug, Default, Clone, Copy)]
+pub struct JsonCodec;
+
+impl<T: serde::Serialize + serde::de::DeserializeOwned> Codec<T> for JsonCodec {
+    fn encoding(&self) -> Encoding {
+        Encoding::APPLICATION_JSON
+    }
+
+    fn encode(&self, value: &T) -> ZResult<ZBytes> {
+        Ok(serde_json::to_vec(value)?.into())
+    }
+
+    fn decode(&self, payload: &ZBytes) -> ZResult<T> {
+        Ok(serde_json::from_slice(&payload.to_bytes())?)
+    }
+}
+
+/// The error of a received sample that doesn't carry a value of the expected type.
+#[zenoh_macros::unstable]
+#[derive(Debug)]
+pub enum TypedError {
+    /// The encoding of the sample is not the one of the expected type.
+    Encoding { expected: Encoding, found: Encoding },
+    /// The payload of the sample could not be decoded.
+    Decode(Error),
+    /// The sample is a deletion, which carries no value.
+    Delete,
+}
+
+impl fmt::Display for TypedError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TypedError::Encoding { expected, found } => {
+                write!(f, "unexpected encoding {found}, expected {expected}")
+            }
+            TypedError::Decode(e) => write!(f, "unable to decode payload: {e}"),
+            TypedError::Delete => write!(f, "deletion carries no value"),
+        }
+    }
+}
+
+impl std::error::Error for TypedError {}
+
+// The encoding of the values of type T serialized with the given codec
+fn typed_encoding<T, C: Codec<T>>(codec: &C, schema: String) -> Encoding {
+    codec.encoding().with_schema(schema)
+}
+
+fn decode<T, C: Codec<T>>(
+    codec: &C,
+    encoding: &Encoding,
+    sample: &Sample,
+) -> Result<T, TypedError> {
+    if sample.kind() == SampleKind::Delete {
+        return Err(TypedError::Delete);
+    }
+    if sample.encoding() != encoding {
+        return Err(TypedError::Encoding {
+            expected: encoding.clone(),
+            found: sample.encoding().clone(),
+        });
+    }
+    codec.decode(sample.payload()).map_err(TypedError::Decode)
+}
+
+/// A received [`Sample`] with the value of type `T` it carries, or the error preventing to decode it.
+#[zenoh_macros::unstable]
+#[derive(Debug)]
+pub struct TypedSample<T> {
+    sample: Sample,
+    value: Result<T, TypedError>,
+}
+
+impl<T> TypedSample<T> {
+    /// The received sample.
+    pub fn sample(&self) -> &Sample {
+        &self.sample
+    }
+
+    /// The value carried by the sample.
+    pub fn value(&self) -> Result<&T, &TypedError> {
+        self.value.as_ref()
+    }
+
+    pub fn into_value(self) -> Result<T, TypedError> {
+        self.value
+    }
+
+    pub fn into_parts(self) -> (Sample, Result<T, TypedError>) {
+        (self.sample, self.value)
+    }
+}
+
+/// The builder of [`TypedPublisher`], allowing to configure it.
+#[zenoh_macros::unstable]
+#[must_use = "Resolvables do nothing unless you resolve them using `.await` or `zenoh::Wait::wait`"]
+pub struct TypedPublisherBuilder<'a, 'b, T, C = ZSerializeCodec> {
+    publisher: PublisherBuilder<'a, 'b>,
+    codec: C,
+    schema: String,
+    _type: PhantomData<fn(&T)>,
+}
+
+impl<'a, 'b, T> TypedPublisherBuilder<'a, 'b, T> {
+    pub(crate) fn new(publisher: PublisherBuilder<'a, 'b>, schema: String) -> Self {
+        TypedPublisherBuilder {
+            publisher,
+            codec: ZSerializeCodec,
+            schema,
+            _type: PhantomData,
+        }
+    }
+}
+
+impl<'a, 'b, T, C> TypedPublisherBuilder<'a, 'b, T, C> {
+    /// Use the given codec to serialize the values.
+    pub fn codec<C2: Codec<T>>(self, codec: C2) -> TypedPublisherBuilder<'a, 'b, T, C2> {
+        TypedPublisherBuilder {
+            publisher: self.publisher,
+            codec,
+            schema: self.schema,
+            _type: PhantomData,
+        }
+    }
+
+    /// Change the [`CongestionControl`] to apply when routing the data.
+    pub fn congestion_control(mut self, congestion_control: CongestionControl) -> Self {
+        self.publisher = self.publisher.congestion_control(congestion_control);
+        self
+    }
+
+    /// Change the [`Priority`] of the written data.
+    pub fn priority(mut self, priority: Priority) -> Self {
+        self.publisher = self.publisher.priority(priority);
+        self
+    }
+
+    /// Change the Express policy to apply when routing the data.
+    pub fn express(mut self, is_express: bool) -> Self {
+        self.publisher = self.publisher.express(is_express);
+        self
+    }
+}
+
+impl<'b, T, C: Codec<T>> Resolvable for TypedPublisherBuilder<'_, 'b, T, C> {
+    type To = ZResult<TypedPublisher<'b, T, C>>;
+}
+
+impl<T, C: Codec<T>> Wait for TypedPublisherBuilder<'_, '_, T, C> {
+    fn wait(self) -> <Self as Resolvable>::To {
+        let encoding = typed_encoding(&self.codec, self.schema);
+        let publisher = self.publisher.encoding(encoding).wait()?;
+        Ok(TypedPublisher {
+            publisher,
+            codec: self.codec,
+            _type: PhantomData,
+        })
+    }
+}
+
+impl<T, C: Codec<T>> IntoFuture for TypedPublisherBuilder<'_, '_, T, C> {
+    type Output = <Self as Resolvable>::To;
+    type IntoFuture = Ready<<Self as Resolvable>::To>;
+
+    fn into_future(self) -> Self::IntoFuture {
+        std::future::ready(self.wait())
+    }
+}
+
+/// A publisher of values of type `T`.
+///
+/// # Examples
+/// ```no_run
+/// # #[tokio::main]
+/// # async fn main() {
+/// use zenoh_ext::SessionExt;
+///
+/// let session = zenoh::open(zenoh::Config::default()).await.unwrap();
+/// let publisher = session
+///     .declare_typed_publisher::<(u32, String), _>("key/expression", "example/Pair")
+///     .await
+///     .unwrap();
+/// publisher.put(&(42, "value".to_string())).await.unwrap();
+/// # }
+/// ```
+#[zenoh_macros::unstable]
+pub struct TypedPublisher<'a, T, C = ZSerializeCodec> {
+    publisher: Publisher<'a>,
+    codec: C,
+    _type: PhantomData<fn(&T)>,
+}
+
+impl<'a, T, C: Codec<T>> TypedPublisher<'a, T, C> {
+    /// Put a value, failing when resolved if it can't be serialized.
+    pub fn put(&self, value: &T) -> TypedPublicationBuilder<'_> {
+        TypedPublicationBuilder {
+            put: self
+                .codec
+                .encode(value)
+                .map(|payload| self.publisher.put(payload)),
+        }
+    }
+
+    /// Delete the value.
+    pub fn delete(&self) -> PublisherDeleteBuilder<'_> {
+        self.publisher.delete()
+    }
+
+    /// The untyped publisher publishing the serialized values.
+    pub fn publisher(&self) -> &Publisher<'a> {
+        &self.publisher
+    }
+
+    pub fn key_expr(&self) -> &KeyExpr<'a> {
+        self.publisher.key_expr()
+    }
+
+    /// The encoding of the published values, including the schema.
+    pub fn encoding(&self) -> &Encoding {
+        self.publisher.encoding()
+    }
+
+    pub fn undeclare(self) -> impl Resolve<ZResult<()>> + 'a {
+        self.publisher.undeclare()
+    }
+}
+
+/// A publication of a [`TypedPublisher`].
+#[zenoh_macros::unstable]
+#[must_use = "Resolvables do nothing unless you resolve them using `.await` or `zenoh::Wait::wait`"]
+pub struct TypedPublicationBuilder<'a> {
+    put: ZResult<PublisherPutBuilder<'a>>,
+}
+
+impl TypedPublicationBuilder<'_> {
+    /// Set the attachment of the published data.
+    pub fn attachment<T: Into<OptionZBytes>>(mut self, attachment: T) -> Self {
+        self.put = self.put.map(|put| put.attachment(attachment));
+        self
+    }
+
+    /// Set the timestamp of the published data.
+    pub fn timestamp<T: Into<Option<Timestamp>>>(mut self, timestamp: T) -> Self {
+        self.put = self.put.map(|put| put.timestamp(timestamp));
+        self
+    }
+}
+
+impl Resolvable for TypedPublicationBuilder<'_> {
+    type To = ZResult<()>;
+}
+
+impl Wait for TypedPublicationBuilder<'_> {
+    fn wait(self) -> <Self as Resolvable>::To {
+        self.put?.wait()
+    }
+}
+
+impl IntoFuture for TypedPublicationBuilder<'_> {
+    type Output = <Self as Resolvable>::To;
+    type IntoFuture = Ready<<Self as Resolvable>::To>;
+
+    fn into_future(self) -> Self::IntoFuture {
+        std::future::ready(self.wait())
+    }
+}
+
+/// The builder of [`TypedSubscriber`], allowing to configure it.
+#[zenoh_macros::unstable]
+#[must_use = "Resolvables do nothing unless you resolve them using `.await` or `zenoh::Wait::wait`"]
+pub struct TypedSubscriberBuilder<'a, 'b, T, C = ZSerializeCodec, Handler = DefaultHandler> {
+    session: &'a Session,
+    key_expr: ZResult<KeyExpr<'b>>,
+    codec: C,
+    schema: String,
+    handler: Handler,
+    _type: PhantomData<fn(&T)>,
+}
+
+impl<'a, 'b, T> TypedSubscriberBuilder<'a, 'b, T> {
+    pub(crate) fn new(
+        session: &'a Session,
+        key_expr: ZResult<KeyExpr<'b>>,
+        schema: String,
+    ) -> Self {
+        TypedSubscriberBuilder {
+            session,
+            key_expr,
+            codec: ZSerializeCodec,
+            schema,
+            handler: DefaultHandler::default(),
+            _type: PhantomData,
+        }
+    }
+}
+
+impl<'a, 'b, T, C, Handler> TypedSubscriberBuilder<'a, 'b, T, C, Handler> {
+    /// Use the given codec to deserialize the values.
+    pub fn codec<C2: Codec<T>>(self, codec: C2) -> TypedSubscriberBuilder<'a, 'b, T, C2, Handler> {
+        TypedSubscriberBuilder {
+            session: self.session,
+            key_expr: self.key_expr,
+            codec,
+            schema: self.schema,
+            handler: self.handler,
+            _type: PhantomData,
+        }
+    }
+}
+
+impl<'a, 'b, T: 'static, C> TypedSubscriberBuilder<'a, 'b, T, C, DefaultHandler> {
+    /// Add callback to [`TypedSubscriber`].
+    #[inline]
+    pub fn callback<F>(
+        self,
+        callback: F,
+    ) -> TypedSubscriberBuilder<'a, 'b, T, C, Callback<TypedSample<T>>>
+    where
+        F: Fn(TypedSample<T>) + Send + Sync + 'static,
+    {
+        self.with(Callback::new(Arc::new(callback)))
+    }
+
+    /// Add callback to [`TypedSubscriber`].
+    ///
+    /// Using this guarantees that your callback will never be called concurrently.
+    /// If your callback is also accepted by the [`callback`](TypedSubscriberBuilder::callback)
+    /// method, we suggest you use it instead of `callback_mut`.
+    #[inline]
+    pub fn callback_mut<F>(
+        self,
+        callback: F,
+    ) -> TypedSubscriberBuilder<'a, 'b, T, C, Callback<TypedSample<T>>>
+    where
+        F: FnMut(TypedSample<T>) + Send + Sync + 'static,
+    {
+        self.callback(locked(callback))
+    }
+
+    /// Use the given handler to receive the typed samples.
+    #[inline]
+    pub fn with<Handler>(self, handler: Handler) -> TypedSubscriberBuilder<'a, 'b, T, C, Handler>
+    where
+        Handler: IntoHandler<TypedSample<T>>,
+    {
+        TypedSubscriberBuilder {
+            session: self.session,
+            key_expr: self.key_expr,
+            codec: self.codec,
+            schema: self.schema,
+            handler,
+            _type: PhantomData,
+        }
+    }
+}
+
+impl<T, C, Handler> Resolvable for TypedSubscriberBuilder<'_, '_, T, C, Handler>
+where
+    T: 'static,
+    C: Codec<T>,
+    Handler: IntoHandler<TypedSample<T>>,
+    Handler::Handler: Send,
+{
+    type To = ZResult<TypedSubscriber<Handler::Handler>>;
+}
+
+impl<T, C, Handler> Wait for TypedSubscriberBuilder<'_, '_, T, C, Handler>
+where
+    T: 'static,
+    C: Codec<T>,
+    Handler: IntoHandler<TypedSample<T>>,
+    Handler::Handler: Send,
+{
+    fn wait(self) -> <Self as Resolvable>::To {
+        let encoding = typed_encoding(&self.codec, self.schema);
+        let codec = self.codec;
+        let (callback, handler) = self.handler.into_handler();
+        let subscriber = self
+            .session
+            .declare_subscriber(self.key_expr?)
+            .callback(move |sample| {
+                let value = decode(&codec, &encoding, &sample);
+                callback.call(TypedSample { sample, value })
+            })
+            .wait()?;
+        Ok(TypedSubscriber {
+            subscriber,
+            handler,
+        })
+    }
+}
+
+impl<T, C, Handler> IntoFuture for TypedSubscriberBuilder<'_, '_, T, C, Handler>
+where
+    T: 'static,
+    C: Codec<T>,
+    Handler: IntoHandler<TypedSample<T>>,
+    Handler::Handler: Send,
+{
+    type Output = <Self as Resolvable>::To;
+    type IntoFuture = Ready<<Self as Resolvable>::To>;
+
+    fn into_future(self) -> Self::IntoFuture {
+        std::future::ready(self.wait())
+    }
+}
+
+/// A subscriber receiving [`TypedSample`]s.
+///
+/// # Examples
+/// ```no_run
+/// # #[tokio::main]
+/// # async fn main() {
+/// use zenoh_ext::SessionExt;
+///
+/// let session = zenoh::open(zenoh::Config::default()).await.unwrap();
+/// let subscriber = session
+///     .declare_typed_subscriber::<(u32, String), _>("key/expression", "example/Pair")
+///     .await
+///     .unwrap();
+/// while let Ok(sample) = subscriber.recv_async().await {
+///     match sample.value() {
+///         Ok((n, s)) => println!("Received ({n}, {s})"),
+///         Err(e) => println!("Received invalid sample: {e}"),
+///     }
+/// }
+/// # }
+/// ```
+#[zenoh_macros::unstable]
+pub struct TypedSubscriber<Handler> {
+    subscriber: Subscriber<()>,
+    handler: Handler,
+}
+
+impl<Handler> std::ops::Deref for TypedSubscriber<Handler> {
+    type Target = Handler;
+    fn deref(&self) -> &Self::Target {
+        &self.handler
+    }
+}
+
+impl<Handler> std::ops::DerefMut for TypedSubscriber<Handler> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.handler
+    }
+}
+
+impl<Handler> TypedSubscriber<Handler> {
+    pub fn key_expr(&self) -> &KeyExpr<'static> {
+        self.subscriber.key_expr()
+    }
+
+    pub fn undeclare(self) -> impl Resolve<ZResult<()>> {
+        self.subscriber.undeclare()
+    }
+}
+
+/// A received [`Query`] to be replied with values of type `T`.
+#[zenoh_macros::unstable]
+pub struct TypedQuery<T, C = ZSerializeCodec> {
+    query: Query,
+    codec: Arc<C>,
+    encoding: Encoding,
+    _type: PhantomData<fn(&T)>,
+}
+
+impl<T, C: Codec<T>> TypedQuery<T, C> {
+    /// The received query.
+    pub fn query(&self) -> &Query {
+        &self.query
+    }
+
+    /// Reply with a value, failing if it can't be serialized.
+    pub fn reply<'b, TryIntoKeyExpr>(
+        &self,
+        key_expr: TryIntoKeyExpr,
+        value: &T,
+    ) -> ZResult<ReplyBuilder<'_, 'b, ReplyBuilderPut>>
+    where
+        TryIntoKeyExpr: TryInto<KeyExpr<'b>>,
+        <TryIntoKeyExpr as TryInto<KeyExpr<'b>>>::Error: Into<Error>,
+    {
+        Ok(self
+            .query
+            .reply(key_expr, self.codec.encode(value)?)
+            .encoding(self.encoding.clone()))
+    }
+}
+
+/// The builder of [`TypedQueryable`], allowing to configure it.
+#[zenoh_macros::unstable]
+#[must_use = "Resolvables do nothing unless you resolve them using `.await` or `zenoh::Wait::wait`"]
+pub struct TypedQueryableBuilder<'a, 'b, T, C = ZSerializeCodec, Handler = DefaultHandler> {
+    session: &'a Session,
+    key_expr: ZResult<KeyExpr<'b>>,
+    codec: C,
+    schema: String,
+    complete: bool,
+    handler: Handler,
+    _type: PhantomData<fn(&T)>,
+}
+
+impl<'a, 'b, T> TypedQueryableBuilder<'a, 'b, T> {
+    pub(crate) fn new(
+        session: &'a Session,
+        key_expr: ZResult<KeyExpr<'b>>,
+        schema: String,
+    ) -> Self {
+        TypedQueryableBuilder {
+            session,
+            key_expr,
+            codec: ZSerializeCodec,
+            schema,
+            complete: false,
+            handler: DefaultHandler::default(),
+            _type: PhantomData,
+        }
+    }
+}
+
+impl<'a, 'b, T, C, Handler> TypedQueryableBuilder<'a, 'b, T, C, Handler> {
+    /// Use the given codec to serialize the values of the replies.
+    pub fn codec<C2: Codec<T>>(self, codec: C2) -> TypedQueryableBuilder<'a, 'b, T, C2, Handler> {
+        TypedQueryableBuilder {
+            session: self.session,
+            key_expr: self.key_expr,
+            codec,
+            schema: self.schema,
+            complete: self.complete,
+            handler: self.handler,
+            _type: PhantomData,
+        }
+    }
+
+    /// Change queryable completeness.
+    pub fn complete(mut self, complete: bool) -> Self {
+        self.complete = complete;
+        self
+    }
+}
+
+impl<'a, 'b, T: 'static, C: Codec<T>> TypedQueryableBuilder<'a, 'b, T, C, DefaultHandler> {
+    /// Add callback to [`TypedQueryable`].
+    #[inline]
+    pub fn callback<F>(
+        self,
+        callback: F,
+    ) -> TypedQueryableBuilder<'a, 'b, T, C, Callback<TypedQuery<T, C>>>
+    where
+        F: Fn(TypedQuery<T, C>) + Send + Sync + 'static,
+    {
+        self.with(Callback::new(Arc::new(callback)))
+    }
+
+    /// Add callback to [`TypedQueryable`].
+    ///
+    /// Using this guarantees that your callback will never be called concurrently.
+    /// If your callback is also accepted by the [`callback`](TypedQueryableBuilder::callback)
+    /// method, we suggest you use it instead of `callback_mut`.
+    #[inline]
+    pub fn callback_mut<F>(
+        self,
+        callback: F,
+    ) -> TypedQueryableBuilder<'a, 'b, T, C, Callback<TypedQuery<T, C>>>
+    where
+        F: FnMut(TypedQuery<T, C>) + Send + Sync + 'static,
+    {
+        self.callback(locked(callback))
+    }
+
+    /// Use the given handler to receive the typed queries.
+    #[inline]
+    pub fn with<Handler>(self, handler: Handler) -> TypedQueryableBuilder<'a, 'b, T, C, Handler>
+    where
+        Handler: IntoHandler<TypedQuery<T, C>>,
+    {
+        TypedQueryableBuilder {
+            session: self.session,
+            key_expr: self.key_expr,
+            codec: self.codec,
+            schema: self.schema,
+            complete: self.complete,
+            handler,
+            _type: PhantomData,
+        }
+    }
+}
+
+impl<T, C, Handler> Resolvable for TypedQueryableBuilder<'_, '_, T, C, Handler>
+where
+    T: 'static,
+    C: Codec<T>,
+    Handler: IntoHandler<TypedQuery<T, C>>,
+    Handler::Handler: Send,
+{
+    type To = ZResult<TypedQueryable<Handler::Handler>>;
+}
+
+impl<T, C, Handler> Wait for TypedQueryableBuilder<'_, '_, T, C, Handler>
+where
+    T: 'static,
+    C: Codec<T>,
+    Handler: IntoHandler<TypedQuery<T, C>>,
+    Handler::Handler: Send,
+{
+    fn wait(self) -> <Self as Resolvable>::To {
+        let encoding = typed_encoding(&self.codec, self.schema);
+        let codec = Arc::new(self.codec);
+        let (callback, handler) = self.handler.into_handler();
+        let queryable = self
+            .session
+            .declare_queryable(self.key_expr?)
+            .complete(self.complete)
+            .callback(move |query| {
+                callback.call(TypedQuery {
+                    query,
+                    codec: codec.clone(),
+                    encoding: encoding.clone(),
+                    _type: PhantomData,
+                })
+            })
+            .wait()?;
+        Ok(TypedQueryable { queryable, handler })
+    }
+}
+
+impl<T, C, Handler> IntoFuture for TypedQueryableBuilder<'_, '_, T, C, Handler>
+where
+    T: 'static,
+    C: Codec<T>,
+    Handler: IntoHandler<TypedQuery<T, C>>,
+    Handler::Handler: Send,
+{
+    type Output = <Self as Resolvable>::To;
+    type IntoFuture = Ready<<Self as Resolvable>::To>;
+
+    fn into_future(self) -> Self::IntoFuture {
+        std::future::ready(self.wait())
+    }
+}
+
+/// A queryable receiving [`TypedQuery`]s.
+///
+/// # Examples
+/// ```no_run
+/// # #[tokio::main]
+/// # async fn main() {
+/// use zenoh_ext::SessionExt;
+///
+/// let session = zenoh::open(zenoh::Config::default()).await.unwrap();
+/// let queryable = session
+///     .declare_typed_queryable::<f64, _>("key/expression", "example/Temperature")
+///     .await
+///     .unwrap();
+/// while let Ok(query) = queryable.recv_async().await {
+///     query.reply("key/expression", &21.5).unwrap().await.unwrap();
+/// }
+/// # }
+/// ```
+#[zenoh_macros::unstable]
+pub struct TypedQueryable<Handler> {
+    queryable: Queryable<()>,
+    handler: Handler,
+}
+
+impl<Handler> std::ops::Deref for TypedQueryable<Handler> {
+    type Target = Handler;
+    fn deref(&self) -> &Self::Target {
+        &self.handler
+    }
+}
+
+impl<Handler> std::ops::DerefMut for TypedQueryable<Handler> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.handler
+    }
+}
+
+impl<Handler> TypedQueryable<Handler> {
+    pub fn undeclare(self) -> impl Resolve<ZResult<()>> {
+        self.queryable.undeclare()
+    }
+}
+
+/// A received [`Reply`] with the value of type `T` it carries.
+#[zenoh_macros::unstable]
+#[derive(Debug)]
+pub struct TypedReply<T> {
+    reply: Reply,
+    result: Result<Result<T, TypedError>, ReplyError>,
+}
+
+impl<T> TypedReply<T> {
+    /// The received reply.
+    pub fn reply(&self) -> &Reply {
+        &self.reply
+    }
+
+    /// The value carried by the reply, or the error replied by the queryable.
+    pub fn result(&self) -> Result<Result<&T, &TypedError>, &ReplyError> {
+        self.result.as_ref().map(|value| value.as_ref())
+    }
+
+    pub fn into_result(self) -> Result<Result<T, TypedError>, ReplyError> {
+        self.result
+    }
+}
+
+/// A builder for configuring a typed `get` operation.
+#[zenoh_macros::unstable]
+#[must_use = "Resolvables do nothing unless you resolve them using `.await` or `zenoh::Wait::wait`"]
+pub struct TypedGetBuilder<'a, 'b, T, C = ZSerializeCodec, Handler = DefaultHandler> {
+    get: SessionGetBuilder<'a, 'b, DefaultHandler>,
+    codec: C,
+    schema: String,
+    handler: Handler,
+    _type: PhantomData<fn(&T)>,
+}
+
+impl<'a, 'b, T> TypedGetBuilder<'a, 'b, T> {
+    pub(crate) fn new(get: SessionGetBuilder<'a, 'b, DefaultHandler>, schema: String) -> Self {
+        TypedGetBuilder {
+            get,
+            codec: ZSerializeCodec,
+            schema,
+            handler: DefaultHandler::default(),
+            _type: PhantomData,
+        }
+    }
+}
+
+impl<'a, 'b, T, C, Handler> TypedGetBuilder<'a, 'b, T, C, Handler> {
+    /// Use the given codec to deserialize the values of the replies.
+    pub fn codec<C2: Codec<T>>(self, codec: C2) -> TypedGetBuilder<'a, 'b, T, C2, Handler> {
+        TypedGetBuilder {
+            get: self.get,
+            codec,
+            schema: self.schema,
+            handler: self.handler,
+            _type: PhantomData,
+        }
+    }
+
+    /// Change the target of the query.
+    pub fn target(mut self, target: QueryTarget) -> Self {
+        self.get = self.get.target(target);
+        self
+    }
+
+    /// Change the timeout of the query.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.get = self.get.timeout(timeout);
+        self
+    }
+}
+
+impl<'a, 'b, T: 'static, C> TypedGetBuilder<'a, 'b, T, C, DefaultHandler> {
+    /// Receive the replies for this query with a callback.
+    #[inline]
+    pub fn callback<F>(self, callback: F) -> TypedGetBuilder<'a, 'b, T, C, Callback<TypedReply<T>>>
+    where
+        F: Fn(TypedReply<T>) + Send + Sync + 'static,
+    {
+        self.with(Callback::new(Arc::new(callback)))
+    }
+
+    /// Receive the replies for this query with a mutable callback.
+    ///
+    /// Using this guarantees that your callback will never be called concurrently.
+    /// If your callback is also accepted by the [`callback`](TypedGetBuilder::callback)
+    /// method, we suggest you use it instead of `callback_mut`.
+    #[inline]
+    pub fn callback_mut<F>(
+        self,
+        callback: F,
+    ) -> TypedGetBuilder<'a, 'b, T, C, Callback<TypedReply<T>>>
+    where
+        F: FnMut(TypedReply<T>) + Send + Sync + 'static,
+    {
+        self.callback(locked(callback))
+    }
+
+    /// Receive the replies for this query with the given handler.
+    #[inline]
+    pub fn with<Handler>(self, handler: Handler) -> TypedGetBuilder<'a, 'b, T, C, Handler>
+    where
+        Handler: IntoHandler<TypedReply<T>>,
+    {
+        TypedGetBuilder {
+            get: self.get,
+            codec: self.codec,
+            schema: self.schema,
+            handler,
+            _type: PhantomData,
+        }
+    }
+}
+
+impl<T, C, Handler> Resolvable for TypedGetBuilder<'_, '_, T, C, Handler>
+where
+    T: 'static,
+    C: Codec<T>,
+    Handler: IntoHandler<TypedReply<T>>,
+    Handler::Handler: Send,
+{
+    type To = ZResult<Handler::Handler>;
+}
+
+impl<T, C, Handler> Wait for TypedGetBuilder<'_, '_, T, C, Handler>
+where
+    T: 'static,
+    C: Codec<T>,
+    Handler: IntoHandler<TypedReply<T>>,
+    Handler::Handler: Send,
+{
+    fn wait(self) -> <Self as Resolvable>::To {
+        let encoding = typed_encoding(&self.codec, self.schema);
+        let codec = self.codec;
+        let (callback, handler) = self.handler.into_handler();
+        self.get
+            .callback(move |reply| {
+                let result = match reply.result() {
+                    Ok(sample) => Ok(decode(&codec, &encoding, sample)),
+                    Err(e) => Err(e.clone()),
+                };
+                callback.call(TypedReply { reply, result })
+            })
+            .wait()
+            .map_err(|e| zerror!("Typed get failed: {}", e))?;
+        Ok(handler)
+    }
+}
+
+impl<T, C, Handler> IntoFuture for TypedGetBuilder<'_, '_, T, C, Handler>
+where
+    T: 'static,
+    C: Codec<T>,
+    Handler: IntoHandler<TypedReply<T>>,
+    Handler::Handler: Send,
+{
+    type Output = <Self as Resolvable>::To;
+    type IntoFuture = Ready<<Self as Resolvable>::To>;
+
+    fn into_future(self) -> Self::IntoFuture {
+        std::future::ready(self.wait())
+    }
+}
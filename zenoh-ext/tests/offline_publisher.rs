@@ -0,0 +1,112 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+#![cfg(feature = "unstable")]
+use std::time::Duration;
+
+use zenoh::{
+    config::{EndPoint, WhatAmI},
+    internal::ztimeout,
+    Session,
+};
+use zenoh_ext::{OverflowPolicy, SessionExt};
+
+const TIMEOUT: Duration = Duration::from_secs(60);
+const SLEEP: Duration = Duration::from_secs(1);
+const ROUTER_ENDPOINT: &str = "tcp/127.0.0.1:47460";
+const KEYEXPR: &str = "test/offline/telemetry";
+
+async fn open_session(mode: WhatAmI) -> Session {
+    let mut c = zenoh::Config::default();
+    let endpoints = vec![ROUTER_ENDPOINT.parse::<EndPoint>().unwrap()];
+    if mode == WhatAmI::Router {
+        c.listen.endpoints.set(endpoints).unwrap();
+    } else {
+        c.connect.endpoints.set(endpoints).unwrap();
+    }
+    c.scouting.multicast.set_enabled(Some(false)).unwrap();
+    let _ = c.set_mode(Some(mode));
+    ztimeout!(zenoh::open(c)).unwrap()
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn test_offline_publisher_replay() {
+    zenoh_util::init_log_from_env_or("error");
+    let path = std::env::temp_dir().join(format!("zenoh-offline-{}.queue", std::process::id()));
+
+    let _router = open_session(WhatAmI::Router).await;
+    let publisher_session = open_session(WhatAmI::Client).await;
+    let subscriber_session = open_session(WhatAmI::Client).await;
+
+    // Without matching subscriber, only the latest publications are queued
+    let publisher = ztimeout!(publisher_session
+        .declare_offline_publisher(KEYEXPR)
+        .path(&path)
+        .max_samples(2)
+        .overflow(OverflowPolicy::DropOldest))
+    .unwrap();
+    for value in ["0", "1", "2"] {
+        ztimeout!(publisher.put(value)).unwrap();
+    }
+    assert_eq!(publisher.queued(), 2);
+
+    // They are replayed in order with their original timestamps once a subscriber matches
+    let sub = ztimeout!(subscriber_session.declare_subscriber(KEYEXPR)).unwrap();
+    let mut timestamps = vec![];
+    for value in ["1", "2"] {
+        let sample = ztimeout!(sub.recv_async()).unwrap();
+        assert_eq!(sample.payload().try_to_string().unwrap(), value);
+        timestamps.push(*sample.timestamp().unwrap());
+    }
+    assert!(timestamps[0] < timestamps[1]);
+    assert_eq!(publisher.queued(), 0);
+
+    ztimeout!(publisher.put("3")).unwrap();
+    let sample = ztimeout!(sub.recv_async()).unwrap();
+    assert_eq!(sample.payload().try_to_string().unwrap(), "3");
+
+    // The queue survives the publisher
+    ztimeout!(sub.undeclare()).unwrap();
+    tokio::time::sleep(SLEEP).await;
+    ztimeout!(publisher.put("4").attachment("meta")).unwrap();
+    assert_eq!(publisher.queued(), 1);
+    drop(publisher);
+
+    let publisher = ztimeout!(publisher_session
+        .declare_offline_publisher(KEYEXPR)
+        .path(&path))
+    .unwrap();
+    assert_eq!(publisher.queued(), 1);
+    let sub = ztimeout!(subscriber_session.declare_subscriber(KEYEXPR)).unwrap();
+    let sample = ztimeout!(sub.recv_async()).unwrap();
+    assert_eq!(sample.payload().try_to_string().unwrap(), "4");
+    assert_eq!(
+        sample.attachment().unwrap().try_to_string().unwrap(),
+        "meta"
+    );
+    assert!(*sample.timestamp().unwrap() > timestamps[1]);
+
+    drop(publisher);
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn test_offline_publisher_no_samples() {
+    zenoh_util::init_log_from_env_or("error");
+    let mut c = zenoh::Config::default();
+    c.scouting.multicast.set_enabled(Some(false)).unwrap();
+    let session = ztimeout!(zenoh::open(c)).unwrap();
+
+    // A queue that can't hold any sample is rejected
+    assert!(ztimeout!(session.declare_offline_publisher(KEYEXPR).max_samples(0)).is_err());
+}
@@ -0,0 +1,236 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+#![cfg(feature = "unstable")]
+use std::time::Duration;
+
+use prost::Message;
+use prost_types::{
+    field_descriptor_proto::{Label, Type},
+    DescriptorProto, FieldDescriptorProto, FileDescriptorProto, FileDescriptorSet,
+};
+use zenoh::{
+    config::{EndPoint, WhatAmI},
+    internal::ztimeout,
+    Session,
+};
+use zenoh_ext::{check_compatibility, Compatibility, Schema, SchemaFormat, SessionExt};
+
+const TIMEOUT: Duration = Duration::from_secs(60);
+const SLEEP: Duration = Duration::from_secs(1);
+const ENDPOINT: &str = "tcp/127.0.0.1:47480";
+
+const READING_V1: &str = r#"{
+    "type": "object",
+    "properties": {"sensor": {"type": "string"}, "value": {"type": "integer"}},
+    "required": ["sensor", "value"]
+}"#;
+// Adds an optional property and widens the type of `value`
+const READING_V2: &str = r#"{
+    "type": "object",
+    "properties": {
+        "sensor": {"type": "string"},
+        "value": {"type": "number"},
+        "unit": {"type": "string"}
+    },
+    "required": ["sensor", "value"]
+}"#;
+// Requires a property the previous versions don't provide
+const READING_V3: &str = r#"{
+    "type": "object",
+    "properties": {
+        "sensor": {"type": "string"},
+        "value": {"type": "number"},
+        "unit": {"type": "string"}
+    },
+    "required": ["sensor", "value", "unit"]
+}"#;
+
+async fn open_peers() -> (Session, Session) {
+    let mut c = zenoh::Config::default();
+    c.listen
+        .endpoints
+        .set(vec![ENDPOINT.parse::<EndPoint>().unwrap()])
+        .unwrap();
+    c.scouting.multicast.set_enabled(Some(false)).unwrap();
+    let _ = c.set_mode(Some(WhatAmI::Peer));
+    let peer1 = ztimeout!(zenoh::open(c)).unwrap();
+
+    let mut c = zenoh::Config::default();
+    c.connect
+        .endpoints
+        .set(vec![ENDPOINT.parse::<EndPoint>().unwrap()])
+        .unwrap();
+    c.scouting.multicast.set_enabled(Some(false)).unwrap();
+    let _ = c.set_mode(Some(WhatAmI::Peer));
+    let peer2 = ztimeout!(zenoh::open(c)).unwrap();
+    (peer1, peer2)
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn test_schema_registry() {
+    zenoh_util::init_log_from_env_or("error");
+    let path = std::env::temp_dir().join(format!("zenoh-schemas-{}.db", std::process::id()));
+    let (peer1, peer2) = open_peers().await;
+
+    let registry = ztimeout!(peer1
+        .declare_schema_registry("test/schemas")
+        .compatibility(Compatibility::Backward)
+        .path(&path))
+    .unwrap();
+    tokio::time::sleep(SLEEP).await;
+    let client = peer2.schema_registry_client("test/schemas").unwrap();
+
+    // Compatible versions are registered, registering the latest one again is a no-op
+    let v1 = ztimeout!(client.register("reading", SchemaFormat::JsonSchema, READING_V1)).unwrap();
+    assert_eq!(v1.version(), 1);
+    let v2 = ztimeout!(client.register("reading", SchemaFormat::JsonSchema, READING_V2)).unwrap();
+    assert_eq!(v2.version(), 2);
+    let again =
+        ztimeout!(client.register("reading", SchemaFormat::JsonSchema, READING_V2)).unwrap();
+    assert_eq!(again.version(), 2);
+
+    // Incompatible versions and publishers are rejected
+    assert!(ztimeout!(client.register("reading", SchemaFormat::JsonSchema, READING_V3)).is_err());
+    assert!(ztimeout!(client.register("reading", SchemaFormat::JsonSchema, "{")).is_err());
+    assert!(ztimeout!(client.declare_publisher(
+        "test/sensors/temperature",
+        "reading",
+        SchemaFormat::JsonSchema,
+        READING_V3
+    ))
+    .is_err());
+    assert_eq!(registry.versions("reading").len(), 2);
+
+    // Subscribers resolve the schema of the received data from its encoding
+    let subscriber = ztimeout!(peer1.declare_subscriber("test/sensors/**")).unwrap();
+    tokio::time::sleep(SLEEP).await;
+    let publisher = ztimeout!(client.declare_publisher(
+        "test/sensors/temperature",
+        "reading",
+        SchemaFormat::JsonSchema,
+        READING_V2
+    ))
+    .unwrap();
+    ztimeout!(publisher.put(r#"{"sensor": "t1", "value": 21.5}"#)).unwrap();
+    let sample = ztimeout!(subscriber.recv_async()).unwrap();
+    let schema = ztimeout!(client.resolve_encoding(sample.encoding())).unwrap();
+    assert_eq!(schema, v2);
+    assert_eq!(ztimeout!(client.resolve("reading", Some(1))).unwrap(), v1);
+    assert!(ztimeout!(client.resolve("unknown", None)).is_err());
+
+    // The registered schemas are reloaded from the file
+    ztimeout!(registry.undeclare()).unwrap();
+    let registry = ztimeout!(peer1.declare_schema_registry("test/schemas").path(&path)).unwrap();
+    tokio::time::sleep(SLEEP).await;
+    assert_eq!(ztimeout!(client.resolve("reading", None)).unwrap(), v2);
+    assert!(ztimeout!(client.register("reading", SchemaFormat::JsonSchema, READING_V3)).is_err());
+
+    ztimeout!(registry.undeclare()).unwrap();
+    ztimeout!(peer1.close()).unwrap();
+    ztimeout!(peer2.close()).unwrap();
+    std::fs::remove_file(&path).unwrap();
+}
+
+fn descriptor(fields: &[(&str, i32, Type, Label)]) -> Vec<u8> {
+    FileDescriptorSet {
+        file: vec![FileDescriptorProto {
+            name: Some("reading.proto".into()),
+            package: Some("sensors".into()),
+            message_type: vec![DescriptorProto {
+                name: Some("Reading".into()),
+                field: fields
+                    .iter()
+                    .map(|(name, number, ty, label)| FieldDescriptorProto {
+                        name: Some(name.to_string()),
+                        number: Some(*number),
+                        r#type: Some(*ty as i32),
+                        label: Some(*label as i32),
+                        ..Default::default()
+                    })
+                    .collect(),
+                ..Default::default()
+            }],
+            ..Default::default()
+        }],
+    }
+    .encode_to_vec()
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn test_schema_registry_protobuf() {
+    zenoh_util::init_log_from_env_or("error");
+    let session = ztimeout!(zenoh::open(zenoh::Config::default())).unwrap();
+    let registry = ztimeout!(session
+        .declare_schema_registry("test/protobuf/schemas")
+        .compatibility(Compatibility::Full))
+    .unwrap();
+    let client = session
+        .schema_registry_client("test/protobuf/schemas")
+        .unwrap();
+
+    let v1 = descriptor(&[("value", 1, Type::Double, Label::Optional)]);
+    ztimeout!(client.register("reading", SchemaFormat::Protobuf, v1)).unwrap();
+    // Adding an optional field and renaming another one are compatible changes
+    let v2 = descriptor(&[
+        ("measure", 1, Type::Double, Label::Optional),
+        ("unit", 2, Type::String, Label::Optional),
+    ]);
+    let schema = ztimeout!(client.register("reading", SchemaFormat::Protobuf, v2)).unwrap();
+    assert_eq!(schema.version(), 2);
+    // Changing the type of a field or adding a required field are not
+    let v3 = descriptor(&[
+        ("measure", 1, Type::Float, Label::Optional),
+        ("unit", 2, Type::String, Label::Optional),
+    ]);
+    assert!(ztimeout!(client.register("reading", SchemaFormat::Protobuf, v3)).is_err());
+    let v3 = descriptor(&[
+        ("measure", 1, Type::Double, Label::Optional),
+        ("unit", 2, Type::String, Label::Optional),
+        ("sensor", 3, Type::String, Label::Required),
+    ]);
+    assert!(ztimeout!(client.register("reading", SchemaFormat::Protobuf, v3)).is_err());
+    assert_eq!(registry.versions("reading").len(), 2);
+
+    ztimeout!(registry.undeclare()).unwrap();
+    ztimeout!(session.close()).unwrap();
+}
+
+#[test]
+fn test_schema_compatibility_modes() {
+    let previous = Schema::new("reading", 1, SchemaFormat::JsonSchema, READING_V2);
+    let schema = Schema::new("reading", 2, SchemaFormat::JsonSchema, READING_V3);
+    // Requiring a new property only keeps the previous consumers working
+    assert!(check_compatibility(&previous, &schema, Compatibility::Backward).is_err());
+    assert!(check_compatibility(&previous, &schema, Compatibility::Forward).is_ok());
+    assert!(check_compatibility(&previous, &schema, Compatibility::Full).is_err());
+    assert!(check_compatibility(&previous, &schema, Compatibility::None).is_ok());
+
+    let previous = Schema::new(
+        "reading",
+        1,
+        SchemaFormat::Protobuf,
+        descriptor(&[("value", 1, Type::Double, Label::Optional)]),
+    );
+    let schema = Schema::new(
+        "reading",
+        2,
+        SchemaFormat::Protobuf,
+        descriptor(&[
+            ("value", 1, Type::Double, Label::Optional),
+            ("sensor", 2, Type::String, Label::Required),
+        ]),
+    );
+    assert!(check_compatibility(&previous, &schema, Compatibility::Backward).is_err());
+    assert!(check_compatibility(&previous, &schema, Compatibility::Forward).is_ok());
+}
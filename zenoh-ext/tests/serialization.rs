@@ -0,0 +1,96 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+#![cfg(feature = "unstable")]
+use std::collections::HashMap;
+
+use zenoh_ext::{z_deserialize, z_deserialize_serde, z_serialize, z_serialize_serde};
+
+#[derive(
+    Debug,
+    PartialEq,
+    zenoh_ext::Serialize,
+    zenoh_ext::Deserialize,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+struct Message<T> {
+    id: u32,
+    name: String,
+    values: Vec<T>,
+    tags: HashMap<String, i64>,
+    status: Status,
+    note: Option<String>,
+}
+
+#[derive(
+    Debug,
+    PartialEq,
+    zenoh_ext::Serialize,
+    zenoh_ext::Deserialize,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+enum Status {
+    Idle,
+    Running(u8),
+    Failed { code: i32, reason: String },
+}
+
+#[derive(Debug, PartialEq, zenoh_ext::Serialize, zenoh_ext::Deserialize)]
+struct Point(f32, f32);
+
+#[test]
+fn derive_roundtrip() {
+    for (status, note) in [
+        (Status::Idle, None),
+        (Status::Running(42), Some("running".to_string())),
+        (
+            Status::Failed {
+                code: -1,
+                reason: "error".into(),
+            },
+            None,
+        ),
+    ] {
+        let message = Message {
+            id: 7,
+            name: "message".into(),
+            values: vec![1.5f64, 2.5],
+            tags: HashMap::from([("tag".to_string(), 3)]),
+            status,
+            note,
+        };
+        let zbytes = z_serialize(&message);
+        assert_eq!(z_deserialize::<Message<f64>>(&zbytes).unwrap(), message);
+        // The derived implementations and serde produce the same format
+        assert_eq!(
+            zbytes.to_bytes(),
+            z_serialize_serde(&message).unwrap().to_bytes()
+        );
+        assert_eq!(
+            z_deserialize_serde::<Message<f64>>(&zbytes).unwrap(),
+            message
+        );
+    }
+}
+
+#[test]
+fn derive_binary_format() {
+    let zbytes = z_serialize(&Point(1.0, 2.0));
+    assert_eq!(zbytes.to_bytes(), z_serialize(&(1.0f32, 2.0f32)).to_bytes());
+    let zbytes = z_serialize(&Status::Running(42));
+    assert_eq!(zbytes.to_bytes(), vec![1, 0, 0, 0, 42]);
+    let zbytes = z_serialize(&(3u32, 0u8));
+    assert!(z_deserialize::<Status>(&zbytes).is_err());
+}
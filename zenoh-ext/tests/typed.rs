@@ -0,0 +1,153 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+#![cfg(feature = "unstable")]
+use std::time::Duration;
+
+use zenoh::{
+    bytes::Encoding,
+    config::{EndPoint, WhatAmI},
+    internal::ztimeout,
+    Session, Wait,
+};
+use zenoh_ext::{JsonCodec, SessionExt, TypedError};
+
+const TIMEOUT: Duration = Duration::from_secs(60);
+const SLEEP: Duration = Duration::from_secs(1);
+const ENDPOINT: &str = "tcp/127.0.0.1:47470";
+
+#[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+struct Reading {
+    sensor: String,
+    value: f64,
+}
+
+async fn open_peers() -> (Session, Session) {
+    let mut c = zenoh::Config::default();
+    c.listen
+        .endpoints
+        .set(vec![ENDPOINT.parse::<EndPoint>().unwrap()])
+        .unwrap();
+    c.scouting.multicast.set_enabled(Some(false)).unwrap();
+    let _ = c.set_mode(Some(WhatAmI::Peer));
+    let peer1 = ztimeout!(zenoh::open(c)).unwrap();
+
+    let mut c = zenoh::Config::default();
+    c.connect
+        .endpoints
+        .set(vec![ENDPOINT.parse::<EndPoint>().unwrap()])
+        .unwrap();
+    c.scouting.multicast.set_enabled(Some(false)).unwrap();
+    let _ = c.set_mode(Some(WhatAmI::Peer));
+    let peer2 = ztimeout!(zenoh::open(c)).unwrap();
+    (peer1, peer2)
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn test_typed_pubsub_and_query() {
+    zenoh_util::init_log_from_env_or("error");
+    let (peer1, peer2) = open_peers().await;
+
+    // Values are received decoded, with the encoding of the codec and the type as schema
+    let sub = ztimeout!(
+        peer2.declare_typed_subscriber::<(u32, String), _>("test/typed/tuple", "test/Pair")
+    )
+    .unwrap();
+    let json_sub = ztimeout!(peer2
+        .declare_typed_subscriber::<Reading, _>("test/typed/json", "test/Reading")
+        .codec(JsonCodec))
+    .unwrap();
+    tokio::time::sleep(SLEEP).await;
+
+    let publisher = ztimeout!(
+        peer1.declare_typed_publisher::<(u32, String), _>("test/typed/tuple", "test/Pair")
+    )
+    .unwrap();
+    ztimeout!(publisher.put(&(42, "value".to_string()))).unwrap();
+    let sample = ztimeout!(sub.recv_async()).unwrap();
+    assert_eq!(
+        sample.sample().encoding(),
+        &Encoding::ZENOH_SERIALIZED.with_schema("test/Pair")
+    );
+    assert_eq!(sample.into_value().unwrap(), (42, "value".to_string()));
+
+    let json_publisher = ztimeout!(peer1
+        .declare_typed_publisher::<Reading, _>("test/typed/json", "test/Reading")
+        .codec(JsonCodec))
+    .unwrap();
+    let reading = Reading {
+        sensor: "temperature".to_string(),
+        value: 21.5,
+    };
+    ztimeout!(json_publisher.put(&reading)).unwrap();
+    let sample = ztimeout!(json_sub.recv_async()).unwrap();
+    assert_eq!(sample.into_value().unwrap(), reading);
+
+    // Samples of another type or deletions are surfaced as errors
+    ztimeout!(peer1.put("test/typed/tuple", "untyped")).unwrap();
+    let sample = ztimeout!(sub.recv_async()).unwrap();
+    assert!(matches!(sample.value(), Err(TypedError::Encoding { .. })));
+    ztimeout!(peer1
+        .put("test/typed/tuple", "garbage")
+        .encoding(publisher.encoding().clone()))
+    .unwrap();
+    let sample = ztimeout!(sub.recv_async()).unwrap();
+    assert!(matches!(sample.value(), Err(TypedError::Decode(_))));
+    ztimeout!(publisher.delete()).unwrap();
+    let sample = ztimeout!(sub.recv_async()).unwrap();
+    assert!(matches!(sample.value(), Err(TypedError::Delete)));
+
+    // Queryables reply typed values to typed gets
+    let _queryable = ztimeout!(peer1
+        .declare_typed_queryable::<Reading, _>("test/typed/json", "test/Reading")
+        .codec(JsonCodec)
+        .callback(|query| {
+            let reading = Reading {
+                sensor: "humidity".to_string(),
+                value: 0.4,
+            };
+            query
+                .reply(query.query().key_expr().clone(), &reading)
+                .unwrap()
+                .wait()
+                .unwrap();
+        }))
+    .unwrap();
+    tokio::time::sleep(SLEEP).await;
+    let replies = ztimeout!(peer2
+        .typed_get::<Reading, _>("test/typed/json", "test/Reading")
+        .codec(JsonCodec))
+    .unwrap();
+    let reply = ztimeout!(replies.recv_async()).unwrap();
+    assert_eq!(
+        reply.into_result().unwrap().unwrap(),
+        Reading {
+            sensor: "humidity".to_string(),
+            value: 0.4,
+        }
+    );
+
+    // A mismatching schema is detected on the receiving side
+    let replies = ztimeout!(peer2
+        .typed_get::<Reading, _>("test/typed/json", "test/OtherReading")
+        .codec(JsonCodec))
+    .unwrap();
+    let reply = ztimeout!(replies.recv_async()).unwrap();
+    assert!(matches!(
+        reply.result(),
+        Ok(Err(TypedError::Encoding { .. }))
+    ));
+
+    ztimeout!(peer1.close()).unwrap();
+    ztimeout!(peer2.close()).unwrap();
+}
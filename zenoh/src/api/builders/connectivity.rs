@@ -0,0 +1,327 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+use std::{
+    future::{IntoFuture, Ready},
+    sync::Arc,
+};
+
+use zenoh_core::{Resolvable, Wait};
+use zenoh_result::ZResult;
+
+use crate::{
+    api::{
+        connectivity::{
+            ConnectivityStateChange, ConnectivityStateListener, EndpointEvent,
+            EndpointEventsListener,
+        },
+        handlers::{Callback, DefaultHandler, IntoHandler},
+    },
+    net::runtime::Runtime,
+};
+
+/// A builder for initializing an [`EndpointEventsListener`].
+///
+/// # Examples
+/// ```
+/// # #[tokio::main]
+/// # async fn main() {
+///
+/// let session = zenoh::open(zenoh::Config::default()).await.unwrap();
+/// let listener = session
+///     .declare_endpoint_events_listener()
+///     .callback(|event| println!("{:?}", event))
+///     .await
+///     .unwrap();
+/// # }
+/// ```
+#[zenoh_macros::unstable]
+#[must_use = "Resolvables do nothing unless you resolve them using `.await` or `zenoh::Wait::wait`"]
+pub struct EndpointEventsListenerBuilder<'a, Handler> {
+    pub(crate) runtime: &'a Runtime,
+    pub handler: Handler,
+}
+
+#[zenoh_macros::unstable]
+impl<'a> EndpointEventsListenerBuilder<'a, DefaultHandler> {
+    /// Receive the [`EndpointEvent`]s with a callback.
+    ///
+    /// # Examples
+    /// ```
+    /// # #[tokio::main]
+    /// # async fn main() {
+    ///
+    /// let session = zenoh::open(zenoh::Config::default()).await.unwrap();
+    /// let listener = session
+    ///     .declare_endpoint_events_listener()
+    ///     .callback(|event| println!("{:?}", event))
+    ///     .await
+    ///     .unwrap();
+    /// # }
+    /// ```
+    #[inline]
+    pub fn callback<F>(
+        self,
+        callback: F,
+    ) -> EndpointEventsListenerBuilder<'a, Callback<EndpointEvent>>
+    where
+        F: Fn(EndpointEvent) + Send + Sync + 'static,
+    {
+        self.with(Callback::new(Arc::new(callback)))
+    }
+
+    /// Receive the [`EndpointEvent`]s with a mutable callback.
+    ///
+    /// # Examples
+    /// ```
+    /// # #[tokio::main]
+    /// # async fn main() {
+    ///
+    /// let mut n = 0;
+    /// let session = zenoh::open(zenoh::Config::default()).await.unwrap();
+    /// let listener = session
+    ///     .declare_endpoint_events_listener()
+    ///     .callback_mut(move |_event| { n += 1; })
+    ///     .await
+    ///     .unwrap();
+    /// # }
+    /// ```
+    #[inline]
+    pub fn callback_mut<F>(
+        self,
+        callback: F,
+    ) -> EndpointEventsListenerBuilder<'a, Callback<EndpointEvent>>
+    where
+        F: FnMut(EndpointEvent) + Send + Sync + 'static,
+    {
+        self.callback(crate::api::handlers::locked(callback))
+    }
+
+    /// Receive the [`EndpointEvent`]s with a [`Handler`](IntoHandler).
+    ///
+    /// # Examples
+    /// ```no_run
+    /// # #[tokio::main]
+    /// # async fn main() {
+    ///
+    /// let session = zenoh::open(zenoh::Config::default()).await.unwrap();
+    /// let listener = session
+    ///     .declare_endpoint_events_listener()
+    ///     .with(flume::bounded(32))
+    ///     .await
+    ///     .unwrap();
+    /// while let Ok(event) = listener.recv_async().await {
+    ///     println!("{:?}", event);
+    /// }
+    /// # }
+    /// ```
+    #[inline]
+    pub fn with<Handler>(self, handler: Handler) -> EndpointEventsListenerBuilder<'a, Handler>
+    where
+        Handler: IntoHandler<EndpointEvent>,
+    {
+        EndpointEventsListenerBuilder {
+            runtime: self.runtime,
+            handler,
+        }
+    }
+}
+
+#[zenoh_macros::unstable]
+impl<Handler> Resolvable for EndpointEventsListenerBuilder<'_, Handler>
+where
+    Handler: IntoHandler<EndpointEvent> + Send,
+    Handler::Handler: Send,
+{
+    type To = ZResult<EndpointEventsListener<Handler::Handler>>;
+}
+
+#[zenoh_macros::unstable]
+impl<Handler> Wait for EndpointEventsListenerBuilder<'_, Handler>
+where
+    Handler: IntoHandler<EndpointEvent> + Send,
+    Handler::Handler: Send,
+{
+    fn wait(self) -> <Self as Resolvable>::To {
+        let (callback, handler) = self.handler.into_handler();
+        let id = self.runtime.add_endpoint_events_callback(callback);
+        Ok(EndpointEventsListener {
+            runtime: self.runtime.clone(),
+            id,
+            undeclare_on_drop: true,
+            handler,
+        })
+    }
+}
+
+#[zenoh_macros::unstable]
+impl<Handler> IntoFuture for EndpointEventsListenerBuilder<'_, Handler>
+where
+    Handler: IntoHandler<EndpointEvent> + Send,
+    Handler::Handler: Send,
+{
+    type Output = <Self as Resolvable>::To;
+    type IntoFuture = Ready<<Self as Resolvable>::To>;
+
+    fn into_future(self) -> Self::IntoFuture {
+        std::future::ready(self.wait())
+    }
+}
+
+/// A builder for initializing an [`ConnectivityStateListener`].
+///
+/// # Examples
+/// ```
+/// # #[tokio::main]
+/// # async fn main() {
+///
+/// let session = zenoh::open(zenoh::Config::default()).await.unwrap();
+/// let listener = session
+///     .declare_connectivity_state_listener()
+///     .callback(|event| println!("{:?}", event))
+///     .await
+///     .unwrap();
+/// # }
+/// ```
+#[zenoh_macros::unstable]
+#[must_use = "Resolvables do nothing unless you resolve them using `.await` or `zenoh::Wait::wait`"]
+pub struct ConnectivityStateListenerBuilder<'a, Handler> {
+    pub(crate) runtime: &'a Runtime,
+    pub handler: Handler,
+}
+
+#[zenoh_macros::unstable]
+impl<'a> ConnectivityStateListenerBuilder<'a, DefaultHandler> {
+    /// Receive the [`ConnectivityStateChange`]s with a callback.
+    ///
+    /// # Examples
+    /// ```
+    /// # #[tokio::main]
+    /// # async fn main() {
+    ///
+    /// let session = zenoh::open(zenoh::Config::default()).await.unwrap();
+    /// let listener = session
+    ///     .declare_connectivity_state_listener()
+    ///     .callback(|event| println!("{:?}", event))
+    ///     .await
+    ///     .unwrap();
+    /// # }
+    /// ```
+    #[inline]
+    pub fn callback<F>(
+        self,
+        callback: F,
+    ) -> ConnectivityStateListenerBuilder<'a, Callback<ConnectivityStateChange>>
+    where
+        F: Fn(ConnectivityStateChange) + Send + Sync + 'static,
+    {
+        self.with(Callback::new(Arc::new(callback)))
+    }
+
+    /// Receive the [`ConnectivityStateChange`]s with a mutable callback.
+    ///
+    /// # Examples
+    /// ```
+    /// # #[tokio::main]
+    /// # async fn main() {
+    ///
+    /// let mut n = 0;
+    /// let session = zenoh::open(zenoh::Config::default()).await.unwrap();
+    /// let listener = session
+    ///     .declare_connectivity_state_listener()
+    ///     .callback_mut(move |_event| { n += 1; })
+    ///     .await
+    ///     .unwrap();
+    /// # }
+    /// ```
+    #[inline]
+    pub fn callback_mut<F>(
+        self,
+        callback: F,
+    ) -> ConnectivityStateListenerBuilder<'a, Callback<ConnectivityStateChange>>
+    where
+        F: FnMut(ConnectivityStateChange) + Send + Sync + 'static,
+    {
+        self.callback(crate::api::handlers::locked(callback))
+    }
+
+    /// Receive the [`ConnectivityStateChange`]s with a [`Handler`](IntoHandler).
+    ///
+    /// # Examples
+    /// ```no_run
+    /// # #[tokio::main]
+    /// # async fn main() {
+    ///
+    /// let session = zenoh::open(zenoh::Config::default()).await.unwrap();
+    /// let listener = session
+    ///     .declare_connectivity_state_listener()
+    ///     .with(flume::bounded(32))
+    ///     .await
+    ///     .unwrap();
+    /// while let Ok(event) = listener.recv_async().await {
+    ///     println!("{:?}", event);
+    /// }
+    /// # }
+    /// ```
+    #[inline]
+    pub fn with<Handler>(self, handler: Handler) -> ConnectivityStateListenerBuilder<'a, Handler>
+    where
+        Handler: IntoHandler<ConnectivityStateChange>,
+    {
+        ConnectivityStateListenerBuilder {
+            runtime: self.runtime,
+            handler,
+        }
+    }
+}
+
+#[zenoh_macros::unstable]
+impl<Handler> Resolvable for ConnectivityStateListenerBuilder<'_, Handler>
+where
+    Handler: IntoHandler<ConnectivityStateChange> + Send,
+    Handler::Handler: Send,
+{
+    type To = ZResult<ConnectivityStateListener<Handler::Handler>>;
+}
+
+#[zenoh_macros::unstable]
+impl<Handler> Wait for ConnectivityStateListenerBuilder<'_, Handler>
+where
+    Handler: IntoHandler<ConnectivityStateChange> + Send,
+    Handler::Handler: Send,
+{
+    fn wait(self) -> <Self as Resolvable>::To {
+        let (callback, handler) = self.handler.into_handler();
+        let id = self.runtime.add_connectivity_state_callback(callback);
+        Ok(ConnectivityStateListener {
+            runtime: self.runtime.clone(),
+            id,
+            undeclare_on_drop: true,
+            handler,
+        })
+    }
+}
+
+#[zenoh_macros::unstable]
+impl<Handler> IntoFuture for ConnectivityStateListenerBuilder<'_, Handler>
+where
+    Handler: IntoHandler<ConnectivityStateChange> + Send,
+    Handler::Handler: Send,
+{
+    type Output = <Self as Resolvable>::To;
+    type IntoFuture = Ready<<Self as Resolvable>::To>;
+
+    fn into_future(self) -> Self::IntoFuture {
+        std::future::ready(self.wait())
+    }
+}
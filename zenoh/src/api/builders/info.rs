@@ -13,11 +13,22 @@
 //
 
 use std::future::{IntoFuture, Ready};
+#[cfg(feature = "unstable")]
+use std::sync::Arc;
 
 use zenoh_config::wrappers::ZenohId;
 use zenoh_core::{Resolvable, Wait};
 use zenoh_protocol::core::WhatAmI;
+#[cfg(feature = "unstable")]
+use zenoh_result::ZResult;
 
+#[cfg(feature = "unstable")]
+use crate::api::{
+    handlers::{Callback, DefaultHandler, IntoHandler},
+    info::{
+        Link, LinkEvent, LinkEventsListener, Transport, TransportEvent, TransportEventsListener,
+    },
+};
 use crate::net::runtime::Runtime;
 
 /// A builder returned by [`SessionInfo::zid()`](crate::session::SessionInfo::zid) that allows
@@ -169,3 +180,416 @@ impl<'a> IntoFuture for PeersZenohIdBuilder<'a> {
         std::future::ready(self.wait())
     }
 }
+
+/// A builder returned by [`SessionInfo::transports()`](crate::session::SessionInfo::transports) that allows
+/// to access the [`Transport`]s this process currently has with the other zenoh nodes.
+///
+/// # Examples
+/// ```
+/// # #[tokio::main]
+/// # async fn main() {
+///
+/// let session = zenoh::open(zenoh::Config::default()).await.unwrap();
+/// let mut transports = session.info().transports().await;
+/// while let Some(transport) = transports.next() {}
+/// # }
+/// ```
+#[zenoh_macros::unstable]
+#[must_use = "Resolvables do nothing unless you resolve them using `.await` or `zenoh::Wait::wait`"]
+pub struct TransportsBuilder<'a> {
+    runtime: &'a Runtime,
+}
+
+#[zenoh_macros::unstable]
+impl<'a> TransportsBuilder<'a> {
+    pub(crate) fn new(runtime: &'a Runtime) -> Self {
+        Self { runtime }
+    }
+}
+
+#[zenoh_macros::unstable]
+impl Resolvable for TransportsBuilder<'_> {
+    type To = Box<dyn Iterator<Item = Transport> + Send + Sync>;
+}
+
+#[zenoh_macros::unstable]
+impl Wait for TransportsBuilder<'_> {
+    fn wait(self) -> Self::To {
+        let manager = self.runtime.manager();
+        let unicast = zenoh_runtime::ZRuntime::Application
+            .block_in_place(manager.get_transports_unicast())
+            .into_iter()
+            .filter_map(|transport| Transport::from_unicast(&transport));
+        let multicast = zenoh_runtime::ZRuntime::Application
+            .block_in_place(manager.get_transports_multicast())
+            .into_iter()
+            .flat_map(|transport| transport.get_peers().unwrap_or_default())
+            .map(|peer| Transport::from_multicast_peer(&peer));
+        Box::new(unicast.chain(multicast).collect::<Vec<_>>().into_iter())
+    }
+}
+
+#[zenoh_macros::unstable]
+impl IntoFuture for TransportsBuilder<'_> {
+    type Output = <Self as Resolvable>::To;
+    type IntoFuture = Ready<<Self as Resolvable>::To>;
+
+    fn into_future(self) -> Self::IntoFuture {
+        std::future::ready(self.wait())
+    }
+}
+
+/// A builder returned by [`SessionInfo::links()`](crate::session::SessionInfo::links) that allows
+/// to access the [`Link`]s of the transports this process currently has with the other zenoh nodes.
+///
+/// # Examples
+/// ```
+/// # #[tokio::main]
+/// # async fn main() {
+///
+/// let session = zenoh::open(zenoh::Config::default()).await.unwrap();
+/// let mut links = session.info().links().await;
+/// while let Some(link) = links.next() {}
+/// # }
+/// ```
+#[zenoh_macros::unstable]
+#[must_use = "Resolvables do nothing unless you resolve them using `.await` or `zenoh::Wait::wait`"]
+pub struct LinksBuilder<'a> {
+    runtime: &'a Runtime,
+}
+
+#[zenoh_macros::unstable]
+impl<'a> LinksBuilder<'a> {
+    pub(crate) fn new(runtime: &'a Runtime) -> Self {
+        Self { runtime }
+    }
+}
+
+#[zenoh_macros::unstable]
+impl Resolvable for LinksBuilder<'_> {
+    type To = Box<dyn Iterator<Item = Link> + Send + Sync>;
+}
+
+#[zenoh_macros::unstable]
+impl Wait for LinksBuilder<'_> {
+    fn wait(self) -> Self::To {
+        let manager = self.runtime.manager();
+        let unicast = zenoh_runtime::ZRuntime::Application
+            .block_in_place(manager.get_transports_unicast())
+            .into_iter()
+            .flat_map(|transport| Link::from_unicast(&transport));
+        let multicast = zenoh_runtime::ZRuntime::Application
+            .block_in_place(manager.get_transports_multicast())
+            .into_iter()
+            .flat_map(|transport| Link::from_multicast(&transport));
+        Box::new(unicast.chain(multicast).collect::<Vec<_>>().into_iter())
+    }
+}
+
+#[zenoh_macros::unstable]
+impl IntoFuture for LinksBuilder<'_> {
+    type Output = <Self as Resolvable>::To;
+    type IntoFuture = Ready<<Self as Resolvable>::To>;
+
+    fn into_future(self) -> Self::IntoFuture {
+        std::future::ready(self.wait())
+    }
+}
+
+/// A builder for initializing an [`TransportEventsListener`].
+///
+/// # Examples
+/// ```
+/// # #[tokio::main]
+/// # async fn main() {
+///
+/// let session = zenoh::open(zenoh::Config::default()).await.unwrap();
+/// let listener = session
+///     .info()
+///     .declare_transport_events_listener()
+///     .callback(|event| println!("{:?}", event))
+///     .await
+///     .unwrap();
+/// # }
+/// ```
+#[zenoh_macros::unstable]
+#[must_use = "Resolvables do nothing unless you resolve them using `.await` or `zenoh::Wait::wait`"]
+pub struct TransportEventsListenerBuilder<'a, Handler> {
+    pub(crate) runtime: &'a Runtime,
+    pub handler: Handler,
+}
+
+#[zenoh_macros::unstable]
+impl<'a> TransportEventsListenerBuilder<'a, DefaultHandler> {
+    /// Receive the [`TransportEvent`]s with a callback.
+    ///
+    /// # Examples
+    /// ```
+    /// # #[tokio::main]
+    /// # async fn main() {
+    ///
+    /// let session = zenoh::open(zenoh::Config::default()).await.unwrap();
+    /// let listener = session
+    ///     .info()
+    ///     .declare_transport_events_listener()
+    ///     .callback(|event| println!("{:?}", event))
+    ///     .await
+    ///     .unwrap();
+    /// # }
+    /// ```
+    #[inline]
+    pub fn callback<F>(
+        self,
+        callback: F,
+    ) -> TransportEventsListenerBuilder<'a, Callback<TransportEvent>>
+    where
+        F: Fn(TransportEvent) + Send + Sync + 'static,
+    {
+        self.with(Callback::new(Arc::new(callback)))
+    }
+
+    /// Receive the [`TransportEvent`]s with a mutable callback.
+    ///
+    /// # Examples
+    /// ```
+    /// # #[tokio::main]
+    /// # async fn main() {
+    ///
+    /// let mut n = 0;
+    /// let session = zenoh::open(zenoh::Config::default()).await.unwrap();
+    /// let listener = session
+    ///     .info()
+    ///     .declare_transport_events_listener()
+    ///     .callback_mut(move |_event| { n += 1; })
+    ///     .await
+    ///     .unwrap();
+    /// # }
+    /// ```
+    #[inline]
+    pub fn callback_mut<F>(
+        self,
+        callback: F,
+    ) -> TransportEventsListenerBuilder<'a, Callback<TransportEvent>>
+    where
+        F: FnMut(TransportEvent) + Send + Sync + 'static,
+    {
+        self.callback(crate::api::handlers::locked(callback))
+    }
+
+    /// Receive the [`TransportEvent`]s with a [`Handler`](IntoHandler).
+    ///
+    /// # Examples
+    /// ```no_run
+    /// # #[tokio::main]
+    /// # async fn main() {
+    ///
+    /// let session = zenoh::open(zenoh::Config::default()).await.unwrap();
+    /// let listener = session
+    ///     .info()
+    ///     .declare_transport_events_listener()
+    ///     .with(flume::bounded(32))
+    ///     .await
+    ///     .unwrap();
+    /// while let Ok(event) = listener.recv_async().await {
+    ///     println!("{:?}", event);
+    /// }
+    /// # }
+    /// ```
+    #[inline]
+    pub fn with<Handler>(self, handler: Handler) -> TransportEventsListenerBuilder<'a, Handler>
+    where
+        Handler: IntoHandler<TransportEvent>,
+    {
+        TransportEventsListenerBuilder {
+            runtime: self.runtime,
+            handler,
+        }
+    }
+}
+
+#[zenoh_macros::unstable]
+impl<Handler> Resolvable for TransportEventsListenerBuilder<'_, Handler>
+where
+    Handler: IntoHandler<TransportEvent> + Send,
+    Handler::Handler: Send,
+{
+    type To = ZResult<TransportEventsListener<Handler::Handler>>;
+}
+
+#[zenoh_macros::unstable]
+impl<Handler> Wait for TransportEventsListenerBuilder<'_, Handler>
+where
+    Handler: IntoHandler<TransportEvent> + Send,
+    Handler::Handler: Send,
+{
+    fn wait(self) -> <Self as Resolvable>::To {
+        let (callback, handler) = self.handler.into_handler();
+        let id = self.runtime.add_transport_events_callback(callback);
+        Ok(TransportEventsListener {
+            runtime: self.runtime.clone(),
+            id,
+            undeclare_on_drop: true,
+            handler,
+        })
+    }
+}
+
+#[zenoh_macros::unstable]
+impl<Handler> IntoFuture for TransportEventsListenerBuilder<'_, Handler>
+where
+    Handler: IntoHandler<TransportEvent> + Send,
+    Handler::Handler: Send,
+{
+    type Output = <Self as Resolvable>::To;
+    type IntoFuture = Ready<<Self as Resolvable>::To>;
+
+    fn into_future(self) -> Self::IntoFuture {
+        std::future::ready(self.wait())
+    }
+}
+
+/// A builder for initializing an [`LinkEventsListener`].
+///
+/// # Examples
+/// ```
+/// # #[tokio::main]
+/// # async fn main() {
+///
+/// let session = zenoh::open(zenoh::Config::default()).await.unwrap();
+/// let listener = session
+///     .info()
+///     .declare_link_events_listener()
+///     .callback(|event| println!("{:?}", event))
+///     .await
+///     .unwrap();
+/// # }
+/// ```
+#[zenoh_macros::unstable]
+#[must_use = "Resolvables do nothing unless you resolve them using `.await` or `zenoh::Wait::wait`"]
+pub struct LinkEventsListenerBuilder<'a, Handler> {
+    pub(crate) runtime: &'a Runtime,
+    pub handler: Handler,
+}
+
+#[zenoh_macros::unstable]
+impl<'a> LinkEventsListenerBuilder<'a, DefaultHandler> {
+    /// Receive the [`LinkEvent`]s with a callback.
+    ///
+    /// # Examples
+    /// ```
+    /// # #[tokio::main]
+    /// # async fn main() {
+    ///
+    /// let session = zenoh::open(zenoh::Config::default()).await.unwrap();
+    /// let listener = session
+    ///     .info()
+    ///     .declare_link_events_listener()
+    ///     .callback(|event| println!("{:?}", event))
+    ///     .await
+    ///     .unwrap();
+    /// # }
+    /// ```
+    #[inline]
+    pub fn callback<F>(self, callback: F) -> LinkEventsListenerBuilder<'a, Callback<LinkEvent>>
+    where
+        F: Fn(LinkEvent) + Send + Sync + 'static,
+    {
+        self.with(Callback::new(Arc::new(callback)))
+    }
+
+    /// Receive the [`LinkEvent`]s with a mutable callback.
+    ///
+    /// # Examples
+    /// ```
+    /// # #[tokio::main]
+    /// # async fn main() {
+    ///
+    /// let mut n = 0;
+    /// let session = zenoh::open(zenoh::Config::default()).await.unwrap();
+    /// let listener = session
+    ///     .info()
+    ///     .declare_link_events_listener()
+    ///     .callback_mut(move |_event| { n += 1; })
+    ///     .await
+    ///     .unwrap();
+    /// # }
+    /// ```
+    #[inline]
+    pub fn callback_mut<F>(self, callback: F) -> LinkEventsListenerBuilder<'a, Callback<LinkEvent>>
+    where
+        F: FnMut(LinkEvent) + Send + Sync + 'static,
+    {
+        self.callback(crate::api::handlers::locked(callback))
+    }
+
+    /// Receive the [`LinkEvent`]s with a [`Handler`](IntoHandler).
+    ///
+    /// # Examples
+    /// ```no_run
+    /// # #[tokio::main]
+    /// # async fn main() {
+    ///
+    /// let session = zenoh::open(zenoh::Config::default()).await.unwrap();
+    /// let listener = session
+    ///     .info()
+    ///     .declare_link_events_listener()
+    ///     .with(flume::bounded(32))
+    ///     .await
+    ///     .unwrap();
+    /// while let Ok(event) = listener.recv_async().await {
+    ///     println!("{:?}", event);
+    /// }
+    /// # }
+    /// ```
+    #[inline]
+    pub fn with<Handler>(self, handler: Handler) -> LinkEventsListenerBuilder<'a, Handler>
+    where
+        Handler: IntoHandler<LinkEvent>,
+    {
+        LinkEventsListenerBuilder {
+            runtime: self.runtime,
+            handler,
+        }
+    }
+}
+
+#[zenoh_macros::unstable]
+impl<Handler> Resolvable for LinkEventsListenerBuilder<'_, Handler>
+where
+    Handler: IntoHandler<LinkEvent> + Send,
+    Handler::Handler: Send,
+{
+    type To = ZResult<LinkEventsListener<Handler::Handler>>;
+}
+
+#[zenoh_macros::unstable]
+impl<Handler> Wait for LinkEventsListenerBuilder<'_, Handler>
+where
+    Handler: IntoHandler<LinkEvent> + Send,
+    Handler::Handler: Send,
+{
+    fn wait(self) -> <Self as Resolvable>::To {
+        let (callback, handler) = self.handler.into_handler();
+        let id = self.runtime.add_link_events_callback(callback);
+        Ok(LinkEventsListener {
+            runtime: self.runtime.clone(),
+            id,
+            undeclare_on_drop: true,
+            handler,
+        })
+    }
+}
+
+#[zenoh_macros::unstable]
+impl<Handler> IntoFuture for LinkEventsListenerBuilder<'_, Handler>
+where
+    Handler: IntoHandler<LinkEvent> + Send,
+    Handler::Handler: Send,
+{
+    type Output = <Self as Resolvable>::To;
+    type IntoFuture = Ready<<Self as Resolvable>::To>;
+
+    fn into_future(self) -> Self::IntoFuture {
+        std::future::ready(self.wait())
+    }
+}
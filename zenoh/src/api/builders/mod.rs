@@ -12,6 +12,8 @@
 //   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
 //
 
+#[cfg(feature = "unstable")]
+pub(crate) mod connectivity;
 pub(crate) mod info;
 pub(crate) mod matching_listener;
 pub(crate) mod publisher;
@@ -494,3 +494,180 @@ impl IntoFuture for PublicationBuilder<&Publisher<'_>, PublicationBuilderDelete>
         std::future::ready(self.wait())
     }
 }
+
+/// A publication of a [`PutBatchBuilder`].
+///
+/// Built from a `(key_expr, payload)` or a `(key_expr, payload, encoding, attachment)` tuple.
+#[zenoh_macros::unstable]
+#[derive(Debug)]
+pub struct BatchPut<'b> {
+    pub(crate) key_expr: ZResult<KeyExpr<'b>>,
+    pub(crate) payload: ZBytes,
+    pub(crate) encoding: Encoding,
+    pub(crate) attachment: Option<ZBytes>,
+}
+
+#[zenoh_macros::unstable]
+impl<'b, TryIntoKeyExpr, IntoZBytes> From<(TryIntoKeyExpr, IntoZBytes)> for BatchPut<'b>
+where
+    TryIntoKeyExpr: TryInto<KeyExpr<'b>>,
+    <TryIntoKeyExpr as TryInto<KeyExpr<'b>>>::Error: Into<zenoh_result::Error>,
+    IntoZBytes: Into<ZBytes>,
+{
+    fn from((key_expr, payload): (TryIntoKeyExpr, IntoZBytes)) -> Self {
+        BatchPut {
+            key_expr: key_expr.try_into().map_err(Into::into),
+            payload: payload.into(),
+            encoding: Encoding::default(),
+            attachment: None,
+        }
+    }
+}
+
+#[zenoh_macros::unstable]
+impl<'b, TryIntoKeyExpr, IntoZBytes, IntoEncoding, IntoOptionZBytes>
+    From<(TryIntoKeyExpr, IntoZBytes, IntoEncoding, IntoOptionZBytes)> for BatchPut<'b>
+where
+    TryIntoKeyExpr: TryInto<KeyExpr<'b>>,
+    <TryIntoKeyExpr as TryInto<KeyExpr<'b>>>::Error: Into<zenoh_result::Error>,
+    IntoZBytes: Into<ZBytes>,
+    IntoEncoding: Into<Encoding>,
+    IntoOptionZBytes: Into<OptionZBytes>,
+{
+    fn from(
+        (key_expr, payload, encoding, attachment): (
+            TryIntoKeyExpr,
+            IntoZBytes,
+            IntoEncoding,
+            IntoOptionZBytes,
+        ),
+    ) -> Self {
+        let attachment: OptionZBytes = attachment.into();
+        BatchPut {
+            key_expr: key_expr.try_into().map_err(Into::into),
+            payload: payload.into(),
+            encoding: encoding.into(),
+            attachment: attachment.into(),
+        }
+    }
+}
+
+/// A builder for publishing a batch of samples at once.
+///
+/// The samples are routed under a single lock acquisition and enqueued contiguously,
+/// so that the transport packs them in as few batches as possible.
+///
+/// # Examples
+/// ```
+/// # #[tokio::main]
+/// # async fn main() {
+/// use zenoh::bytes::Encoding;
+///
+/// let session = zenoh::open(zenoh::Config::default()).await.unwrap();
+/// session
+///     .put_batch([
+///         ("key/expression/1", "payload").into(),
+///         ("key/expression/2", "42", Encoding::TEXT_PLAIN, None::<&str>).into(),
+///     ])
+///     .await
+///     .unwrap();
+/// # }
+/// ```
+#[zenoh_macros::unstable]
+#[must_use = "Resolvables do nothing unless you resolve them using `.await` or `zenoh::Wait::wait`"]
+#[derive(Debug)]
+pub struct PutBatchBuilder<'a, 'b> {
+    pub(crate) session: &'a Session,
+    pub(crate) items: Vec<BatchPut<'b>>,
+    pub(crate) congestion_control: CongestionControl,
+    pub(crate) priority: Priority,
+    pub(crate) is_express: bool,
+    pub(crate) reliability: Reliability,
+    pub(crate) destination: Locality,
+}
+
+#[cfg(feature = "unstable")]
+#[zenoh_macros::internal_trait]
+impl QoSBuilderTrait for PutBatchBuilder<'_, '_> {
+    /// Changes the [`crate::qos::CongestionControl`] to apply when routing the data.
+    #[inline]
+    fn congestion_control(self, congestion_control: CongestionControl) -> Self {
+        Self {
+            congestion_control,
+            ..self
+        }
+    }
+
+    /// Changes the [`crate::qos::Priority`] of the written data.
+    #[inline]
+    fn priority(self, priority: Priority) -> Self {
+        Self { priority, ..self }
+    }
+
+    /// Changes the Express policy to apply when routing the data.
+    ///
+    /// When express is set to `true`, then the message will not be batched.
+    /// This usually has a positive impact on latency but negative impact on throughput.
+    #[inline]
+    fn express(self, is_express: bool) -> Self {
+        Self { is_express, ..self }
+    }
+}
+
+#[zenoh_macros::unstable]
+impl PutBatchBuilder<'_, '_> {
+    /// Changes the [`crate::sample::Locality`] applied when routing the data.
+    ///
+    /// This restricts the matching subscribers that will receive the published data to the ones
+    /// that have the given [`crate::sample::Locality`].
+    #[inline]
+    pub fn allowed_destination(self, destination: Locality) -> Self {
+        Self {
+            destination,
+            ..self
+        }
+    }
+
+    /// Changes the [`crate::qos::Reliability`] to apply when routing the data.
+    #[inline]
+    pub fn reliability(self, reliability: Reliability) -> Self {
+        Self {
+            reliability,
+            ..self
+        }
+    }
+}
+
+#[zenoh_macros::unstable]
+impl Resolvable for PutBatchBuilder<'_, '_> {
+    type To = ZResult<()>;
+}
+
+#[zenoh_macros::unstable]
+impl Wait for PutBatchBuilder<'_, '_> {
+    fn wait(self) -> <Self as Resolvable>::To {
+        let items = self
+            .items
+            .into_iter()
+            .map(|item| Ok((item.key_expr?, item.payload, item.encoding, item.attachment)))
+            .collect::<ZResult<Vec<_>>>()?;
+        self.session.0.resolve_put_batch(
+            items,
+            self.congestion_control,
+            self.priority,
+            self.is_express,
+            self.destination,
+            self.reliability,
+        )
+    }
+}
+
+#[zenoh_macros::unstable]
+impl IntoFuture for PutBatchBuilder<'_, '_> {
+    type Output = <Self as Resolvable>::To;
+    type IntoFuture = Ready<<Self as Resolvable>::To>;
+
+    fn into_future(self) -> Self::IntoFuture {
+        std::future::ready(self.wait())
+    }
+}
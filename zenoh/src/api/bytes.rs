@@ -117,6 +117,32 @@ impl ZBytes {
         self.0.contiguous()
     }
 
+    /// Access raw bytes contained in the [`ZBytes`] as a contiguous slice.
+    ///
+    /// In the case `ZBytes` contains non-contiguous regions of memory, they are copied once into
+    /// a single region which replaces them, so that the following calls are zero-copy.
+    ///
+    /// ```rust
+    /// use std::io::Write;
+    /// use zenoh::bytes::ZBytes;
+    ///
+    /// let mut writer = ZBytes::writer();
+    /// writer.append(ZBytes::from(vec![1u8, 2, 3]));
+    /// writer.append(ZBytes::from(vec![4u8, 5]));
+    /// let mut zbytes = writer.finish();
+    /// assert_eq!(zbytes.slices().count(), 2);
+    ///
+    /// assert_eq!(zbytes.contiguous(), &[1, 2, 3, 4, 5]);
+    /// assert_eq!(zbytes.slices().count(), 1);
+    /// ```
+    #[zenoh_macros::unstable]
+    pub fn contiguous(&mut self) -> &[u8] {
+        if self.0.zslices().nth(1).is_some() {
+            self.0 = self.0.to_zslice().into();
+        }
+        self.0.zslices().next().map_or(&[], ZSlice::as_slice)
+    }
+
     /// Try to access a string contained in the [`ZBytes`], fail if it contains non-UTF8 bytes.
     ///
     /// In the case `ZBytes` contains non-contiguous regions of memory, an allocation and a copy
@@ -235,7 +261,7 @@ const _: () = {
 #[derive(Debug)]
 pub struct ZBytesReader<'a>(ZBufReader<'a>);
 
-impl ZBytesReader<'_> {
+impl<'a> ZBytesReader<'a> {
     /// Returns the number of bytes that can still be read
     pub fn remaining(&self) -> usize {
         self.0.remaining()
@@ -245,6 +271,32 @@ impl ZBytesReader<'_> {
     pub fn is_empty(&self) -> bool {
         self.remaining() == 0
     }
+
+    /// Read `len` bytes borrowed from the [`ZBytes`], without copy.
+    ///
+    /// Returns `None`, without reading anything, if the bytes are not stored contiguously
+    /// or if less than `len` bytes remain.
+    #[zenoh_macros::unstable]
+    pub fn read_borrowed(&mut self, len: usize) -> Option<&'a [u8]> {
+        self.0.read_borrowed(len)
+    }
+}
+
+/// Reading a [`ZBytes`] across its slices without copy.
+#[cfg(feature = "unstable")]
+impl bytes::Buf for ZBytesReader<'_> {
+    fn remaining(&self) -> usize {
+        self.0.remaining()
+    }
+
+    fn chunk(&self) -> &[u8] {
+        self.0.chunk()
+    }
+
+    fn advance(&mut self, cnt: usize) {
+        zenoh_buffers::reader::AdvanceableReader::skip(&mut self.0, cnt)
+            .expect("cannot advance past the end of ZBytes");
+    }
 }
 
 impl std::io::Read for ZBytesReader<'_> {
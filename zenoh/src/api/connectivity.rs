@@ -0,0 +1,321 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+
+//! Listeners and connect endpoints management of a zenoh [`Session`](crate::Session).
+#[cfg(feature = "unstable")]
+use std::future::{IntoFuture, Ready};
+
+#[cfg(feature = "unstable")]
+use tracing::error;
+#[cfg(feature = "unstable")]
+use zenoh_core::{Resolvable, Wait};
+use zenoh_protocol::core::{EndPoint, Locator};
+#[cfg(feature = "unstable")]
+use zenoh_result::ZResult;
+
+#[cfg(feature = "unstable")]
+use crate::{
+    api::{session::UndeclarableSealed, Id},
+    net::runtime::Runtime,
+};
+
+/// An event reported when the listeners or the connect endpoints of a zenoh
+/// [`Session`](crate::Session) change, either through the [`Session`](crate::Session) API
+/// or through a configuration update (e.g. a put on the admin space).
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum EndpointEvent {
+    /// A listener was opened on `endpoint` and can be reached at `locator`.
+    ListenerAdded {
+        endpoint: EndPoint,
+        locator: Locator,
+    },
+    /// A listener could not be opened on `endpoint`.
+    ListenerFailed { endpoint: EndPoint, error: String },
+    /// The listener on `endpoint` was closed.
+    ListenerRemoved { endpoint: EndPoint },
+    /// `endpoint` was added to the connect endpoints.
+    ConnectAdded { endpoint: EndPoint },
+    /// `endpoint` was removed from the connect endpoints and the transports established through it were closed.
+    ConnectRemoved { endpoint: EndPoint },
+}
+
+impl EndpointEvent {
+    /// The endpoint concerned by this event.
+    #[zenoh_macros::unstable]
+    pub fn endpoint(&self) -> &EndPoint {
+        match self {
+            Self::ListenerAdded { endpoint, .. }
+            | Self::ListenerFailed { endpoint, .. }
+            | Self::ListenerRemoved { endpoint }
+            | Self::ConnectAdded { endpoint }
+            | Self::ConnectRemoved { endpoint } => endpoint,
+        }
+    }
+}
+
+/// A listener that sends notifications when the listeners or the connect endpoints
+/// of a [`Session`](crate::Session) change.
+///
+/// The listener is automatically undeclared when dropped.
+///
+/// # Examples
+/// ```no_run
+/// # #[tokio::main]
+/// # async fn main() {
+///
+/// let session = zenoh::open(zenoh::Config::default()).await.unwrap();
+/// let listener = session.declare_endpoint_events_listener().await.unwrap();
+/// while let Ok(event) = listener.recv_async().await {
+///     println!("{:?}", event);
+/// }
+/// # }
+/// ```
+#[zenoh_macros::unstable]
+pub struct EndpointEventsListener<Handler> {
+    pub(crate) runtime: Runtime,
+    pub(crate) id: Id,
+    pub(crate) undeclare_on_drop: bool,
+    pub(crate) handler: Handler,
+}
+
+#[zenoh_macros::unstable]
+impl<Handler> EndpointEventsListener<Handler> {
+    /// Undeclare the [`EndpointEventsListener`].
+    ///
+    /// # Examples
+    /// ```
+    /// # #[tokio::main]
+    /// # async fn main() {
+    ///
+    /// let session = zenoh::open(zenoh::Config::default()).await.unwrap();
+    /// let listener = session.declare_endpoint_events_listener().await.unwrap();
+    /// listener.undeclare().await.unwrap();
+    /// # }
+    /// ```
+    #[inline]
+    pub fn undeclare(self) -> EndpointEventsListenerUndeclaration<Handler>
+    where
+        Handler: Send,
+    {
+        self.undeclare_inner(())
+    }
+
+    fn undeclare_impl(&mut self) -> ZResult<()> {
+        // set the flag first to avoid double panic if this function panic
+        self.undeclare_on_drop = false;
+        self.runtime.remove_endpoint_events_callback(self.id);
+        Ok(())
+    }
+}
+
+#[cfg(feature = "unstable")]
+impl<Handler> Drop for EndpointEventsListener<Handler> {
+    fn drop(&mut self) {
+        if self.undeclare_on_drop {
+            if let Err(error) = self.undeclare_impl() {
+                error!(error);
+            }
+        }
+    }
+}
+
+#[zenoh_macros::unstable]
+impl<Handler: Send> UndeclarableSealed<()> for EndpointEventsListener<Handler> {
+    type Undeclaration = EndpointEventsListenerUndeclaration<Handler>;
+
+    fn undeclare_inner(self, _: ()) -> Self::Undeclaration {
+        EndpointEventsListenerUndeclaration(self)
+    }
+}
+
+#[zenoh_macros::unstable]
+impl<Handler> std::ops::Deref for EndpointEventsListener<Handler> {
+    type Target = Handler;
+
+    fn deref(&self) -> &Self::Target {
+        &self.handler
+    }
+}
+
+#[zenoh_macros::unstable]
+impl<Handler> std::ops::DerefMut for EndpointEventsListener<Handler> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.handler
+    }
+}
+
+#[zenoh_macros::unstable]
+pub struct EndpointEventsListenerUndeclaration<Handler>(EndpointEventsListener<Handler>);
+
+#[zenoh_macros::unstable]
+impl<Handler> Resolvable for EndpointEventsListenerUndeclaration<Handler> {
+    type To = ZResult<()>;
+}
+
+#[zenoh_macros::unstable]
+impl<Handler> Wait for EndpointEventsListenerUndeclaration<Handler> {
+    fn wait(mut self) -> <Self as Resolvable>::To {
+        self.0.undeclare_impl()
+    }
+}
+
+#[zenoh_macros::unstable]
+impl<Handler> IntoFuture for EndpointEventsListenerUndeclaration<Handler> {
+    type Output = <Self as Resolvable>::To;
+    type IntoFuture = Ready<<Self as Resolvable>::To>;
+
+    fn into_future(self) -> Self::IntoFuture {
+        std::future::ready(self.wait())
+    }
+}
+
+/// The connectivity state of a zenoh [`Session`](crate::Session).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ConnectivityState {
+    /// The session has transports with other zenoh nodes and, for a client,
+    /// is connected through the first of its connect endpoints.
+    Connected,
+    /// The session is a client connected through another endpoint than the first
+    /// of its connect endpoints, e.g. after a failover.
+    Degraded,
+    /// The session has no transport with any other zenoh node.
+    Disconnected,
+}
+
+/// A change of the [`ConnectivityState`] of a zenoh [`Session`](crate::Session).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ConnectivityStateChange {
+    /// The state before the change.
+    pub previous: ConnectivityState,
+    /// The state after the change.
+    pub current: ConnectivityState,
+}
+
+/// A listener that sends notifications when the [`ConnectivityState`]
+/// of a [`Session`](crate::Session) changes.
+///
+/// The listener is automatically undeclared when dropped.
+///
+/// # Examples
+/// ```no_run
+/// # #[tokio::main]
+/// # async fn main() {
+///
+/// let session = zenoh::open(zenoh::Config::default()).await.unwrap();
+/// let listener = session.declare_connectivity_state_listener().await.unwrap();
+/// while let Ok(event) = listener.recv_async().await {
+///     println!("{:?}", event);
+/// }
+/// # }
+/// ```
+#[zenoh_macros::unstable]
+pub struct ConnectivityStateListener<Handler> {
+    pub(crate) runtime: Runtime,
+    pub(crate) id: Id,
+    pub(crate) undeclare_on_drop: bool,
+    pub(crate) handler: Handler,
+}
+
+#[zenoh_macros::unstable]
+impl<Handler> ConnectivityStateListener<Handler> {
+    /// Undeclare the [`ConnectivityStateListener`].
+    ///
+    /// # Examples
+    /// ```
+    /// # #[tokio::main]
+    /// # async fn main() {
+    ///
+    /// let session = zenoh::open(zenoh::Config::default()).await.unwrap();
+    /// let listener = session.declare_connectivity_state_listener().await.unwrap();
+    /// listener.undeclare().await.unwrap();
+    /// # }
+    /// ```
+    #[inline]
+    pub fn undeclare(self) -> ConnectivityStateListenerUndeclaration<Handler>
+    where
+        Handler: Send,
+    {
+        self.undeclare_inner(())
+    }
+
+    fn undeclare_impl(&mut self) -> ZResult<()> {
+        // set the flag first to avoid double panic if this function panic
+        self.undeclare_on_drop = false;
+        self.runtime.remove_connectivity_state_callback(self.id);
+        Ok(())
+    }
+}
+
+#[cfg(feature = "unstable")]
+impl<Handler> Drop for ConnectivityStateListener<Handler> {
+    fn drop(&mut self) {
+        if self.undeclare_on_drop {
+            if let Err(error) = self.undeclare_impl() {
+                error!(error);
+            }
+        }
+    }
+}
+
+#[zenoh_macros::unstable]
+impl<Handler: Send> UndeclarableSealed<()> for ConnectivityStateListener<Handler> {
+    type Undeclaration = ConnectivityStateListenerUndeclaration<Handler>;
+
+    fn undeclare_inner(self, _: ()) -> Self::Undeclaration {
+        ConnectivityStateListenerUndeclaration(self)
+    }
+}
+
+#[zenoh_macros::unstable]
+impl<Handler> std::ops::Deref for ConnectivityStateListener<Handler> {
+    type Target = Handler;
+
+    fn deref(&self) -> &Self::Target {
+        &self.handler
+    }
+}
+
+#[zenoh_macros::unstable]
+impl<Handler> std::ops::DerefMut for ConnectivityStateListener<Handler> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.handler
+    }
+}
+
+#[zenoh_macros::unstable]
+pub struct ConnectivityStateListenerUndeclaration<Handler>(ConnectivityStateListener<Handler>);
+
+#[zenoh_macros::unstable]
+impl<Handler> Resolvable for ConnectivityStateListenerUndeclaration<Handler> {
+    type To = ZResult<()>;
+}
+
+#[zenoh_macros::unstable]
+impl<Handler> Wait for ConnectivityStateListenerUndeclaration<Handler> {
+    fn wait(mut self) -> <Self as Resolvable>::To {
+        self.0.undeclare_impl()
+    }
+}
+
+#[zenoh_macros::unstable]
+impl<Handler> IntoFuture for ConnectivityStateListenerUndeclaration<Handler> {
+    type Output = <Self as Resolvable>::To;
+    type IntoFuture = Ready<<Self as Resolvable>::To>;
+
+    fn into_future(self) -> Self::IntoFuture {
+        std::future::ready(self.wait())
+    }
+}
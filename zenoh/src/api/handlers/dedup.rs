@@ -0,0 +1,123 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+
+//! Deduplicating handler.
+use std::{
+    collections::{HashSet, VecDeque},
+    sync::{Arc, Mutex},
+};
+
+use zenoh_config::wrappers::EntityGlobalId;
+
+use crate::api::{
+    handlers::{callback::Callback, DefaultHandler, IntoHandler},
+    sample::{Sample, SourceSn},
+    session::API_DATA_RECEPTION_CHANNEL_SIZE,
+};
+
+/// A handler dropping the duplicated samples before forwarding them to another handler.
+///
+/// Samples are identified by their [`SourceInfo`](crate::sample::SourceInfo), i.e. the id of the
+/// source entity and the sequence number of the sample. The identifiers of the last `history`
+/// samples are remembered, so that duplicates received through different paths are dropped.
+/// Samples without source id or sequence number are always forwarded.
+///
+/// # Examples
+/// ```
+/// # #[tokio::main]
+/// # async fn main() {
+/// use zenoh::handlers::{DedupChannel, RingChannel};
+///
+/// let session = zenoh::open(zenoh::Config::default()).await.unwrap();
+/// let subscriber = session
+///     .declare_subscriber("key/expression")
+///     .with(DedupChannel::new(RingChannel::new(16), 1024))
+///     .await
+///     .unwrap();
+/// # }
+/// ```
+#[zenoh_macros::unstable]
+pub struct DedupChannel<Handler = DefaultHandler> {
+    handler: Handler,
+    history: usize,
+}
+
+#[zenoh_macros::unstable]
+impl<Handler> DedupChannel<Handler> {
+    /// Initialize the [`DedupChannel`] with the handler receiving the deduplicated samples and the
+    /// number of sample identifiers to remember.
+    pub fn new(handler: Handler, history: usize) -> Self {
+        Self { handler, history }
+    }
+}
+
+#[zenoh_macros::unstable]
+impl Default for DedupChannel {
+    fn default() -> Self {
+        Self::new(DefaultHandler::default(), *API_DATA_RECEPTION_CHANNEL_SIZE)
+    }
+}
+
+struct Deduplicator {
+    history: usize,
+    order: VecDeque<(EntityGlobalId, SourceSn)>,
+    received: HashSet<(EntityGlobalId, SourceSn)>,
+}
+
+impl Deduplicator {
+    // Returns false if the sample is a duplicate
+    fn insert(&mut self, sample: &Sample) -> bool {
+        let source_info = sample.source_info();
+        let (Some(source_id), Some(source_sn)) = (source_info.source_id(), source_info.source_sn())
+        else {
+            return true;
+        };
+        let id = (*source_id, source_sn);
+        if self.history == 0 {
+            return true;
+        }
+        if !self.received.insert(id) {
+            return false;
+        }
+        if self.order.len() >= self.history {
+            if let Some(oldest) = self.order.pop_front() {
+                self.received.remove(&oldest);
+            }
+        }
+        self.order.push_back(id);
+        true
+    }
+}
+
+#[zenoh_macros::unstable]
+impl<Handler: IntoHandler<Sample>> IntoHandler<Sample> for DedupChannel<Handler> {
+    type Handler = Handler::Handler;
+
+    fn into_handler(self) -> (Callback<Sample>, Self::Handler) {
+        let (callback, handler) = self.handler.into_handler();
+        let deduplicator = Mutex::new(Deduplicator {
+            history: self.history,
+            order: VecDeque::new(),
+            received: HashSet::new(),
+        });
+        (
+            Callback::new(Arc::new(move |sample| {
+                if zlock!(deduplicator).insert(&sample) {
+                    callback.call(sample);
+                }
+            })),
+            handler,
+        )
+    }
+}
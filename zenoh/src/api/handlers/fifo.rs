@@ -15,6 +15,7 @@
 //! Callback handler trait.
 
 use std::{
+    fmt,
     future::Future,
     pin::Pin,
     sync::Arc,
@@ -50,8 +51,27 @@ impl Default for FifoChannel {
 }
 
 /// [`FifoChannel`] handler.
-#[derive(Debug, Clone)]
-pub struct FifoChannelHandler<T>(flume::Receiver<T>);
+///
+/// The handler is also a [`Stream`](futures::stream::Stream) of the received values, finishing when
+/// all senders have been dropped. Values that are not consumed fast enough fill the channel, which
+/// then blocks the sender: a slow stream consumer applies backpressure on the Zenoh thread pushing
+/// into the channel.
+pub struct FifoChannelHandler<T>(
+    flume::Receiver<T>,
+    Option<Pin<Box<dyn futures::stream::Stream<Item = T> + Send + Sync>>>,
+);
+
+impl<T> fmt::Debug for FifoChannelHandler<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("FifoChannelHandler").field(&self.0).finish()
+    }
+}
+
+impl<T> Clone for FifoChannelHandler<T> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone(), None)
+    }
+}
 
 impl<T: Send + 'static> IntoHandler<T> for FifoChannel {
     type Handler = FifoChannelHandler<T>;
@@ -64,7 +84,7 @@ impl<T: Send + 'static> IntoHandler<T> for FifoChannel {
                     tracing::error!(%error)
                 }
             })),
-            FifoChannelHandler(receiver),
+            FifoChannelHandler(receiver, None),
         )
     }
 }
@@ -346,6 +366,20 @@ impl<'a, T> futures::stream::FusedStream for RecvStream<'a, T> {
     }
 }
 
+impl<T: Send + 'static> futures::stream::Stream for FifoChannelHandler<T> {
+    type Item = T;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+        // The stream is created lazily, to not register an additional receiver for handlers which
+        // are never polled.
+        let stream = this
+            .1
+            .get_or_insert_with(|| Box::pin(this.0.clone().into_stream()));
+        stream.as_mut().poll_next(cx)
+    }
+}
+
 impl<T: Clone + Send + Sync + 'static> IntoHandler<T>
     for (std::sync::mpsc::SyncSender<T>, std::sync::mpsc::Receiver<T>)
 {
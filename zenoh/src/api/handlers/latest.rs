@@ -0,0 +1,161 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+
+//! Latest sample per key expression handler.
+use std::{
+    collections::{HashMap, VecDeque},
+    pin::Pin,
+    task::{Context, Poll},
+    time::{Duration, Instant},
+};
+
+use zenoh_result::ZResult;
+
+use crate::api::{
+    handlers::{
+        callback::Callback,
+        queue::{queue_channel, Queue, QueueHandler},
+        IntoHandler,
+    },
+    key_expr::KeyExpr,
+    sample::Sample,
+    session::API_DATA_RECEPTION_CHANNEL_SIZE,
+};
+
+/// A channel keeping only the latest sample received on each key expression.
+///
+/// A sample received while the previous one on the same key expression is still in the channel
+/// replaces it, keeping its place in the channel: bursty updates are coalesced, while the key
+/// expressions are still delivered in FIFO order. The capacity bounds the number of key expressions
+/// in the channel, the oldest one being dropped when a sample on a new key expression arrives while
+/// the channel is full.
+#[zenoh_macros::unstable]
+pub struct LatestChannel {
+    capacity: usize,
+}
+
+#[zenoh_macros::unstable]
+impl LatestChannel {
+    /// Initialize the [`LatestChannel`] with the capacity size.
+    pub fn new(capacity: usize) -> Self {
+        Self { capacity }
+    }
+}
+
+#[zenoh_macros::unstable]
+impl Default for LatestChannel {
+    fn default() -> Self {
+        Self::new(*API_DATA_RECEPTION_CHANNEL_SIZE)
+    }
+}
+
+struct LatestQueue {
+    capacity: usize,
+    order: VecDeque<KeyExpr<'static>>,
+    samples: HashMap<KeyExpr<'static>, Sample>,
+}
+
+impl Queue<Sample> for LatestQueue {
+    fn push(&mut self, sample: Sample) {
+        if let Some(latest) = self.samples.get_mut(sample.key_expr()) {
+            *latest = sample;
+            return;
+        }
+        if self.capacity == 0 {
+            return;
+        }
+        if self.order.len() >= self.capacity {
+            if let Some(oldest) = self.order.pop_front() {
+                self.samples.remove(&oldest);
+            }
+        }
+        self.order.push_back(sample.key_expr().clone());
+        self.samples.insert(sample.key_expr().clone(), sample);
+    }
+
+    fn pull(&mut self) -> Option<Sample> {
+        let key_expr = self.order.pop_front()?;
+        self.samples.remove(&key_expr)
+    }
+}
+
+/// [`LatestChannel`] handler.
+///
+/// The handler is also a [`Stream`](futures::stream::Stream) of the received samples, finishing
+/// when the channel has been deleted. The channel never blocks the sender.
+#[zenoh_macros::unstable]
+pub struct LatestChannelHandler(QueueHandler<LatestQueue>);
+
+#[zenoh_macros::unstable]
+impl LatestChannelHandler {
+    /// Receive the latest sample of the oldest key expression in the channel.
+    ///
+    /// If the channel is empty, this call will block until a sample is available in the channel.
+    pub fn recv(&self) -> ZResult<Sample> {
+        self.0.recv()
+    }
+
+    /// Receive the latest sample of the oldest key expression in the channel with a deadline.
+    ///
+    /// If the channel is empty, this call will block until a sample is available in the channel,
+    /// or return `None` if the deadline has passed.
+    pub fn recv_deadline(&self, deadline: Instant) -> ZResult<Option<Sample>> {
+        self.0.recv_deadline(deadline)
+    }
+
+    /// Receive the latest sample of the oldest key expression in the channel with a timeout.
+    ///
+    /// If the channel is empty, this call will block until a sample is available in the channel,
+    /// or return `None` if the timeout has expired.
+    pub fn recv_timeout(&self, timeout: Duration) -> ZResult<Option<Sample>> {
+        self.0.recv_timeout(timeout)
+    }
+
+    /// Receive the latest sample of the oldest key expression in the channel.
+    ///
+    /// If the channel is empty, this call will wait until a sample is available in the channel.
+    pub async fn recv_async(&self) -> ZResult<Sample> {
+        self.0.recv_async().await
+    }
+
+    /// Try to receive the latest sample of the oldest key expression in the channel.
+    ///
+    /// If the channel is empty, this call will return immediately without blocking.
+    pub fn try_recv(&self) -> ZResult<Option<Sample>> {
+        self.0.try_recv()
+    }
+}
+
+#[zenoh_macros::unstable]
+impl futures::stream::Stream for LatestChannelHandler {
+    type Item = Sample;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.0.poll_next(cx)
+    }
+}
+
+#[zenoh_macros::unstable]
+impl IntoHandler<Sample> for LatestChannel {
+    type Handler = LatestChannelHandler;
+
+    fn into_handler(self) -> (Callback<Sample>, Self::Handler) {
+        let (callback, handler) = queue_channel(LatestQueue {
+            capacity: self.capacity,
+            order: VecDeque::new(),
+            samples: HashMap::new(),
+        });
+        (callback, LatestChannelHandler(handler))
+    }
+}
@@ -14,11 +14,24 @@
 
 //! Callback handler trait.
 mod callback;
+#[cfg(feature = "unstable")]
+mod dedup;
 mod fifo;
+#[cfg(feature = "unstable")]
+mod latest;
+#[cfg(feature = "unstable")]
+mod priority;
+mod queue;
 mod ring;
 
 pub use callback::*;
+#[cfg(feature = "unstable")]
+pub use dedup::*;
 pub use fifo::*;
+#[cfg(feature = "unstable")]
+pub use latest::*;
+#[cfg(feature = "unstable")]
+pub use priority::*;
 pub use ring::*;
 
 use crate::api::session::API_DATA_RECEPTION_CHANNEL_SIZE;
@@ -0,0 +1,155 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+
+//! Priority queue handler.
+use std::{
+    collections::BTreeMap,
+    pin::Pin,
+    task::{Context, Poll},
+    time::{Duration, Instant},
+};
+
+use zenoh_result::ZResult;
+
+use crate::api::{
+    handlers::{
+        callback::Callback,
+        queue::{queue_channel, Queue, QueueHandler},
+        IntoHandler,
+    },
+    sample::Sample,
+    session::API_DATA_RECEPTION_CHANNEL_SIZE,
+};
+
+/// A channel with a limited size delivering the samples by order of [`Sample::priority`].
+///
+/// Samples with the same priority are delivered in FIFO order. When the channel is full, the sample
+/// with the lowest priority is dropped, the newest one first: the received sample itself is dropped
+/// if it has no higher priority than all the samples in the channel.
+#[zenoh_macros::unstable]
+pub struct PriorityChannel {
+    capacity: usize,
+}
+
+#[zenoh_macros::unstable]
+impl PriorityChannel {
+    /// Initialize the [`PriorityChannel`] with the capacity size.
+    pub fn new(capacity: usize) -> Self {
+        Self { capacity }
+    }
+}
+
+#[zenoh_macros::unstable]
+impl Default for PriorityChannel {
+    fn default() -> Self {
+        Self::new(*API_DATA_RECEPTION_CHANNEL_SIZE)
+    }
+}
+
+struct PriorityQueue {
+    capacity: usize,
+    next_sn: u64,
+    // Ordered by priority, from [`Priority::MAX`](crate::qos::Priority::MAX), and then by reception
+    samples: BTreeMap<(u8, u64), Sample>,
+}
+
+impl Queue<Sample> for PriorityQueue {
+    fn push(&mut self, sample: Sample) {
+        let key = (sample.priority() as u8, self.next_sn);
+        self.next_sn += 1;
+        if self.samples.len() >= self.capacity {
+            match self.samples.last_entry() {
+                Some(last) if *last.key() > key => {
+                    last.remove();
+                }
+                _ => return,
+            }
+        }
+        self.samples.insert(key, sample);
+    }
+
+    fn pull(&mut self) -> Option<Sample> {
+        self.samples.pop_first().map(|(_, sample)| sample)
+    }
+}
+
+/// [`PriorityChannel`] handler.
+///
+/// The handler is also a [`Stream`](futures::stream::Stream) of the received samples, finishing
+/// when the channel has been deleted. The channel never blocks the sender.
+#[zenoh_macros::unstable]
+pub struct PriorityChannelHandler(QueueHandler<PriorityQueue>);
+
+#[zenoh_macros::unstable]
+impl PriorityChannelHandler {
+    /// Receive the sample with the highest priority from the channel.
+    ///
+    /// If the channel is empty, this call will block until a sample is available in the channel.
+    pub fn recv(&self) -> ZResult<Sample> {
+        self.0.recv()
+    }
+
+    /// Receive the sample with the highest priority from the channel with a deadline.
+    ///
+    /// If the channel is empty, this call will block until a sample is available in the channel,
+    /// or return `None` if the deadline has passed.
+    pub fn recv_deadline(&self, deadline: Instant) -> ZResult<Option<Sample>> {
+        self.0.recv_deadline(deadline)
+    }
+
+    /// Receive the sample with the highest priority from the channel with a timeout.
+    ///
+    /// If the channel is empty, this call will block until a sample is available in the channel,
+    /// or return `None` if the timeout has expired.
+    pub fn recv_timeout(&self, timeout: Duration) -> ZResult<Option<Sample>> {
+        self.0.recv_timeout(timeout)
+    }
+
+    /// Receive the sample with the highest priority from the channel.
+    ///
+    /// If the channel is empty, this call will wait until a sample is available in the channel.
+    pub async fn recv_async(&self) -> ZResult<Sample> {
+        self.0.recv_async().await
+    }
+
+    /// Try to receive the sample with the highest priority from the channel.
+    ///
+    /// If the channel is empty, this call will return immediately without blocking.
+    pub fn try_recv(&self) -> ZResult<Option<Sample>> {
+        self.0.try_recv()
+    }
+}
+
+#[zenoh_macros::unstable]
+impl futures::stream::Stream for PriorityChannelHandler {
+    type Item = Sample;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.0.poll_next(cx)
+    }
+}
+
+#[zenoh_macros::unstable]
+impl IntoHandler<Sample> for PriorityChannel {
+    type Handler = PriorityChannelHandler;
+
+    fn into_handler(self) -> (Callback<Sample>, Self::Handler) {
+        let (callback, handler) = queue_channel(PriorityQueue {
+            capacity: self.capacity,
+            next_sn: 0,
+            samples: BTreeMap::new(),
+        });
+        (callback, PriorityChannelHandler(handler))
+    }
+}
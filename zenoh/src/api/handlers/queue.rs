@@ -0,0 +1,174 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+
+//! Non-blocking channel over a custom queue, shared by the ring, priority and latest handlers.
+use std::{
+    pin::Pin,
+    sync::{Arc, Weak},
+    task::{Context, Poll},
+    time::{Duration, Instant},
+};
+
+use zenoh_result::ZResult;
+
+use crate::api::handlers::callback::Callback;
+
+/// A queue storing the values pushed into a [`QueueHandler`] channel.
+///
+/// Pushing must never block, the queue dropping values instead if needed.
+pub(crate) trait Queue<T> {
+    fn push(&mut self, t: T);
+    fn pull(&mut self) -> Option<T>;
+}
+
+struct QueueChannelInner<Q> {
+    queue: std::sync::Mutex<Q>,
+    not_empty: flume::Receiver<()>,
+}
+
+/// The receiving side of a channel over a [`Queue`].
+pub(crate) struct QueueHandler<Q> {
+    queue: Weak<QueueChannelInner<Q>>,
+    not_empty: Option<flume::r#async::RecvStream<'static, ()>>,
+}
+
+pub(crate) fn queue_channel<T, Q>(queue: Q) -> (Callback<T>, QueueHandler<Q>)
+where
+    Q: Queue<T> + Send + 'static,
+{
+    let (sender, receiver) = flume::bounded(1);
+    let inner = Arc::new(QueueChannelInner {
+        queue: std::sync::Mutex::new(queue),
+        not_empty: receiver,
+    });
+    let handler = QueueHandler {
+        queue: Arc::downgrade(&inner),
+        not_empty: None,
+    };
+    (
+        Callback::new(Arc::new(move |t| match inner.queue.lock() {
+            Ok(mut g) => {
+                g.push(t);
+                drop(g);
+                let _ = sender.try_send(());
+            }
+            Err(e) => tracing::error!("{}", e),
+        })),
+        handler,
+    )
+}
+
+impl<Q> QueueHandler<Q> {
+    fn channel(&self) -> ZResult<Arc<QueueChannelInner<Q>>> {
+        self.queue
+            .upgrade()
+            .ok_or_else(|| zerror!("The channel has been deleted.").into())
+    }
+
+    fn pull<T>(channel: &QueueChannelInner<Q>) -> ZResult<Option<T>>
+    where
+        Q: Queue<T>,
+    {
+        Ok(channel.queue.lock().map_err(|e| zerror!("{}", e))?.pull())
+    }
+
+    pub(crate) fn recv<T>(&self) -> ZResult<T>
+    where
+        Q: Queue<T>,
+    {
+        let channel = self.channel()?;
+        loop {
+            if let Some(t) = Self::pull(&channel)? {
+                return Ok(t);
+            }
+            channel.not_empty.recv().map_err(|e| zerror!("{}", e))?;
+        }
+    }
+
+    pub(crate) fn recv_deadline<T>(&self, deadline: Instant) -> ZResult<Option<T>>
+    where
+        Q: Queue<T>,
+    {
+        let channel = self.channel()?;
+        loop {
+            if let Some(t) = Self::pull(&channel)? {
+                return Ok(Some(t));
+            }
+            match channel.not_empty.recv_deadline(deadline) {
+                Ok(()) => {}
+                Err(flume::RecvTimeoutError::Timeout) => return Ok(None),
+                Err(err) => bail!("{}", err),
+            }
+        }
+    }
+
+    pub(crate) fn recv_timeout<T>(&self, timeout: Duration) -> ZResult<Option<T>>
+    where
+        Q: Queue<T>,
+    {
+        self.recv_deadline(Instant::now() + timeout)
+    }
+
+    pub(crate) async fn recv_async<T>(&self) -> ZResult<T>
+    where
+        Q: Queue<T>,
+    {
+        let channel = self.channel()?;
+        loop {
+            if let Some(t) = Self::pull(&channel)? {
+                return Ok(t);
+            }
+            channel
+                .not_empty
+                .recv_async()
+                .await
+                .map_err(|e| zerror!("{}", e))?;
+        }
+    }
+
+    pub(crate) fn try_recv<T>(&self) -> ZResult<Option<T>>
+    where
+        Q: Queue<T>,
+    {
+        Self::pull(&*self.channel()?)
+    }
+
+    pub(crate) fn poll_next<T>(&mut self, cx: &mut Context<'_>) -> Poll<Option<T>>
+    where
+        Q: Queue<T>,
+    {
+        loop {
+            let Ok(channel) = self.channel() else {
+                return Poll::Ready(None);
+            };
+            match Self::pull(&channel) {
+                Ok(Some(t)) => return Poll::Ready(Some(t)),
+                Ok(None) => {}
+                Err(e) => {
+                    tracing::error!("{}", e);
+                    return Poll::Ready(None);
+                }
+            }
+            let not_empty = self
+                .not_empty
+                .get_or_insert_with(|| channel.not_empty.clone().into_stream());
+            drop(channel);
+            match futures::stream::Stream::poll_next(Pin::new(not_empty), cx) {
+                Poll::Ready(Some(())) => {}
+                Poll::Ready(None) => return Poll::Ready(None),
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+}
@@ -14,7 +14,8 @@
 
 //! Callback handler trait.
 use std::{
-    sync::{Arc, Weak},
+    pin::Pin,
+    task::{Context, Poll},
     time::{Duration, Instant},
 };
 
@@ -22,7 +23,11 @@ use zenoh_collections::RingBuffer;
 use zenoh_result::ZResult;
 
 use crate::api::{
-    handlers::{callback::Callback, IntoHandler},
+    handlers::{
+        callback::Callback,
+        queue::{queue_channel, Queue, QueueHandler},
+        IntoHandler,
+    },
     session::API_DATA_RECEPTION_CHANNEL_SIZE,
 };
 
@@ -47,29 +52,31 @@ impl Default for RingChannel {
     }
 }
 
-struct RingChannelInner<T> {
-    ring: std::sync::Mutex<RingBuffer<T>>,
-    not_empty: flume::Receiver<()>,
-}
+impl<T> Queue<T> for RingBuffer<T> {
+    fn push(&mut self, t: T) {
+        // Eventually drop the oldest element.
+        self.push_force(t);
+    }
 
-pub struct RingChannelHandler<T> {
-    ring: Weak<RingChannelInner<T>>,
+    fn pull(&mut self) -> Option<T> {
+        RingBuffer::pull(self)
+    }
 }
 
+/// [`RingChannel`] handler.
+///
+/// The handler is also a [`Stream`](futures::stream::Stream) of the received values, finishing when
+/// the ring channel has been deleted; like for [`RingChannelHandler::recv`], the values left in the
+/// channel are then lost. As the ring channel never blocks the sender, a slow stream consumer
+/// doesn't apply backpressure: it only receives the last values pushed into the channel.
+pub struct RingChannelHandler<T>(QueueHandler<RingBuffer<T>>);
+
 impl<T> RingChannelHandler<T> {
     /// Receive from the ring channel.
     ///
     /// If the ring channel is empty, this call will block until an element is available in the channel.
     pub fn recv(&self) -> ZResult<T> {
-        let Some(channel) = self.ring.upgrade() else {
-            bail!("The ringbuffer has been deleted.");
-        };
-        loop {
-            if let Some(t) = channel.ring.lock().map_err(|e| zerror!("{}", e))?.pull() {
-                return Ok(t);
-            }
-            channel.not_empty.recv().map_err(|e| zerror!("{}", e))?;
-        }
+        self.0.recv()
     }
 
     /// Receive from the ring channel with a deadline.
@@ -77,20 +84,7 @@ impl<T> RingChannelHandler<T> {
     /// If the ring channel is empty, this call will block until an element is available in the channel,
     /// or return `None` if the deadline has passed.
     pub fn recv_deadline(&self, deadline: Instant) -> ZResult<Option<T>> {
-        let Some(channel) = self.ring.upgrade() else {
-            bail!("The ringbuffer has been deleted.");
-        };
-
-        loop {
-            if let Some(t) = channel.ring.lock().map_err(|e| zerror!("{}", e))?.pull() {
-                return Ok(Some(t));
-            }
-            match channel.not_empty.recv_deadline(deadline) {
-                Ok(()) => {}
-                Err(flume::RecvTimeoutError::Timeout) => return Ok(None),
-                Err(err) => bail!("{}", err),
-            }
-        }
+        self.0.recv_deadline(deadline)
     }
 
     /// Receive from the ring channel with a timeout.
@@ -98,50 +92,29 @@ impl<T> RingChannelHandler<T> {
     /// If the ring channel is empty, this call will block until an element is available in the channel,
     /// or return `None` if the deadline has expired.
     pub fn recv_timeout(&self, timeout: Duration) -> ZResult<Option<T>> {
-        let Some(channel) = self.ring.upgrade() else {
-            bail!("The ringbuffer has been deleted.");
-        };
-
-        loop {
-            if let Some(t) = channel.ring.lock().map_err(|e| zerror!("{}", e))?.pull() {
-                return Ok(Some(t));
-            }
-            match channel.not_empty.recv_timeout(timeout) {
-                Ok(()) => {}
-                Err(flume::RecvTimeoutError::Timeout) => return Ok(None),
-                Err(err) => bail!("{}", err),
-            }
-        }
+        self.0.recv_timeout(timeout)
     }
 
     /// Receive from the ring channel.
     ///
     /// If the ring channel is empty, this call will block until an element is available in the channel.
     pub async fn recv_async(&self) -> ZResult<T> {
-        let Some(channel) = self.ring.upgrade() else {
-            bail!("The ringbuffer has been deleted.");
-        };
-        loop {
-            if let Some(t) = channel.ring.lock().map_err(|e| zerror!("{}", e))?.pull() {
-                return Ok(t);
-            }
-            channel
-                .not_empty
-                .recv_async()
-                .await
-                .map_err(|e| zerror!("{}", e))?;
-        }
+        self.0.recv_async().await
     }
 
     /// Try to receive from the ring channel.
     ///
     /// If the ring channel is empty, this call will return immediately without blocking.
     pub fn try_recv(&self) -> ZResult<Option<T>> {
-        let Some(channel) = self.ring.upgrade() else {
-            bail!("The ringbuffer has been deleted.");
-        };
-        let mut guard = channel.ring.lock().map_err(|e| zerror!("{}", e))?;
-        Ok(guard.pull())
+        self.0.try_recv()
+    }
+}
+
+impl<T> futures::stream::Stream for RingChannelHandler<T> {
+    type Item = T;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.0.poll_next(cx)
     }
 }
 
@@ -149,25 +122,7 @@ impl<T: Send + 'static> IntoHandler<T> for RingChannel {
     type Handler = RingChannelHandler<T>;
 
     fn into_handler(self) -> (Callback<T>, Self::Handler) {
-        let (sender, receiver) = flume::bounded(1);
-        let inner = Arc::new(RingChannelInner {
-            ring: std::sync::Mutex::new(RingBuffer::new(self.capacity)),
-            not_empty: receiver,
-        });
-        let receiver = RingChannelHandler {
-            ring: Arc::downgrade(&inner),
-        };
-        (
-            Callback::new(Arc::new(move |t| match inner.ring.lock() {
-                Ok(mut g) => {
-                    // Eventually drop the oldest element.
-                    g.push_force(t);
-                    drop(g);
-                    let _ = sender.try_send(());
-                }
-                Err(e) => tracing::error!("{}", e),
-            })),
-            receiver,
-        )
+        let (callback, handler) = queue_channel(RingBuffer::new(self.capacity));
+        (callback, RingChannelHandler(handler))
     }
 }
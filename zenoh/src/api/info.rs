@@ -13,6 +13,33 @@
 //
 
 //! Tools to access information about the current zenoh [`Session`](crate::Session).
+#[cfg(feature = "unstable")]
+use std::future::{IntoFuture, Ready};
+
+#[cfg(feature = "unstable")]
+use tracing::error;
+use zenoh_config::wrappers::ZenohId;
+#[cfg(feature = "unstable")]
+use zenoh_core::{Resolvable, Wait};
+use zenoh_protocol::core::{Locator, WhatAmI};
+#[cfg(feature = "unstable")]
+use zenoh_result::ZResult;
+#[cfg(feature = "unstable")]
+use zenoh_transport::multicast::TransportMulticast;
+use zenoh_transport::{
+    unicast::{authentication, TransportUnicast},
+    TransportPeer,
+};
+
+#[cfg(feature = "unstable")]
+use crate::api::builders::info::{LinksBuilder, TransportsBuilder};
+#[cfg(feature = "unstable")]
+use crate::api::{
+    builders::info::{LinkEventsListenerBuilder, TransportEventsListenerBuilder},
+    handlers::DefaultHandler,
+    session::UndeclarableSealed,
+    Id,
+};
 use crate::{
     api::builders::info::{PeersZenohIdBuilder, RoutersZenohIdBuilder, ZenohIdBuilder},
     net::runtime::Runtime,
@@ -82,4 +109,590 @@ impl SessionInfo {
     pub fn peers_zid(&self) -> PeersZenohIdBuilder<'_> {
         PeersZenohIdBuilder::new(&self.runtime)
     }
+
+    /// Return the [`Transport`]s this process currently has with the other zenoh nodes.
+    ///
+    /// # Examples
+    /// ```
+    /// # #[tokio::main]
+    /// # async fn main() {
+    ///
+    /// let session = zenoh::open(zenoh::Config::default()).await.unwrap();
+    /// for transport in session.info().transports().await {
+    ///     println!("{} ({})", transport.zid(), transport.whatami());
+    /// }
+    /// # }
+    /// ```
+    #[zenoh_macros::unstable]
+    pub fn transports(&self) -> TransportsBuilder<'_> {
+        TransportsBuilder::new(&self.runtime)
+    }
+
+    /// Return the [`Link`]s of the [`Transport`]s this process currently has with the other zenoh nodes.
+    ///
+    /// # Examples
+    /// ```
+    /// # #[tokio::main]
+    /// # async fn main() {
+    ///
+    /// let session = zenoh::open(zenoh::Config::default()).await.unwrap();
+    /// for link in session.info().links().await {
+    ///     println!("{} -> {}", link.src(), link.dst());
+    /// }
+    /// # }
+    /// ```
+    #[zenoh_macros::unstable]
+    pub fn links(&self) -> LinksBuilder<'_> {
+        LinksBuilder::new(&self.runtime)
+    }
+
+    /// Declare a [`TransportEventsListener`] notified when transports are opened or closed.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// # #[tokio::main]
+    /// # async fn main() {
+    ///
+    /// let session = zenoh::open(zenoh::Config::default()).await.unwrap();
+    /// let listener = session.info().declare_transport_events_listener().await.unwrap();
+    /// while let Ok(event) = listener.recv_async().await {
+    ///     println!("{:?}", event);
+    /// }
+    /// # }
+    /// ```
+    #[zenoh_macros::unstable]
+    pub fn declare_transport_events_listener(
+        &self,
+    ) -> TransportEventsListenerBuilder<'_, DefaultHandler> {
+        TransportEventsListenerBuilder {
+            runtime: &self.runtime,
+            handler: DefaultHandler::default(),
+        }
+    }
+
+    /// Declare a [`LinkEventsListener`] notified when links are added to or removed from transports.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// # #[tokio::main]
+    /// # async fn main() {
+    ///
+    /// let session = zenoh::open(zenoh::Config::default()).await.unwrap();
+    /// let listener = session.info().declare_link_events_listener().await.unwrap();
+    /// while let Ok(event) = listener.recv_async().await {
+    ///     println!("{:?}", event);
+    /// }
+    /// # }
+    /// ```
+    #[zenoh_macros::unstable]
+    pub fn declare_link_events_listener(&self) -> LinkEventsListenerBuilder<'_, DefaultHandler> {
+        LinkEventsListenerBuilder {
+            runtime: &self.runtime,
+            handler: DefaultHandler::default(),
+        }
+    }
+}
+
+/// An identity a remote zenoh node authenticated with.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum AuthId {
+    /// The common name of the certificate of a TLS or QUIC link.
+    CertCommonName(String),
+    /// The user of the user-password authentication.
+    Username(String),
+}
+
+/// The counters of a [`Transport`].
+#[cfg(feature = "stats")]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct TransportStats {
+    pub tx_bytes: usize,
+    pub tx_n_msgs: usize,
+    pub tx_n_dropped: usize,
+    pub rx_bytes: usize,
+    pub rx_n_msgs: usize,
+    pub rx_n_dropped: usize,
+}
+
+#[cfg(feature = "stats")]
+impl From<&zenoh_transport::stats::TransportStats> for TransportStats {
+    fn from(stats: &zenoh_transport::stats::TransportStats) -> Self {
+        Self {
+            tx_bytes: stats.get_tx_bytes(),
+            tx_n_msgs: stats.get_tx_n_msgs(),
+            tx_n_dropped: stats.get_tx_n_dropped(),
+            rx_bytes: stats.get_rx_bytes(),
+            rx_n_msgs: stats.get_rx_n_msgs(),
+            rx_n_dropped: stats.get_rx_n_dropped(),
+        }
+    }
+}
+
+/// A transport between this process and a remote zenoh node, as returned by
+/// [`SessionInfo::transports()`] and reported by the [`TransportEventsListener`].
+///
+/// A multicast transport is reported as one [`Transport`] per remote peer.
+#[derive(Clone, Debug)]
+#[cfg_attr(not(feature = "unstable"), allow(dead_code))]
+pub struct Transport {
+    zid: ZenohId,
+    whatami: WhatAmI,
+    is_qos: bool,
+    is_lowlatency: bool,
+    is_multicast: bool,
+    auth_ids: Vec<AuthId>,
+    #[cfg(feature = "stats")]
+    stats: Option<TransportStats>,
+}
+
+impl Transport {
+    pub(crate) fn from_unicast(transport: &TransportUnicast) -> Option<Self> {
+        Some(Self {
+            zid: transport.get_zid().ok()?.into(),
+            whatami: transport.get_whatami().ok()?,
+            is_qos: transport.is_qos().ok()?,
+            is_lowlatency: transport.is_lowlatency().ok()?,
+            is_multicast: false,
+            auth_ids: transport
+                .get_auth_ids()
+                .ok()?
+                .into_iter()
+                .filter_map(|auth_id| match auth_id {
+                    authentication::AuthId::CertCommonName(name) => {
+                        Some(AuthId::CertCommonName(name))
+                    }
+                    authentication::AuthId::Username(name) => Some(AuthId::Username(name)),
+                    authentication::AuthId::None => None,
+                })
+                .collect(),
+            #[cfg(feature = "stats")]
+            stats: transport
+                .get_stats()
+                .ok()
+                .map(|stats| stats.as_ref().into()),
+        })
+    }
+
+    pub(crate) fn from_multicast_peer(peer: &TransportPeer) -> Self {
+        Self {
+            zid: peer.zid.into(),
+            whatami: peer.whatami,
+            is_qos: peer.is_qos,
+            is_lowlatency: false,
+            is_multicast: true,
+            auth_ids: vec![],
+            #[cfg(feature = "stats")]
+            stats: None,
+        }
+    }
+
+    /// The [`ZenohId`] of the remote zenoh node.
+    #[zenoh_macros::unstable]
+    pub fn zid(&self) -> ZenohId {
+        self.zid
+    }
+
+    /// The [`WhatAmI`] of the remote zenoh node.
+    #[zenoh_macros::unstable]
+    pub fn whatami(&self) -> WhatAmI {
+        self.whatami
+    }
+
+    /// Whether the transport supports QoS (i.e. carries priorities).
+    #[zenoh_macros::unstable]
+    pub fn is_qos(&self) -> bool {
+        self.is_qos
+    }
+
+    /// Whether the transport uses the low latency mode.
+    #[zenoh_macros::unstable]
+    pub fn is_lowlatency(&self) -> bool {
+        self.is_lowlatency
+    }
+
+    /// Whether the transport is a multicast transport.
+    #[zenoh_macros::unstable]
+    pub fn is_multicast(&self) -> bool {
+        self.is_multicast
+    }
+
+    /// The identities the remote zenoh node authenticated with.
+    #[zenoh_macros::unstable]
+    pub fn auth_ids(&self) -> &[AuthId] {
+        &self.auth_ids
+    }
+
+    /// The counters of the transport when it was retrieved, if the `stats` feature is enabled.
+    #[cfg(feature = "stats")]
+    #[zenoh_macros::unstable]
+    pub fn stats(&self) -> Option<&TransportStats> {
+        self.stats.as_ref()
+    }
+}
+
+/// A link of a [`Transport`], as returned by [`SessionInfo::links()`] and reported by the
+/// [`LinkEventsListener`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Link {
+    zid: ZenohId,
+    src: Locator,
+    dst: Locator,
+    group: Option<Locator>,
+    mtu: u16,
+    is_streamed: bool,
+    interfaces: Vec<String>,
+}
+
+impl Link {
+    pub(crate) fn new(zid: ZenohId, link: &zenoh_link::Link) -> Self {
+        Self {
+            zid,
+            src: link.src.clone(),
+            dst: link.dst.clone(),
+            group: link.group.clone(),
+            mtu: link.mtu,
+            is_streamed: link.is_streamed,
+            interfaces: link.interfaces.clone(),
+        }
+    }
+
+    #[cfg(feature = "unstable")]
+    pub(crate) fn from_unicast(transport: &TransportUnicast) -> Vec<Self> {
+        match (transport.get_zid(), transport.get_links()) {
+            (Ok(zid), Ok(links)) => links
+                .iter()
+                .map(|link| Self::new(zid.into(), link))
+                .collect(),
+            _ => vec![],
+        }
+    }
+
+    #[cfg(feature = "unstable")]
+    pub(crate) fn from_multicast(transport: &TransportMulticast) -> Vec<Self> {
+        transport
+            .get_peers()
+            .unwrap_or_default()
+            .iter()
+            .flat_map(|peer| {
+                peer.links
+                    .iter()
+                    .map(|link| Self::new(peer.zid.into(), link))
+            })
+            .collect()
+    }
+
+    /// The [`ZenohId`] of the remote zenoh node of the transport of this link.
+    #[zenoh_macros::unstable]
+    pub fn zid(&self) -> ZenohId {
+        self.zid
+    }
+
+    /// The local locator of the link.
+    #[zenoh_macros::unstable]
+    pub fn src(&self) -> &Locator {
+        &self.src
+    }
+
+    /// The remote locator of the link.
+    #[zenoh_macros::unstable]
+    pub fn dst(&self) -> &Locator {
+        &self.dst
+    }
+
+    /// The multicast group locator of the link, if any.
+    #[zenoh_macros::unstable]
+    pub fn group(&self) -> Option<&Locator> {
+        self.group.as_ref()
+    }
+
+    /// The MTU of the link.
+    #[zenoh_macros::unstable]
+    pub fn mtu(&self) -> u16 {
+        self.mtu
+    }
+
+    /// Whether the link is a stream (e.g. TCP) rather than datagram (e.g. UDP) based link.
+    #[zenoh_macros::unstable]
+    pub fn is_streamed(&self) -> bool {
+        self.is_streamed
+    }
+
+    /// The names of the network interfaces the link is bound to.
+    #[zenoh_macros::unstable]
+    pub fn interfaces(&self) -> &[String] {
+        &self.interfaces
+    }
+}
+
+/// An event reported when a [`Transport`] is opened or closed.
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub enum TransportEvent {
+    /// A transport was opened with a remote zenoh node.
+    Opened(Transport),
+    /// The transport with a remote zenoh node was closed.
+    Closed(Transport),
+}
+
+impl TransportEvent {
+    /// The transport concerned by this event.
+    #[zenoh_macros::unstable]
+    pub fn transport(&self) -> &Transport {
+        match self {
+            Self::Opened(transport) | Self::Closed(transport) => transport,
+        }
+    }
+}
+
+/// An event reported when a [`Link`] is added to or removed from a [`Transport`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum LinkEvent {
+    /// A link was added to the transport with a remote zenoh node.
+    Added(Link),
+    /// A link was removed from the transport with a remote zenoh node,
+    /// either on its own or because the transport was closed.
+    Removed(Link),
+}
+
+impl LinkEvent {
+    /// The link concerned by this event.
+    #[zenoh_macros::unstable]
+    pub fn link(&self) -> &Link {
+        match self {
+            Self::Added(link) | Self::Removed(link) => link,
+        }
+    }
+}
+
+/// A listener that sends notifications when transports are opened or closed.
+///
+/// The listener is automatically undeclared when dropped.
+///
+/// # Examples
+/// ```no_run
+/// # #[tokio::main]
+/// # async fn main() {
+///
+/// let session = zenoh::open(zenoh::Config::default()).await.unwrap();
+/// let listener = session.info().declare_transport_events_listener().await.unwrap();
+/// while let Ok(event) = listener.recv_async().await {
+///     println!("{:?}", event);
+/// }
+/// # }
+/// ```
+#[zenoh_macros::unstable]
+pub struct TransportEventsListener<Handler> {
+    pub(crate) runtime: Runtime,
+    pub(crate) id: Id,
+    pub(crate) undeclare_on_drop: bool,
+    pub(crate) handler: Handler,
+}
+
+#[zenoh_macros::unstable]
+impl<Handler> TransportEventsListener<Handler> {
+    /// Undeclare the [`TransportEventsListener`].
+    ///
+    /// # Examples
+    /// ```
+    /// # #[tokio::main]
+    /// # async fn main() {
+    ///
+    /// let session = zenoh::open(zenoh::Config::default()).await.unwrap();
+    /// let listener = session.info().declare_transport_events_listener().await.unwrap();
+    /// listener.undeclare().await.unwrap();
+    /// # }
+    /// ```
+    #[inline]
+    pub fn undeclare(self) -> TransportEventsListenerUndeclaration<Handler>
+    where
+        Handler: Send,
+    {
+        self.undeclare_inner(())
+    }
+
+    fn undeclare_impl(&mut self) -> ZResult<()> {
+        // set the flag first to avoid double panic if this function panic
+        self.undeclare_on_drop = false;
+        self.runtime.remove_transport_events_callback(self.id);
+        Ok(())
+    }
+}
+
+#[cfg(feature = "unstable")]
+impl<Handler> Drop for TransportEventsListener<Handler> {
+    fn drop(&mut self) {
+        if self.undeclare_on_drop {
+            if let Err(error) = self.undeclare_impl() {
+                error!(error);
+            }
+        }
+    }
+}
+
+#[zenoh_macros::unstable]
+impl<Handler: Send> UndeclarableSealed<()> for TransportEventsListener<Handler> {
+    type Undeclaration = TransportEventsListenerUndeclaration<Handler>;
+
+    fn undeclare_inner(self, _: ()) -> Self::Undeclaration {
+        TransportEventsListenerUndeclaration(self)
+    }
+}
+
+#[zenoh_macros::unstable]
+impl<Handler> std::ops::Deref for TransportEventsListener<Handler> {
+    type Target = Handler;
+
+    fn deref(&self) -> &Self::Target {
+        &self.handler
+    }
+}
+
+#[zenoh_macros::unstable]
+impl<Handler> std::ops::DerefMut for TransportEventsListener<Handler> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.handler
+    }
+}
+
+#[zenoh_macros::unstable]
+pub struct TransportEventsListenerUndeclaration<Handler>(TransportEventsListener<Handler>);
+
+#[zenoh_macros::unstable]
+impl<Handler> Resolvable for TransportEventsListenerUndeclaration<Handler> {
+    type To = ZResult<()>;
+}
+
+#[zenoh_macros::unstable]
+impl<Handler> Wait for TransportEventsListenerUndeclaration<Handler> {
+    fn wait(mut self) -> <Self as Resolvable>::To {
+        self.0.undeclare_impl()
+    }
+}
+
+#[zenoh_macros::unstable]
+impl<Handler> IntoFuture for TransportEventsListenerUndeclaration<Handler> {
+    type Output = <Self as Resolvable>::To;
+    type IntoFuture = Ready<<Self as Resolvable>::To>;
+
+    fn into_future(self) -> Self::IntoFuture {
+        std::future::ready(self.wait())
+    }
+}
+
+/// A listener that sends notifications when links are added to or removed from transports.
+///
+/// The listener is automatically undeclared when dropped.
+///
+/// # Examples
+/// ```no_run
+/// # #[tokio::main]
+/// # async fn main() {
+///
+/// let session = zenoh::open(zenoh::Config::default()).await.unwrap();
+/// let listener = session.info().declare_link_events_listener().await.unwrap();
+/// while let Ok(event) = listener.recv_async().await {
+///     println!("{:?}", event);
+/// }
+/// # }
+/// ```
+#[zenoh_macros::unstable]
+pub struct LinkEventsListener<Handler> {
+    pub(crate) runtime: Runtime,
+    pub(crate) id: Id,
+    pub(crate) undeclare_on_drop: bool,
+    pub(crate) handler: Handler,
+}
+
+#[zenoh_macros::unstable]
+impl<Handler> LinkEventsListener<Handler> {
+    /// Undeclare the [`LinkEventsListener`].
+    ///
+    /// # Examples
+    /// ```
+    /// # #[tokio::main]
+    /// # async fn main() {
+    ///
+    /// let session = zenoh::open(zenoh::Config::default()).await.unwrap();
+    /// let listener = session.info().declare_link_events_listener().await.unwrap();
+    /// listener.undeclare().await.unwrap();
+    /// # }
+    /// ```
+    #[inline]
+    pub fn undeclare(self) -> LinkEventsListenerUndeclaration<Handler>
+    where
+        Handler: Send,
+    {
+        self.undeclare_inner(())
+    }
+
+    fn undeclare_impl(&mut self) -> ZResult<()> {
+        // set the flag first to avoid double panic if this function panic
+        self.undeclare_on_drop = false;
+        self.runtime.remove_link_events_callback(self.id);
+        Ok(())
+    }
+}
+
+#[cfg(feature = "unstable")]
+impl<Handler> Drop for LinkEventsListener<Handler> {
+    fn drop(&mut self) {
+        if self.undeclare_on_drop {
+            if let Err(error) = self.undeclare_impl() {
+                error!(error);
+            }
+        }
+    }
+}
+
+#[zenoh_macros::unstable]
+impl<Handler: Send> UndeclarableSealed<()> for LinkEventsListener<Handler> {
+    type Undeclaration = LinkEventsListenerUndeclaration<Handler>;
+
+    fn undeclare_inner(self, _: ()) -> Self::Undeclaration {
+        LinkEventsListenerUndeclaration(self)
+    }
+}
+
+#[zenoh_macros::unstable]
+impl<Handler> std::ops::Deref for LinkEventsListener<Handler> {
+    type Target = Handler;
+
+    fn deref(&self) -> &Self::Target {
+        &self.handler
+    }
+}
+
+#[zenoh_macros::unstable]
+impl<Handler> std::ops::DerefMut for LinkEventsListener<Handler> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.handler
+    }
+}
+
+#[zenoh_macros::unstable]
+pub struct LinkEventsListenerUndeclaration<Handler>(LinkEventsListener<Handler>);
+
+#[zenoh_macros::unstable]
+impl<Handler> Resolvable for LinkEventsListenerUndeclaration<Handler> {
+    type To = ZResult<()>;
+}
+
+#[zenoh_macros::unstable]
+impl<Handler> Wait for LinkEventsListenerUndeclaration<Handler> {
+    fn wait(mut self) -> <Self as Resolvable>::To {
+        self.0.undeclare_impl()
+    }
+}
+
+#[zenoh_macros::unstable]
+impl<Handler> IntoFuture for LinkEventsListenerUndeclaration<Handler> {
+    type Output = <Self as Resolvable>::To;
+    type IntoFuture = Ready<<Self as Resolvable>::To>;
+
+    fn into_future(self) -> Self::IntoFuture {
+        std::future::ready(self.wait())
+    }
 }
@@ -18,6 +18,7 @@ pub(crate) mod admin;
 pub(crate) mod builders;
 pub(crate) mod bytes;
 pub(crate) mod config;
+pub(crate) mod connectivity;
 pub(crate) mod encoding;
 pub(crate) mod handlers;
 pub(crate) mod info;
@@ -96,6 +96,18 @@ impl fmt::Debug for PublisherState {
 /// subscriber.stream().map(Ok).forward(publisher).await.unwrap();
 /// # }
 /// ```
+///
+/// It also implements the `Sink` trait for any payload, published with [`Publisher::put`].
+/// ```no_run
+/// # #[tokio::main]
+/// # async fn main() {
+/// use futures::{stream, StreamExt};
+///
+/// let session = zenoh::open(zenoh::Config::default()).await.unwrap();
+/// let publisher = session.declare_publisher("key/expression").await.unwrap();
+/// stream::iter(["a", "b", "c"]).map(Ok).forward(publisher).await.unwrap();
+/// # }
+/// ```
 #[derive(Debug)]
 pub struct Publisher<'a> {
     pub(crate) session: WeakSession,
@@ -220,6 +232,42 @@ impl<'a> Publisher<'a> {
         }
     }
 
+    /// Put many payloads at once.
+    ///
+    /// The publications are routed under a single lock acquisition and enqueued contiguously,
+    /// which is much cheaper than as many calls to [`put`](Publisher::put) for small payloads.
+    ///
+    /// # Examples
+    /// ```
+    /// # #[tokio::main]
+    /// # async fn main() {
+    ///
+    /// let session = zenoh::open(zenoh::Config::default()).await.unwrap();
+    /// let publisher = session.declare_publisher("key/expression").await.unwrap();
+    /// publisher.put_many((0..100).map(|i| i.to_string())).await.unwrap();
+    /// # }
+    /// ```
+    #[zenoh_macros::unstable]
+    pub fn put_many<I>(&self, payloads: I) -> impl Resolve<ZResult<()>> + '_
+    where
+        I: IntoIterator,
+        I::Item: Into<ZBytes>,
+    {
+        let payloads: Vec<ZBytes> = payloads.into_iter().map(Into::into).collect();
+        zenoh_core::ResolveClosure::new(move || {
+            self.session.resolve_put_batch(
+                payloads
+                    .into_iter()
+                    .map(|payload| (self.key_expr.clone(), payload, self.encoding.clone(), None)),
+                self.congestion_control,
+                self.priority,
+                self.is_express,
+                self.destination,
+                self.reliability,
+            )
+        })
+    }
+
     /// Return the [`MatchingStatus`] of the publisher.
     ///
     /// [`MatchingStatus::matching_subscribers`] will return true if there exist Subscribers
@@ -405,6 +453,36 @@ impl<'a> Sink<Sample> for Publisher<'a> {
     }
 }
 
+/// Publishing the items of a stream as the payloads of [`Publisher::put`].
+///
+/// The sink doesn't buffer anything: [`Sink::poll_ready`] always returns immediately and each item
+/// is published by [`Sink::start_send`]. Backpressure is thus given by the publisher
+/// [`CongestionControl`]: with [`CongestionControl::Block`], sending an item blocks while the
+/// transmission queues are full, while with [`CongestionControl::Drop`] the item is dropped instead.
+impl<'a, T: Into<ZBytes>> Sink<T> for Publisher<'a> {
+    type Error = Error;
+
+    #[inline]
+    fn poll_ready(self: Pin<&mut Self>, _cx: &mut Context) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    #[inline]
+    fn start_send(self: Pin<&mut Self>, item: T) -> Result<(), Self::Error> {
+        self.put(item).wait()
+    }
+
+    #[inline]
+    fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    #[inline]
+    fn poll_close(self: Pin<&mut Self>, _cx: &mut Context) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+}
+
 /// Message priority.
 ///
 /// If QoS is enabled, Zenoh keeps one transmission queue per [`Priority`] P, where all messages in
@@ -149,13 +149,6 @@ pub(crate) fn _scout(
         .multicast
         .address()
         .unwrap_or(default_addr);
-    let default_multicast_ttl = zenoh_config::defaults::scouting::multicast::ttl;
-    let multicast_ttl = config
-        .0
-        .scouting
-        .multicast
-        .ttl
-        .unwrap_or(default_multicast_ttl);
     let ifaces = config.0.scouting.multicast.interface().as_ref().map_or(
         zenoh_config::defaults::scouting::multicast::interface,
         |s| s.as_ref(),
@@ -164,7 +157,7 @@ pub(crate) fn _scout(
     if !ifaces.is_empty() {
         let sockets: Vec<UdpSocket> = ifaces
             .into_iter()
-            .filter_map(|iface| Runtime::bind_ucast_port(iface, multicast_ttl).ok())
+            .filter_map(|iface| Runtime::bind_ucast_port(iface, &config.0.scouting.multicast).ok())
             .collect();
         if !sockets.is_empty() {
             let cancellation_token = TerminatableTask::create_cancellation_token();
@@ -14,7 +14,7 @@
 #[cfg(feature = "unstable")]
 use std::collections::hash_map::Entry;
 use std::{
-    collections::HashMap,
+    collections::{HashMap, VecDeque},
     convert::TryInto,
     fmt,
     ops::Deref,
@@ -71,6 +71,7 @@ use zenoh_task::TaskController;
 use crate::api::selector::ZenohParameters;
 #[cfg(feature = "unstable")]
 use crate::api::{
+    builders::publisher::{BatchPut, PutBatchBuilder},
     liveliness::{Liveliness, LivelinessTokenState},
     publisher::Publisher,
     publisher::{MatchingListenerState, MatchingStatus},
@@ -91,6 +92,7 @@ use crate::{
             subscriber::SubscriberBuilder,
         },
         bytes::ZBytes,
+        connectivity::{ConnectivityState, ConnectivityStateChange},
         encoding::Encoding,
         handlers::{Callback, DefaultHandler},
         info::SessionInfo,
@@ -104,7 +106,7 @@ use crate::{
         Id,
     },
     net::{
-        primitives::Primitives,
+        primitives::{ENamespace, Namespace, Primitives},
         routing::dispatcher::face::Face,
         runtime::{Runtime, RuntimeBuilder},
     },
@@ -120,7 +122,9 @@ zconfigurable! {
 }
 
 pub(crate) struct SessionState {
-    pub(crate) primitives: Option<Arc<Face>>, // @TODO replace with MaybeUninit ??
+    pub(crate) primitives: Option<Arc<dyn Primitives>>, // @TODO replace with MaybeUninit ??
+    pub(crate) face: Option<Arc<Face>>,
+    pub(crate) namespace: Option<OwnedKeyExpr>,
     pub(crate) expr_id_counter: AtomicExprId, // @TODO: manage rollover and uniqueness
     pub(crate) qid_counter: AtomicRequestId,
     #[cfg(feature = "unstable")]
@@ -145,6 +149,12 @@ pub(crate) struct SessionState {
     pub(crate) liveliness_queries: HashMap<InterestId, LivelinessQueryState>,
     pub(crate) aggregated_subscribers: Vec<OwnedKeyExpr>,
     pub(crate) aggregated_publishers: Vec<OwnedKeyExpr>,
+    pub(crate) disconnected_buffer: VecDeque<(Push, Reliability)>,
+    pub(crate) disconnected_buffer_size: usize,
+    // Whether the buffered publications are being sent, the publications made in the meantime
+    // being buffered after them
+    pub(crate) flushing_disconnected_buffer: bool,
+    pub(crate) connectivity_callback: Option<Id>,
 }
 
 impl SessionState {
@@ -154,6 +164,8 @@ impl SessionState {
     ) -> SessionState {
         SessionState {
             primitives: None,
+            face: None,
+            namespace: None,
             expr_id_counter: AtomicExprId::new(1), // Note: start at 1 because 0 is reserved for NO_RESOURCE
             qid_counter: AtomicRequestId::new(0),
             #[cfg(feature = "unstable")]
@@ -178,13 +190,17 @@ impl SessionState {
             liveliness_queries: HashMap::new(),
             aggregated_subscribers,
             aggregated_publishers,
+            disconnected_buffer: VecDeque::new(),
+            disconnected_buffer_size: 0,
+            flushing_disconnected_buffer: false,
+            connectivity_callback: None,
         }
     }
 }
 
 impl SessionState {
     #[inline]
-    pub(crate) fn primitives(&self) -> ZResult<Arc<Face>> {
+    pub(crate) fn primitives(&self) -> ZResult<Arc<dyn Primitives>> {
         self.primitives
             .as_ref()
             .cloned()
@@ -559,8 +575,48 @@ impl Session {
 
             runtime.new_handler(Arc::new(admin::Handler::new(session.downgrade())));
 
-            let primitives = Some(router.new_primitives(Arc::new(session.downgrade())));
-            zwrite!(session.0.state).primitives = primitives;
+            let namespace = runtime.config().lock().0.namespace().clone();
+            let (face, primitives): (_, Arc<dyn Primitives>) = match &namespace {
+                Some(namespace) => {
+                    let face = router.new_primitives(Arc::new(ENamespace::new(
+                        namespace.clone(),
+                        Arc::new(session.downgrade()),
+                    )));
+                    let primitives = Arc::new(Namespace::new(namespace.clone(), face.clone()));
+                    (face, primitives)
+                }
+                None => {
+                    let face = router.new_primitives(Arc::new(session.downgrade()));
+                    (face.clone(), face)
+                }
+            };
+            let disconnected_buffer_size = runtime
+                .config()
+                .lock()
+                .0
+                .connect()
+                .disconnected_buffer_size()
+                .unwrap_or(0);
+            let mut state = zwrite!(session.0.state);
+            state.primitives = Some(primitives);
+            state.face = Some(face);
+            state.namespace = namespace;
+            state.disconnected_buffer_size = disconnected_buffer_size;
+            if disconnected_buffer_size > 0 {
+                let weak = session.downgrade();
+                state.connectivity_callback = Some(runtime.add_connectivity_state_callback(
+                    Callback::new(Arc::new(move |change: ConnectivityStateChange| {
+                        if change.previous == ConnectivityState::Disconnected {
+                            // Flush asynchronously, once the new transport is fully established
+                            let session = weak.clone();
+                            weak.runtime.spawn(async move {
+                                session.flush_disconnected_buffer();
+                            });
+                        }
+                    })),
+                ));
+            }
+            drop(state);
 
             admin::init(session.downgrade());
 
@@ -674,6 +730,187 @@ impl Session {
         self.0.runtime.config()
     }
 
+    /// Start listening on a new endpoint.
+    ///
+    /// The endpoint is added to the `listen/endpoints` configuration and the
+    /// [`Locator`](crate::config::Locator) the listener can be reached at is returned.
+    ///
+    /// # Examples
+    /// ```
+    /// # #[tokio::main]
+    /// # async fn main() {
+    ///
+    /// let session = zenoh::open(zenoh::Config::default()).await.unwrap();
+    /// let locator = session.add_listener("tcp/127.0.0.1:0").await.unwrap();
+    /// # }
+    /// ```
+    #[zenoh_macros::unstable]
+    pub fn add_listener<TryIntoEndPoint>(
+        &self,
+        endpoint: TryIntoEndPoint,
+    ) -> impl Resolve<ZResult<crate::config::Locator>> + '_
+    where
+        TryIntoEndPoint: TryInto<crate::config::EndPoint>,
+        <TryIntoEndPoint as TryInto<crate::config::EndPoint>>::Error: Into<zenoh_result::Error>,
+    {
+        let endpoint = endpoint.try_into().map_err(Into::into);
+        ResolveFuture::new(async move { self.0.runtime.add_listener_endpoint(endpoint?).await })
+    }
+
+    /// Stop listening on an endpoint previously configured or added with [`Session::add_listener`].
+    ///
+    /// The endpoint is removed from the `listen/endpoints` configuration.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// # #[tokio::main]
+    /// # async fn main() {
+    ///
+    /// let session = zenoh::open(zenoh::Config::default()).await.unwrap();
+    /// session.add_listener("tcp/127.0.0.1:7448").await.unwrap();
+    /// session.remove_listener("tcp/127.0.0.1:7448").await.unwrap();
+    /// # }
+    /// ```
+    #[zenoh_macros::unstable]
+    pub fn remove_listener<TryIntoEndPoint>(
+        &self,
+        endpoint: TryIntoEndPoint,
+    ) -> impl Resolve<ZResult<()>> + '_
+    where
+        TryIntoEndPoint: TryInto<crate::config::EndPoint>,
+        <TryIntoEndPoint as TryInto<crate::config::EndPoint>>::Error: Into<zenoh_result::Error>,
+    {
+        let endpoint = endpoint.try_into().map_err(Into::into);
+        ResolveFuture::new(async move { self.0.runtime.remove_listener_endpoint(&endpoint?).await })
+    }
+
+    /// Add an endpoint to connect to.
+    ///
+    /// The endpoint is added to the `connect/endpoints` configuration and peers and routers
+    /// connect to it in background, retrying as configured in `connect/retry`.
+    /// Clients connect to it when their current transport is closed.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// # #[tokio::main]
+    /// # async fn main() {
+    ///
+    /// let session = zenoh::open(zenoh::Config::default()).await.unwrap();
+    /// session.add_connect_endpoint("tcp/127.0.0.1:7447").await.unwrap();
+    /// # }
+    /// ```
+    #[zenoh_macros::unstable]
+    pub fn add_connect_endpoint<TryIntoEndPoint>(
+        &self,
+        endpoint: TryIntoEndPoint,
+    ) -> impl Resolve<ZResult<()>> + '_
+    where
+        TryIntoEndPoint: TryInto<crate::config::EndPoint>,
+        <TryIntoEndPoint as TryInto<crate::config::EndPoint>>::Error: Into<zenoh_result::Error>,
+    {
+        let endpoint = endpoint.try_into().map_err(Into::into);
+        ResolveFuture::new(async move { self.0.runtime.add_connect_endpoint(endpoint?).await })
+    }
+
+    /// Remove an endpoint to connect to, closing the transports established through it.
+    ///
+    /// The endpoint is removed from the `connect/endpoints` configuration.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// # #[tokio::main]
+    /// # async fn main() {
+    ///
+    /// let session = zenoh::open(zenoh::Config::default()).await.unwrap();
+    /// session.add_connect_endpoint("tcp/127.0.0.1:7447").await.unwrap();
+    /// session.remove_connect_endpoint("tcp/127.0.0.1:7447").await.unwrap();
+    /// # }
+    /// ```
+    #[zenoh_macros::unstable]
+    pub fn remove_connect_endpoint<TryIntoEndPoint>(
+        &self,
+        endpoint: TryIntoEndPoint,
+    ) -> impl Resolve<ZResult<()>> + '_
+    where
+        TryIntoEndPoint: TryInto<crate::config::EndPoint>,
+        <TryIntoEndPoint as TryInto<crate::config::EndPoint>>::Error: Into<zenoh_result::Error>,
+    {
+        let endpoint = endpoint.try_into().map_err(Into::into);
+        ResolveFuture::new(async move { self.0.runtime.remove_connect_endpoint(&endpoint?).await })
+    }
+
+    /// Return an [`EndpointEventsListener`](crate::session::EndpointEventsListener) notified
+    /// each time a listener or a connect endpoint of this [`Session`] is added or removed.
+    ///
+    /// # Examples
+    /// ```
+    /// # #[tokio::main]
+    /// # async fn main() {
+    ///
+    /// let session = zenoh::open(zenoh::Config::default()).await.unwrap();
+    /// let listener = session
+    ///     .declare_endpoint_events_listener()
+    ///     .callback(|event| println!("{:?}", event))
+    ///     .await
+    ///     .unwrap();
+    /// # }
+    /// ```
+    #[zenoh_macros::unstable]
+    pub fn declare_endpoint_events_listener(
+        &self,
+    ) -> crate::api::builders::connectivity::EndpointEventsListenerBuilder<'_, DefaultHandler> {
+        crate::api::builders::connectivity::EndpointEventsListenerBuilder {
+            runtime: &self.0.runtime,
+            handler: DefaultHandler::default(),
+        }
+    }
+
+    /// Return the current [`ConnectivityState`](crate::session::ConnectivityState) of this [`Session`].
+    ///
+    /// # Examples
+    /// ```
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// use zenoh::session::ConnectivityState;
+    ///
+    /// let session = zenoh::open(zenoh::Config::default()).await.unwrap();
+    /// if session.connectivity_state() == ConnectivityState::Disconnected {
+    ///     println!("No other zenoh node reachable");
+    /// }
+    /// # }
+    /// ```
+    #[zenoh_macros::unstable]
+    pub fn connectivity_state(&self) -> crate::api::connectivity::ConnectivityState {
+        self.0.runtime.connectivity_state()
+    }
+
+    /// Return a [`ConnectivityStateListener`](crate::session::ConnectivityStateListener) notified
+    /// each time the [`ConnectivityState`](crate::session::ConnectivityState) of this [`Session`] changes.
+    ///
+    /// # Examples
+    /// ```
+    /// # #[tokio::main]
+    /// # async fn main() {
+    ///
+    /// let session = zenoh::open(zenoh::Config::default()).await.unwrap();
+    /// let listener = session
+    ///     .declare_connectivity_state_listener()
+    ///     .callback(|change| println!("{:?} -> {:?}", change.previous, change.current))
+    ///     .await
+    ///     .unwrap();
+    /// # }
+    /// ```
+    #[zenoh_macros::unstable]
+    pub fn declare_connectivity_state_listener(
+        &self,
+    ) -> crate::api::builders::connectivity::ConnectivityStateListenerBuilder<'_, DefaultHandler>
+    {
+        crate::api::builders::connectivity::ConnectivityStateListenerBuilder {
+            runtime: &self.0.runtime,
+            handler: DefaultHandler::default(),
+        }
+    }
+
     /// Get a new Timestamp from a Zenoh [`Session`].
     ///
     /// The returned timestamp has the current time, with the Session's runtime [`ZenohId`].
@@ -958,6 +1195,44 @@ impl Session {
         }
     }
 
+    /// Put a batch of data at once.
+    ///
+    /// The publications are routed under a single lock acquisition and enqueued contiguously,
+    /// which is much cheaper than as many calls to [`put`](Session::put) for small payloads.
+    ///
+    /// # Arguments
+    ///
+    /// * `items` - The publications to put, built from `(key_expr, payload)` or
+    ///   `(key_expr, payload, encoding, attachment)` tuples
+    ///
+    /// # Examples
+    /// ```
+    /// # #[tokio::main]
+    /// # async fn main() {
+    ///
+    /// let session = zenoh::open(zenoh::Config::default()).await.unwrap();
+    /// session
+    ///     .put_batch((0..100).map(|i| (format!("key/expression/{i}"), i.to_string()).into()))
+    ///     .await
+    ///     .unwrap();
+    /// # }
+    /// ```
+    #[zenoh_macros::unstable]
+    pub fn put_batch<'a, 'b: 'a, I>(&'a self, items: I) -> PutBatchBuilder<'a, 'b>
+    where
+        I: IntoIterator<Item = BatchPut<'b>>,
+    {
+        PutBatchBuilder {
+            session: self,
+            items: items.into_iter().collect(),
+            congestion_control: CongestionControl::DEFAULT,
+            priority: Priority::DEFAULT,
+            is_express: false,
+            reliability: Reliability::DEFAULT,
+            destination: Locality::default(),
+        }
+    }
+
     /// Delete data.
     ///
     /// # Arguments
@@ -1012,6 +1287,21 @@ impl Session {
     /// }
     /// # }
     /// ```
+    ///
+    /// The replies handler is also a [`Stream`](futures::stream::Stream) of replies, which finishes
+    /// when the query is complete.
+    /// ```
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// use futures::StreamExt;
+    ///
+    /// let session = zenoh::open(zenoh::Config::default()).await.unwrap();
+    /// let mut replies = session.get("key/expression").await.unwrap();
+    /// while let Some(reply) = replies.next().await {
+    ///     println!(">> Received {:?}", reply.result());
+    /// }
+    /// # }
+    /// ```
     pub fn get<'a, 'b: 'a, TryIntoSelector>(
         &'a self,
         selector: TryIntoSelector,
@@ -1083,6 +1373,10 @@ impl SessionInner {
             let Some(primitives) = zwrite!(self.state).primitives.take() else {
                 return Ok(());
             };
+            zwrite!(self.state).face = None;
+            if let Some(id) = zwrite!(self.state).connectivity_callback.take() {
+                self.runtime.remove_connectivity_state_callback(id);
+            }
             if self.owns_runtime {
                 info!(zid = %self.zid(), "close session");
             }
@@ -1746,16 +2040,20 @@ impl SessionInner {
         destination: Locality,
     ) -> ZResult<MatchingStatus> {
         let router = self.runtime.router();
+        let key_expr = match &zread!(self.state).namespace {
+            Some(namespace) => KeyExpr::from(namespace / key_expr),
+            None => key_expr.clone(),
+        };
         let tables = zread!(router.tables.tables);
 
         let matching_subscriptions =
-            crate::net::routing::dispatcher::pubsub::get_matching_subscriptions(&tables, key_expr);
+            crate::net::routing::dispatcher::pubsub::get_matching_subscriptions(&tables, &key_expr);
 
         drop(tables);
         let matching = match destination {
             Locality::Any => !matching_subscriptions.is_empty(),
             Locality::Remote => {
-                if let Some(face) = zread!(self.state).primitives.as_ref() {
+                if let Some(face) = zread!(self.state).face.as_ref() {
                     matching_subscriptions
                         .values()
                         .any(|dir| !Arc::ptr_eq(dir, &face.state))
@@ -1764,7 +2062,7 @@ impl SessionInner {
                 }
             }
             Locality::SessionLocal => {
-                if let Some(face) = zread!(self.state).primitives.as_ref() {
+                if let Some(face) = zread!(self.state).face.as_ref() {
                     matching_subscriptions
                         .values()
                         .any(|dir| Arc::ptr_eq(dir, &face.state))
@@ -1943,6 +2241,168 @@ impl SessionInner {
         }
     }
 
+    /// Buffers a publication if the session is disconnected and the buffering is enabled,
+    /// or if the buffered publications are being sent, dropping the oldest buffered
+    /// publication if the buffer is full.
+    /// Returns false if the publication should be sent right away.
+    fn buffer_if_disconnected(&self, push: &Push, reliability: Reliability) -> bool {
+        let mut state = zwrite!(self.state);
+        if state.disconnected_buffer_size == 0
+            || (!state.flushing_disconnected_buffer
+                && self.runtime.connectivity_state() != ConnectivityState::Disconnected)
+        {
+            return false;
+        }
+        if state.disconnected_buffer.len() >= state.disconnected_buffer_size {
+            tracing::warn!("Disconnected buffer full: drop the oldest publication");
+            state.disconnected_buffer.pop_front();
+        }
+        state
+            .disconnected_buffer
+            .push_back((push.clone(), reliability));
+        true
+    }
+
+    /// Sends the publications buffered while the session was disconnected.
+    pub(crate) fn flush_disconnected_buffer(&self) {
+        let mut state = zwrite!(self.state);
+        if state.flushing_disconnected_buffer {
+            return;
+        }
+        let Ok(primitives) = state.primitives() else {
+            return;
+        };
+        // The lock is released while sending, the publications made in the meantime being
+        // buffered and sent after the buffered ones
+        state.flushing_disconnected_buffer = true;
+        loop {
+            let buffer = std::mem::take(&mut state.disconnected_buffer);
+            if buffer.is_empty() {
+                state.flushing_disconnected_buffer = false;
+                return;
+            }
+            drop(state);
+            tracing::debug!(
+                "Send {} publications buffered while disconnected",
+                buffer.len()
+            );
+            for (push, reliability) in buffer {
+                primitives.send_push(push, reliability);
+            }
+            state = zwrite!(self.state);
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn new_push(
+        wire_expr: &WireExpr,
+        payload: &ZBytes,
+        kind: SampleKind,
+        encoding: &Encoding,
+        ext_qos: push::ext::QoSType,
+        timestamp: Option<uhlc::Timestamp>,
+        #[cfg(feature = "unstable")] source_info: SourceInfo,
+        attachment: &Option<ZBytes>,
+    ) -> Push {
+        Push {
+            wire_expr: wire_expr.to_owned(),
+            ext_qos,
+            ext_tstamp: None,
+            ext_nodeid: push::ext::NodeIdType::DEFAULT,
+            ext_redundancy: None,
+            payload: match kind {
+                SampleKind::Put => PushBody::Put(Put {
+                    timestamp,
+                    encoding: encoding.clone().into(),
+                    #[cfg(feature = "unstable")]
+                    ext_sinfo: source_info.into(),
+                    #[cfg(not(feature = "unstable"))]
+                    ext_sinfo: None,
+                    #[cfg(feature = "shared-memory")]
+                    ext_shm: None,
+                    ext_attachment: attachment.clone().map(|a| a.into()),
+                    ext_unknown: vec![],
+                    payload: payload.clone().into(),
+                }),
+                SampleKind::Delete => PushBody::Del(Del {
+                    timestamp,
+                    #[cfg(feature = "unstable")]
+                    ext_sinfo: source_info.into(),
+                    #[cfg(not(feature = "unstable"))]
+                    ext_sinfo: None,
+                    ext_attachment: attachment.clone().map(|a| a.into()),
+                    ext_unknown: vec![],
+                }),
+            },
+        }
+    }
+
+    /// Publishes the given samples with the same qos, routing them at once so that they are
+    /// enqueued contiguously in the transmission pipelines.
+    #[zenoh_macros::unstable]
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn resolve_put_batch<'k, I>(
+        &self,
+        items: I,
+        congestion_control: CongestionControl,
+        priority: Priority,
+        is_express: bool,
+        destination: Locality,
+        reliability: Reliability,
+    ) -> ZResult<()>
+    where
+        I: IntoIterator<Item = (KeyExpr<'k>, ZBytes, Encoding, Option<ZBytes>)>,
+    {
+        trace!("write_batch([...])");
+        let primitives = zread!(self.state).primitives()?;
+        let ext_qos = push::ext::QoSType::new(priority.into(), congestion_control, is_express);
+        let mut pushes = vec![];
+        for (key_expr, payload, encoding, attachment) in items {
+            let timestamp = self.runtime.new_timestamp();
+            let wire_expr = key_expr.to_wire(self);
+            if destination != Locality::SessionLocal {
+                let push = Self::new_push(
+                    &wire_expr,
+                    &payload,
+                    SampleKind::Put,
+                    &encoding,
+                    ext_qos,
+                    timestamp,
+                    SourceInfo::empty(),
+                    &attachment,
+                );
+                if congestion_control != CongestionControl::Block
+                    || !self.buffer_if_disconnected(&push, reliability)
+                {
+                    pushes.push((push, reliability));
+                }
+            }
+            if destination != Locality::Remote {
+                let data_info = DataInfo {
+                    kind: SampleKind::Put,
+                    encoding: Some(encoding),
+                    timestamp,
+                    source_id: None,
+                    source_sn: None,
+                    qos: QoS::from(ext_qos),
+                };
+                self.execute_subscriber_callbacks(
+                    true,
+                    &wire_expr,
+                    Some(data_info),
+                    payload.into(),
+                    SubscriberKind::Subscriber,
+                    reliability,
+                    attachment,
+                );
+            }
+        }
+        if !pushes.is_empty() {
+            primitives.send_push_batch(pushes);
+        }
+        Ok(())
+    }
+
     #[allow(clippy::too_many_arguments)] // TODO fixme
     pub(crate) fn resolve_put(
         &self,
@@ -1964,46 +2424,24 @@ impl SessionInner {
         let timestamp = timestamp.or_else(|| self.runtime.new_timestamp());
         let wire_expr = key_expr.to_wire(self);
         if destination != Locality::SessionLocal {
-            primitives.send_push(
-                Push {
-                    wire_expr: wire_expr.to_owned(),
-                    ext_qos: push::ext::QoSType::new(
-                        priority.into(),
-                        congestion_control,
-                        is_express,
-                    ),
-                    ext_tstamp: None,
-                    ext_nodeid: push::ext::NodeIdType::DEFAULT,
-                    payload: match kind {
-                        SampleKind::Put => PushBody::Put(Put {
-                            timestamp,
-                            encoding: encoding.clone().into(),
-                            #[cfg(feature = "unstable")]
-                            ext_sinfo: source_info.into(),
-                            #[cfg(not(feature = "unstable"))]
-                            ext_sinfo: None,
-                            #[cfg(feature = "shared-memory")]
-                            ext_shm: None,
-                            ext_attachment: attachment.clone().map(|a| a.into()),
-                            ext_unknown: vec![],
-                            payload: payload.clone().into(),
-                        }),
-                        SampleKind::Delete => PushBody::Del(Del {
-                            timestamp,
-                            #[cfg(feature = "unstable")]
-                            ext_sinfo: source_info.into(),
-                            #[cfg(not(feature = "unstable"))]
-                            ext_sinfo: None,
-                            ext_attachment: attachment.clone().map(|a| a.into()),
-                            ext_unknown: vec![],
-                        }),
-                    },
-                },
+            #[cfg(not(feature = "unstable"))]
+            let reliability = Reliability::DEFAULT;
+            let push = Self::new_push(
+                &wire_expr,
+                &payload,
+                kind,
+                &encoding,
+                push::ext::QoSType::new(priority.into(), congestion_control, is_express),
+                timestamp,
                 #[cfg(feature = "unstable")]
-                reliability,
-                #[cfg(not(feature = "unstable"))]
-                Reliability::DEFAULT,
+                source_info,
+                &attachment,
             );
+            if congestion_control != CongestionControl::Block
+                || !self.buffer_if_disconnected(&push, reliability)
+            {
+                primitives.send_push(push, reliability);
+            }
         }
         if destination != Locality::Remote {
             let data_info = DataInfo {
@@ -189,8 +189,33 @@ pub mod session {
     #[zenoh_macros::unstable]
     pub use zenoh_protocol::core::EntityId;
 
+    #[zenoh_macros::unstable]
+    pub use crate::api::builders::publisher::{BatchPut, PutBatchBuilder};
     #[zenoh_macros::internal]
     pub use crate::api::builders::session::{init, InitBuilder};
+    #[cfg(all(feature = "unstable", feature = "stats"))]
+    pub use crate::api::info::TransportStats;
+    #[zenoh_macros::unstable]
+    pub use crate::api::{
+        builders::connectivity::{ConnectivityStateListenerBuilder, EndpointEventsListenerBuilder},
+        connectivity::{
+            ConnectivityState, ConnectivityStateChange, ConnectivityStateListener,
+            ConnectivityStateListenerUndeclaration, EndpointEvent, EndpointEventsListener,
+            EndpointEventsListenerUndeclaration,
+        },
+    };
+    #[zenoh_macros::unstable]
+    pub use crate::api::{
+        builders::info::{
+            LinkEventsListenerBuilder, LinksBuilder, TransportEventsListenerBuilder,
+            TransportsBuilder,
+        },
+        info::{
+            AuthId, Link, LinkEvent, LinkEventsListener, LinkEventsListenerUndeclaration,
+            Transport, TransportEvent, TransportEventsListener,
+            TransportEventsListenerUndeclaration,
+        },
+    };
     pub use crate::api::{
         builders::{
             info::{PeersZenohIdBuilder, RoutersZenohIdBuilder, ZenohIdBuilder},
@@ -329,6 +354,10 @@ pub mod handlers {
         Callback, CallbackDrop, DefaultHandler, FifoChannel, FifoChannelHandler, IntoHandler,
         RingChannel, RingChannelHandler,
     };
+    #[zenoh_macros::unstable]
+    pub use crate::api::handlers::{
+        DedupChannel, LatestChannel, LatestChannelHandler, PriorityChannel, PriorityChannelHandler,
+    };
     pub mod fifo {
         pub use crate::api::handlers::{
             Drain, FifoChannel, FifoChannelHandler, IntoIter, Iter, RecvFut, RecvStream, TryIter,
@@ -487,7 +516,13 @@ pub mod internal {
     pub mod runtime {
         pub use zenoh_runtime::ZRuntime;
 
-        pub use crate::net::runtime::{AdminSpace, Runtime, RuntimeBuilder};
+        pub use crate::net::{
+            routing::dispatcher::explain::{
+                InterceptorVerdict, MatchingDeclaration, RouteDirection, RouteExplanation,
+                RouteFace, RoutingTree,
+            },
+            runtime::{AdminSpace, Runtime, RuntimeBuilder},
+        };
     }
     /// Plugins support
     #[cfg(feature = "plugins")]
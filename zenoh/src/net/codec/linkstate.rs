@@ -14,19 +14,20 @@
 use core::convert::TryFrom;
 
 use zenoh_buffers::{
-    reader::{DidntRead, Reader},
-    writer::{DidntWrite, Writer},
+    reader::{DidntRead, HasReader, Reader},
+    writer::{DidntWrite, HasWriter, Writer},
+    ZBuf,
 };
-use zenoh_codec::{RCodec, WCodec, Zenoh080};
+use zenoh_codec::{common::extension, RCodec, WCodec, Zenoh080, Zenoh080Header};
 use zenoh_protocol::{
-    common::imsg,
+    common::{iext, imsg},
     core::{Locator, WhatAmI, ZenohIdProto},
 };
 
 use super::Zenoh080Routing;
 use crate::net::protocol::{
     linkstate,
-    linkstate::{LinkState, LinkStateList},
+    linkstate::{ext, LinkState, LinkStateList},
 };
 
 // LinkState
@@ -116,6 +117,8 @@ where
             whatami,
             locators,
             links,
+            link_weights: None,
+            region: None,
         })
     }
 }
@@ -135,6 +138,16 @@ where
             self.write(&mut *writer, ls)?;
         }
 
+        // Extensions
+        let weights = write_link_weights(x)?.map(ext::LinkWeights::new);
+        let region = write_regions(x)?.map(ext::Region::new);
+        if let Some(weights) = weights.as_ref() {
+            codec.write(&mut *writer, (weights, region.is_some()))?;
+        }
+        if let Some(region) = region.as_ref() {
+            codec.write(&mut *writer, (region, false))?;
+        }
+
         Ok(())
     }
 }
@@ -155,6 +168,104 @@ where
             link_states.push(ls);
         }
 
+        // Extensions
+        let mut has_ext = reader.can_read();
+        while has_ext {
+            let ext: u8 = codec.read(&mut *reader)?;
+            let eodec = Zenoh080Header::new(ext);
+            match iext::eid(ext) {
+                ext::LinkWeights::ID => {
+                    let (e, more): (ext::LinkWeights, bool) = eodec.read(&mut *reader)?;
+                    read_link_weights(&e.value, &mut link_states)?;
+                    has_ext = more;
+                }
+                ext::Region::ID => {
+                    let (e, more): (ext::Region, bool) = eodec.read(&mut *reader)?;
+                    read_regions(&e.value, &mut link_states)?;
+                    has_ext = more;
+                }
+                _ => {
+                    has_ext = extension::skip(reader, "LinkStateList", ext)?;
+                }
+            }
+        }
+
         Ok(LinkStateList { link_states })
     }
 }
+
+// The link weights extension: [(index of the link state, [weights])]
+fn write_link_weights(x: &LinkStateList) -> Result<Option<ZBuf>, DidntWrite> {
+    let codec = Zenoh080::new();
+    let weights: Vec<(usize, &Vec<u16>)> = x
+        .link_states
+        .iter()
+        .enumerate()
+        .filter_map(|(i, ls)| Some((i, ls.link_weights.as_ref()?)))
+        .collect();
+    if weights.is_empty() {
+        return Ok(None);
+    }
+    let mut buf = ZBuf::empty();
+    let mut writer = buf.writer();
+    codec.write(&mut writer, weights.len())?;
+    for (i, ws) in weights {
+        codec.write(&mut writer, i)?;
+        codec.write(&mut writer, ws.len())?;
+        for w in ws.iter() {
+            codec.write(&mut writer, *w)?;
+        }
+    }
+    Ok(Some(buf))
+}
+
+fn read_link_weights(buf: &ZBuf, link_states: &mut [LinkState]) -> Result<(), DidntRead> {
+    let codec = Zenoh080::new();
+    let mut reader = buf.reader();
+    let len: usize = codec.read(&mut reader)?;
+    for _ in 0..len {
+        let i: usize = codec.read(&mut reader)?;
+        let n: usize = codec.read(&mut reader)?;
+        let mut weights: Vec<u16> = Vec::with_capacity(n);
+        for _ in 0..n {
+            let w: u16 = codec.read(&mut reader)?;
+            weights.push(w);
+        }
+        link_states.get_mut(i).ok_or(DidntRead)?.link_weights = Some(weights);
+    }
+    Ok(())
+}
+
+// The region extension: [(index of the link state, region)]
+fn write_regions(x: &LinkStateList) -> Result<Option<ZBuf>, DidntWrite> {
+    let codec = Zenoh080::new();
+    let regions: Vec<(usize, &String)> = x
+        .link_states
+        .iter()
+        .enumerate()
+        .filter_map(|(i, ls)| Some((i, ls.region.as_ref()?)))
+        .collect();
+    if regions.is_empty() {
+        return Ok(None);
+    }
+    let mut buf = ZBuf::empty();
+    let mut writer = buf.writer();
+    codec.write(&mut writer, regions.len())?;
+    for (i, region) in regions {
+        codec.write(&mut writer, i)?;
+        codec.write(&mut writer, region.as_str())?;
+    }
+    Ok(Some(buf))
+}
+
+fn read_regions(buf: &ZBuf, link_states: &mut [LinkState]) -> Result<(), DidntRead> {
+    let codec = Zenoh080::new();
+    let mut reader = buf.reader();
+    let len: usize = codec.read(&mut reader)?;
+    for _ in 0..len {
+        let i: usize = codec.read(&mut reader)?;
+        let region: String = codec.read(&mut reader)?;
+        link_states.get_mut(i).ok_or(DidntRead)?.region = Some(region);
+    }
+    Ok(())
+}
@@ -13,11 +13,13 @@
 //
 mod demux;
 mod mux;
+mod namespace;
 
 use std::any::Any;
 
 pub use demux::*;
 pub use mux::*;
+pub(crate) use namespace::*;
 use zenoh_protocol::{
     core::Reliability,
     network::{interest::Interest, Declare, Push, Request, Response, ResponseFinal},
@@ -32,6 +34,13 @@ pub trait Primitives: Send + Sync {
 
     fn send_push(&self, msg: Push, reliability: Reliability);
 
+    /// Sends the given data messages in order, routing them at once when possible.
+    fn send_push_batch(&self, msgs: Vec<(Push, Reliability)>) {
+        for (msg, reliability) in msgs {
+            self.send_push(msg, reliability)
+        }
+    }
+
     fn send_request(&self, msg: Request);
 
     fn send_response(&self, msg: Response);
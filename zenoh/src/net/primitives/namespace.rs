@@ -0,0 +1,225 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+use std::{any::Any, sync::Arc};
+
+use zenoh_keyexpr::keyexpr;
+use zenoh_protocol::{
+    core::{key_expr::OwnedKeyExpr, Reliability, WireExpr},
+    network::{interest::Interest, Declare, DeclareBody, Push, Request, Response, ResponseFinal},
+};
+
+use super::{EPrimitives, Primitives};
+use crate::net::routing::{strip_wild_prefix, RoutingContext};
+
+// Only the key expressions that are not relative to a declared key expression are rewritten:
+// the declared ones are themselves rewritten when declared. The admin space key expressions
+// (starting with '@') are left untouched.
+fn is_rewritable(wire_expr: &WireExpr) -> bool {
+    wire_expr.scope == 0 && !wire_expr.suffix.starts_with('@')
+}
+
+fn declare_wire_expr(declare: &mut Declare) -> Option<&mut WireExpr<'static>> {
+    match &mut declare.body {
+        DeclareBody::DeclareKeyExpr(m) => Some(&mut m.wire_expr),
+        DeclareBody::DeclareSubscriber(m) => Some(&mut m.wire_expr),
+        DeclareBody::UndeclareSubscriber(m) => Some(&mut m.ext_wire_expr.wire_expr),
+        DeclareBody::DeclareQueryable(m) => Some(&mut m.wire_expr),
+        DeclareBody::UndeclareQueryable(m) => Some(&mut m.ext_wire_expr.wire_expr),
+        DeclareBody::DeclareToken(m) => Some(&mut m.wire_expr),
+        DeclareBody::UndeclareToken(m) => Some(&mut m.ext_wire_expr.wire_expr),
+        DeclareBody::UndeclareKeyExpr(_) | DeclareBody::DeclareFinal(_) => None,
+    }
+}
+
+/// Prefixes the key expressions of the messages sent by a session with its namespace.
+pub(crate) struct Namespace {
+    namespace: OwnedKeyExpr,
+    primitives: Arc<dyn Primitives>,
+}
+
+impl Namespace {
+    pub(crate) fn new(namespace: OwnedKeyExpr, primitives: Arc<dyn Primitives>) -> Self {
+        Self {
+            namespace,
+            primitives,
+        }
+    }
+
+    fn prefix(&self, wire_expr: &mut WireExpr) {
+        if is_rewritable(wire_expr) {
+            wire_expr.suffix = if wire_expr.suffix.is_empty() {
+                self.namespace.to_string().into()
+            } else {
+                format!("{}/{}", self.namespace, wire_expr.suffix).into()
+            };
+        }
+    }
+}
+
+impl Primitives for Namespace {
+    fn send_interest(&self, mut msg: Interest) {
+        if let Some(wire_expr) = msg.wire_expr.as_mut() {
+            self.prefix(wire_expr);
+        }
+        self.primitives.send_interest(msg)
+    }
+
+    fn send_declare(&self, mut msg: Declare) {
+        if let Some(wire_expr) = declare_wire_expr(&mut msg) {
+            self.prefix(wire_expr);
+        }
+        self.primitives.send_declare(msg)
+    }
+
+    fn send_push(&self, mut msg: Push, reliability: Reliability) {
+        self.prefix(&mut msg.wire_expr);
+        self.primitives.send_push(msg, reliability)
+    }
+
+    fn send_push_batch(&self, mut msgs: Vec<(Push, Reliability)>) {
+        for (msg, _) in &mut msgs {
+            self.prefix(&mut msg.wire_expr);
+        }
+        self.primitives.send_push_batch(msgs)
+    }
+
+    fn send_request(&self, mut msg: Request) {
+        self.prefix(&mut msg.wire_expr);
+        self.primitives.send_request(msg)
+    }
+
+    fn send_response(&self, mut msg: Response) {
+        self.prefix(&mut msg.wire_expr);
+        self.primitives.send_response(msg)
+    }
+
+    fn send_response_final(&self, msg: ResponseFinal) {
+        self.primitives.send_response_final(msg)
+    }
+
+    fn send_close(&self) {
+        self.primitives.send_close()
+    }
+}
+
+/// Strips the namespace of a session from the key expressions of the messages it receives.
+///
+/// The messages whose key expressions are outside of the namespace are dropped. The wildcard key
+/// expressions of queries, interests and declarations that intersect the namespace are replaced
+/// by their intersection with it, e.g. `**/temp` is seen as `**/temp` by a session in namespace
+/// `ns` while `*/temp` is seen as `temp`. The messages are dropped if this intersection can't be
+/// expressed as a single key expression.
+pub(crate) struct ENamespace {
+    namespace: OwnedKeyExpr,
+    primitives: Arc<dyn EPrimitives + Send + Sync>,
+}
+
+impl ENamespace {
+    pub(crate) fn new(
+        namespace: OwnedKeyExpr,
+        primitives: Arc<dyn EPrimitives + Send + Sync>,
+    ) -> Self {
+        Self {
+            namespace,
+            primitives,
+        }
+    }
+
+    // Returns false if the message should be dropped
+    fn strip(&self, wire_expr: &mut WireExpr, wildcard: bool) -> bool {
+        if !is_rewritable(wire_expr) {
+            return true;
+        }
+        if let Some(suffix) = wire_expr
+            .suffix
+            .strip_prefix(self.namespace.as_str())
+            .and_then(|suffix| suffix.strip_prefix('/'))
+        {
+            wire_expr.suffix = suffix.to_string().into();
+            return true;
+        }
+        if wildcard {
+            if let Ok(key_expr) = keyexpr::new(wire_expr.suffix.as_ref()) {
+                match strip_wild_prefix(key_expr, &self.namespace).as_slice() {
+                    [] => {}
+                    [suffix] => {
+                        wire_expr.suffix = suffix.to_string().into();
+                        return true;
+                    }
+                    _ => {
+                        tracing::debug!(
+                            "Drop message on {}: its intersection with namespace {} is not a single key expression",
+                            wire_expr,
+                            self.namespace
+                        );
+                        return false;
+                    }
+                }
+            }
+        }
+        tracing::trace!(
+            "Drop message on {} outside of namespace {}",
+            wire_expr,
+            self.namespace
+        );
+        false
+    }
+}
+
+impl EPrimitives for ENamespace {
+    fn send_interest(&self, mut ctx: RoutingContext<Interest>) {
+        if let Some(wire_expr) = ctx.msg.wire_expr.as_mut() {
+            if !self.strip(wire_expr, true) {
+                return;
+            }
+        }
+        self.primitives.send_interest(ctx)
+    }
+
+    fn send_declare(&self, mut ctx: RoutingContext<Declare>) {
+        let wildcard = !matches!(ctx.msg.body, DeclareBody::DeclareKeyExpr(_));
+        if let Some(wire_expr) = declare_wire_expr(&mut ctx.msg) {
+            if !self.strip(wire_expr, wildcard) {
+                return;
+            }
+        }
+        self.primitives.send_declare(ctx)
+    }
+
+    fn send_push(&self, mut msg: Push, reliability: Reliability) {
+        if self.strip(&mut msg.wire_expr, false) {
+            self.primitives.send_push(msg, reliability)
+        }
+    }
+
+    fn send_request(&self, mut msg: Request) {
+        if self.strip(&mut msg.wire_expr, true) {
+            self.primitives.send_request(msg)
+        }
+    }
+
+    fn send_response(&self, mut msg: Response) {
+        if self.strip(&mut msg.wire_expr, false) {
+            self.primitives.send_response(msg)
+        }
+    }
+
+    fn send_response_final(&self, msg: ResponseFinal) {
+        self.primitives.send_response_final(msg)
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
@@ -33,6 +33,8 @@ pub const LOC: u64 = 1 << 2; // 0x04
 // +---------------+
 // ~    [links]    ~
 // +---------------+
+//
+// The link weights and the region are carried by the extensions of the [`LinkStateList`].
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub(crate) struct LinkState {
     pub(crate) psid: u64,
@@ -41,6 +43,10 @@ pub(crate) struct LinkState {
     pub(crate) whatami: Option<WhatAmI>,
     pub(crate) locators: Option<Vec<Locator>>,
     pub(crate) links: Vec<u64>,
+    // The weights of the links, in the same order, 0 meaning no weight
+    pub(crate) link_weights: Option<Vec<u16>>,
+    // The routing region of the node, if any
+    pub(crate) region: Option<String>,
 }
 
 impl LinkState {
@@ -74,6 +80,16 @@ impl LinkState {
         };
         let n = rng.gen_range(MIN..=MAX);
         let links = (0..n).map(|_| rng.gen()).collect::<Vec<u64>>();
+        let link_weights = if rng.gen_bool(0.5) {
+            Some((0..n).map(|_| rng.gen()).collect::<Vec<u16>>())
+        } else {
+            None
+        };
+        let region = if rng.gen_bool(0.5) {
+            Some(format!("region{}", rng.gen::<u8>()))
+        } else {
+            None
+        };
 
         Self {
             psid,
@@ -82,6 +98,8 @@ impl LinkState {
             whatami,
             locators,
             links,
+            link_weights,
+            region,
         }
     }
 }
@@ -92,11 +110,26 @@ impl LinkState {
 // +-+-+-+---------+
 // ~ [link_states] ~
 // +---------------+
+// ~  [list_exts]  ~ if any
+// +---------------+
+//
+// The extensions follow the link states, so that the decoders which don't know them ignore them.
+// Each extension carries a list of (index of the link state, value) for the link states with
+// such a value.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub(crate) struct LinkStateList {
     pub(crate) link_states: Vec<LinkState>,
 }
 
+pub(crate) mod ext {
+    use zenoh_protocol::{common::ZExtZBuf, zextzbuf};
+
+    /// The weights of the links, in the same order: [(index, [weights])]
+    pub(crate) type LinkWeights = zextzbuf!(0x1, false);
+    /// The routing region of the node: [(index, region)]
+    pub(crate) type Region = zextzbuf!(0x2, false);
+}
+
 impl LinkStateList {
     #[cfg(feature = "test")]
     pub fn rand() -> Self {
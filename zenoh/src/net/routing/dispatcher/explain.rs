@@ -0,0 +1,331 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+use std::sync::Arc;
+
+use serde::Serialize;
+use zenoh_config::ZenohId;
+use zenoh_core::zread;
+use zenoh_protocol::{
+    core::{key_expr::keyexpr, WhatAmI, WireExpr, ZenohIdProto},
+    network::{
+        push,
+        request::{self, ext::QueryTarget},
+        NetworkMessage, Push, Request,
+    },
+    zenoh::{ConsolidationMode, PushBody, Put, Query, RequestBody},
+};
+use zenoh_result::{zerror, ZResult};
+
+use super::{
+    face::FaceState,
+    tables::{RoutingExpr, Tables, TablesLock},
+};
+use crate::{
+    api::key_expr::KeyExpr,
+    net::{
+        primitives::{McastMux, Mux},
+        routing::{hat::Sources, interceptor::InterceptorsChain},
+    },
+};
+
+/// A face of the routing tables.
+#[derive(Debug, Clone, Serialize)]
+pub struct RouteFace {
+    pub zid: ZenohId,
+    pub whatami: WhatAmI,
+}
+
+/// The verdict of an interceptor (access control, downsampling...) on a message.
+#[derive(Debug, Clone, Serialize)]
+pub struct InterceptorVerdict {
+    pub interceptor: &'static str,
+    /// `None` if the message was dropped by a previous interceptor of the chain.
+    pub allowed: Option<bool>,
+}
+
+/// A face a message would be forwarded to.
+#[derive(Debug, Clone, Serialize)]
+pub struct RouteDirection {
+    pub face: RouteFace,
+    /// Whether the routing hat lets the message go out on this face.
+    pub egress_filter: bool,
+    /// The verdicts of the egress interceptors of this face.
+    pub interceptors: Vec<InterceptorVerdict>,
+    /// Whether the queryables behind this face are complete (query routes only).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub complete: Option<bool>,
+    /// The distance of the queryables behind this face (query routes only).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub distance: Option<u16>,
+}
+
+impl RouteDirection {
+    /// Whether a message would actually be sent on this face.
+    pub fn is_forwarded(&self) -> bool {
+        self.egress_filter && self.interceptors.iter().all(|v| v.allowed == Some(true))
+    }
+}
+
+/// A subscriber or queryable declaration matching the explained key expression.
+#[derive(Debug, Clone, Serialize)]
+pub struct MatchingDeclaration {
+    pub key_expr: String,
+    pub routers: Vec<ZenohId>,
+    pub peers: Vec<ZenohId>,
+    pub clients: Vec<ZenohId>,
+}
+
+impl MatchingDeclaration {
+    fn new(key_expr: String, sources: Sources) -> Self {
+        let zids = |zids: Vec<ZenohIdProto>| zids.into_iter().map(ZenohId::from).collect();
+        MatchingDeclaration {
+            key_expr,
+            routers: zids(sources.routers),
+            peers: zids(sources.peers),
+            clients: zids(sources.clients),
+        }
+    }
+}
+
+/// The local view of a linkstate tree used to route messages from a given source.
+#[derive(Debug, Clone, Serialize)]
+pub struct RoutingTree {
+    /// The linkstate network the tree belongs to (routers or peers).
+    pub network: WhatAmI,
+    /// The root of the tree.
+    pub source: ZenohId,
+    /// The node messages are received from, or `None` at the root.
+    pub parent: Option<ZenohId>,
+    /// The nodes messages are forwarded to.
+    pub children: Vec<ZenohId>,
+}
+
+/// How the routing tables would route a message on a key expression from a given face.
+#[derive(Debug, Clone, Serialize)]
+pub struct RouteExplanation {
+    pub key_expr: String,
+    pub source: RouteFace,
+    /// Whether the routing hat accepts messages from the source face.
+    pub ingress_filter: bool,
+    /// The verdicts of the ingress interceptors of the source face on a put.
+    pub ingress_interceptors: Vec<InterceptorVerdict>,
+    /// The faces a put would be forwarded to.
+    pub data_route: Vec<RouteDirection>,
+    /// The faces a query would be forwarded to.
+    pub query_route: Vec<RouteDirection>,
+    pub subscribers: Vec<MatchingDeclaration>,
+    pub queryables: Vec<MatchingDeclaration>,
+    pub trees: Vec<RoutingTree>,
+}
+
+impl RouteExplanation {
+    /// Whether a put would pass the ingress checks of the source face.
+    pub fn is_accepted(&self) -> bool {
+        self.ingress_filter
+            && self
+                .ingress_interceptors
+                .iter()
+                .all(|v| v.allowed == Some(true))
+    }
+}
+
+fn probe_push(key_expr: &keyexpr) -> NetworkMessage {
+    Push {
+        wire_expr: WireExpr::from(key_expr).to_owned(),
+        ext_qos: push::ext::QoSType::DEFAULT,
+        ext_tstamp: None,
+        ext_nodeid: push::ext::NodeIdType::DEFAULT,
+        ext_redundancy: None,
+        payload: PushBody::Put(Put {
+            timestamp: None,
+            encoding: Default::default(),
+            ext_sinfo: None,
+            ext_attachment: None,
+            #[cfg(feature = "shared-memory")]
+            ext_shm: None,
+            ext_unknown: vec![],
+            payload: Default::default(),
+        }),
+    }
+    .into()
+}
+
+fn probe_request(key_expr: &keyexpr) -> NetworkMessage {
+    Request {
+        id: 0,
+        wire_expr: WireExpr::from(key_expr).to_owned(),
+        ext_qos: request::ext::QoSType::DEFAULT,
+        ext_tstamp: None,
+        ext_nodeid: request::ext::NodeIdType::DEFAULT,
+        ext_target: QueryTarget::DEFAULT,
+        ext_budget: None,
+        ext_timeout: None,
+        payload: RequestBody::Query(Query {
+            consolidation: ConsolidationMode::DEFAULT,
+            parameters: String::new(),
+            ext_sinfo: None,
+            ext_body: None,
+            ext_attachment: None,
+            ext_unknown: vec![],
+        }),
+    }
+    .into()
+}
+
+fn route_face(face: &FaceState) -> RouteFace {
+    RouteFace {
+        zid: face.zid.into(),
+        whatami: face.whatami,
+    }
+}
+
+fn egress_verdicts(
+    face: &FaceState,
+    msg: &NetworkMessage,
+    key_expr: &KeyExpr<'_>,
+) -> Vec<InterceptorVerdict> {
+    let chain: Option<&InterceptorsChain> = face
+        .primitives
+        .as_any()
+        .downcast_ref::<Mux>()
+        .map(|mux| &mux.interceptor)
+        .or_else(|| {
+            face.primitives
+                .as_any()
+                .downcast_ref::<McastMux>()
+                .map(|mux| &mux.interceptor)
+        });
+    verdicts(chain, msg, key_expr)
+}
+
+fn verdicts(
+    chain: Option<&InterceptorsChain>,
+    msg: &NetworkMessage,
+    key_expr: &KeyExpr<'_>,
+) -> Vec<InterceptorVerdict> {
+    chain
+        .map(|chain| {
+            chain
+                .verdicts(msg, key_expr)
+                .into_iter()
+                .map(|(interceptor, allowed)| InterceptorVerdict {
+                    interceptor,
+                    allowed,
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn source_face<'a>(
+    tables: &'a Tables,
+    source: Option<&ZenohIdProto>,
+) -> ZResult<&'a Arc<FaceState>> {
+    let source = source.unwrap_or(&tables.zid);
+    tables
+        .get_face(source)
+        .ok_or_else(|| zerror!("No face to {}", source).into())
+}
+
+/// Evaluates the data and query routes the tables would compute for a message on `key_expr`
+/// received from the face to `source` (a local session by default), without sending anything
+/// nor updating the state of the interceptors.
+pub(crate) fn explain_route(
+    tables_ref: &Arc<TablesLock>,
+    key_expr: &keyexpr,
+    source: Option<&ZenohIdProto>,
+) -> ZResult<RouteExplanation> {
+    let tables = zread!(tables_ref.tables);
+    let face = source_face(&tables, source)?;
+    let ke = KeyExpr::from(key_expr);
+    let push = probe_push(key_expr);
+    let request = probe_request(key_expr);
+
+    let prefix = tables.root_res.clone();
+    let mut expr = RoutingExpr::new(&prefix, key_expr.as_str());
+
+    // A message originated by the source node itself
+    let local_context = tables.hat_code.map_routing_context(&tables, face, 0);
+
+    let ingress_filter = tables.hat_code.ingress_filter(&tables, face, &mut expr);
+    let ingress_interceptors = verdicts(face.in_interceptors.as_deref(), &push, &ke);
+
+    let data_route =
+        tables
+            .hat_code
+            .compute_data_route(&tables, &mut expr, local_context, face.whatami);
+    let mut data_route = data_route
+        .values()
+        .filter(|(outface, _, _)| outface.id != face.id)
+        .map(|(outface, _, _)| RouteDirection {
+            face: route_face(outface),
+            egress_filter: tables
+                .hat_code
+                .egress_filter(&tables, face, outface, &mut expr),
+            interceptors: egress_verdicts(outface, &push, &ke),
+            complete: None,
+            distance: None,
+        })
+        .collect::<Vec<_>>();
+    data_route.sort_by_key(|d| d.face.zid);
+
+    let query_route =
+        tables
+            .hat_code
+            .compute_query_route(&tables, &mut expr, local_context, face.whatami);
+    let query_route = query_route
+        .iter()
+        .filter(|qabl| qabl.direction.0.id != face.id)
+        .map(|qabl| RouteDirection {
+            face: route_face(&qabl.direction.0),
+            egress_filter: tables.hat_code.egress_filter(
+                &tables,
+                face,
+                &qabl.direction.0,
+                &mut expr,
+            ),
+            interceptors: egress_verdicts(&qabl.direction.0, &request, &ke),
+            complete: qabl.info.map(|info| info.complete),
+            distance: qabl.info.map(|info| info.distance),
+        })
+        .collect();
+
+    let matching = |declarations: Vec<(Arc<super::resource::Resource>, Sources)>| {
+        declarations
+            .into_iter()
+            .filter(|(res, _)| {
+                keyexpr::new(&res.expr()).is_ok_and(|res_ke| res_ke.intersects(key_expr))
+            })
+            .map(|(res, sources)| MatchingDeclaration::new(res.expr(), sources))
+            .collect()
+    };
+    let subscribers = matching(tables.hat_code.get_subscriptions(&tables));
+    let queryables = matching(tables.hat_code.get_queryables(&tables));
+
+    let trees = tables
+        .hat_code
+        .routing_trees(&tables, local_context, face.whatami);
+
+    Ok(RouteExplanation {
+        key_expr: key_expr.to_string(),
+        source: route_face(face),
+        ingress_filter,
+        ingress_interceptors,
+        data_route,
+        query_route,
+        subscribers,
+        queryables,
+        trees,
+    })
+}
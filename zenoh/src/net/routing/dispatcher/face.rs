@@ -391,6 +391,11 @@ impl Primitives for Face {
         route_data(&self.tables, &self.state, msg, reliability);
     }
 
+    #[inline]
+    fn send_push_batch(&self, msgs: Vec<(Push, Reliability)>) {
+        route_data_batch(&self.tables, &self.state, msgs);
+    }
+
     fn send_request(&self, msg: Request) {
         match msg.payload {
             RequestBody::Query(_) => {
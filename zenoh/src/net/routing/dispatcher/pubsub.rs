@@ -18,6 +18,7 @@ use zenoh_protocol::{
     core::{key_expr::keyexpr, Reliability, WhatAmI, WireExpr},
     network::{
         declare::{ext, SubscriberId},
+        push::ext::RedundancyType,
         Push,
     },
     zenoh::PushBody,
@@ -26,7 +27,7 @@ use zenoh_sync::get_mut_unchecked;
 
 use super::{
     face::FaceState,
-    resource::{DataRoutes, Direction, Resource},
+    resource::{DataRoutes, Resource},
     tables::{NodeId, Route, RoutingExpr, Tables, TablesLock},
 };
 #[zenoh_macros::unstable]
@@ -388,13 +389,56 @@ macro_rules! inc_stats {
     };
 }
 
-pub fn route_data(
-    tables_ref: &Arc<TablesLock>,
+// Computes the data messages to send on the redundant routes computed by the routing hat
+#[allow(clippy::too_many_arguments)]
+fn route_redundant_data(
+    tables: &Tables,
+    face: &FaceState,
+    mut payload: PushBody,
+    ext_qos: ext::QoSType,
+    expr: &mut RoutingExpr,
+    routes: Vec<(Arc<Route>, RedundancyType)>,
+    #[cfg(feature = "stats")] admin: bool,
+    sends: &mut Vec<(Arc<FaceState>, Push)>,
+) {
+    treat_timestamp!(&tables.hlc, payload, tables.drop_future_timestamp);
+
+    for (route, redundancy) in &routes {
+        for (outface, key_expr, context) in route.values() {
+            if face.id != outface.id && tables.hat_code.egress_filter(tables, face, outface, expr) {
+                #[cfg(feature = "stats")]
+                if !admin {
+                    inc_stats!(face, tx, user, payload)
+                } else {
+                    inc_stats!(face, tx, admin, payload)
+                }
+
+                // Only the routers care about the redundancy of the data
+                let redundancy = (outface.whatami == WhatAmI::Router).then_some(*redundancy);
+                sends.push((
+                    outface.clone(),
+                    Push {
+                        wire_expr: key_expr.clone(),
+                        ext_qos,
+                        ext_tstamp: None,
+                        ext_nodeid: ext::NodeIdType { node_id: *context },
+                        ext_redundancy: redundancy,
+                        payload: payload.clone(),
+                    },
+                ));
+            }
+        }
+    }
+}
+
+// Computes the data messages to send to route the given data message.
+// The messages are sent by the caller once the tables lock is released.
+fn compute_data_sends(
+    tables: &Tables,
     face: &FaceState,
     mut msg: Push,
-    reliability: Reliability,
+    sends: &mut Vec<(Arc<FaceState>, Push)>,
 ) {
-    let tables = zread!(tables_ref.tables);
     match tables
         .get_mapping(face, &msg.wire_expr.scope, msg.wire_expr.mapping)
         .cloned()
@@ -417,10 +461,31 @@ pub fn route_data(
                 inc_stats!(face, rx, admin, msg.payload)
             }
 
-            if tables.hat_code.ingress_filter(&tables, face, &mut expr) {
+            if tables.hat_code.ingress_filter(tables, face, &mut expr) {
+                if let Some(routes) = tables.hat_code.compute_redundant_data_routes(
+                    tables,
+                    face,
+                    &mut expr,
+                    msg.ext_nodeid.node_id,
+                    msg.ext_redundancy,
+                ) {
+                    route_redundant_data(
+                        tables,
+                        face,
+                        msg.payload,
+                        msg.ext_qos,
+                        &mut expr,
+                        routes,
+                        #[cfg(feature = "stats")]
+                        admin,
+                        sends,
+                    );
+                    return;
+                }
+
                 let res = Resource::get_resource(&prefix, expr.suffix);
 
-                let route = get_data_route(&tables, face, &res, &mut expr, msg.ext_nodeid.node_id);
+                let route = get_data_route(tables, face, &res, &mut expr, msg.ext_nodeid.node_id);
 
                 if !route.is_empty() {
                     treat_timestamp!(&tables.hlc, msg.payload, tables.drop_future_timestamp);
@@ -429,9 +494,8 @@ pub fn route_data(
                         let (outface, key_expr, context) = route.values().next().unwrap();
                         if tables
                             .hat_code
-                            .egress_filter(&tables, face, outface, &mut expr)
+                            .egress_filter(tables, face, outface, &mut expr)
                         {
-                            drop(tables);
                             #[cfg(feature = "stats")]
                             if !admin {
                                 inc_stats!(face, tx, user, msg.payload)
@@ -439,50 +503,45 @@ pub fn route_data(
                                 inc_stats!(face, tx, admin, msg.payload)
                             }
 
-                            outface.primitives.send_push(
+                            sends.push((
+                                outface.clone(),
                                 Push {
                                     wire_expr: key_expr.into(),
                                     ext_qos: msg.ext_qos,
                                     ext_tstamp: msg.ext_tstamp,
                                     ext_nodeid: ext::NodeIdType { node_id: *context },
+                                    ext_redundancy: None,
                                     payload: msg.payload,
                                 },
-                                reliability,
-                            )
+                            ))
                         }
                     } else if tables.whatami == WhatAmI::Router {
-                        let route = route
-                            .values()
-                            .filter(|(outface, _key_expr, _context)| {
-                                tables
-                                    .hat_code
-                                    .egress_filter(&tables, face, outface, &mut expr)
-                            })
-                            .cloned()
-                            .collect::<Vec<Direction>>();
-
-                        drop(tables);
-                        for (outface, key_expr, context) in route {
-                            #[cfg(feature = "stats")]
-                            if !admin {
-                                inc_stats!(face, tx, user, msg.payload)
-                            } else {
-                                inc_stats!(face, tx, admin, msg.payload)
-                            }
+                        for (outface, key_expr, context) in route.values() {
+                            if tables
+                                .hat_code
+                                .egress_filter(tables, face, outface, &mut expr)
+                            {
+                                #[cfg(feature = "stats")]
+                                if !admin {
+                                    inc_stats!(face, tx, user, msg.payload)
+                                } else {
+                                    inc_stats!(face, tx, admin, msg.payload)
+                                }
 
-                            outface.primitives.send_push(
-                                Push {
-                                    wire_expr: key_expr,
-                                    ext_qos: msg.ext_qos,
-                                    ext_tstamp: None,
-                                    ext_nodeid: ext::NodeIdType { node_id: context },
-                                    payload: msg.payload.clone(),
-                                },
-                                reliability,
-                            )
+                                sends.push((
+                                    outface.clone(),
+                                    Push {
+                                        wire_expr: key_expr.into(),
+                                        ext_qos: msg.ext_qos,
+                                        ext_tstamp: None,
+                                        ext_nodeid: ext::NodeIdType { node_id: *context },
+                                        ext_redundancy: None,
+                                        payload: msg.payload.clone(),
+                                    },
+                                ))
+                            }
                         }
                     } else {
-                        drop(tables);
                         for (outface, key_expr, context) in route.values() {
                             if face.id != outface.id
                                 && match (face.mcast_group.as_ref(), outface.mcast_group.as_ref()) {
@@ -497,16 +556,17 @@ pub fn route_data(
                                     inc_stats!(face, tx, admin, msg.payload)
                                 }
 
-                                outface.primitives.send_push(
+                                sends.push((
+                                    outface.clone(),
                                     Push {
                                         wire_expr: key_expr.into(),
                                         ext_qos: msg.ext_qos,
                                         ext_tstamp: None,
                                         ext_nodeid: ext::NodeIdType { node_id: *context },
+                                        ext_redundancy: None,
                                         payload: msg.payload.clone(),
                                     },
-                                    reliability,
-                                )
+                                ))
                             }
                         }
                     }
@@ -522,3 +582,41 @@ pub fn route_data(
         }
     }
 }
+
+pub fn route_data(
+    tables_ref: &Arc<TablesLock>,
+    face: &FaceState,
+    msg: Push,
+    reliability: Reliability,
+) {
+    let mut sends = vec![];
+    compute_data_sends(&zread!(tables_ref.tables), face, msg, &mut sends);
+    for (outface, msg) in sends {
+        outface.primitives.send_push(msg, reliability)
+    }
+}
+
+/// Routes the given data messages under a single acquisition of the tables lock,
+/// then sends them in order.
+pub fn route_data_batch(
+    tables_ref: &Arc<TablesLock>,
+    face: &FaceState,
+    msgs: Vec<(Push, Reliability)>,
+) {
+    let mut sends = Vec::with_capacity(msgs.len());
+    {
+        let tables = zread!(tables_ref.tables);
+        let mut routed = vec![];
+        for (msg, reliability) in msgs {
+            compute_data_sends(&tables, face, msg, &mut routed);
+            sends.extend(
+                routed
+                    .drain(..)
+                    .map(|(outface, msg)| (outface, msg, reliability)),
+            );
+        }
+    }
+    for (outface, msg, reliability) in sends {
+        outface.primitives.send_push(msg, reliability)
+    }
+}
@@ -13,8 +13,8 @@
 //
 use std::{
     collections::HashMap,
-    sync::{Arc, Weak},
-    time::Duration,
+    sync::{atomic::Ordering, Arc, Mutex, Weak},
+    time::{Duration, Instant},
 };
 
 use async_trait::async_trait;
@@ -35,12 +35,13 @@ use zenoh_protocol::{
     },
     zenoh::{self, RequestBody, ResponseBody},
 };
+use zenoh_result::{bail, ZResult};
 use zenoh_sync::get_mut_unchecked;
 use zenoh_util::Timed;
 
 use super::{
     face::FaceState,
-    resource::{QueryRoute, QueryRoutes, QueryTargetQablSet, Resource},
+    resource::{Direction, QueryRoute, QueryRoutes, QueryTargetQablSet, Resource},
     tables::{NodeId, RoutingExpr, Tables, TablesLock},
 };
 use crate::net::routing::hat::{HatTrait, SendDeclare};
@@ -48,6 +49,29 @@ use crate::net::routing::hat::{HatTrait, SendDeclare};
 pub(crate) struct Query {
     src_face: Arc<FaceState>,
     src_qid: RequestId,
+    retry: Mutex<Option<QueryRetry>>,
+}
+
+/// The selection of the complete queryables that receive a `BestMatching` query.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum QueriesLoadBalancing {
+    BestMatching,
+    RoundRobin,
+    LeastOutstanding,
+}
+
+impl QueriesLoadBalancing {
+    pub(crate) fn from_config(value: &str) -> ZResult<Self> {
+        match value {
+            "best_matching" => Ok(QueriesLoadBalancing::BestMatching),
+            "round_robin" => Ok(QueriesLoadBalancing::RoundRobin),
+            "least_outstanding" => Ok(QueriesLoadBalancing::LeastOutstanding),
+            _ => bail!(
+                "Unknown queries load balancing `{}`: expected `best_matching`, `round_robin` or `least_outstanding`",
+                value
+            ),
+        }
+    }
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -299,6 +323,8 @@ fn insert_pending_query(outface: &mut Arc<FaceState>, query: Arc<Query>) -> Requ
     qid
 }
 
+// Computes the faces a query is sent to, along with the alternative faces it is forwarded to,
+// in turn, if it fails or is too slow (with failover or hedging only).
 #[inline]
 fn compute_final_route(
     tables: &Tables,
@@ -307,7 +333,7 @@ fn compute_final_route(
     expr: &mut RoutingExpr,
     target: &QueryTarget,
     query: Arc<Query>,
-) -> QueryRoute {
+) -> (QueryRoute, Vec<Direction>) {
     match target {
         QueryTarget::All => {
             let mut route = HashMap::new();
@@ -323,7 +349,7 @@ fn compute_final_route(
                     });
                 }
             }
-            route
+            (route, vec![])
         }
         QueryTarget::AllComplete => {
             let mut route = HashMap::new();
@@ -340,9 +366,24 @@ fn compute_final_route(
                     });
                 }
             }
-            route
+            (route, vec![])
         }
         QueryTarget::BestMatching => {
+            let retry =
+                tables.queries_failover_timeout.is_some() || tables.queries_hedge_delay.is_some();
+            if retry || tables.queries_load_balancing != QueriesLoadBalancing::BestMatching {
+                let mut candidates = balanced_candidates(tables, qabls, src_face, expr);
+                if !candidates.is_empty() {
+                    let mut direction = candidates.remove(0);
+                    let qid = insert_pending_query(&mut direction.0, query);
+                    let mut route = HashMap::new();
+                    route.insert(direction.0.id, (direction, qid));
+                    if !retry {
+                        candidates.clear();
+                    }
+                    return (route, candidates);
+                }
+            }
             if let Some(qabl) = qabls.iter().find(|qabl| {
                 qabl.direction.0.id != src_face.id && qabl.info.is_some_and(|info| info.complete)
             }) {
@@ -352,7 +393,7 @@ fn compute_final_route(
                 let qid = insert_pending_query(&mut direction.0, query);
                 route.insert(direction.0.id, (direction, qid));
 
-                route
+                (route, vec![])
             } else {
                 compute_final_route(tables, qabls, src_face, expr, &QueryTarget::All, query)
             }
@@ -360,12 +401,298 @@ fn compute_final_route(
     }
 }
 
+// Returns the faces of the nearest complete queryables in the order they should receive a
+// query according to the load balancing policy, the first one being selected and the others
+// being the failover candidates.
+fn balanced_candidates(
+    tables: &Tables,
+    qabls: &Arc<QueryTargetQablSet>,
+    src_face: &Arc<FaceState>,
+    expr: &mut RoutingExpr,
+) -> Vec<Direction> {
+    let mut candidates: Vec<(Direction, u16)> = vec![];
+    for qabl in qabls.iter() {
+        if let Some(info) = qabl.info.filter(|info| info.complete) {
+            if qabl.direction.0.id != src_face.id
+                && !candidates
+                    .iter()
+                    .any(|(c, _)| c.0.id == qabl.direction.0.id)
+                && tables
+                    .hat_code
+                    .egress_filter(tables, src_face, &qabl.direction.0, expr)
+            {
+                candidates.push((qabl.direction.clone(), info.distance));
+            }
+        }
+    }
+    // The queries are only balanced between the queryables at the minimum distance
+    let min_distance = candidates.iter().map(|(_, distance)| *distance).min();
+    let mut candidates: Vec<Direction> = candidates
+        .into_iter()
+        .filter(|(_, distance)| Some(*distance) == min_distance)
+        .map(|(direction, _)| direction)
+        .collect();
+    if candidates.len() > 1 {
+        let first = match tables.queries_load_balancing {
+            QueriesLoadBalancing::RoundRobin => {
+                tables.queries_round_robin.fetch_add(1, Ordering::Relaxed) % candidates.len()
+            }
+            QueriesLoadBalancing::LeastOutstanding => candidates
+                .iter()
+                .enumerate()
+                .min_by_key(|(_, c)| c.0.pending_queries.len())
+                .map(|(idx, _)| idx)
+                .unwrap_or(0),
+            QueriesLoadBalancing::BestMatching => 0,
+        };
+        candidates.rotate_left(first);
+    }
+    candidates
+}
+
+// The request and the alternative faces a query is forwarded to when its queryable times out,
+// closes or is too slow to reply, along with the requests sent so far
+struct QueryRetry {
+    candidates: Vec<Direction>,
+    ext_qos: ext::QoSType,
+    ext_tstamp: Option<ext::TimestampType>,
+    ext_target: QueryTarget,
+    ext_budget: Option<BudgetType>,
+    ext_timeout: Option<TimeoutType>,
+    body: RequestBody,
+    failover_timeout: Option<Duration>,
+    deadline: Instant,
+    requests: Vec<(Weak<FaceState>, RequestId)>,
+    // The request whose responses are forwarded to the querier, the first one to respond
+    responder: Option<(usize, RequestId)>,
+}
+
+impl QueryRetry {
+    // The time to wait for the final reply of a request
+    fn timeout(&self) -> Duration {
+        let remaining = self.deadline.saturating_duration_since(Instant::now());
+        match self.failover_timeout {
+            Some(failover_timeout) if !self.candidates.is_empty() => {
+                failover_timeout.min(remaining)
+            }
+            _ => remaining,
+        }
+    }
+
+    fn is_request(
+        face: &Weak<FaceState>,
+        qid: RequestId,
+        other: &(Weak<FaceState>, RequestId),
+    ) -> bool {
+        other.1 == qid && Weak::ptr_eq(&other.0, face)
+    }
+}
+
+impl Query {
+    // Returns whether the responses received from the face `face_id` to request `qid`
+    // should be forwarded to the querier, only the first request of a hedged query to
+    // respond being forwarded
+    fn accepts_response(&self, face_id: usize, qid: RequestId) -> bool {
+        match zlock!(self.retry).as_mut() {
+            Some(retry) => *retry.responder.get_or_insert((face_id, qid)) == (face_id, qid),
+            None => true,
+        }
+    }
+
+    fn has_retry(&self) -> bool {
+        zlock!(self.retry).is_some()
+    }
+
+    // Ends the other requests of the query when the request `qid` on `face` completes. A final
+    // reply answers the query even without any response, a complete queryable having nothing
+    // to reply, so the query is not forwarded to the other candidates.
+    fn complete_request(&self, face: &Arc<FaceState>, qid: RequestId) {
+        let mut retry = zlock!(self.retry);
+        let Some(retry) = retry.as_mut() else {
+            return;
+        };
+        let weak = Arc::downgrade(face);
+        retry
+            .requests
+            .retain(|request| !QueryRetry::is_request(&weak, qid, request));
+        if *retry.responder.get_or_insert((face.id, qid)) != (face.id, qid) {
+            // Another request responded first and is still forwarded to the querier
+            return;
+        }
+        retry.candidates.clear();
+        for (mut other, other_qid) in retry
+            .requests
+            .drain(..)
+            .filter_map(|(f, q)| f.upgrade().map(|f| (f, q)))
+        {
+            if let Some((_, cancellation_token)) = get_mut_unchecked(&mut other)
+                .pending_queries
+                .remove(&other_qid)
+            {
+                cancellation_token.cancel();
+            }
+        }
+    }
+}
+
+// Withdraws the request `qid` sent to `face` of a query with alternative queryables and
+// forwards the query to the next alternative if no other request of the query is pending.
+// Returns false if the query has no alternative, in which case the request is left pending.
+fn retry_query(tables_ref: &Arc<TablesLock>, face: &mut Arc<FaceState>, qid: RequestId) -> bool {
+    let tables = zread!(tables_ref.tables);
+    let queries_lock = zwrite!(tables_ref.queries_lock);
+    let Some(query) = face
+        .pending_queries
+        .get(&qid)
+        .map(|(query, _)| query.clone())
+    else {
+        // The query was finalized in the meantime
+        return true;
+    };
+    let mut guard = zlock!(query.retry);
+    let Some(retry) = guard.as_mut() else {
+        return false;
+    };
+    if retry.responder == Some((face.id, qid)) {
+        // The responses already forwarded to the querier can't be completed by another queryable
+        return false;
+    }
+    let weak = Arc::downgrade(face);
+    let pending = retry
+        .requests
+        .iter()
+        .any(|request| !QueryRetry::is_request(&weak, qid, request));
+    let next = if pending {
+        None
+    } else if Instant::now() >= retry.deadline {
+        // The query timed out
+        return false;
+    } else {
+        match next_candidate(&tables, retry) {
+            Some(next) => Some(next),
+            None => return false,
+        }
+    };
+    retry
+        .requests
+        .retain(|request| !QueryRetry::is_request(&weak, qid, request));
+    if let Some((_, cancellation_token)) = get_mut_unchecked(face).pending_queries.remove(&qid) {
+        cancellation_token.cancel();
+    }
+    if let Some(next) = next {
+        let (outface, request, timeout) = send_retry(retry, &query, next);
+        drop(guard);
+        drop(queries_lock);
+        drop(tables);
+        tracing::debug!(
+            "Query {}:{} failed on {}:{}, forward it to {}:{}",
+            query.src_face,
+            query.src_qid,
+            face,
+            qid,
+            outface,
+            request.id
+        );
+        QueryCleanup::spawn_query_clean_up_task(&outface, tables_ref, request.id, timeout);
+        outface.primitives.send_request(request);
+    }
+    true
+}
+
+// Forwards a query that didn't get any response yet to its next alternative queryable
+fn hedge_query(tables_ref: &Arc<TablesLock>, query: &Arc<Query>, delay: Duration) {
+    let tables = zread!(tables_ref.tables);
+    let queries_lock = zwrite!(tables_ref.queries_lock);
+    let mut guard = zlock!(query.retry);
+    let Some(retry) = guard.as_mut() else {
+        return;
+    };
+    if retry.responder.is_some() || retry.requests.is_empty() {
+        return;
+    }
+    if let Some(next) = next_candidate(&tables, retry) {
+        let (outface, request, timeout) = send_retry(retry, query, next);
+        let hedge = !retry.candidates.is_empty();
+        drop(guard);
+        drop(queries_lock);
+        drop(tables);
+        tracing::debug!(
+            "Query {}:{} got no response after {:?}, hedge it to {}:{}",
+            query.src_face,
+            query.src_qid,
+            delay,
+            outface,
+            request.id
+        );
+        QueryCleanup::spawn_query_clean_up_task(&outface, tables_ref, request.id, timeout);
+        outface.primitives.send_request(request);
+        if hedge {
+            spawn_query_hedge_task(tables_ref, query, delay);
+        }
+    }
+}
+
+fn spawn_query_hedge_task(tables_ref: &Arc<TablesLock>, query: &Arc<Query>, delay: Duration) {
+    let tables_ref = tables_ref.clone();
+    let weak = Arc::downgrade(query);
+    query
+        .src_face
+        .task_controller
+        .spawn_with_rt(zenoh_runtime::ZRuntime::Net, async move {
+            tokio::time::sleep(delay).await;
+            if let Some(query) = weak.upgrade() {
+                hedge_query(&tables_ref, &query, delay);
+            }
+        });
+}
+
+// Pops the next alternative queryable still connected
+fn next_candidate(tables: &Tables, retry: &mut QueryRetry) -> Option<Direction> {
+    while !retry.candidates.is_empty() {
+        let direction = retry.candidates.remove(0);
+        if tables
+            .faces
+            .get(&direction.0.id)
+            .is_some_and(|f| Arc::ptr_eq(f, &direction.0))
+        {
+            return Some(direction);
+        }
+    }
+    None
+}
+
+// Registers a new request of the query to the given queryable, returning its face, the
+// request to send and its timeout
+fn send_retry(
+    retry: &mut QueryRetry,
+    query: &Arc<Query>,
+    direction: Direction,
+) -> (Arc<FaceState>, Request, Duration) {
+    let (mut outface, key_expr, context) = direction;
+    let outqid = insert_pending_query(&mut outface, query.clone());
+    retry.requests.push((Arc::downgrade(&outface), outqid));
+    let request = Request {
+        id: outqid,
+        wire_expr: key_expr,
+        ext_qos: retry.ext_qos,
+        ext_tstamp: retry.ext_tstamp,
+        ext_nodeid: ext::NodeIdType { node_id: context },
+        ext_target: retry.ext_target,
+        ext_budget: retry.ext_budget,
+        ext_timeout: retry.ext_timeout,
+        payload: retry.body.clone(),
+    };
+    (outface, request, retry.timeout())
+}
+
 #[derive(Clone)]
 struct QueryCleanup {
     tables: Arc<TablesLock>,
     face: Weak<FaceState>,
     qid: RequestId,
     timeout: Duration,
+    // Whether the query has alternative queryables to forward it to on timeout
+    retry: bool,
 }
 
 impl QueryCleanup {
@@ -375,13 +702,14 @@ impl QueryCleanup {
         qid: u32,
         timeout: Duration,
     ) {
-        let mut cleanup = QueryCleanup {
-            tables: tables_ref.clone(),
-            face: Arc::downgrade(face),
-            qid,
-            timeout,
-        };
-        if let Some((_, cancellation_token)) = face.pending_queries.get(&qid) {
+        if let Some((query, cancellation_token)) = face.pending_queries.get(&qid) {
+            let mut cleanup = QueryCleanup {
+                tables: tables_ref.clone(),
+                face: Arc::downgrade(face),
+                qid,
+                timeout,
+                retry: query.has_retry(),
+            };
             let c_cancellation_token = cancellation_token.clone();
             face.task_controller
                 .spawn_with_rt(zenoh_runtime::ZRuntime::Net, async move {
@@ -398,6 +726,9 @@ impl QueryCleanup {
 impl Timed for QueryCleanup {
     async fn run(&mut self) {
         if let Some(mut face) = self.face.upgrade() {
+            if self.retry && retry_query(&self.tables, &mut face, self.qid) {
+                return;
+            }
             let ext_respid = Some(response::ext::ResponderIdType {
                 zid: face.zid,
                 eid: 0,
@@ -583,12 +914,41 @@ pub fn route_query(
                 let query = Arc::new(Query {
                     src_face: face.clone(),
                     src_qid: qid,
+                    retry: Mutex::new(None),
                 });
 
                 let queries_lock = zwrite!(tables_ref.queries_lock);
-                let route =
-                    compute_final_route(&rtables, &route, face, &mut expr, &ext_target, query);
-                let timeout = ext_timeout.unwrap_or(rtables.queries_default_timeout);
+                let (route, candidates) = compute_final_route(
+                    &rtables,
+                    &route,
+                    face,
+                    &mut expr,
+                    &ext_target,
+                    query.clone(),
+                );
+                let mut timeout = ext_timeout.unwrap_or(rtables.queries_default_timeout);
+                let mut hedge_delay = None;
+                if !candidates.is_empty() {
+                    let retry = QueryRetry {
+                        candidates,
+                        ext_qos,
+                        ext_tstamp,
+                        ext_target,
+                        ext_budget,
+                        ext_timeout,
+                        body: body.clone(),
+                        failover_timeout: rtables.queries_failover_timeout,
+                        deadline: Instant::now() + timeout,
+                        requests: route
+                            .values()
+                            .map(|((outface, _, _), outqid)| (Arc::downgrade(outface), *outqid))
+                            .collect(),
+                        responder: None,
+                    };
+                    timeout = retry.timeout();
+                    hedge_delay = rtables.queries_hedge_delay;
+                    *zlock!(query.retry) = Some(retry);
+                }
                 drop(queries_lock);
                 drop(rtables);
 
@@ -604,6 +964,11 @@ pub fn route_query(
                         ext_tstamp: None,
                     });
                 } else {
+                    if let Some(delay) = hedge_delay {
+                        spawn_query_hedge_task(tables_ref, &query, delay);
+                    }
+                    // The query is finalized when its last pending request is
+                    drop(query);
                     for ((outface, key_expr, context), outqid) in route.values() {
                         QueryCleanup::spawn_query_clean_up_task(
                             outface, tables_ref, *outqid, timeout,
@@ -683,6 +1048,15 @@ pub(crate) fn route_send_response(
     }
 
     match face.pending_queries.get(&qid) {
+        Some((query, _)) if !query.accepts_response(face.id, qid) => {
+            tracing::trace!(
+                "Drop reply {}:{} for {}:{}: another queryable responds",
+                face,
+                qid,
+                query.src_face,
+                query.src_qid
+            );
+        }
         Some((query, _)) => {
             drop(queries_lock);
 
@@ -719,6 +1093,7 @@ pub(crate) fn route_send_response_final(
     let queries_lock = zwrite!(tables_ref.queries_lock);
     match get_mut_unchecked(face).pending_queries.remove(&qid) {
         Some(query) => {
+            query.0.complete_request(face, qid);
             drop(queries_lock);
             tracing::debug!(
                 "Received final reply {}:{} for {}:{}",
@@ -733,7 +1108,17 @@ pub(crate) fn route_send_response_final(
     }
 }
 
-pub(crate) fn finalize_pending_queries(tables_ref: &TablesLock, face: &mut Arc<FaceState>) {
+pub(crate) fn finalize_pending_queries(tables_ref: &Arc<TablesLock>, face: &mut Arc<FaceState>) {
+    // The queries with alternative queryables are forwarded to them
+    let qids = face
+        .pending_queries
+        .iter()
+        .filter(|(_, (query, _))| query.has_retry())
+        .map(|(qid, _)| *qid)
+        .collect::<Vec<_>>();
+    for qid in qids {
+        retry_query(tables_ref, face, qid);
+    }
     let queries_lock = zwrite!(tables_ref.queries_lock);
     for (_, query) in get_mut_unchecked(face).pending_queries.drain() {
         finalize_pending_query(query);
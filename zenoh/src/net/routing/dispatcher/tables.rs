@@ -14,7 +14,7 @@
 use std::{
     any::Any,
     collections::HashMap,
-    sync::{Arc, Mutex, RwLock},
+    sync::{atomic::AtomicUsize, Arc, Mutex, RwLock},
     time::Duration,
 };
 
@@ -71,6 +71,10 @@ pub struct Tables {
     pub(crate) hlc: Option<Arc<HLC>>,
     pub(crate) drop_future_timestamp: bool,
     pub(crate) queries_default_timeout: Duration,
+    pub(crate) queries_load_balancing: QueriesLoadBalancing,
+    pub(crate) queries_failover_timeout: Option<Duration>,
+    pub(crate) queries_hedge_delay: Option<Duration>,
+    pub(crate) queries_round_robin: AtomicUsize,
     pub(crate) root_res: Arc<Resource>,
     pub(crate) faces: HashMap<usize, Arc<FaceState>>,
     pub(crate) mcast_groups: Vec<Arc<FaceState>>,
@@ -93,6 +97,24 @@ impl Tables {
             unwrap_or_default!(config.routing().router().peers_failover_brokering());
         let queries_default_timeout =
             Duration::from_millis(unwrap_or_default!(config.queries_default_timeout()));
+        let queries_load_balancing = QueriesLoadBalancing::from_config(
+            config
+                .routing()
+                .queries()
+                .load_balancing()
+                .as_deref()
+                .unwrap_or(zenoh_config::defaults::routing::queries::load_balancing),
+        )?;
+        let queries_failover_timeout = config
+            .routing()
+            .queries()
+            .failover_timeout()
+            .map(Duration::from_millis);
+        let queries_hedge_delay = config
+            .routing()
+            .queries()
+            .hedge_delay()
+            .map(Duration::from_millis);
         let hat_code = hat::new_hat(whatami, config);
         Ok(Tables {
             zid,
@@ -102,6 +124,10 @@ impl Tables {
             hlc,
             drop_future_timestamp,
             queries_default_timeout,
+            queries_load_balancing,
+            queries_failover_timeout,
+            queries_hedge_delay,
+            queries_round_robin: AtomicUsize::new(0),
             root_res: Resource::root(),
             faces: HashMap::new(),
             mcast_groups: vec![],
@@ -25,10 +25,13 @@ use std::{
 
 use token::{token_new_face, undeclare_simple_token};
 use zenoh_config::WhatAmI;
-use zenoh_protocol::network::{
-    declare::{queryable::ext::QueryableInfoType, QueryableId, SubscriberId, TokenId},
-    interest::InterestId,
-    Oam,
+use zenoh_protocol::{
+    core::ZenohIdProto,
+    network::{
+        declare::{queryable::ext::QueryableInfoType, QueryableId, SubscriberId, TokenId},
+        interest::InterestId,
+        Oam,
+    },
 };
 use zenoh_result::ZResult;
 use zenoh_sync::get_mut_unchecked;
@@ -41,10 +44,11 @@ use self::{
 };
 use super::{
     super::dispatcher::{
+        explain::RoutingTree,
         face::FaceState,
         tables::{NodeId, Resource, RoutingExpr, Tables, TablesLock},
     },
-    HatBaseTrait, HatTrait, SendDeclare,
+    HatBaseTrait, HatTrait, SendDeclare, Snapshot,
 };
 use crate::net::{
     routing::{
@@ -284,6 +288,32 @@ impl HatBaseTrait for HatCode {
     fn info(&self, _tables: &Tables, _kind: WhatAmI) -> String {
         "graph {}".to_string()
     }
+
+    fn routing_trees(
+        &self,
+        _tables: &Tables,
+        _source: NodeId,
+        _source_type: WhatAmI,
+    ) -> Vec<RoutingTree> {
+        vec![]
+    }
+
+    fn update_locators(&self, _tables: &mut Tables) {}
+
+    fn set_link_weight(
+        &self,
+        _tables: &mut Tables,
+        _tables_ref: &Arc<TablesLock>,
+        _zid: &ZenohIdProto,
+        _weight: u16,
+    ) {
+    }
+
+    fn update_rtt_weights(&self, _tables: &mut Tables, _tables_ref: &Arc<TablesLock>) {}
+
+    fn snapshot(&self, _tables: &Tables) -> Option<Snapshot> {
+        None
+    }
 }
 
 struct HatContext {}
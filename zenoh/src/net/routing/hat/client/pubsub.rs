@@ -19,9 +19,12 @@ use std::{
 
 use zenoh_protocol::{
     core::{key_expr::OwnedKeyExpr, WhatAmI},
-    network::declare::{
-        common::ext::WireExprType, ext, Declare, DeclareBody, DeclareSubscriber, SubscriberId,
-        UndeclareSubscriber,
+    network::{
+        declare::{
+            common::ext::WireExprType, ext, Declare, DeclareBody, DeclareSubscriber, SubscriberId,
+            UndeclareSubscriber,
+        },
+        push::ext::RedundancyType,
     },
 };
 use zenoh_sync::get_mut_unchecked;
@@ -401,6 +404,17 @@ impl HatPubSubTrait for HatCode {
         Arc::new(route)
     }
 
+    fn compute_redundant_data_routes(
+        &self,
+        _tables: &Tables,
+        _face: &FaceState,
+        _expr: &mut RoutingExpr,
+        _routing_context: NodeId,
+        _redundancy: Option<RedundancyType>,
+    ) -> Option<Vec<(Arc<Route>, RedundancyType)>> {
+        None
+    }
+
     fn get_data_routes_entries(&self, _tables: &Tables) -> RoutesIndexes {
         get_routes_entries()
     }
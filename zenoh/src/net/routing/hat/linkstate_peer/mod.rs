@@ -47,10 +47,11 @@ use self::{
 };
 use super::{
     super::dispatcher::{
+        explain::RoutingTree,
         face::FaceState,
         tables::{NodeId, Resource, RoutingExpr, Tables, TablesLock},
     },
-    HatBaseTrait, HatTrait, SendDeclare,
+    HatBaseTrait, HatTrait, SendDeclare, Snapshot,
 };
 use crate::net::{
     codec::Zenoh080Routing,
@@ -195,6 +196,9 @@ impl HatBaseTrait for HatCode {
             unwrap_or_default!(config.routing().peer().mode()) == *"linkstate";
         let router_peers_failover_brokering =
             unwrap_or_default!(config.routing().router().peers_failover_brokering());
+        let rtt_weights = unwrap_or_default!(config.routing().linkstate().rtt_weights());
+        let weight_hysteresis =
+            unwrap_or_default!(config.routing().linkstate().weight_hysteresis());
         drop(config_guard);
 
         hat_mut!(tables).linkstatepeers_net = Some(Network::new(
@@ -206,6 +210,8 @@ impl HatBaseTrait for HatCode {
             gossip,
             gossip_multihop,
             autoconnect,
+            rtt_weights,
+            weight_hysteresis,
         ));
     }
 
@@ -468,6 +474,62 @@ impl HatBaseTrait for HatCode {
             _ => "graph {}".to_string(),
         }
     }
+
+    fn routing_trees(
+        &self,
+        tables: &Tables,
+        source: NodeId,
+        source_type: WhatAmI,
+    ) -> Vec<RoutingTree> {
+        let net = hat!(tables).linkstatepeers_net.as_ref();
+        net.and_then(|net| {
+            let source = match source_type {
+                WhatAmI::Peer => source,
+                _ => net.idx.index() as NodeId,
+            };
+            net.routing_tree(source, WhatAmI::Peer)
+        })
+        .into_iter()
+        .collect()
+    }
+
+    fn update_locators(&self, tables: &mut Tables) {
+        if let Some(net) = hat_mut!(tables).linkstatepeers_net.as_mut() {
+            net.update_locators();
+        }
+    }
+
+    fn set_link_weight(
+        &self,
+        tables: &mut Tables,
+        tables_ref: &Arc<TablesLock>,
+        zid: &ZenohIdProto,
+        weight: u16,
+    ) {
+        let hat = hat_mut!(tables);
+        if hat
+            .linkstatepeers_net
+            .as_mut()
+            .is_some_and(|net| net.set_link_weight(zid, weight))
+        {
+            hat.schedule_compute_trees(tables_ref.clone());
+        }
+    }
+
+    fn update_rtt_weights(&self, tables: &mut Tables, tables_ref: &Arc<TablesLock>) {
+        let hat = hat_mut!(tables);
+        if hat
+            .linkstatepeers_net
+            .as_mut()
+            .is_some_and(|net| net.update_rtt_weights())
+        {
+            hat.schedule_compute_trees(tables_ref.clone());
+        }
+    }
+
+    fn snapshot(&self, _tables: &Tables) -> Option<Snapshot> {
+        None
+    }
 }
 
 struct HatContext {
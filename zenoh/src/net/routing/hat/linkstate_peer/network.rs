@@ -11,7 +11,10 @@
 // Contributors:
 //   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
 //
-use std::convert::TryInto;
+use std::{
+    collections::{HashMap, HashSet},
+    convert::TryInto,
+};
 
 use petgraph::{
     graph::NodeIndex,
@@ -35,15 +38,23 @@ use zenoh_transport::unicast::TransportUnicast;
 use crate::net::{
     codec::Zenoh080Routing,
     protocol::linkstate::{LinkState, LinkStateList},
-    routing::dispatcher::tables::NodeId,
+    routing::{
+        dispatcher::{explain::RoutingTree, tables::NodeId},
+        hat::{exceeds_hysteresis, send_updated_locators, Details},
+    },
     runtime::{Runtime, WeakRuntime},
 };
 
-#[derive(Clone)]
-struct Details {
-    zid: bool,
-    locators: bool,
-    links: bool,
+// The weight of the links with no advertised weight
+const DEFAULT_LINK_WEIGHT: u16 = 100;
+
+// The weight of a link derived from its smallest measured round-trip time, in units of 10 µs
+fn rtt_weight(transport: &TransportUnicast) -> Option<u16> {
+    transport
+        .get_rtt()
+        .ok()
+        .flatten()
+        .map(|rtt| (rtt.as_micros() / 10).clamp(1, u16::MAX as u128) as u16)
 }
 
 #[derive(Clone)]
@@ -53,6 +64,7 @@ pub(super) struct Node {
     pub(super) locators: Option<Vec<Locator>>,
     pub(super) sn: u64,
     pub(super) links: Vec<ZenohIdProto>,
+    pub(super) link_weights: HashMap<ZenohIdProto, u16>,
 }
 
 impl std::fmt::Debug for Node {
@@ -120,6 +132,10 @@ pub(super) struct Network {
     pub(super) gossip: bool,
     pub(super) gossip_multihop: bool,
     pub(super) autoconnect: WhatAmIMatcher,
+    pub(super) rtt_weights: bool,
+    pub(super) weight_hysteresis: f64,
+    // The links whose weight is configured and not derived from their round-trip time
+    pub(super) cost_links: HashSet<ZenohIdProto>,
     pub(super) idx: NodeIndex,
     pub(super) links: VecMap<Link>,
     pub(super) trees: Vec<Tree>,
@@ -139,6 +155,8 @@ impl Network {
         gossip: bool,
         gossip_multihop: bool,
         autoconnect: WhatAmIMatcher,
+        rtt_weights: bool,
+        weight_hysteresis: f64,
     ) -> Self {
         let mut graph = petgraph::stable_graph::StableGraph::default();
         tracing::debug!("{} Add node (self) {}", name, zid);
@@ -148,6 +166,7 @@ impl Network {
             locators: None,
             sn: 1,
             links: vec![],
+            link_weights: HashMap::new(),
         });
         Network {
             name,
@@ -156,6 +175,9 @@ impl Network {
             gossip,
             gossip_multihop,
             autoconnect,
+            rtt_weights,
+            weight_hysteresis,
+            cost_links: HashSet::new(),
             idx,
             links: VecMap::new(),
             trees: vec![Tree {
@@ -169,10 +191,26 @@ impl Network {
         }
     }
 
+    pub(super) fn routing_tree(&self, source: NodeId, network: WhatAmI) -> Option<RoutingTree> {
+        let tree = self.trees.get(source as usize)?;
+        let zid = |idx: NodeIndex| self.graph.node_weight(idx).map(|node| node.zid.into());
+        Some(RoutingTree {
+            network,
+            source: zid(NodeIndex::new(source as usize))?,
+            parent: tree.parent.and_then(zid),
+            children: tree.children.iter().filter_map(|idx| zid(*idx)).collect(),
+        })
+    }
+
     pub(super) fn dot(&self) -> String {
         std::format!(
             "{:?}",
-            petgraph::dot::Dot::with_config(&self.graph, &[petgraph::dot::Config::EdgeNoLabel])
+            petgraph::dot::Dot::with_attr_getters(
+                &self.graph,
+                &[petgraph::dot::Config::EdgeNoLabel],
+                &|_, edge| format!("label = \"{}\"", edge.weight().trunc()),
+                &|_, _| String::new(),
+            )
         )
     }
 
@@ -226,13 +264,17 @@ impl Network {
     }
 
     fn make_link_state(&self, idx: NodeIndex, details: Details) -> LinkState {
-        let links = if details.links {
-            self.graph[idx]
+        let (links, link_weights) = if details.links {
+            let node = &self.graph[idx];
+            let (links, weights): (Vec<u64>, Vec<u16>) = node
                 .links
                 .iter()
                 .filter_map(|zid| {
                     if let Some(idx2) = self.get_idx(zid) {
-                        Some(idx2.index().try_into().unwrap())
+                        Some((
+                            u64::try_from(idx2.index()).unwrap(),
+                            node.link_weights.get(zid).copied().unwrap_or(0),
+                        ))
                     } else {
                         tracing::error!(
                             "{} Internal error building link state: cannot get index of {}",
@@ -242,9 +284,12 @@ impl Network {
                         None
                     }
                 })
-                .collect()
+                .unzip();
+            // Only advertise the weights when some are set
+            let weights = weights.iter().any(|w| *w != 0).then_some(weights);
+            (links, weights)
         } else {
-            vec![]
+            (vec![], None)
         };
         LinkState {
             psid: idx.index().try_into().unwrap(),
@@ -265,6 +310,8 @@ impl Network {
                 None
             },
             links,
+            link_weights,
+            region: None,
         }
     }
 
@@ -330,7 +377,10 @@ impl Network {
                 }))
     }
 
-    fn update_edge(&mut self, idx1: NodeIndex, idx2: NodeIndex) {
+    // Adds or updates the edge between the two nodes and returns true if its weight changed.
+    // The weight is the highest of the weights advertised by both ends of the link,
+    // slightly perturbed to break ties.
+    fn update_edge(&mut self, idx1: NodeIndex, idx2: NodeIndex) -> bool {
         use std::hash::Hasher;
         let mut hasher = std::collections::hash_map::DefaultHasher::default();
         if self.graph[idx1].zid > self.graph[idx2].zid {
@@ -340,8 +390,24 @@ impl Network {
             hasher.write(&self.graph[idx1].zid.to_le_bytes());
             hasher.write(&self.graph[idx2].zid.to_le_bytes());
         }
-        let weight = 100.0 + ((hasher.finish() as u32) as f64) / u32::MAX as f64;
+        let advertised = self.graph[idx1]
+            .link_weights
+            .get(&self.graph[idx2].zid)
+            .max(self.graph[idx2].link_weights.get(&self.graph[idx1].zid))
+            .copied()
+            .unwrap_or(DEFAULT_LINK_WEIGHT);
+        let weight = advertised as f64 + ((hasher.finish() as u32) as f64) / u32::MAX as f64;
+        if let Some(old) = self
+            .graph
+            .find_edge(idx1, idx2)
+            .map(|edge| self.graph[edge])
+        {
+            if !exceeds_hysteresis(old, weight, self.weight_hysteresis) {
+                return false;
+            }
+        }
         self.graph.update_edge(idx1, idx2, weight);
+        true
     }
 
     pub(super) fn link_states(
@@ -385,6 +451,7 @@ impl Network {
                         link_state.locators,
                         link_state.sn,
                         link_state.links,
+                        link_state.link_weights,
                     ))
                 } else {
                     match src_link.get_zid(&link_state.psid) {
@@ -394,6 +461,7 @@ impl Network {
                             link_state.locators,
                             link_state.sn,
                             link_state.links,
+                            link_state.link_weights,
                         )),
                         None => {
                             tracing::error!(
@@ -412,11 +480,18 @@ impl Network {
         let src_link = self.get_link_from_zid(&src).unwrap();
         let link_states = link_states
             .into_iter()
-            .map(|(zid, wai, locs, sn, links)| {
+            .map(|(zid, wai, locs, sn, links, weights)| {
+                let mut link_weights = HashMap::new();
                 let links: Vec<ZenohIdProto> = links
                     .iter()
-                    .filter_map(|l| {
+                    .enumerate()
+                    .filter_map(|(i, l)| {
                         if let Some(zid) = src_link.get_zid(l) {
+                            if let Some(w) = weights.as_ref().and_then(|w| w.get(i)) {
+                                if *w != 0 {
+                                    link_weights.insert(*zid, *w);
+                                }
+                            }
                             Some(*zid)
                         } else {
                             tracing::error!(
@@ -429,7 +504,7 @@ impl Network {
                         }
                     })
                     .collect();
-                (zid, wai, locs, sn, links)
+                (zid, wai, locs, sn, links, link_weights)
             })
             .collect::<Vec<_>>();
 
@@ -453,7 +528,7 @@ impl Network {
                 updated_nodes: vec![],
                 removed_nodes: vec![],
             };
-            for (zid, whatami, locators, sn, links) in link_states.into_iter() {
+            for (zid, whatami, locators, sn, links, link_weights) in link_states.into_iter() {
                 let idx = match self.get_idx(&zid) {
                     None => {
                         let idx = self.add_node(Node {
@@ -462,6 +537,7 @@ impl Network {
                             locators: locators.clone(),
                             sn,
                             links,
+                            link_weights,
                         });
                         changes.updated_nodes.push((idx, self.graph[idx].clone()));
                         locators.is_some().then_some(idx)
@@ -473,6 +549,7 @@ impl Network {
                             .then(|| {
                                 node.sn = sn;
                                 node.links.clone_from(&links);
+                                node.link_weights = link_weights;
                                 changes.updated_nodes.push((idx, node.clone()));
                                 (node.locators != locators && locators.is_some()).then(|| {
                                     node.locators.clone_from(&locators);
@@ -529,14 +606,15 @@ impl Network {
         // Add nodes to graph & filter out up to date states
         let mut link_states = link_states
             .into_iter()
-            .filter_map(
-                |(zid, whatami, locators, sn, links)| match self.get_idx(&zid) {
+            .filter_map(|(zid, whatami, locators, sn, links, link_weights)| {
+                match self.get_idx(&zid) {
                     Some(idx) => {
                         let node = &mut self.graph[idx];
                         let oldsn = node.sn;
                         if oldsn < sn {
                             node.sn = sn;
                             node.links.clone_from(&links);
+                            node.link_weights = link_weights;
                             if locators.is_some() {
                                 node.locators = locators;
                             }
@@ -556,13 +634,14 @@ impl Network {
                             locators,
                             sn,
                             links: links.clone(),
+                            link_weights,
                         };
                         tracing::debug!("{} Add node (state) {}", self.name, zid);
                         let idx = self.add_node(node);
                         Some((links, idx, true))
                     }
-                },
-            )
+                }
+            })
             .collect::<Vec<(Vec<ZenohIdProto>, NodeIndex, bool)>>();
 
         // Add/remove edges from graph
@@ -586,6 +665,7 @@ impl Network {
                         locators: None,
                         sn: 0,
                         links: vec![],
+                        link_weights: HashMap::new(),
                     };
                     tracing::debug!("{} Add node (reintroduced) {}", self.name, link.clone());
                     let idx = self.add_node(node);
@@ -720,6 +800,18 @@ impl Network {
         let zid = transport.get_zid().unwrap();
         let whatami = transport.get_whatami().unwrap();
 
+        if self.rtt_weights {
+            if let Some(weight) = rtt_weight(&transport) {
+                tracing::debug!(
+                    "{} Weight of link to {} from RTT: {}",
+                    self.name,
+                    zid,
+                    weight
+                );
+                self.graph[self.idx].link_weights.insert(zid, weight);
+            }
+        }
+
         if self.full_linkstate || self.router_peers_failover_brokering {
             let (idx, new) = match self.get_idx(&zid) {
                 Some(idx) => (idx, false),
@@ -732,6 +824,7 @@ impl Network {
                             locators: None,
                             sn: 0,
                             links: vec![],
+                            link_weights: HashMap::new(),
                         }),
                         true,
                     )
@@ -823,6 +916,8 @@ impl Network {
         tracing::trace!("{} remove_link {}", self.name, zid);
         self.links.retain(|_, link| link.zid != *zid);
         self.graph[self.idx].links.retain(|link| *link != *zid);
+        self.graph[self.idx].link_weights.remove(zid);
+        self.cost_links.remove(zid);
 
         if self.full_linkstate {
             if let Some((edge, _)) = self
@@ -873,6 +968,90 @@ impl Network {
         }
     }
 
+    // Sets the configured weight of the link to `zid`, which is no longer derived from its
+    // round-trip time, and returns true if the routes need to be recomputed.
+    pub(super) fn set_link_weight(&mut self, zid: &ZenohIdProto, weight: u16) -> bool {
+        self.cost_links.insert(*zid);
+        self.update_link_weight(zid, weight)
+    }
+
+    // Updates the weights of the links derived from their round-trip time when they changed by more
+    // than the hysteresis, and returns true if the routes need to be recomputed.
+    pub(super) fn update_rtt_weights(&mut self) -> bool {
+        if !self.rtt_weights {
+            return false;
+        }
+        let weights = self
+            .links
+            .values()
+            .filter(|link| !self.cost_links.contains(&link.zid))
+            .filter_map(|link| Some((link.zid, rtt_weight(&link.transport)?)))
+            .collect::<Vec<_>>();
+        let mut changed = false;
+        for (zid, weight) in weights {
+            let old = self.graph[self.idx]
+                .link_weights
+                .get(&zid)
+                .copied()
+                .unwrap_or(DEFAULT_LINK_WEIGHT) as f64;
+            if exceeds_hysteresis(old, weight as f64, self.weight_hysteresis) {
+                tracing::debug!(
+                    "{} Weight of link to {} from RTT: {}",
+                    self.name,
+                    zid,
+                    weight
+                );
+                changed |= self.update_link_weight(&zid, weight);
+            }
+        }
+        changed
+    }
+
+    fn update_link_weight(&mut self, zid: &ZenohIdProto, weight: u16) -> bool {
+        if self.graph[self.idx].link_weights.insert(*zid, weight) == Some(weight)
+            || !self.full_linkstate
+        {
+            return false;
+        }
+        tracing::debug!("{} Set weight of link to {}: {}", self.name, zid, weight);
+        let changed = match self.get_idx(zid) {
+            Some(idx) if self.graph.find_edge(self.idx, idx).is_some() => {
+                self.update_edge(self.idx, idx)
+            }
+            _ => false,
+        };
+        self.graph[self.idx].sn += 1;
+
+        self.send_on_links(
+            vec![(
+                self.idx,
+                Details {
+                    zid: false,
+                    locators: self.gossip,
+                    links: true,
+                },
+            )],
+            |_| true,
+        );
+        changed
+    }
+
+    pub(super) fn update_locators(&mut self) {
+        if !self.gossip {
+            return;
+        }
+        tracing::trace!("{} update_locators", self.name);
+        self.graph[self.idx].sn += 1;
+
+        // Send updated self linkstate with the new locators on all links
+        send_updated_locators(
+            self.links.values().map(|link| (link, &link.transport)),
+            self.full_linkstate,
+            self.router_peers_failover_brokering,
+            |link, details| self.send_on_link(vec![(self.idx, details)], &link.transport),
+        );
+    }
+
     fn remove_detached_nodes(&mut self) -> Vec<(NodeIndex, Node)> {
         let mut dfs_stack = vec![self.idx];
         let mut visit_map = self.graph.visit_map();
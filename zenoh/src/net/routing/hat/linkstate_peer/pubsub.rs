@@ -26,6 +26,7 @@ use zenoh_protocol::{
             UndeclareSubscriber,
         },
         interest::{InterestId, InterestMode},
+        push::ext::RedundancyType,
     },
 };
 use zenoh_sync::get_mut_unchecked;
@@ -967,6 +968,17 @@ impl HatPubSubTrait for HatCode {
         Arc::new(route)
     }
 
+    fn compute_redundant_data_routes(
+        &self,
+        _tables: &Tables,
+        _face: &FaceState,
+        _expr: &mut RoutingExpr,
+        _routing_context: NodeId,
+        _redundancy: Option<RedundancyType>,
+    ) -> Option<Vec<(Arc<Route>, RedundancyType)>> {
+        None
+    }
+
     fn get_data_routes_entries(&self, tables: &Tables) -> RoutesIndexes {
         get_routes_entries(tables)
     }
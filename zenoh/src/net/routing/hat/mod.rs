@@ -17,7 +17,7 @@
 //! This module is intended for Zenoh's internal use.
 //!
 //! [Click here for Zenoh's documentation](https://docs.rs/zenoh/latest/zenoh)
-use std::{any::Any, sync::Arc};
+use std::{any::Any, path::PathBuf, sync::Arc};
 
 use zenoh_config::{unwrap_or_default, Config, WhatAmI};
 use zenoh_protocol::{
@@ -25,6 +25,7 @@ use zenoh_protocol::{
     network::{
         declare::{queryable::ext::QueryableInfoType, QueryableId, SubscriberId, TokenId},
         interest::{InterestId, InterestMode, InterestOptions},
+        push::ext::RedundancyType,
         Declare, Oam,
     },
 };
@@ -35,6 +36,7 @@ use {crate::key_expr::KeyExpr, std::collections::HashMap};
 
 use super::{
     dispatcher::{
+        explain::RoutingTree,
         face::{Face, FaceState},
         pubsub::SubscriberInfo,
         tables::{NodeId, QueryTargetQablSet, Resource, Route, RoutingExpr, Tables, TablesLock},
@@ -55,9 +57,9 @@ zconfigurable! {
 
 #[derive(serde::Serialize)]
 pub(crate) struct Sources {
-    routers: Vec<ZenohIdProto>,
-    peers: Vec<ZenohIdProto>,
-    clients: Vec<ZenohIdProto>,
+    pub(crate) routers: Vec<ZenohIdProto>,
+    pub(crate) peers: Vec<ZenohIdProto>,
+    pub(crate) clients: Vec<ZenohIdProto>,
 }
 
 impl Sources {
@@ -131,6 +133,27 @@ pub(crate) trait HatBaseTrait {
 
     fn info(&self, tables: &Tables, kind: WhatAmI) -> String;
 
+    fn routing_trees(
+        &self,
+        tables: &Tables,
+        source: NodeId,
+        source_type: WhatAmI,
+    ) -> Vec<RoutingTree>;
+
+    fn update_locators(&self, tables: &mut Tables);
+
+    fn set_link_weight(
+        &self,
+        tables: &mut Tables,
+        tables_ref: &Arc<TablesLock>,
+        zid: &ZenohIdProto,
+        weight: u16,
+    );
+
+    fn update_rtt_weights(&self, tables: &mut Tables, tables_ref: &Arc<TablesLock>);
+
+    fn snapshot(&self, tables: &Tables) -> Option<Snapshot>;
+
     fn close_face(
         &self,
         tables: &TablesLock,
@@ -189,6 +212,17 @@ pub(crate) trait HatPubSubTrait {
         source_type: WhatAmI,
     ) -> Arc<Route>;
 
+    /// Returns the routes of a data message forwarded on redundant routes, each with the
+    /// redundancy extension to attach to it, or `None` if the message is routed normally.
+    fn compute_redundant_data_routes(
+        &self,
+        tables: &Tables,
+        face: &FaceState,
+        expr: &mut RoutingExpr,
+        routing_context: NodeId,
+        redundancy: Option<RedundancyType>,
+    ) -> Option<Vec<(Arc<Route>, RedundancyType)>>;
+
     fn get_data_routes_entries(&self, tables: &Tables) -> RoutesIndexes;
 
     #[zenoh_macros::unstable]
@@ -234,6 +268,73 @@ pub(crate) trait HatQueriesTrait {
     fn get_query_routes_entries(&self, tables: &Tables) -> RoutesIndexes;
 }
 
+// The routing state of a node to save to its snapshot file
+pub(crate) struct Snapshot {
+    path: PathBuf,
+    content: Vec<u8>,
+}
+
+impl Snapshot {
+    // Writes the snapshot from a blocking task, to a temporary file first not to leave a
+    // truncated snapshot behind
+    pub(crate) async fn save(self) {
+        let Snapshot { path, content } = self;
+        let result = tokio::task::spawn_blocking(move || {
+            let mut tmp = path.as_os_str().to_owned();
+            tmp.push(".tmp");
+            std::fs::write(&tmp, content)
+                .and_then(|_| std::fs::rename(&tmp, &path))
+                .map_err(|e| (path, e))
+        })
+        .await;
+        match result {
+            Ok(Ok(())) => (),
+            Ok(Err((path, e))) => {
+                tracing::warn!("Unable to save routing snapshot to {:?}: {}", path, e)
+            }
+            Err(e) => tracing::warn!("Unable to save routing snapshot: {}", e),
+        }
+    }
+}
+
+// The parts of the state of a node sent in a linkstate message
+#[derive(Clone)]
+struct Details {
+    zid: bool,
+    locators: bool,
+    links: bool,
+}
+
+// Whether a link weight going from `old` to `new` changes by more than the relative `hysteresis`,
+// in which case the routes are recomputed
+fn exceeds_hysteresis(old: f64, new: f64, hysteresis: f64) -> bool {
+    (new - old).abs() > old * hysteresis
+}
+
+// Sends the state of the local node with its updated locators on all the links of a gossip
+// network. Its links are only sent to the nodes keeping its full linkstate: all of them with
+// `full_linkstate`, and the routers with `router_peers_failover_brokering`.
+fn send_updated_locators<'a, L: 'a>(
+    links: impl Iterator<Item = (&'a L, &'a TransportUnicast)>,
+    full_linkstate: bool,
+    router_peers_failover_brokering: bool,
+    mut send: impl FnMut(&'a L, Details),
+) {
+    for (link, transport) in links {
+        let links = full_linkstate
+            || (router_peers_failover_brokering
+                && transport.get_whatami().unwrap_or(WhatAmI::Peer) == WhatAmI::Router);
+        send(
+            link,
+            Details {
+                zid: false,
+                locators: true,
+                links,
+            },
+        );
+    }
+}
+
 pub(crate) fn new_hat(whatami: WhatAmI, config: &Config) -> Box<dyn HatTrait + Send + Sync> {
     match whatami {
         WhatAmI::Client => Box::new(client::HatCode {}),
@@ -288,3 +389,21 @@ impl CurrentFutureTrait for InterestMode {
         self == &InterestMode::Current || self == &InterestMode::CurrentFuture
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::exceeds_hysteresis;
+
+    #[test]
+    fn link_weight_hysteresis() {
+        // The small variations of the round-trip time of a link don't change the routes
+        assert!(!exceeds_hysteresis(100.0, 100.0, 0.1));
+        assert!(!exceeds_hysteresis(100.0, 109.0, 0.1));
+        assert!(!exceeds_hysteresis(100.0, 91.0, 0.1));
+        assert!(exceeds_hysteresis(100.0, 111.0, 0.1));
+        assert!(exceeds_hysteresis(100.0, 89.0, 0.1));
+        // Any change is taken into account without hysteresis
+        assert!(exceeds_hysteresis(100.0, 100.5, 0.0));
+        assert!(!exceeds_hysteresis(100.0, 100.0, 0.0));
+    }
+}
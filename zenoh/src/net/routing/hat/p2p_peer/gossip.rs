@@ -31,16 +31,10 @@ use zenoh_transport::unicast::TransportUnicast;
 use crate::net::{
     codec::Zenoh080Routing,
     protocol::linkstate::{LinkState, LinkStateList},
+    routing::hat::{send_updated_locators, Details},
     runtime::{Runtime, WeakRuntime},
 };
 
-#[derive(Clone)]
-struct Details {
-    zid: bool,
-    locators: bool,
-    links: bool,
-}
-
 #[derive(Clone)]
 pub(super) struct Node {
     pub(super) zid: ZenohIdProto,
@@ -210,6 +204,8 @@ impl Network {
                 None
             },
             links,
+            link_weights: None,
+            region: None,
         }
     }
 
@@ -590,4 +586,20 @@ impl Network {
         }
         vec![]
     }
+
+    pub(super) fn update_locators(&mut self) {
+        if !self.gossip {
+            return;
+        }
+        tracing::trace!("{} update_locators", self.name);
+        self.graph[self.idx].sn += 1;
+
+        // Send updated self linkstate with the new locators on all links
+        send_updated_locators(
+            self.links.values().map(|link| (link, &link.transport)),
+            false,
+            self.router_peers_failover_brokering,
+            |link, details| self.send_on_link(vec![(self.idx, details)], &link.transport),
+        );
+    }
 }
@@ -27,6 +27,7 @@ use token::{token_new_face, undeclare_simple_token};
 use zenoh_config::{unwrap_or_default, ModeDependent, WhatAmI, WhatAmIMatcher};
 use zenoh_protocol::{
     common::ZExtBody,
+    core::ZenohIdProto,
     network::{
         declare::{
             ext::{NodeIdType, QoSType},
@@ -50,10 +51,11 @@ use self::{
 };
 use super::{
     super::dispatcher::{
+        explain::RoutingTree,
         face::FaceState,
         tables::{NodeId, Resource, RoutingExpr, Tables, TablesLock},
     },
-    HatBaseTrait, HatTrait, SendDeclare,
+    HatBaseTrait, HatTrait, SendDeclare, Snapshot,
 };
 use crate::net::{
     codec::Zenoh080Routing,
@@ -391,6 +393,36 @@ impl HatBaseTrait for HatCode {
     fn info(&self, _tables: &Tables, _kind: WhatAmI) -> String {
         "graph {}".to_string()
     }
+
+    fn routing_trees(
+        &self,
+        _tables: &Tables,
+        _source: NodeId,
+        _source_type: WhatAmI,
+    ) -> Vec<RoutingTree> {
+        vec![]
+    }
+
+    fn update_locators(&self, tables: &mut Tables) {
+        if let Some(net) = hat_mut!(tables).gossip.as_mut() {
+            net.update_locators();
+        }
+    }
+
+    fn set_link_weight(
+        &self,
+        _tables: &mut Tables,
+        _tables_ref: &Arc<TablesLock>,
+        _zid: &ZenohIdProto,
+        _weight: u16,
+    ) {
+    }
+
+    fn update_rtt_weights(&self, _tables: &mut Tables, _tables_ref: &Arc<TablesLock>) {}
+
+    fn snapshot(&self, _tables: &Tables) -> Option<Snapshot> {
+        None
+    }
 }
 
 struct HatContext {}
@@ -21,14 +21,22 @@ use std::{
     any::Any,
     collections::{hash_map::DefaultHasher, HashMap, HashSet},
     hash::Hasher,
+    path::PathBuf,
     sync::{atomic::AtomicU32, Arc},
+    time::Duration,
 };
 
-use token::{token_linkstate_change, token_remove_node, undeclare_simple_token};
+use petgraph::graph::NodeIndex;
+use token::{
+    token_linkstate_change, token_new_border_face, token_remove_node, undeclare_simple_token,
+};
 use zenoh_config::{unwrap_or_default, ModeDependent, WhatAmI, WhatAmIMatcher};
 use zenoh_protocol::{
     common::ZExtBody,
-    core::ZenohIdProto,
+    core::{
+        key_expr::{keyexpr, OwnedKeyExpr},
+        ZenohIdProto,
+    },
     network::{
         declare::{queryable::ext::QueryableInfoType, QueryableId, SubscriberId, TokenId},
         interest::InterestId,
@@ -43,15 +51,22 @@ use zenoh_transport::unicast::TransportUnicast;
 
 use self::{
     network::{shared_nodes, Network},
-    pubsub::{pubsub_linkstate_change, pubsub_remove_node, undeclare_simple_subscription},
-    queries::{queries_linkstate_change, queries_remove_node, undeclare_simple_queryable},
+    pubsub::{
+        pubsub_linkstate_change, pubsub_new_border_face, pubsub_remove_node,
+        undeclare_simple_subscription, RedundancyFilter,
+    },
+    queries::{
+        queries_linkstate_change, queries_new_border_face, queries_remove_node,
+        undeclare_simple_queryable,
+    },
 };
 use super::{
     super::dispatcher::{
+        explain::RoutingTree,
         face::FaceState,
         tables::{NodeId, Resource, RoutingExpr, Tables, TablesLock},
     },
-    HatBaseTrait, HatTrait, SendDeclare,
+    HatBaseTrait, HatTrait, SendDeclare, Snapshot,
 };
 use crate::net::{
     codec::Zenoh080Routing,
@@ -68,6 +83,7 @@ mod interests;
 mod network;
 mod pubsub;
 mod queries;
+mod snapshot;
 mod token;
 
 macro_rules! hat {
@@ -172,6 +188,19 @@ struct HatTables {
     routers_trees_worker: TreesComputationWorker,
     linkstatepeers_trees_worker: TreesComputationWorker,
     router_peers_failover_brokering: bool,
+    redundant_key_exprs: Vec<OwnedKeyExpr>,
+    redundancy_filter: RedundancyFilter,
+    // The key expressions the subscriptions declared to other regions are aggregated into
+    border_subs_aggregation: Vec<OwnedKeyExpr>,
+    // The declarations of this region declared to the border routers of other regions
+    border_subs: HashSet<OwnedKeyExpr>,
+    border_qabls: HashMap<OwnedKeyExpr, QueryableInfoType>,
+    border_tokens: HashSet<OwnedKeyExpr>,
+    // The file the routing state is saved to
+    snapshot_path: Option<PathBuf>,
+    // The declarations restored from the snapshot that were not declared again yet
+    provisional_subs: HashSet<(Arc<Resource>, ZenohIdProto)>,
+    provisional_qabls: HashSet<(Arc<Resource>, ZenohIdProto)>,
 }
 
 impl HatTables {
@@ -189,9 +218,35 @@ impl HatTables {
             routers_trees_worker: TreesComputationWorker::new(WhatAmI::Router),
             linkstatepeers_trees_worker: TreesComputationWorker::new(WhatAmI::Peer),
             router_peers_failover_brokering,
+            redundant_key_exprs: vec![],
+            redundancy_filter: RedundancyFilter::default(),
+            border_subs_aggregation: vec![],
+            border_subs: HashSet::new(),
+            border_qabls: HashMap::new(),
+            border_tokens: HashSet::new(),
+            snapshot_path: None,
+            provisional_subs: HashSet::new(),
+            provisional_qabls: HashSet::new(),
         }
     }
 
+    // Returns true if the data on `expr` is forwarded on redundant routes
+    #[inline]
+    fn is_redundant(&self, expr: &mut RoutingExpr) -> bool {
+        !self.redundant_key_exprs.is_empty()
+            && keyexpr::new(expr.full_expr())
+                .map(|ke| self.redundant_key_exprs.iter().any(|r| r.includes(ke)))
+                .unwrap_or(false)
+    }
+
+    // Returns true if the given router belongs to the region of this router
+    #[inline]
+    fn is_native_router(&self, zid: &ZenohIdProto) -> bool {
+        self.routers_net
+            .as_ref()
+            .is_some_and(|net| net.get_idx(zid).is_some_and(|idx| !net.is_foreign(idx)))
+    }
+
     #[inline]
     fn get_net(&self, net_type: WhatAmI) -> Option<&Network> {
         match net_type {
@@ -268,6 +323,36 @@ impl HatTables {
         }
     }
 
+    // Returns the routers of other regions among `routers` that the messages from `source`
+    // are routed to: a single one per region, elected for the key expression,
+    // and none if the source itself belongs to another region.
+    fn elect_border_routers<'a>(
+        &'a self,
+        self_zid: &'a ZenohIdProto,
+        key_expr: &str,
+        source: NodeId,
+        routers: impl Iterator<Item = &'a ZenohIdProto>,
+    ) -> HashSet<ZenohIdProto> {
+        let net = self.routers_net.as_ref().unwrap();
+        let source = NodeIndex::new(source as usize);
+        if net.graph.contains_node(source) && net.is_foreign(source) {
+            return HashSet::new();
+        }
+        let mut regions: HashMap<&Option<String>, Vec<&ZenohIdProto>> = HashMap::new();
+        for router in routers {
+            if let Some(region) = net.get_idx(router).and_then(|idx| net.foreign_region(idx)) {
+                let routers = regions.entry(region).or_default();
+                if !routers.contains(&router) {
+                    routers.push(router);
+                }
+            }
+        }
+        regions
+            .into_values()
+            .map(|routers| *self.elect_router(self_zid, key_expr, routers.into_iter()))
+            .collect()
+    }
+
     #[inline]
     fn failover_brokering_to(source_links: &[ZenohIdProto], dest: ZenohIdProto) -> bool {
         // if source_links is empty then gossip is probably disabled in source peer
@@ -323,8 +408,28 @@ impl HatBaseTrait for HatCode {
             unwrap_or_default!(config.routing().peer().mode()) == *"linkstate";
         let router_peers_failover_brokering =
             unwrap_or_default!(config.routing().router().peers_failover_brokering());
+        let redundant_key_exprs = config.routing().router().redundant_key_exprs().clone();
+        let rtt_weights = unwrap_or_default!(config.routing().linkstate().rtt_weights());
+        let weight_hysteresis =
+            unwrap_or_default!(config.routing().linkstate().weight_hysteresis());
+        let region = config.routing().router().region().clone();
+        let border_subs_aggregation = config.aggregation().subscribers().clone();
+        let snapshot_path = config
+            .routing()
+            .router()
+            .snapshot()
+            .path()
+            .as_ref()
+            .map(PathBuf::from);
+        let snapshot_period = unwrap_or_default!(config.routing().router().snapshot().period());
+        let reconciliation_delay =
+            unwrap_or_default!(config.routing().router().snapshot().reconciliation_delay());
         drop(config_guard);
 
+        let redundancy = !redundant_key_exprs.is_empty();
+        hat_mut!(tables).redundant_key_exprs = redundant_key_exprs;
+        hat_mut!(tables).border_subs_aggregation = border_subs_aggregation;
+
         if router_full_linkstate | gossip {
             hat_mut!(tables).routers_net = Some(Network::new(
                 "[Routers network]".to_string(),
@@ -335,18 +440,26 @@ impl HatBaseTrait for HatCode {
                 gossip,
                 gossip_multihop,
                 autoconnect,
+                rtt_weights,
+                weight_hysteresis,
+                redundancy,
+                region,
             ));
         }
         if peer_full_linkstate | gossip {
             hat_mut!(tables).linkstatepeers_net = Some(Network::new(
                 "[Peers network]".to_string(),
                 tables.zid,
-                runtime,
+                runtime.clone(),
                 peer_full_linkstate,
                 router_peers_failover_brokering,
                 gossip,
                 gossip_multihop,
                 autoconnect,
+                rtt_weights,
+                weight_hysteresis,
+                false,
+                None,
             ));
         }
         if router_full_linkstate && peer_full_linkstate {
@@ -355,6 +468,20 @@ impl HatBaseTrait for HatCode {
                 hat!(tables).linkstatepeers_net.as_ref().unwrap(),
             );
         }
+        if let Some(path) = snapshot_path {
+            match snapshot::load_snapshot(&path, &tables.zid) {
+                Ok(Some(snapshot)) => snapshot::restore_snapshot(tables, snapshot),
+                Ok(None) => (),
+                Err(e) => tracing::warn!("Unable to restore routing snapshot {:?}: {}", path, e),
+            }
+            snapshot::spawn_snapshot_tasks(
+                &runtime,
+                path.clone(),
+                Duration::from_millis(snapshot_period),
+                Duration::from_millis(reconciliation_delay),
+            );
+            hat_mut!(tables).snapshot_path = Some(path);
+        }
     }
 
     fn new_tables(&self, router_peers_failover_brokering: bool) -> Box<dyn Any + Send + Sync> {
@@ -389,11 +516,16 @@ impl HatBaseTrait for HatCode {
         _send_declare: &mut SendDeclare,
     ) -> ZResult<()> {
         let link_id = match face.state.whatami {
-            WhatAmI::Router => hat_mut!(tables)
-                .routers_net
-                .as_mut()
-                .unwrap()
-                .add_link(transport.clone()),
+            WhatAmI::Router => {
+                hat!(tables)
+                    .redundancy_filter
+                    .remove_source(&face.state.zid);
+                hat_mut!(tables)
+                    .routers_net
+                    .as_mut()
+                    .unwrap()
+                    .add_link(transport.clone())
+            }
             WhatAmI::Peer => {
                 if let Some(net) = hat_mut!(tables).linkstatepeers_net.as_mut() {
                     net.add_link(transport.clone())
@@ -672,6 +804,24 @@ impl HatBaseTrait for HatCode {
                                 );
                             }
 
+                            // Declare the declarations of this region to the border routers
+                            // of other regions once their region is known
+                            if let Some(mut face) = tables.get_face(&zid).cloned() {
+                                if !face_hat!(face).border
+                                    && hat!(tables)
+                                        .routers_net
+                                        .as_ref()
+                                        .unwrap()
+                                        .get_link_from_zid(&zid)
+                                        .is_some_and(|link| link.foreign_idx.is_some())
+                                {
+                                    face_hat_mut!(&mut face).border = true;
+                                    pubsub_new_border_face(tables, &face);
+                                    queries_new_border_face(tables, &face);
+                                    token_new_border_face(tables, &face);
+                                }
+                            }
+
                             hat_mut!(tables)
                                 .schedule_compute_trees(tables_ref.clone(), WhatAmI::Router);
                         }
@@ -827,6 +977,91 @@ impl HatBaseTrait for HatCode {
             _ => "graph {}".to_string(),
         }
     }
+
+    fn routing_trees(
+        &self,
+        tables: &Tables,
+        source: NodeId,
+        source_type: WhatAmI,
+    ) -> Vec<RoutingTree> {
+        let hat = hat!(tables);
+        let mut trees = vec![];
+        if let Some(net) = hat.routers_net.as_ref() {
+            let source = match source_type {
+                WhatAmI::Router => source,
+                _ => net.idx.index() as NodeId,
+            };
+            trees.extend(net.routing_tree(source, WhatAmI::Router));
+        }
+        if hat.full_net(WhatAmI::Peer) {
+            if let Some(net) = hat.linkstatepeers_net.as_ref() {
+                let source = match source_type {
+                    WhatAmI::Peer => source,
+                    _ => net.idx.index() as NodeId,
+                };
+                trees.extend(net.routing_tree(source, WhatAmI::Peer));
+            }
+        }
+        trees
+    }
+
+    fn update_locators(&self, tables: &mut Tables) {
+        if let Some(net) = hat_mut!(tables).routers_net.as_mut() {
+            net.update_locators();
+        }
+        if let Some(net) = hat_mut!(tables).linkstatepeers_net.as_mut() {
+            net.update_locators();
+        }
+    }
+
+    fn set_link_weight(
+        &self,
+        tables: &mut Tables,
+        tables_ref: &Arc<TablesLock>,
+        zid: &ZenohIdProto,
+        weight: u16,
+    ) {
+        let hat = hat_mut!(tables);
+        if hat
+            .routers_net
+            .as_mut()
+            .is_some_and(|net| net.set_link_weight(zid, weight))
+        {
+            hat.schedule_compute_trees(tables_ref.clone(), WhatAmI::Router);
+        }
+        if hat
+            .linkstatepeers_net
+            .as_mut()
+            .is_some_and(|net| net.set_link_weight(zid, weight))
+            && hat.full_net(WhatAmI::Peer)
+        {
+            hat.schedule_compute_trees(tables_ref.clone(), WhatAmI::Peer);
+        }
+    }
+
+    fn update_rtt_weights(&self, tables: &mut Tables, tables_ref: &Arc<TablesLock>) {
+        let hat = hat_mut!(tables);
+        if hat
+            .routers_net
+            .as_mut()
+            .is_some_and(|net| net.update_rtt_weights())
+        {
+            hat.schedule_compute_trees(tables_ref.clone(), WhatAmI::Router);
+        }
+        if hat
+            .linkstatepeers_net
+            .as_mut()
+            .is_some_and(|net| net.update_rtt_weights())
+            && hat.full_net(WhatAmI::Peer)
+        {
+            hat.schedule_compute_trees(tables_ref.clone(), WhatAmI::Peer);
+        }
+    }
+
+    fn snapshot(&self, tables: &Tables) -> Option<Snapshot> {
+        let path = hat!(tables).snapshot_path.as_ref()?;
+        snapshot::encode_snapshot(path, tables)
+    }
 }
 
 struct HatContext {
@@ -853,6 +1088,8 @@ impl HatContext {
 
 struct HatFace {
     link_id: usize,
+    // True if the face is connected to a router of another region
+    border: bool,
     next_id: AtomicU32, // @TODO: manage rollover and uniqueness
     remote_interests: HashMap<InterestId, RemoteInterest>,
     local_subs: HashMap<Arc<Resource>, SubscriberId>,
@@ -867,6 +1104,7 @@ impl HatFace {
     fn new() -> Self {
         Self {
             link_id: 0,
+            border: false,
             next_id: AtomicU32::new(0),
             remote_interests: HashMap::new(),
             local_subs: HashMap::new(),
@@ -887,6 +1125,14 @@ fn get_router(tables: &Tables, face: &Arc<FaceState>, nodeid: NodeId) -> Option<
         .get_link(face_hat!(face).link_id)
     {
         Some(link) => match link.get_zid(&(nodeid as u64)) {
+            // Border routers only declare on their own behalf to other regions
+            Some(router) if link.foreign_idx.is_some() && *router != face.zid => {
+                tracing::debug!(
+                    "Ignoring declaration from another region on behalf of router {}",
+                    router
+                );
+                None
+            }
             Some(router) => Some(*router),
             None => {
                 tracing::error!(
@@ -11,7 +11,10 @@
 // Contributors:
 //   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
 //
-use std::convert::TryInto;
+use std::{
+    collections::{HashMap, HashSet},
+    convert::TryInto,
+};
 
 use petgraph::{
     graph::NodeIndex,
@@ -35,15 +38,26 @@ use zenoh_transport::unicast::TransportUnicast;
 use crate::net::{
     codec::Zenoh080Routing,
     protocol::linkstate::{LinkState, LinkStateList},
-    routing::dispatcher::tables::NodeId,
+    routing::{
+        dispatcher::{explain::RoutingTree, tables::NodeId},
+        hat::{exceeds_hysteresis, send_updated_locators, Details},
+    },
     runtime::Runtime,
 };
 
-#[derive(Clone)]
-struct Details {
-    zid: bool,
-    locators: bool,
-    links: bool,
+// The weight of the links with no advertised weight
+const DEFAULT_LINK_WEIGHT: u16 = 100;
+
+// The weight added to the links of a primary tree when computing the alternate tree
+const ALTERNATE_TREE_PENALTY: f64 = 1e9;
+
+// The weight of a link derived from its smallest measured round-trip time, in units of 10 µs
+fn rtt_weight(transport: &TransportUnicast) -> Option<u16> {
+    transport
+        .get_rtt()
+        .ok()
+        .flatten()
+        .map(|rtt| (rtt.as_micros() / 10).clamp(1, u16::MAX as u128) as u16)
 }
 
 #[derive(Clone)]
@@ -53,6 +67,8 @@ pub(super) struct Node {
     pub(super) locators: Option<Vec<Locator>>,
     pub(super) sn: u64,
     pub(super) links: Vec<ZenohIdProto>,
+    pub(super) link_weights: HashMap<ZenohIdProto, u16>,
+    pub(super) region: Option<String>,
 }
 
 impl std::fmt::Debug for Node {
@@ -66,6 +82,8 @@ pub(super) struct Link {
     zid: ZenohIdProto,
     mappings: VecMap<ZenohIdProto>,
     local_mappings: VecMap<u64>,
+    // The index of the node at the other end of the link if it belongs to another region
+    pub(super) foreign_idx: Option<NodeIndex>,
 }
 
 impl Link {
@@ -76,6 +94,7 @@ impl Link {
             zid,
             mappings: VecMap::new(),
             local_mappings: VecMap::new(),
+            foreign_idx: None,
         }
     }
 
@@ -120,9 +139,15 @@ pub(super) struct Network {
     pub(super) gossip: bool,
     pub(super) gossip_multihop: bool,
     pub(super) autoconnect: WhatAmIMatcher,
+    pub(super) rtt_weights: bool,
+    pub(super) weight_hysteresis: f64,
+    // The links whose weight is configured and not derived from their round-trip time
+    pub(super) cost_links: HashSet<ZenohIdProto>,
     pub(super) idx: NodeIndex,
     pub(super) links: VecMap<Link>,
     pub(super) trees: Vec<Tree>,
+    pub(super) redundancy: bool,
+    pub(super) alternate_trees: Vec<Tree>,
     pub(super) distances: Vec<f64>,
     pub(super) graph: petgraph::stable_graph::StableUnGraph<Node, f64>,
     pub(super) runtime: Runtime,
@@ -139,6 +164,10 @@ impl Network {
         gossip: bool,
         gossip_multihop: bool,
         autoconnect: WhatAmIMatcher,
+        rtt_weights: bool,
+        weight_hysteresis: f64,
+        redundancy: bool,
+        region: Option<String>,
     ) -> Self {
         let mut graph = petgraph::stable_graph::StableGraph::default();
         tracing::debug!("{} Add node (self) {}", name, zid);
@@ -148,6 +177,8 @@ impl Network {
             locators: None,
             sn: 1,
             links: vec![],
+            link_weights: HashMap::new(),
+            region,
         });
         Network {
             name,
@@ -156,6 +187,9 @@ impl Network {
             gossip,
             gossip_multihop,
             autoconnect,
+            rtt_weights,
+            weight_hysteresis,
+            cost_links: HashSet::new(),
             idx,
             links: VecMap::new(),
             trees: vec![Tree {
@@ -163,6 +197,8 @@ impl Network {
                 children: vec![],
                 directions: vec![None],
             }],
+            redundancy,
+            alternate_trees: vec![],
             distances: vec![0.0],
             graph,
             runtime,
@@ -172,10 +208,26 @@ impl Network {
     pub(super) fn dot(&self) -> String {
         std::format!(
             "{:?}",
-            petgraph::dot::Dot::with_config(&self.graph, &[petgraph::dot::Config::EdgeNoLabel])
+            petgraph::dot::Dot::with_attr_getters(
+                &self.graph,
+                &[petgraph::dot::Config::EdgeNoLabel],
+                &|_, edge| format!("label = \"{}\"", edge.weight().trunc()),
+                &|_, _| String::new(),
+            )
         )
     }
 
+    pub(super) fn routing_tree(&self, source: NodeId, network: WhatAmI) -> Option<RoutingTree> {
+        let tree = self.trees.get(source as usize)?;
+        let zid = |idx: NodeIndex| self.graph.node_weight(idx).map(|node| node.zid.into());
+        Some(RoutingTree {
+            network,
+            source: zid(NodeIndex::new(source as usize))?,
+            parent: tree.parent.and_then(zid),
+            children: tree.children.iter().filter_map(|idx| zid(*idx)).collect(),
+        })
+    }
+
     #[inline]
     pub(super) fn get_node(&self, zid: &ZenohIdProto) -> Option<&Node> {
         self.graph.node_weights().find(|weight| weight.zid == *zid)
@@ -198,9 +250,27 @@ impl Network {
         self.links.values().find(|link| link.zid == *zid)
     }
 
+    // Returns the region of the given node if it is known to differ from the region of this node
+    #[inline]
+    pub(super) fn foreign_region(&self, idx: NodeIndex) -> Option<&Option<String>> {
+        let node = &self.graph[idx];
+        (node.sn != 0 && node.region != self.graph[self.idx].region).then_some(&node.region)
+    }
+
+    #[inline]
+    pub(super) fn is_foreign(&self, idx: NodeIndex) -> bool {
+        self.foreign_region(idx).is_some()
+    }
+
     #[inline]
     pub(super) fn get_local_context(&self, context: NodeId, link_id: usize) -> NodeId {
         match self.get_link(link_id) {
+            // The nodes of another region are unknown: its messages are routed
+            // as if they were sourced by the border router that sent them
+            Some(Link {
+                foreign_idx: Some(idx),
+                ..
+            }) => idx.index() as NodeId,
             Some(link) => match link.get_local_psid(&(context as u64)) {
                 Some(psid) => (*psid).try_into().unwrap_or(0),
                 None => {
@@ -230,14 +300,30 @@ impl Network {
         idx
     }
 
-    fn make_link_state(&self, idx: NodeIndex, details: Details) -> LinkState {
-        let links = if details.links {
-            self.graph[idx]
+    // Builds the link state of the given node, restricting its links to the nodes
+    // of `links_region` if any.
+    fn make_link_state(
+        &self,
+        idx: NodeIndex,
+        details: Details,
+        links_region: Option<&Option<String>>,
+    ) -> LinkState {
+        let (links, link_weights) = if details.links {
+            let node = &self.graph[idx];
+            let (links, weights): (Vec<u64>, Vec<u16>) = node
                 .links
                 .iter()
                 .filter_map(|zid| {
                     if let Some(idx2) = self.get_idx(zid) {
-                        Some(idx2.index().try_into().unwrap())
+                        if links_region.is_some_and(|region| {
+                            self.graph[idx2].sn == 0 || self.graph[idx2].region != *region
+                        }) {
+                            return None;
+                        }
+                        Some((
+                            u64::try_from(idx2.index()).unwrap(),
+                            node.link_weights.get(zid).copied().unwrap_or(0),
+                        ))
                     } else {
                         tracing::error!(
                             "{} Internal error building link state: cannot get index of {}",
@@ -247,9 +333,12 @@ impl Network {
                         None
                     }
                 })
-                .collect()
+                .unzip();
+            // Only advertise the weights when some are set
+            let weights = weights.iter().any(|w| *w != 0).then_some(weights);
+            (links, weights)
         } else {
-            vec![]
+            (vec![], None)
         };
         LinkState {
             psid: idx.index().try_into().unwrap(),
@@ -270,13 +359,19 @@ impl Network {
                 None
             },
             links,
+            link_weights,
+            region: self.graph[idx].region.clone(),
         }
     }
 
-    fn make_msg(&self, idxs: Vec<(NodeIndex, Details)>) -> Result<NetworkMessage, DidntWrite> {
+    fn make_msg(
+        &self,
+        idxs: Vec<(NodeIndex, Details)>,
+        links_region: Option<&Option<String>>,
+    ) -> Result<NetworkMessage, DidntWrite> {
         let mut link_states = vec![];
         for (idx, details) in idxs {
-            link_states.push(self.make_link_state(idx, details));
+            link_states.push(self.make_link_state(idx, details, links_region));
         }
         let codec = Zenoh080Routing::new();
         let mut buf = ZBuf::empty();
@@ -290,10 +385,46 @@ impl Network {
         .into())
     }
 
-    fn send_on_link(&self, idxs: Vec<(NodeIndex, Details)>, transport: &TransportUnicast) {
-        if let Ok(msg) = self.make_msg(idxs) {
-            tracing::trace!("{} Send to {:?} {:?}", self.name, transport.get_zid(), msg);
-            if let Err(e) = transport.schedule(msg) {
+    // Only the state of this node, restricted to its links to the region of the link,
+    // is sent to another region, along with the zids of these links.
+    fn foreign_msg(
+        &self,
+        idxs: Vec<(NodeIndex, Details)>,
+        foreign_idx: NodeIndex,
+    ) -> Option<Result<NetworkMessage, DidntWrite>> {
+        let (_, details) = idxs.into_iter().find(|(idx, _)| *idx == self.idx)?;
+        let region = &self.graph[foreign_idx].region;
+        let mut idxs: Vec<(NodeIndex, Details)> = self.graph[self.idx]
+            .links
+            .iter()
+            .filter_map(|zid| self.get_idx(zid))
+            .filter(|idx| self.foreign_region(*idx) == Some(region))
+            .map(|idx| {
+                (
+                    idx,
+                    Details {
+                        zid: true,
+                        locators: false,
+                        links: false,
+                    },
+                )
+            })
+            .collect();
+        idxs.push((self.idx, details));
+        Some(self.make_msg(idxs, Some(region)))
+    }
+
+    fn send_on_link(&self, idxs: Vec<(NodeIndex, Details)>, link: &Link) {
+        let msg = match link.foreign_idx {
+            Some(foreign_idx) => match self.foreign_msg(idxs, foreign_idx) {
+                Some(msg) => msg,
+                None => return,
+            },
+            None => self.make_msg(idxs, None),
+        };
+        if let Ok(msg) = msg {
+            tracing::trace!("{} Send to {} {:?}", self.name, link.zid, msg);
+            if let Err(e) = link.transport.schedule(msg) {
                 tracing::debug!("{} Error sending LinkStateList: {}", self.name, e);
             }
         } else {
@@ -305,9 +436,13 @@ impl Network {
     where
         P: FnMut(&Link) -> bool,
     {
-        if let Ok(msg) = self.make_msg(idxs) {
+        if let Ok(msg) = self.make_msg(idxs.clone(), None) {
             for link in self.links.values() {
-                if parameters(link) {
+                if link.foreign_idx.is_some() {
+                    if parameters(link) {
+                        self.send_on_link(idxs.clone(), link);
+                    }
+                } else if parameters(link) {
                     tracing::trace!("{} Send to {} {:?}", self.name, link.zid, msg);
                     if let Err(e) = link.transport.schedule(msg.clone()) {
                         tracing::debug!("{} Error sending LinkStateList: {}", self.name, e);
@@ -335,7 +470,10 @@ impl Network {
                 }))
     }
 
-    fn update_edge(&mut self, idx1: NodeIndex, idx2: NodeIndex) {
+    // Adds or updates the edge between the two nodes and returns true if its weight changed.
+    // The weight is the highest of the weights advertised by both ends of the link,
+    // slightly perturbed to break ties.
+    fn update_edge(&mut self, idx1: NodeIndex, idx2: NodeIndex) -> bool {
         use std::hash::Hasher;
         let mut hasher = std::collections::hash_map::DefaultHasher::default();
         if self.graph[idx1].zid > self.graph[idx2].zid {
@@ -345,8 +483,24 @@ impl Network {
             hasher.write(&self.graph[idx1].zid.to_le_bytes());
             hasher.write(&self.graph[idx2].zid.to_le_bytes());
         }
-        let weight = 100.0 + ((hasher.finish() as u32) as f64) / u32::MAX as f64;
+        let advertised = self.graph[idx1]
+            .link_weights
+            .get(&self.graph[idx2].zid)
+            .max(self.graph[idx2].link_weights.get(&self.graph[idx1].zid))
+            .copied()
+            .unwrap_or(DEFAULT_LINK_WEIGHT);
+        let weight = advertised as f64 + ((hasher.finish() as u32) as f64) / u32::MAX as f64;
+        if let Some(old) = self
+            .graph
+            .find_edge(idx1, idx2)
+            .map(|edge| self.graph[edge])
+        {
+            if !exceeds_hysteresis(old, weight, self.weight_hysteresis) {
+                return false;
+            }
+        }
         self.graph.update_edge(idx1, idx2, weight);
+        true
     }
 
     pub(super) fn link_states(
@@ -389,6 +543,8 @@ impl Network {
                         link_state.locators,
                         link_state.sn,
                         link_state.links,
+                        link_state.link_weights,
+                        link_state.region,
                     ))
                 } else {
                     match src_link.get_zid(&link_state.psid) {
@@ -398,6 +554,8 @@ impl Network {
                             link_state.locators,
                             link_state.sn,
                             link_state.links,
+                            link_state.link_weights,
+                            link_state.region,
                         )),
                         None => {
                             tracing::error!(
@@ -416,11 +574,18 @@ impl Network {
         let src_link = self.get_link_from_zid(&src).unwrap();
         let link_states = link_states
             .into_iter()
-            .map(|(zid, wai, locs, sn, links)| {
+            .map(|(zid, wai, locs, sn, links, weights, region)| {
+                let mut link_weights = HashMap::new();
                 let links: Vec<ZenohIdProto> = links
                     .iter()
-                    .filter_map(|l| {
+                    .enumerate()
+                    .filter_map(|(i, l)| {
                         if let Some(zid) = src_link.get_zid(l) {
+                            if let Some(w) = weights.as_ref().and_then(|w| w.get(i)) {
+                                if *w != 0 {
+                                    link_weights.insert(*zid, *w);
+                                }
+                            }
                             Some(*zid)
                         } else {
                             tracing::error!(
@@ -433,10 +598,46 @@ impl Network {
                         }
                     })
                     .collect();
-                (zid, wai, locs, sn, links)
+                (zid, wai, locs, sn, links, link_weights, region)
             })
             .collect::<Vec<_>>();
 
+        // Only the state of the sender, restricted to its links to this region,
+        // is kept from the states received from another region
+        let region = self.graph[self.idx].region.clone();
+        let src_region = link_states
+            .iter()
+            .find(|ls| ls.0 == src)
+            .map(|ls| ls.6.clone())
+            .or_else(|| {
+                self.get_node(&src)
+                    .filter(|node| node.sn != 0)
+                    .map(|node| node.region.clone())
+            });
+        let link_states = if src_region.is_some_and(|r| r != region) {
+            let regions: HashMap<ZenohIdProto, Option<String>> =
+                link_states.iter().map(|ls| (ls.0, ls.6.clone())).collect();
+            link_states
+                .into_iter()
+                .filter(|ls| ls.0 == src)
+                .map(|mut ls| {
+                    ls.4.retain(|zid| {
+                        regions
+                            .get(zid)
+                            .or_else(|| {
+                                self.get_node(zid)
+                                    .filter(|node| node.sn != 0)
+                                    .map(|node| &node.region)
+                            })
+                            .is_some_and(|r| *r == region)
+                    });
+                    ls
+                })
+                .collect()
+        } else {
+            link_states
+        };
+
         // tracing::trace!(
         //     "{} Received from {} mapped: {:?}",
         //     self.name,
@@ -457,7 +658,8 @@ impl Network {
                 updated_nodes: vec![],
                 removed_nodes: vec![],
             };
-            for (zid, whatami, locators, sn, links) in link_states.into_iter() {
+            for (zid, whatami, locators, sn, links, link_weights, region) in link_states.into_iter()
+            {
                 let idx = match self.get_idx(&zid) {
                     None => {
                         let idx = self.add_node(Node {
@@ -466,6 +668,8 @@ impl Network {
                             locators: locators.clone(),
                             sn,
                             links,
+                            link_weights,
+                            region,
                         });
                         changes.updated_nodes.push((idx, self.graph[idx].clone()));
                         locators.is_some().then_some(idx)
@@ -477,6 +681,8 @@ impl Network {
                             .then(|| {
                                 node.sn = sn;
                                 node.links.clone_from(&links);
+                                node.link_weights = link_weights;
+                                node.region = region;
                                 changes.updated_nodes.push((idx, node.clone()));
                                 (node.locators != locators && locators.is_some()).then(|| {
                                     node.locators.clone_from(&locators);
@@ -534,13 +740,16 @@ impl Network {
         let mut link_states = link_states
             .into_iter()
             .filter_map(
-                |(zid, whatami, locators, sn, links)| match self.get_idx(&zid) {
+                |(zid, whatami, locators, sn, links, link_weights, region)| match self.get_idx(&zid)
+                {
                     Some(idx) => {
                         let node = &mut self.graph[idx];
                         let oldsn = node.sn;
                         if oldsn < sn {
                             node.sn = sn;
                             node.links.clone_from(&links);
+                            node.link_weights = link_weights;
+                            node.region = region;
                             if locators.is_some() {
                                 node.locators = locators;
                             }
@@ -560,6 +769,8 @@ impl Network {
                             locators,
                             sn,
                             links: links.clone(),
+                            link_weights,
+                            region,
                         };
                         tracing::debug!("{} Add node (state) {}", self.name, zid);
                         let idx = self.add_node(node);
@@ -569,6 +780,15 @@ impl Network {
             )
             .collect::<Vec<(Vec<ZenohIdProto>, NodeIndex, bool)>>();
 
+        let foreign_idx = self.get_idx(&src).filter(|idx| self.is_foreign(*idx));
+        let new_foreign = match self.links.values_mut().find(|link| link.zid == src) {
+            Some(link) if link.foreign_idx != foreign_idx => {
+                link.foreign_idx = foreign_idx;
+                foreign_idx.is_some()
+            }
+            _ => false,
+        };
+
         // Add/remove edges from graph
         let mut reintroduced_nodes = vec![];
         for (links, idx1, _) in &link_states {
@@ -590,6 +810,8 @@ impl Network {
                         locators: None,
                         sn: 0,
                         links: vec![],
+                        link_weights: HashMap::new(),
+                        region: None,
                     };
                     tracing::debug!("{} Add node (reintroduced) {}", self.name, link.clone());
                     let idx = self.add_node(node);
@@ -695,16 +917,30 @@ impl Network {
                         })
                         .collect();
                     if !new_idxs.is_empty() || !updated_idxs.is_empty() {
-                        self.send_on_link(
-                            [&new_idxs[..], &updated_idxs[..]].concat(),
-                            &link.transport,
-                        );
+                        self.send_on_link([&new_idxs[..], &updated_idxs[..]].concat(), link);
                     }
                 } else if !new_idxs.is_empty() {
-                    self.send_on_link(new_idxs.clone(), &link.transport);
+                    self.send_on_link(new_idxs.clone(), link);
                 }
             }
         }
+        if new_foreign {
+            // Advertise the links to the other region now that it is known
+            tracing::debug!("{} Link to another region: {}", self.name, src);
+            self.graph[self.idx].sn += 1;
+            self.send_on_links(
+                vec![(
+                    self.idx,
+                    Details {
+                        zid: false,
+                        locators: self.gossip,
+                        links: true,
+                    },
+                )],
+                |_| true,
+            );
+        }
+
         Changes {
             updated_nodes: vec![],
             removed_nodes: removed,
@@ -724,6 +960,18 @@ impl Network {
         let zid = transport.get_zid().unwrap();
         let whatami = transport.get_whatami().unwrap();
 
+        if self.rtt_weights {
+            if let Some(weight) = rtt_weight(&transport) {
+                tracing::debug!(
+                    "{} Weight of link to {} from RTT: {}",
+                    self.name,
+                    zid,
+                    weight
+                );
+                self.graph[self.idx].link_weights.insert(zid, weight);
+            }
+        }
+
         if self.full_linkstate || self.router_peers_failover_brokering {
             let (idx, new) = match self.get_idx(&zid) {
                 Some(idx) => (idx, false),
@@ -736,6 +984,8 @@ impl Network {
                             locators: None,
                             sn: 0,
                             links: vec![],
+                            link_weights: HashMap::new(),
+                            region: None,
                         }),
                         true,
                     )
@@ -788,7 +1038,7 @@ impl Network {
                                 },
                             )]
                         },
-                        &link.transport,
+                        link,
                     )
                 });
         }
@@ -819,7 +1069,7 @@ impl Network {
                 )
             })
             .collect();
-        self.send_on_link(idxs, &transport);
+        self.send_on_link(idxs, &self.links[free_index]);
         free_index
     }
 
@@ -827,6 +1077,8 @@ impl Network {
         tracing::trace!("{} remove_link {}", self.name, zid);
         self.links.retain(|_, link| link.zid != *zid);
         self.graph[self.idx].links.retain(|link| *link != *zid);
+        self.graph[self.idx].link_weights.remove(zid);
+        self.cost_links.remove(zid);
 
         if self.full_linkstate {
             if let Some((edge, _)) = self
@@ -877,7 +1129,118 @@ impl Network {
         }
     }
 
-    fn remove_detached_nodes(&mut self) -> Vec<(NodeIndex, Node)> {
+    // Sets the configured weight of the link to `zid`, which is no longer derived from its
+    // round-trip time, and returns true if the routes need to be recomputed.
+    pub(super) fn set_link_weight(&mut self, zid: &ZenohIdProto, weight: u16) -> bool {
+        self.cost_links.insert(*zid);
+        self.update_link_weight(zid, weight)
+    }
+
+    // Updates the weights of the links derived from their round-trip time when they changed by more
+    // than the hysteresis, and returns true if the routes need to be recomputed.
+    pub(super) fn update_rtt_weights(&mut self) -> bool {
+        if !self.rtt_weights {
+            return false;
+        }
+        let weights = self
+            .links
+            .values()
+            .filter(|link| !self.cost_links.contains(&link.zid))
+            .filter_map(|link| Some((link.zid, rtt_weight(&link.transport)?)))
+            .collect::<Vec<_>>();
+        let mut changed = false;
+        for (zid, weight) in weights {
+            let old = self.graph[self.idx]
+                .link_weights
+                .get(&zid)
+                .copied()
+                .unwrap_or(DEFAULT_LINK_WEIGHT) as f64;
+            if exceeds_hysteresis(old, weight as f64, self.weight_hysteresis) {
+                tracing::debug!(
+                    "{} Weight of link to {} from RTT: {}",
+                    self.name,
+                    zid,
+                    weight
+                );
+                changed |= self.update_link_weight(&zid, weight);
+            }
+        }
+        changed
+    }
+
+    fn update_link_weight(&mut self, zid: &ZenohIdProto, weight: u16) -> bool {
+        if self.graph[self.idx].link_weights.insert(*zid, weight) == Some(weight)
+            || !self.full_linkstate
+        {
+            return false;
+        }
+        tracing::debug!("{} Set weight of link to {}: {}", self.name, zid, weight);
+        let changed = match self.get_idx(zid) {
+            Some(idx) if self.graph.find_edge(self.idx, idx).is_some() => {
+                self.update_edge(self.idx, idx)
+            }
+            _ => false,
+        };
+        self.graph[self.idx].sn += 1;
+
+        self.send_on_links(
+            vec![(
+                self.idx,
+                Details {
+                    zid: false,
+                    locators: self.gossip,
+                    links: true,
+                },
+            )],
+            |_| true,
+        );
+        changed
+    }
+
+    pub(super) fn update_locators(&mut self) {
+        if !self.gossip {
+            return;
+        }
+        tracing::trace!("{} update_locators", self.name);
+        self.graph[self.idx].sn += 1;
+
+        // Send updated self linkstate with the new locators on all links
+        send_updated_locators(
+            self.links.values().map(|link| (link, &link.transport)),
+            self.full_linkstate,
+            self.router_peers_failover_brokering,
+            |link, details| self.send_on_link(vec![(self.idx, details)], link),
+        );
+    }
+
+    // Adds the nodes saved by a previous run of this node. Their links only connect them to this
+    // node once a link is established with one of them, and their states are replaced by the
+    // states received from the network. The restored nodes get no sequence number so that any
+    // state is accepted, including the one of a node that restarted without snapshot.
+    pub(super) fn restore_nodes(&mut self, sn: u64, nodes: Vec<Node>) {
+        // The other nodes may still know this node with the sequence number of its previous run
+        self.graph[self.idx].sn = self.graph[self.idx].sn.max(sn + 1);
+        let mut idxs = vec![];
+        for node in nodes {
+            if node.zid != self.graph[self.idx].zid && self.get_idx(&node.zid).is_none() {
+                tracing::debug!("{} Add node (snapshot) {}", self.name, node.zid);
+                idxs.push(self.add_node(Node { sn: 0, ..node }));
+            }
+        }
+        for idx1 in &idxs {
+            for zid in self.graph[*idx1].links.clone() {
+                if let Some(idx2) = self.get_idx(&zid) {
+                    if idxs.contains(&idx2)
+                        && self.graph[idx2].links.contains(&self.graph[*idx1].zid)
+                    {
+                        self.update_edge(*idx1, idx2);
+                    }
+                }
+            }
+        }
+    }
+
+    pub(super) fn remove_detached_nodes(&mut self) -> Vec<(NodeIndex, Node)> {
         let mut dfs_stack = vec![self.idx];
         let mut visit_map = self.graph.visit_map();
         while let Some(node) = dfs_stack.pop() {
@@ -902,6 +1265,57 @@ impl Network {
         removed
     }
 
+    // Builds the tree described by the given predecessors as seen from this node
+    fn make_tree(
+        &self,
+        predecessors: &[Option<NodeIndex>],
+        indexes: &[NodeIndex],
+        len: usize,
+    ) -> Tree {
+        let mut tree = Tree {
+            parent: predecessors[self.idx.index()],
+            children: vec![],
+            directions: vec![None; len],
+        };
+
+        for idx in indexes {
+            if let Some(parent_idx) = predecessors[idx.index()] {
+                if parent_idx == self.idx {
+                    tree.children.push(*idx);
+                }
+            }
+        }
+
+        let mut dfs = petgraph::algo::DfsSpace::new(&self.graph);
+        for destination in indexes {
+            if self.idx != *destination
+                && petgraph::algo::has_path_connecting(
+                    &self.graph,
+                    self.idx,
+                    *destination,
+                    Some(&mut dfs),
+                )
+            {
+                let mut direction = None;
+                let mut current = *destination;
+                while let Some(parent) = predecessors[current.index()] {
+                    if parent == self.idx {
+                        direction = Some(current);
+                        break;
+                    } else {
+                        current = parent;
+                    }
+                }
+
+                tree.directions[destination.index()] = match direction {
+                    Some(direction) => Some(direction),
+                    None => tree.parent,
+                };
+            }
+        }
+        tree
+    }
+
     pub(super) fn compute_trees(&mut self) -> Vec<Vec<NodeIndex>> {
         let indexes = self.graph.node_indices().collect::<Vec<NodeIndex>>();
         let max_idx = indexes.iter().max().unwrap();
@@ -915,6 +1329,16 @@ impl Network {
             children: vec![],
             directions: vec![],
         });
+        self.alternate_trees.clear();
+        if self.redundancy {
+            self.alternate_trees
+                .resize_with(max_idx.index() + 1, || Tree {
+                    parent: None,
+                    children: vec![],
+                    directions: vec![],
+                });
+        }
+        let weights = self.graph.map(|_, _| (), |_, weight| *weight);
 
         for tree_root_idx in &indexes {
             let paths = petgraph::algo::bellman_ford(&self.graph, *tree_root_idx).unwrap();
@@ -941,46 +1365,23 @@ impl Network {
                 tracing::debug!("Tree {} {:?}", self.graph[*tree_root_idx].zid, ps);
             }
 
-            self.trees[tree_root_idx.index()].parent = paths.predecessors[self.idx.index()];
-
-            for idx in &indexes {
-                if let Some(parent_idx) = paths.predecessors[idx.index()] {
-                    if parent_idx == self.idx {
-                        self.trees[tree_root_idx.index()].children.push(*idx);
-                    }
-                }
-            }
+            self.trees[tree_root_idx.index()] =
+                self.make_tree(&paths.predecessors, &indexes, max_idx.index() + 1);
 
-            self.trees[tree_root_idx.index()]
-                .directions
-                .resize_with(max_idx.index() + 1, || None);
-            let mut dfs = petgraph::algo::DfsSpace::new(&self.graph);
-            for destination in &indexes {
-                if self.idx != *destination
-                    && petgraph::algo::has_path_connecting(
-                        &self.graph,
-                        self.idx,
-                        *destination,
-                        Some(&mut dfs),
-                    )
-                {
-                    let mut direction = None;
-                    let mut current = *destination;
-                    while let Some(parent) = paths.predecessors[current.index()] {
-                        if parent == self.idx {
-                            direction = Some(current);
-                            break;
-                        } else {
-                            current = parent;
+            if self.redundancy {
+                // The alternate tree avoids the links of the primary tree whenever possible
+                let mut alternate_weights = weights.clone();
+                for idx in &indexes {
+                    if let Some(parent_idx) = paths.predecessors[idx.index()] {
+                        if let Some(edge) = alternate_weights.find_edge(*idx, parent_idx) {
+                            alternate_weights[edge] += ALTERNATE_TREE_PENALTY;
                         }
                     }
-
-                    self.trees[tree_root_idx.index()].directions[destination.index()] =
-                        match direction {
-                            Some(direction) => Some(direction),
-                            None => self.trees[tree_root_idx.index()].parent,
-                        };
                 }
+                let alternate_paths =
+                    petgraph::algo::bellman_ford(&alternate_weights, *tree_root_idx).unwrap();
+                self.alternate_trees[tree_root_idx.index()] =
+                    self.make_tree(&alternate_paths.predecessors, &indexes, max_idx.index() + 1);
             }
         }
 
@@ -14,18 +14,27 @@
 use std::{
     borrow::Cow,
     collections::{HashMap, HashSet},
-    sync::{atomic::Ordering, Arc},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+    time::{Duration, Instant},
 };
 
 use petgraph::graph::NodeIndex;
+use zenoh_core::zlock;
 use zenoh_protocol::{
-    core::{key_expr::OwnedKeyExpr, WhatAmI, ZenohIdProto},
+    core::{
+        key_expr::{keyexpr, OwnedKeyExpr},
+        WhatAmI, WireExpr, ZenohIdProto,
+    },
     network::{
         declare::{
             common::ext::WireExprType, ext, Declare, DeclareBody, DeclareSubscriber, SubscriberId,
             UndeclareSubscriber,
         },
         interest::{InterestId, InterestMode},
+        push::ext::RedundancyType,
     },
 };
 use zenoh_sync::get_mut_unchecked;
@@ -45,7 +54,7 @@ use crate::net::routing::{
         resource::{NodeId, Resource, SessionContext},
         tables::{Route, RoutingExpr, Tables},
     },
-    hat::{CurrentFutureTrait, HatPubSubTrait, SendDeclare, Sources},
+    hat::{CurrentFutureTrait, HatBaseTrait, HatPubSubTrait, SendDeclare, Sources},
     router::RoutesIndexes,
     RoutingContext,
 };
@@ -61,7 +70,7 @@ fn send_sourced_subscription_to_net_children(
     routing_context: NodeId,
 ) {
     for child in children {
-        if net.graph.contains_node(*child) {
+        if net.graph.contains_node(*child) && !net.is_foreign(*child) {
             match tables.get_face(&net.graph[*child].zid).cloned() {
                 Some(mut someface) => {
                     if src_face
@@ -231,7 +240,11 @@ fn register_router_subscription(
     router: ZenohIdProto,
     send_declare: &mut SendDeclare,
 ) {
-    if !res_hat!(res).router_subs.contains(&router) {
+    // A subscription restored from a snapshot is propagated once declared again
+    let provisional = hat_mut!(tables)
+        .provisional_subs
+        .remove(&(res.clone(), router));
+    if provisional || !res_hat!(res).router_subs.contains(&router) {
         // Register router subscription
         {
             res_hat_mut!(res).router_subs.insert(router);
@@ -240,6 +253,11 @@ fn register_router_subscription(
 
         // Propagate subscription to routers
         propagate_sourced_subscription(tables, res, sub_info, Some(face), &router, WhatAmI::Router);
+
+        // Propagate subscription to other regions
+        if hat!(tables).is_native_router(&router) {
+            update_border_subscription(tables, res);
+        }
     }
     // Propagate subscription to peers
     if hat!(tables).full_net(WhatAmI::Peer) && face.whatami != WhatAmI::Peer {
@@ -341,7 +359,17 @@ fn remote_router_subs(tables: &Tables, res: &Arc<Resource>) -> bool {
         && res_hat!(res)
             .router_subs
             .iter()
-            .any(|peer| peer != &tables.zid)
+            .any(|peer| peer != &tables.zid && !is_provisional(tables, res, peer))
+}
+
+// Whether the subscription of `router` on `res` was restored from a snapshot and not declared
+// again
+#[inline]
+fn is_provisional(tables: &Tables, res: &Arc<Resource>, router: &ZenohIdProto) -> bool {
+    !hat!(tables).provisional_subs.is_empty()
+        && hat!(tables)
+            .provisional_subs
+            .contains(&(res.clone(), *router))
 }
 
 #[inline]
@@ -384,7 +412,7 @@ fn send_forget_sourced_subscription_to_net_children(
     routing_context: Option<NodeId>,
 ) {
     for child in children {
-        if net.graph.contains_node(*child) {
+        if net.graph.contains_node(*child) && !net.is_foreign(*child) {
             match tables.get_face(&net.graph[*child].zid).cloned() {
                 Some(mut someface) => {
                     if src_face
@@ -569,6 +597,10 @@ fn unregister_router_subscription(
     send_declare: &mut SendDeclare,
 ) {
     res_hat_mut!(res).router_subs.retain(|sub| sub != router);
+    hat_mut!(tables)
+        .provisional_subs
+        .remove(&(res.clone(), *router));
+    update_border_subscription(tables, res);
 
     if res_hat!(res).router_subs.is_empty() {
         hat_mut!(tables)
@@ -584,6 +616,22 @@ fn unregister_router_subscription(
     propagate_forget_simple_subscription_to_peers(tables, res, send_declare);
 }
 
+// Removes a subscription restored from a snapshot that was not declared again, without
+// propagating its removal as it was never propagated
+pub(super) fn remove_provisional_subscription(
+    tables: &mut Tables,
+    res: &mut Arc<Resource>,
+    router: &ZenohIdProto,
+) {
+    res_hat_mut!(res).router_subs.retain(|sub| sub != router);
+    if res_hat!(res).router_subs.is_empty() {
+        hat_mut!(tables)
+            .router_subs
+            .retain(|sub| !Arc::ptr_eq(sub, res));
+    }
+    update_border_subscription(tables, res);
+}
+
 fn undeclare_router_subscription(
     tables: &mut Tables,
     face: Option<&Arc<FaceState>>,
@@ -739,6 +787,111 @@ fn forget_simple_subscription(
     }
 }
 
+// Returns the key expression the subscriptions on `res` are declared on to other regions
+// and whether it is an aggregate
+fn border_sub_key(tables: &Tables, res: &Arc<Resource>) -> Option<(OwnedKeyExpr, bool)> {
+    let expr = res.expr();
+    let ke = keyexpr::new(&expr).ok()?;
+    Some(
+        match hat!(tables)
+            .border_subs_aggregation
+            .iter()
+            .find(|aggregate| aggregate.includes(ke))
+        {
+            Some(aggregate) => (aggregate.clone(), true),
+            None => (ke.into(), false),
+        },
+    )
+}
+
+#[inline]
+fn native_router_subs(tables: &Tables, res: &Arc<Resource>) -> bool {
+    res_hat!(res)
+        .router_subs
+        .iter()
+        .any(|router| hat!(tables).is_native_router(router))
+}
+
+fn send_border_subscription(
+    face: &Arc<FaceState>,
+    net: &Network,
+    key: &OwnedKeyExpr,
+    declare: bool,
+) {
+    let wire_expr = WireExpr::from(key).to_owned();
+    let body = if declare {
+        DeclareBody::DeclareSubscriber(DeclareSubscriber { id: 0, wire_expr })
+    } else {
+        DeclareBody::UndeclareSubscriber(UndeclareSubscriber {
+            id: 0,
+            ext_wire_expr: WireExprType { wire_expr },
+        })
+    };
+    face.primitives.send_declare(RoutingContext::with_expr(
+        Declare {
+            interest_id: None,
+            ext_qos: ext::QoSType::DECLARE,
+            ext_tstamp: None,
+            ext_nodeid: ext::NodeIdType {
+                node_id: net.idx.index() as NodeId,
+            },
+            body,
+        },
+        key.to_string(),
+    ));
+}
+
+// Declares or undeclares the subscriptions of this region on `res` to the border routers
+// of other regions, as subscriptions of this router
+fn update_border_subscription(tables: &mut Tables, res: &Arc<Resource>) {
+    let faces = tables
+        .faces
+        .values()
+        .filter(|face| face_hat!(face).border)
+        .cloned()
+        .collect::<Vec<_>>();
+    if faces.is_empty() {
+        return;
+    }
+    let Some((key, aggregated)) = border_sub_key(tables, res) else {
+        return;
+    };
+    let declare = if aggregated {
+        hat!(tables).router_subs.iter().any(|sub| {
+            keyexpr::new(&sub.expr()).is_ok_and(|ke| key.includes(ke))
+                && native_router_subs(tables, sub)
+        })
+    } else {
+        native_router_subs(tables, res)
+    };
+    if declare == hat!(tables).border_subs.contains(&key) {
+        return;
+    }
+    if declare {
+        hat_mut!(tables).border_subs.insert(key.clone());
+    } else {
+        hat_mut!(tables).border_subs.remove(&key);
+    }
+    let net = hat!(tables).routers_net.as_ref().unwrap();
+    for face in faces {
+        send_border_subscription(&face, net, &key, declare);
+    }
+}
+
+pub(super) fn pubsub_new_border_face(tables: &mut Tables, face: &Arc<FaceState>) {
+    let keys = hat!(tables)
+        .router_subs
+        .iter()
+        .filter(|res| native_router_subs(tables, res))
+        .filter_map(|res| border_sub_key(tables, res).map(|(key, _)| key))
+        .collect::<HashSet<_>>();
+    let net = hat!(tables).routers_net.as_ref().unwrap();
+    for key in &keys {
+        send_border_subscription(face, net, key, true);
+    }
+    hat_mut!(tables).border_subs = keys;
+}
+
 pub(super) fn pubsub_remove_node(
     tables: &mut Tables,
     node: &ZenohIdProto,
@@ -747,6 +900,7 @@ pub(super) fn pubsub_remove_node(
 ) {
     match net_type {
         WhatAmI::Router => {
+            hat!(tables).redundancy_filter.remove_source(node);
             for mut res in hat!(tables)
                 .router_subs
                 .iter()
@@ -819,7 +973,9 @@ pub(super) fn pubsub_tree_change(
                         _ => &res_hat!(res).linkstatepeer_subs,
                     };
                     for sub in subs {
-                        if *sub == tree_id {
+                        if *sub == tree_id
+                            && !hat!(tables).provisional_subs.contains(&(res.clone(), *sub))
+                        {
                             let sub_info = SubscriberInfo;
                             send_sourced_subscription_to_net_children(
                                 tables,
@@ -1061,6 +1217,255 @@ pub(crate) fn declare_sub_interest(
     }
 }
 
+// Computes the data route on the primary trees, or on the alternate trees if `alternate` is true
+fn compute_data_route_(
+    tables: &Tables,
+    expr: &mut RoutingExpr,
+    source: NodeId,
+    source_type: WhatAmI,
+    alternate: bool,
+) -> Arc<Route> {
+    #[inline]
+    #[allow(clippy::too_many_arguments)]
+    fn insert_faces_for_subs(
+        route: &mut Route,
+        expr: &RoutingExpr,
+        tables: &Tables,
+        net: &Network,
+        source: NodeId,
+        subs: &HashSet<ZenohIdProto>,
+        borders: &HashSet<ZenohIdProto>,
+        alternate: bool,
+    ) {
+        let trees = if alternate && !net.alternate_trees.is_empty() {
+            &net.alternate_trees
+        } else {
+            &net.trees
+        };
+        if trees.len() > source as usize {
+            for sub in subs {
+                if let Some(sub_idx) = net.get_idx(sub) {
+                    if net.is_foreign(sub_idx) && !borders.contains(sub) {
+                        continue;
+                    }
+                    if trees[source as usize].directions.len() > sub_idx.index() {
+                        if let Some(direction) = trees[source as usize].directions[sub_idx.index()]
+                        {
+                            if net.graph.contains_node(direction) {
+                                if let Some(face) = tables.get_face(&net.graph[direction].zid) {
+                                    route.entry(face.id).or_insert_with(|| {
+                                        let key_expr = Resource::get_best_key(
+                                            expr.prefix,
+                                            expr.suffix,
+                                            face.id,
+                                        );
+                                        (face.clone(), key_expr.to_owned(), source)
+                                    });
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        } else {
+            tracing::trace!("Tree for node sid:{} not yet ready", source);
+        }
+    }
+
+    let mut route = HashMap::new();
+    let key_expr = expr.full_expr();
+    if key_expr.ends_with('/') {
+        return Arc::new(route);
+    }
+    tracing::trace!(
+        "compute_data_route({}, {:?}, {:?})",
+        key_expr,
+        source,
+        source_type
+    );
+    let key_expr = match OwnedKeyExpr::try_from(key_expr) {
+        Ok(ke) => ke,
+        Err(e) => {
+            tracing::warn!("Invalid KE reached the system: {}", e);
+            return Arc::new(route);
+        }
+    };
+    let res = Resource::get_resource(expr.prefix, expr.suffix);
+    let matches = res
+        .as_ref()
+        .and_then(|res| res.context.as_ref())
+        .map(|ctx| Cow::from(&ctx.matches))
+        .unwrap_or_else(|| Cow::from(Resource::get_matches(tables, &key_expr)));
+
+    let master = !hat!(tables).full_net(WhatAmI::Peer)
+        || *hat!(tables).elect_router(&tables.zid, &key_expr, hat!(tables).shared_nodes.iter())
+            == tables.zid;
+
+    let net = hat!(tables).routers_net.as_ref().unwrap();
+    let router_source = match source_type {
+        WhatAmI::Router => source,
+        _ => net.idx.index() as NodeId,
+    };
+    let mres_subs = matches
+        .iter()
+        .map(|mres| mres.upgrade().unwrap())
+        .collect::<Vec<_>>();
+    let borders = hat!(tables).elect_border_routers(
+        &tables.zid,
+        &key_expr,
+        router_source,
+        mres_subs
+            .iter()
+            .flat_map(|mres| res_hat!(mres).router_subs.iter()),
+    );
+    let no_borders = HashSet::new();
+
+    for mres in mres_subs.iter() {
+        if master || source_type == WhatAmI::Router {
+            insert_faces_for_subs(
+                &mut route,
+                expr,
+                tables,
+                net,
+                router_source,
+                &res_hat!(mres).router_subs,
+                &borders,
+                alternate,
+            );
+        }
+
+        if (master || source_type != WhatAmI::Router) && hat!(tables).full_net(WhatAmI::Peer) {
+            let net = hat!(tables).linkstatepeers_net.as_ref().unwrap();
+            let peer_source = match source_type {
+                WhatAmI::Peer => source,
+                _ => net.idx.index() as NodeId,
+            };
+            insert_faces_for_subs(
+                &mut route,
+                expr,
+                tables,
+                net,
+                peer_source,
+                &res_hat!(mres).linkstatepeer_subs,
+                &no_borders,
+                false,
+            );
+        }
+
+        if master || source_type == WhatAmI::Router {
+            for (sid, context) in &mres.session_ctxs {
+                if context.subs.is_some() && context.face.whatami != WhatAmI::Router {
+                    route.entry(*sid).or_insert_with(|| {
+                        let key_expr = Resource::get_best_key(expr.prefix, expr.suffix, *sid);
+                        (context.face.clone(), key_expr.to_owned(), NodeId::default())
+                    });
+                }
+            }
+        }
+    }
+    for mcast_group in &tables.mcast_groups {
+        route.insert(
+            mcast_group.id,
+            (
+                mcast_group.clone(),
+                expr.full_expr().to_string().into(),
+                NodeId::default(),
+            ),
+        );
+    }
+    Arc::new(route)
+}
+
+// Keeps only the directions of the route towards other routers
+fn routers_only(route: &Route) -> Arc<Route> {
+    Arc::new(
+        route
+            .iter()
+            .filter(|(_, (face, _, _))| face.whatami == WhatAmI::Router)
+            .map(|(id, direction)| (*id, direction.clone()))
+            .collect(),
+    )
+}
+
+// The size of the window of sequence numbers in which the duplicates are detected
+const REDUNDANCY_WINDOW: u64 = 64;
+
+// The time after which the sequence numbers received from a silent source router are forgotten,
+// as it may have restarted in the meantime
+const REDUNDANCY_TIMEOUT: Duration = Duration::from_secs(10);
+
+// The sequence numbers of the redundant data recently received from a source router
+struct ReceivedSns {
+    last: u64,
+    // Bit n is set if `last - n` was received
+    mask: u64,
+    received_at: Instant,
+}
+
+impl ReceivedSns {
+    fn new(now: Instant) -> Self {
+        ReceivedSns {
+            last: 0,
+            mask: 0,
+            received_at: now,
+        }
+    }
+
+    // Records `sn` and returns true if it was already received or is older than the window
+    fn check_duplicate(&mut self, sn: u64, now: Instant) -> bool {
+        if now.saturating_duration_since(self.received_at) >= REDUNDANCY_TIMEOUT {
+            self.mask = 0;
+        }
+        self.received_at = now;
+        if self.mask == 0 || sn > self.last {
+            let shift = sn.wrapping_sub(self.last);
+            self.mask = if self.mask == 0 || shift >= REDUNDANCY_WINDOW {
+                1
+            } else {
+                (self.mask << shift) | 1
+            };
+            self.last = sn;
+            false
+        } else if self.last - sn >= REDUNDANCY_WINDOW {
+            // A copy delayed beyond the window, whose other copy was most likely received
+            true
+        } else {
+            let bit = 1 << (self.last - sn);
+            let duplicate = self.mask & bit != 0;
+            self.mask |= bit;
+            duplicate
+        }
+    }
+}
+
+// Assigns the sequence numbers of the redundant data sourced by this router
+// and detects the duplicates of the redundant data received from other routers
+#[derive(Default)]
+pub(super) struct RedundancyFilter {
+    next_sn: AtomicU64,
+    received: Mutex<HashMap<ZenohIdProto, ReceivedSns>>,
+}
+
+impl RedundancyFilter {
+    fn next_sn(&self) -> u64 {
+        self.next_sn.fetch_add(1, Ordering::Relaxed)
+    }
+
+    fn is_duplicate(&self, source: ZenohIdProto, sn: u64) -> bool {
+        let now = Instant::now();
+        zlock!(self.received)
+            .entry(source)
+            .or_insert_with(|| ReceivedSns::new(now))
+            .check_duplicate(sn, now)
+    }
+
+    // Forgets the sequence numbers received from a source router, e.g. when it is removed from the
+    // network or a new link is established with it, as it may have restarted
+    pub(super) fn remove_source(&self, source: &ZenohIdProto) {
+        zlock!(self.received).remove(source);
+    }
+}
+
 impl HatPubSubTrait for HatCode {
     fn declare_subscription(
         &self,
@@ -1190,129 +1595,68 @@ impl HatPubSubTrait for HatCode {
         source: NodeId,
         source_type: WhatAmI,
     ) -> Arc<Route> {
-        #[inline]
-        fn insert_faces_for_subs(
-            route: &mut Route,
-            expr: &RoutingExpr,
-            tables: &Tables,
-            net: &Network,
-            source: NodeId,
-            subs: &HashSet<ZenohIdProto>,
-        ) {
-            if net.trees.len() > source as usize {
-                for sub in subs {
-                    if let Some(sub_idx) = net.get_idx(sub) {
-                        if net.trees[source as usize].directions.len() > sub_idx.index() {
-                            if let Some(direction) =
-                                net.trees[source as usize].directions[sub_idx.index()]
-                            {
-                                if net.graph.contains_node(direction) {
-                                    if let Some(face) = tables.get_face(&net.graph[direction].zid) {
-                                        route.entry(face.id).or_insert_with(|| {
-                                            let key_expr = Resource::get_best_key(
-                                                expr.prefix,
-                                                expr.suffix,
-                                                face.id,
-                                            );
-                                            (face.clone(), key_expr.to_owned(), source)
-                                        });
-                                    }
-                                }
-                            }
-                        }
-                    }
-                }
-            } else {
-                tracing::trace!("Tree for node sid:{} not yet ready", source);
-            }
-        }
-
-        let mut route = HashMap::new();
-        let key_expr = expr.full_expr();
-        if key_expr.ends_with('/') {
-            return Arc::new(route);
-        }
-        tracing::trace!(
-            "compute_data_route({}, {:?}, {:?})",
-            key_expr,
-            source,
-            source_type
-        );
-        let key_expr = match OwnedKeyExpr::try_from(key_expr) {
-            Ok(ke) => ke,
-            Err(e) => {
-                tracing::warn!("Invalid KE reached the system: {}", e);
-                return Arc::new(route);
-            }
-        };
-        let res = Resource::get_resource(expr.prefix, expr.suffix);
-        let matches = res
-            .as_ref()
-            .and_then(|res| res.context.as_ref())
-            .map(|ctx| Cow::from(&ctx.matches))
-            .unwrap_or_else(|| Cow::from(Resource::get_matches(tables, &key_expr)));
-
-        let master = !hat!(tables).full_net(WhatAmI::Peer)
-            || *hat!(tables).elect_router(&tables.zid, &key_expr, hat!(tables).shared_nodes.iter())
-                == tables.zid;
-
-        for mres in matches.iter() {
-            let mres = mres.upgrade().unwrap();
+        compute_data_route_(tables, expr, source, source_type, false)
+    }
 
-            if master || source_type == WhatAmI::Router {
-                let net = hat!(tables).routers_net.as_ref().unwrap();
-                let router_source = match source_type {
-                    WhatAmI::Router => source,
-                    _ => net.idx.index() as NodeId,
-                };
-                insert_faces_for_subs(
-                    &mut route,
-                    expr,
+    fn compute_redundant_data_routes(
+        &self,
+        tables: &Tables,
+        face: &FaceState,
+        expr: &mut RoutingExpr,
+        routing_context: NodeId,
+        redundancy: Option<RedundancyType>,
+    ) -> Option<Vec<(Arc<Route>, RedundancyType)>> {
+        let hat = hat!(tables);
+        let net = hat.routers_net.as_ref()?;
+        match redundancy {
+            // Forwarded by another router on one of its redundant routes
+            Some(redundancy) if face.whatami == WhatAmI::Router => {
+                let source = self.map_routing_context(tables, face, routing_context);
+                let source_zid = net.graph.node_weight(NodeIndex::new(source as usize))?.zid;
+                let route = compute_data_route_(
                     tables,
-                    net,
-                    router_source,
-                    &res_hat!(mres).router_subs,
-                );
-            }
-
-            if (master || source_type != WhatAmI::Router) && hat!(tables).full_net(WhatAmI::Peer) {
-                let net = hat!(tables).linkstatepeers_net.as_ref().unwrap();
-                let peer_source = match source_type {
-                    WhatAmI::Peer => source,
-                    _ => net.idx.index() as NodeId,
-                };
-                insert_faces_for_subs(
-                    &mut route,
                     expr,
-                    tables,
-                    net,
-                    peer_source,
-                    &res_hat!(mres).linkstatepeer_subs,
+                    source,
+                    WhatAmI::Router,
+                    redundancy.alternate,
                 );
+                // A duplicate is still forwarded to the next routers as the other copy
+                // may not reach them
+                let route = if hat
+                    .redundancy_filter
+                    .is_duplicate(source_zid, redundancy.sn)
+                {
+                    routers_only(&route)
+                } else {
+                    route
+                };
+                Some(vec![(route, redundancy)])
             }
-
-            if master || source_type == WhatAmI::Router {
-                for (sid, context) in &mres.session_ctxs {
-                    if context.subs.is_some() && context.face.whatami != WhatAmI::Router {
-                        route.entry(*sid).or_insert_with(|| {
-                            let key_expr = Resource::get_best_key(expr.prefix, expr.suffix, *sid);
-                            (context.face.clone(), key_expr.to_owned(), NodeId::default())
-                        });
-                    }
-                }
+            // Received from a session: this router is the source of the redundant routes
+            None if face.whatami != WhatAmI::Router && hat.is_redundant(expr) => {
+                let source = self.map_routing_context(tables, face, routing_context);
+                let sn = hat.redundancy_filter.next_sn();
+                let primary = compute_data_route_(tables, expr, source, face.whatami, false);
+                let alternate = compute_data_route_(tables, expr, source, face.whatami, true);
+                Some(vec![
+                    (
+                        primary,
+                        RedundancyType {
+                            sn,
+                            alternate: false,
+                        },
+                    ),
+                    (
+                        routers_only(&alternate),
+                        RedundancyType {
+                            sn,
+                            alternate: true,
+                        },
+                    ),
+                ])
             }
+            _ => None,
         }
-        for mcast_group in &tables.mcast_groups {
-            route.insert(
-                mcast_group.id,
-                (
-                    mcast_group.clone(),
-                    expr.full_expr().to_string().into(),
-                    NodeId::default(),
-                ),
-            );
-        }
-        Arc::new(route)
     }
 
     fn get_data_routes_entries(&self, tables: &Tables) -> RoutesIndexes {
@@ -1407,3 +1751,53 @@ impl HatPubSubTrait for HatCode {
         matching_subscriptions
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use zenoh_protocol::core::ZenohIdProto;
+
+    use super::{ReceivedSns, RedundancyFilter, REDUNDANCY_TIMEOUT, REDUNDANCY_WINDOW};
+
+    #[test]
+    fn redundancy_stale_copies() {
+        let now = Instant::now();
+        let mut sns = ReceivedSns::new(now);
+        for sn in 0..200 {
+            assert!(!sns.check_duplicate(sn, now));
+        }
+        assert!(sns.check_duplicate(199, now));
+        assert!(sns.check_duplicate(199 - REDUNDANCY_WINDOW + 1, now));
+        // A copy late by more than the window is dropped and doesn't reset the window
+        assert!(sns.check_duplicate(10, now));
+        assert!(sns.check_duplicate(198, now));
+        assert!(!sns.check_duplicate(200, now));
+        assert!(sns.check_duplicate(200, now));
+    }
+
+    #[test]
+    fn redundancy_source_restart() {
+        let now = Instant::now();
+        let mut sns = ReceivedSns::new(now);
+        for sn in 0..200 {
+            assert!(!sns.check_duplicate(sn, now));
+        }
+        // The samples of a source restarting at 0 are forwarded once it has been silent long enough
+        let later = now + REDUNDANCY_TIMEOUT;
+        assert!(!sns.check_duplicate(0, later));
+        assert!(!sns.check_duplicate(1, later));
+        assert!(sns.check_duplicate(1, later + Duration::from_millis(1)));
+
+        // Or once the source has been removed, as done on a new link with it
+        let filter = RedundancyFilter::default();
+        let source = ZenohIdProto::default();
+        for sn in 0..200 {
+            assert!(!filter.is_duplicate(source, sn));
+        }
+        assert!(filter.is_duplicate(source, 0));
+        filter.remove_source(&source);
+        assert!(!filter.is_duplicate(source, 0));
+        assert!(filter.is_duplicate(source, 0));
+    }
+}
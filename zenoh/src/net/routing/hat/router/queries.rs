@@ -13,7 +13,7 @@
 //
 use std::{
     borrow::Cow,
-    collections::HashMap,
+    collections::{HashMap, HashSet},
     sync::{atomic::Ordering, Arc},
 };
 
@@ -24,7 +24,7 @@ use zenoh_protocol::{
             include::{Includer, DEFAULT_INCLUDER},
             OwnedKeyExpr,
         },
-        WhatAmI, ZenohIdProto,
+        WhatAmI, WireExpr, ZenohIdProto,
     },
     network::{
         declare::{
@@ -101,7 +101,7 @@ fn local_peer_qabl_info(tables: &Tables, res: &Arc<Resource>) -> QueryableInfoTy
             .router_qabls
             .iter()
             .fold(None, |accu, (zid, info)| {
-                if *zid != tables.zid {
+                if *zid != tables.zid && !is_provisional(tables, res, zid) {
                     Some(match accu {
                         Some(accu) => merge_qabl_infos(accu, info),
                         None => *info,
@@ -138,7 +138,7 @@ fn local_qabl_info(
             .router_qabls
             .iter()
             .fold(None, |accu, (zid, info)| {
-                if *zid != tables.zid {
+                if *zid != tables.zid && !is_provisional(tables, res, zid) {
                     Some(match accu {
                         Some(accu) => merge_qabl_infos(accu, info),
                         None => *info,
@@ -198,7 +198,7 @@ fn send_sourced_queryable_to_net_children(
     routing_context: NodeId,
 ) {
     for child in children {
-        if net.graph.contains_node(*child) {
+        if net.graph.contains_node(*child) && !net.is_foreign(*child) {
             match tables.get_face(&net.graph[*child].zid).cloned() {
                 Some(mut someface) => {
                     if src_face
@@ -342,8 +342,12 @@ fn register_router_queryable(
     router: ZenohIdProto,
     send_declare: &mut SendDeclare,
 ) {
+    // A queryable restored from a snapshot is propagated once declared again
+    let provisional = hat_mut!(tables)
+        .provisional_qabls
+        .remove(&(res.clone(), router));
     let current_info = res_hat!(res).router_qabls.get(&router);
-    if current_info.is_none() || current_info.unwrap() != qabl_info {
+    if provisional || current_info.is_none() || current_info.unwrap() != qabl_info {
         // Register router queryable
         {
             res_hat_mut!(res).router_qabls.insert(router, *qabl_info);
@@ -359,6 +363,11 @@ fn register_router_queryable(
             &router,
             WhatAmI::Router,
         );
+
+        // Propagate queryable to other regions
+        if hat!(tables).is_native_router(&router) {
+            update_border_queryable(tables, res);
+        }
     }
 
     if hat!(tables).full_net(WhatAmI::Peer) {
@@ -467,7 +476,16 @@ fn remote_router_qabls(tables: &Tables, res: &Arc<Resource>) -> bool {
         && res_hat!(res)
             .router_qabls
             .keys()
-            .any(|router| router != &tables.zid)
+            .any(|router| router != &tables.zid && !is_provisional(tables, res, router))
+}
+
+// Whether the queryable of `router` on `res` was restored from a snapshot and not declared again
+#[inline]
+fn is_provisional(tables: &Tables, res: &Arc<Resource>, router: &ZenohIdProto) -> bool {
+    !hat!(tables).provisional_qabls.is_empty()
+        && hat!(tables)
+            .provisional_qabls
+            .contains(&(res.clone(), *router))
 }
 
 #[inline]
@@ -510,7 +528,7 @@ fn send_forget_sourced_queryable_to_net_children(
     routing_context: NodeId,
 ) {
     for child in children {
-        if net.graph.contains_node(*child) {
+        if net.graph.contains_node(*child) && !net.is_foreign(*child) {
             match tables.get_face(&net.graph[*child].zid).cloned() {
                 Some(mut someface) => {
                     if src_face
@@ -695,6 +713,10 @@ fn unregister_router_queryable(
     send_declare: &mut SendDeclare,
 ) {
     res_hat_mut!(res).router_qabls.remove(router);
+    hat_mut!(tables)
+        .provisional_qabls
+        .remove(&(res.clone(), *router));
+    update_border_queryable(tables, res);
 
     if res_hat!(res).router_qabls.is_empty() {
         hat_mut!(tables)
@@ -710,6 +732,22 @@ fn unregister_router_queryable(
     propagate_forget_simple_queryable_to_peers(tables, res, send_declare);
 }
 
+// Removes a queryable restored from a snapshot that was not declared again, without
+// propagating its removal as it was never propagated
+pub(super) fn remove_provisional_queryable(
+    tables: &mut Tables,
+    res: &mut Arc<Resource>,
+    router: &ZenohIdProto,
+) {
+    res_hat_mut!(res).router_qabls.remove(router);
+    if res_hat!(res).router_qabls.is_empty() {
+        hat_mut!(tables)
+            .router_qabls
+            .retain(|qabl| !Arc::ptr_eq(qabl, res));
+    }
+    update_border_queryable(tables, res);
+}
+
 fn undeclare_router_queryable(
     tables: &mut Tables,
     face: Option<&Arc<FaceState>>,
@@ -878,6 +916,97 @@ fn forget_simple_queryable(
     }
 }
 
+// Returns the merged info of the queryables of this region on `res`, if any
+fn native_router_qabl_info(tables: &Tables, res: &Arc<Resource>) -> Option<QueryableInfoType> {
+    res_hat!(res)
+        .router_qabls
+        .iter()
+        .filter(|(router, _)| hat!(tables).is_native_router(router))
+        .fold(None, |accu, (_, info)| {
+            Some(match accu {
+                Some(accu) => merge_qabl_infos(accu, info),
+                None => *info,
+            })
+        })
+}
+
+fn send_border_queryable(
+    face: &Arc<FaceState>,
+    net: &Network,
+    key: &OwnedKeyExpr,
+    qabl_info: Option<&QueryableInfoType>,
+) {
+    let wire_expr = WireExpr::from(key).to_owned();
+    let body = match qabl_info {
+        Some(qabl_info) => DeclareBody::DeclareQueryable(DeclareQueryable {
+            id: 0,
+            wire_expr,
+            ext_info: *qabl_info,
+        }),
+        None => DeclareBody::UndeclareQueryable(UndeclareQueryable {
+            id: 0,
+            ext_wire_expr: WireExprType { wire_expr },
+        }),
+    };
+    face.primitives.send_declare(RoutingContext::with_expr(
+        Declare {
+            interest_id: None,
+            ext_qos: ext::QoSType::DECLARE,
+            ext_tstamp: None,
+            ext_nodeid: ext::NodeIdType {
+                node_id: net.idx.index() as NodeId,
+            },
+            body,
+        },
+        key.to_string(),
+    ));
+}
+
+// Declares, updates or undeclares the queryables of this region on `res` to the border routers
+// of other regions, as queryables of this router
+fn update_border_queryable(tables: &mut Tables, res: &Arc<Resource>) {
+    let faces = tables
+        .faces
+        .values()
+        .filter(|face| face_hat!(face).border)
+        .cloned()
+        .collect::<Vec<_>>();
+    if faces.is_empty() {
+        return;
+    }
+    let Ok(key) = OwnedKeyExpr::try_from(res.expr()) else {
+        return;
+    };
+    let qabl_info = native_router_qabl_info(tables, res);
+    if qabl_info.as_ref() == hat!(tables).border_qabls.get(&key) {
+        return;
+    }
+    match qabl_info {
+        Some(qabl_info) => hat_mut!(tables).border_qabls.insert(key.clone(), qabl_info),
+        None => hat_mut!(tables).border_qabls.remove(&key),
+    };
+    let net = hat!(tables).routers_net.as_ref().unwrap();
+    for face in faces {
+        send_border_queryable(&face, net, &key, qabl_info.as_ref());
+    }
+}
+
+pub(super) fn queries_new_border_face(tables: &mut Tables, face: &Arc<FaceState>) {
+    let qabls = hat!(tables)
+        .router_qabls
+        .iter()
+        .filter_map(|res| {
+            let qabl_info = native_router_qabl_info(tables, res)?;
+            Some((OwnedKeyExpr::try_from(res.expr()).ok()?, qabl_info))
+        })
+        .collect::<HashMap<_, _>>();
+    let net = hat!(tables).routers_net.as_ref().unwrap();
+    for (key, qabl_info) in &qabls {
+        send_border_queryable(face, net, key, Some(qabl_info));
+    }
+    hat_mut!(tables).border_qabls = qabls;
+}
+
 pub(super) fn queries_remove_node(
     tables: &mut Tables,
     node: &ZenohIdProto,
@@ -1058,7 +1187,11 @@ pub(super) fn queries_tree_change(
                         WhatAmI::Router => &res_hat!(res).router_qabls,
                         _ => &res_hat!(res).linkstatepeer_qabls,
                     };
-                    if let Some(qabl_info) = qabls.get(&tree_id) {
+                    if let Some(qabl_info) = qabls.get(&tree_id).filter(|_| {
+                        !hat!(tables)
+                            .provisional_qabls
+                            .contains(&(res.clone(), tree_id))
+                    }) {
                         send_sourced_queryable_to_net_children(
                             tables,
                             net,
@@ -1079,6 +1212,7 @@ pub(super) fn queries_tree_change(
 }
 
 #[inline]
+#[allow(clippy::too_many_arguments)]
 fn insert_target_for_qabls(
     route: &mut QueryTargetQablSet,
     expr: &mut RoutingExpr,
@@ -1086,11 +1220,15 @@ fn insert_target_for_qabls(
     net: &Network,
     source: NodeId,
     qabls: &HashMap<ZenohIdProto, QueryableInfoType>,
+    borders: &HashSet<ZenohIdProto>,
     complete: bool,
 ) {
     if net.trees.len() > source as usize {
         for (qabl, qabl_info) in qabls {
             if let Some(qabl_idx) = net.get_idx(qabl) {
+                if net.is_foreign(qabl_idx) && !borders.contains(qabl) {
+                    continue;
+                }
                 if net.trees[source as usize].directions.len() > qabl_idx.index() {
                     if let Some(direction) = net.trees[source as usize].directions[qabl_idx.index()]
                     {
@@ -1429,15 +1567,28 @@ impl HatQueriesTrait for HatCode {
             || *hat!(tables).elect_router(&tables.zid, &key_expr, hat!(tables).shared_nodes.iter())
                 == tables.zid;
 
-        for mres in matches.iter() {
-            let mres = mres.upgrade().unwrap();
+        let net = hat!(tables).routers_net.as_ref().unwrap();
+        let router_source = match source_type {
+            WhatAmI::Router => source,
+            _ => net.idx.index() as NodeId,
+        };
+        let mres_qabls = matches
+            .iter()
+            .map(|mres| mres.upgrade().unwrap())
+            .collect::<Vec<_>>();
+        let borders = hat!(tables).elect_border_routers(
+            &tables.zid,
+            &key_expr,
+            router_source,
+            mres_qabls
+                .iter()
+                .flat_map(|mres| res_hat!(mres).router_qabls.keys()),
+        );
+        let no_borders = HashSet::new();
+
+        for mres in mres_qabls.iter() {
             let complete = DEFAULT_INCLUDER.includes(mres.expr().as_bytes(), key_expr.as_bytes());
             if master || source_type == WhatAmI::Router {
-                let net = hat!(tables).routers_net.as_ref().unwrap();
-                let router_source = match source_type {
-                    WhatAmI::Router => source,
-                    _ => net.idx.index() as NodeId,
-                };
                 insert_target_for_qabls(
                     &mut route,
                     expr,
@@ -1445,6 +1596,7 @@ impl HatQueriesTrait for HatCode {
                     net,
                     router_source,
                     &res_hat!(mres).router_qabls,
+                    &borders,
                     complete,
                 );
             }
@@ -1462,6 +1614,7 @@ impl HatQueriesTrait for HatCode {
                     net,
                     peer_source,
                     &res_hat!(mres).linkstatepeer_qabls,
+                    &no_borders,
                     complete,
                 );
             }
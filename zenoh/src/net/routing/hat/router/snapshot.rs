@@ -0,0 +1,325 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Duration,
+};
+
+use serde::{Deserialize, Serialize};
+use zenoh_protocol::{
+    core::{key_expr::keyexpr, WhatAmI, ZenohIdProto},
+    network::declare::queryable::ext::QueryableInfoType,
+};
+use zenoh_result::{bail, ZResult};
+use zenoh_sync::get_mut_unchecked;
+
+use super::{
+    hat, hat_mut,
+    network::Node,
+    pubsub::{pubsub_remove_node, remove_provisional_subscription},
+    queries::{queries_remove_node, remove_provisional_queryable},
+    res_hat, res_hat_mut,
+    token::token_remove_node,
+    HatContext, HatTables,
+};
+use crate::net::{
+    routing::{
+        dispatcher::{
+            pubsub::{update_data_routes_from, update_matches_data_routes},
+            queries::{update_matches_query_routes, update_query_routes_from},
+            resource::Resource,
+            tables::{Tables, TablesLock},
+        },
+        hat::{SendDeclare, Snapshot},
+    },
+    runtime::Runtime,
+};
+
+#[derive(Serialize, Deserialize)]
+struct SnapshotNode {
+    zid: ZenohIdProto,
+    whatami: Option<WhatAmI>,
+    links: Vec<ZenohIdProto>,
+    link_weights: HashMap<ZenohIdProto, u16>,
+    region: Option<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct SnapshotSubscriber {
+    key_expr: String,
+    router: ZenohIdProto,
+}
+
+#[derive(Serialize, Deserialize)]
+struct SnapshotQueryable {
+    key_expr: String,
+    router: ZenohIdProto,
+    complete: bool,
+    distance: u16,
+}
+
+// The state of the routers network as seen by a router: its linkstate graph
+// and the subscribers and queryables declared by the other routers. The liveliness
+// tokens are left out as a restored token would assert the liveliness of an entity
+// that may be gone.
+#[derive(Serialize, Deserialize)]
+pub(super) struct RoutingSnapshot {
+    zid: ZenohIdProto,
+    sn: u64,
+    nodes: Vec<SnapshotNode>,
+    subscribers: Vec<SnapshotSubscriber>,
+    queryables: Vec<SnapshotQueryable>,
+}
+
+pub(super) fn make_snapshot(tables: &Tables) -> Option<RoutingSnapshot> {
+    let net = hat!(tables).routers_net.as_ref()?;
+    let nodes = net
+        .graph
+        .node_indices()
+        // The restored nodes that did not send their state yet are saved again, unlike the nodes
+        // only known as the neighbours of other nodes
+        .filter(|idx| {
+            *idx != net.idx && (net.graph[*idx].sn != 0 || !net.graph[*idx].links.is_empty())
+        })
+        .map(|idx| {
+            let node = &net.graph[idx];
+            SnapshotNode {
+                zid: node.zid,
+                whatami: node.whatami,
+                links: node.links.clone(),
+                link_weights: node.link_weights.clone(),
+                region: node.region.clone(),
+            }
+        })
+        .collect();
+    let subscribers = hat!(tables)
+        .router_subs
+        .iter()
+        .flat_map(|res| {
+            res_hat!(res)
+                .router_subs
+                .iter()
+                .filter(|router| **router != tables.zid)
+                .map(|router| SnapshotSubscriber {
+                    key_expr: res.expr(),
+                    router: *router,
+                })
+        })
+        .collect();
+    let queryables = hat!(tables)
+        .router_qabls
+        .iter()
+        .flat_map(|res| {
+            res_hat!(res)
+                .router_qabls
+                .iter()
+                .filter(|(router, _)| **router != tables.zid)
+                .map(|(router, info)| SnapshotQueryable {
+                    key_expr: res.expr(),
+                    router: *router,
+                    complete: info.complete,
+                    distance: info.distance,
+                })
+        })
+        .collect();
+    Some(RoutingSnapshot {
+        zid: tables.zid,
+        sn: net.graph[net.idx].sn,
+        nodes,
+        subscribers,
+        queryables,
+    })
+}
+
+pub(super) fn encode_snapshot(path: &Path, tables: &Tables) -> Option<Snapshot> {
+    let snapshot = make_snapshot(tables)?;
+    match serde_json::to_vec(&snapshot) {
+        Ok(content) => Some(Snapshot {
+            path: path.to_path_buf(),
+            content,
+        }),
+        Err(e) => {
+            tracing::warn!("Unable to encode routing snapshot: {}", e);
+            None
+        }
+    }
+}
+
+pub(super) fn load_snapshot(path: &Path, zid: &ZenohIdProto) -> ZResult<Option<RoutingSnapshot>> {
+    let content = match std::fs::read(path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(e.into()),
+    };
+    let snapshot: RoutingSnapshot = serde_json::from_slice(&content)?;
+    if snapshot.zid != *zid {
+        bail!("Snapshot of router {} ignored", snapshot.zid);
+    }
+    Ok(Some(snapshot))
+}
+
+// Restores the state saved in the snapshot as provisional state, the restored declarations
+// being neither propagated nor kept if they are not declared again before reconciliation
+pub(super) fn restore_snapshot(tables: &mut Tables, snapshot: RoutingSnapshot) {
+    let Some(net) = hat_mut!(tables).routers_net.as_mut() else {
+        return;
+    };
+    net.restore_nodes(
+        snapshot.sn,
+        snapshot
+            .nodes
+            .into_iter()
+            .map(|node| Node {
+                zid: node.zid,
+                whatami: node.whatami,
+                locators: None,
+                sn: 0,
+                links: node.links,
+                link_weights: node.link_weights,
+                region: node.region,
+            })
+            .collect(),
+    );
+
+    for sub in snapshot.subscribers {
+        if let Some(mut res) = restored_resource(tables, &sub.key_expr, &sub.router) {
+            res_hat_mut!(&mut res).router_subs.insert(sub.router);
+            hat_mut!(tables).router_subs.insert(res.clone());
+            hat_mut!(tables).provisional_subs.insert((res, sub.router));
+        }
+    }
+    for qabl in snapshot.queryables {
+        if let Some(mut res) = restored_resource(tables, &qabl.key_expr, &qabl.router) {
+            let info = QueryableInfoType {
+                complete: qabl.complete,
+                distance: qabl.distance,
+            };
+            res_hat_mut!(&mut res)
+                .router_qabls
+                .insert(qabl.router, info);
+            hat_mut!(tables).router_qabls.insert(res.clone());
+            hat_mut!(tables)
+                .provisional_qabls
+                .insert((res, qabl.router));
+        }
+    }
+    tracing::debug!(
+        "Restored {} subscribers and {} queryables from snapshot",
+        hat!(tables).provisional_subs.len(),
+        hat!(tables).provisional_qabls.len()
+    );
+
+    update_data_routes_from(tables, &mut tables.root_res.clone());
+    update_query_routes_from(tables, &mut tables.root_res.clone());
+}
+
+fn restored_resource(
+    tables: &mut Tables,
+    key_expr: &str,
+    router: &ZenohIdProto,
+) -> Option<Arc<Resource>> {
+    if *router == tables.zid {
+        return None;
+    }
+    let Ok(ke) = keyexpr::new(key_expr) else {
+        tracing::warn!("Invalid key expression in snapshot: {}", key_expr);
+        return None;
+    };
+    let mut matches = Resource::get_matches(tables, ke);
+    let mut res = Resource::make_resource(tables, &mut tables.root_res.clone(), key_expr);
+    matches.push(Arc::downgrade(&res));
+    Resource::match_resource(tables, &mut res, matches);
+    Some(res)
+}
+
+// Removes the restored nodes that are still not connected to this router
+// and the restored declarations that were not declared again
+pub(super) fn reconcile(
+    tables: &mut Tables,
+    tables_ref: &Arc<TablesLock>,
+    send_declare: &mut SendDeclare,
+) {
+    let removed = hat_mut!(tables)
+        .routers_net
+        .as_mut()
+        .map(|net| net.remove_detached_nodes())
+        .unwrap_or_default();
+    for (_, node) in &removed {
+        pubsub_remove_node(tables, &node.zid, WhatAmI::Router, send_declare);
+        queries_remove_node(tables, &node.zid, WhatAmI::Router, send_declare);
+        token_remove_node(tables, &node.zid, WhatAmI::Router, send_declare);
+    }
+    if !removed.is_empty() {
+        hat_mut!(tables).schedule_compute_trees(tables_ref.clone(), WhatAmI::Router);
+    }
+
+    let subs = std::mem::take(&mut hat_mut!(tables).provisional_subs);
+    let qabls = std::mem::take(&mut hat_mut!(tables).provisional_qabls);
+    tracing::debug!(
+        "Reconcile snapshot: remove {} nodes, {} subscribers and {} queryables",
+        removed.len(),
+        subs.len(),
+        qabls.len()
+    );
+    // The provisional declarations were never propagated and are removed silently
+    for (mut res, router) in subs {
+        remove_provisional_subscription(tables, &mut res, &router);
+        update_matches_data_routes(tables, &mut res);
+        Resource::clean(&mut res);
+    }
+    for (mut res, router) in qabls {
+        remove_provisional_queryable(tables, &mut res, &router);
+        update_matches_query_routes(tables, &res);
+        Resource::clean(&mut res);
+    }
+}
+
+// Saves the snapshot periodically and reconciles the restored state after the given delay
+pub(super) fn spawn_snapshot_tasks(
+    runtime: &Runtime,
+    path: PathBuf,
+    period: Duration,
+    reconciliation_delay: Duration,
+) {
+    let r = runtime.clone();
+    runtime.spawn_abortable(async move {
+        tokio::time::sleep(reconciliation_delay).await;
+        let tables_ref = r.router().tables.clone();
+        let mut declares = vec![];
+        let ctrl_lock = zlock!(tables_ref.ctrl_lock);
+        let mut tables = zwrite!(tables_ref.tables);
+        reconcile(&mut tables, &tables_ref, &mut |p, m| {
+            declares.push((p.clone(), m))
+        });
+        drop(tables);
+        drop(ctrl_lock);
+        for (p, m) in declares {
+            p.send_declare(m);
+        }
+    });
+
+    let r = runtime.clone();
+    runtime.spawn_abortable(async move {
+        loop {
+            tokio::time::sleep(period).await;
+            let tables_ref = r.router().tables.clone();
+            let snapshot = encode_snapshot(&path, &zread!(tables_ref.tables));
+            if let Some(snapshot) = snapshot {
+                snapshot.save().await;
+            }
+        }
+    });
+}
@@ -12,11 +12,14 @@
 //   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
 //
 
-use std::sync::{atomic::Ordering, Arc};
+use std::{
+    collections::HashSet,
+    sync::{atomic::Ordering, Arc},
+};
 
 use petgraph::graph::NodeIndex;
 use zenoh_protocol::{
-    core::{WhatAmI, ZenohIdProto},
+    core::{key_expr::OwnedKeyExpr, WhatAmI, WireExpr, ZenohIdProto},
     network::{
         declare::{common::ext::WireExprType, TokenId},
         ext,
@@ -48,7 +51,7 @@ fn send_sourced_token_to_net_clildren(
     routing_context: NodeId,
 ) {
     for child in clildren {
-        if net.graph.contains_node(*child) {
+        if net.graph.contains_node(*child) && !net.is_foreign(*child) {
             match tables.get_face(&net.graph[*child].zid).cloned() {
                 Some(mut someface) => {
                     if src_face
@@ -221,6 +224,11 @@ fn register_router_token(
 
         // Propagate liveliness to routers
         propagate_sourced_token(tables, res, Some(face), &router, WhatAmI::Router);
+
+        // Propagate liveliness to other regions
+        if hat!(tables).is_native_router(&router) {
+            update_border_token(tables, res);
+        }
     }
     // Propagate liveliness to peers
     if hat!(tables).full_net(WhatAmI::Peer) && face.whatami != WhatAmI::Peer {
@@ -359,7 +367,7 @@ fn send_forget_sourced_token_to_net_clildren(
     routing_context: Option<NodeId>,
 ) {
     for child in clildren {
-        if net.graph.contains_node(*child) {
+        if net.graph.contains_node(*child) && !net.is_foreign(*child) {
             match tables.get_face(&net.graph[*child].zid).cloned() {
                 Some(mut someface) => {
                     if src_face
@@ -612,6 +620,7 @@ fn unregister_router_token(
     res_hat_mut!(res)
         .router_tokens
         .retain(|token| token != router);
+    update_border_token(tables, res);
 
     if res_hat!(res).router_tokens.is_empty() {
         hat_mut!(tables)
@@ -792,6 +801,82 @@ fn forget_simple_token(
     }
 }
 
+#[inline]
+fn native_router_tokens(tables: &Tables, res: &Arc<Resource>) -> bool {
+    res_hat!(res)
+        .router_tokens
+        .iter()
+        .any(|router| hat!(tables).is_native_router(router))
+}
+
+fn send_border_token(face: &Arc<FaceState>, net: &Network, key: &OwnedKeyExpr, declare: bool) {
+    let wire_expr = WireExpr::from(key).to_owned();
+    let body = if declare {
+        DeclareBody::DeclareToken(DeclareToken { id: 0, wire_expr })
+    } else {
+        DeclareBody::UndeclareToken(UndeclareToken {
+            id: 0,
+            ext_wire_expr: WireExprType { wire_expr },
+        })
+    };
+    face.primitives.send_declare(RoutingContext::with_expr(
+        Declare {
+            interest_id: None,
+            ext_qos: ext::QoSType::DECLARE,
+            ext_tstamp: None,
+            ext_nodeid: ext::NodeIdType {
+                node_id: net.idx.index() as NodeId,
+            },
+            body,
+        },
+        key.to_string(),
+    ));
+}
+
+// Declares or undeclares the tokens of this region on `res` to the border routers
+// of other regions, as tokens of this router
+fn update_border_token(tables: &mut Tables, res: &Arc<Resource>) {
+    let faces = tables
+        .faces
+        .values()
+        .filter(|face| face_hat!(face).border)
+        .cloned()
+        .collect::<Vec<_>>();
+    if faces.is_empty() {
+        return;
+    }
+    let Ok(key) = OwnedKeyExpr::try_from(res.expr()) else {
+        return;
+    };
+    let declare = native_router_tokens(tables, res);
+    if declare == hat!(tables).border_tokens.contains(&key) {
+        return;
+    }
+    if declare {
+        hat_mut!(tables).border_tokens.insert(key.clone());
+    } else {
+        hat_mut!(tables).border_tokens.remove(&key);
+    }
+    let net = hat!(tables).routers_net.as_ref().unwrap();
+    for face in faces {
+        send_border_token(&face, net, &key, declare);
+    }
+}
+
+pub(super) fn token_new_border_face(tables: &mut Tables, face: &Arc<FaceState>) {
+    let keys = hat!(tables)
+        .router_tokens
+        .iter()
+        .filter(|res| native_router_tokens(tables, res))
+        .filter_map(|res| OwnedKeyExpr::try_from(res.expr()).ok())
+        .collect::<HashSet<_>>();
+    let net = hat!(tables).routers_net.as_ref().unwrap();
+    for key in &keys {
+        send_border_token(face, net, key, true);
+    }
+    hat_mut!(tables).border_tokens = keys;
+}
+
 pub(super) fn token_remove_node(
     tables: &mut Tables,
     node: &ZenohIdProto,
@@ -215,6 +215,10 @@ impl InterceptorFactoryTrait for AclEnforcer {
 }
 
 impl InterceptorTrait for IngressAclEnforcer {
+    fn name(&self) -> &'static str {
+        "access_control"
+    }
+
     fn compute_keyexpr_cache(&self, key_expr: &KeyExpr<'_>) -> Option<Box<dyn Any + Send + Sync>> {
         Some(Box::new(key_expr.to_string()))
     }
@@ -364,6 +368,10 @@ impl InterceptorTrait for IngressAclEnforcer {
 }
 
 impl InterceptorTrait for EgressAclEnforcer {
+    fn name(&self) -> &'static str {
+        "access_control"
+    }
+
     fn compute_keyexpr_cache(&self, key_expr: &KeyExpr<'_>) -> Option<Box<dyn Any + Send + Sync>> {
         Some(Box::new(key_expr.to_string()))
     }
@@ -138,6 +138,31 @@ impl InterceptorTrait for DownsamplingInterceptor {
         &self,
         ctx: RoutingContext<NetworkMessage>,
         cache: Option<&Box<dyn Any + Send + Sync>>,
+    ) -> Option<RoutingContext<NetworkMessage>> {
+        self.filter(ctx, cache, true)
+    }
+
+    fn check(
+        &self,
+        ctx: RoutingContext<NetworkMessage>,
+        cache: Option<&Box<dyn Any + Send + Sync>>,
+    ) -> Option<RoutingContext<NetworkMessage>> {
+        self.filter(ctx, cache, false)
+    }
+
+    fn name(&self) -> &'static str {
+        "downsampling"
+    }
+}
+
+const NANOS_PER_SEC: f64 = 1_000_000_000.0;
+
+impl DownsamplingInterceptor {
+    fn filter(
+        &self,
+        ctx: RoutingContext<NetworkMessage>,
+        cache: Option<&Box<dyn Any + Send + Sync>>,
+        update: bool,
     ) -> Option<RoutingContext<NetworkMessage>> {
         if matches!(ctx.msg.body, NetworkBody::Push(_)) {
             if let Some(cache) = cache {
@@ -148,7 +173,9 @@ impl InterceptorTrait for DownsamplingInterceptor {
                             let timestamp = tokio::time::Instant::now();
 
                             if timestamp - state.latest_message_timestamp >= state.threshold {
-                                state.latest_message_timestamp = timestamp;
+                                if update {
+                                    state.latest_message_timestamp = timestamp;
+                                }
                                 return Some(ctx);
                             } else {
                                 return None;
@@ -165,11 +192,7 @@ impl InterceptorTrait for DownsamplingInterceptor {
 
         Some(ctx)
     }
-}
 
-const NANOS_PER_SEC: f64 = 1_000_000_000.0;
-
-impl DownsamplingInterceptor {
     pub fn new(rules: Vec<DownsamplingRuleConf>) -> Self {
         let mut ke_id = KeBoxTree::default();
         let mut ke_state = HashMap::default();
@@ -0,0 +1,391 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+
+//! ⚠️ WARNING ⚠️
+//!
+//! This module is intended for Zenoh's internal use.
+//!
+//! [Click here for Zenoh's documentation](https://docs.rs/zenoh/latest/zenoh)
+//!
+//! Mirrors the messages matching some key expressions to a debug key space and/or a capture file.
+//!
+//! A capture file starts with the `ZCAP` magic followed by a version byte, then contains
+//! a sequence of records. The records published to the debug key space use the same layout:
+//! - the capture time in nanoseconds since the UNIX epoch (u64 LE),
+//! - the flow (u8): 0 for ingress, 1 for egress,
+//! - the length (u8) and bytes of the zid of the face the message was received from or sent to,
+//! - the length (u8) and bytes of the reason: `forwarded` or the name of the dropping interceptor,
+//! - the length (u32 LE) and bytes of the message encoded with the zenoh wire codec.
+
+use std::{
+    fs::OpenOptions,
+    io::Write,
+    sync::{Arc, Mutex},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use zenoh_codec::{WCodec, Zenoh080};
+use zenoh_config::{InterceptorFlow, MirrorItemConf};
+use zenoh_core::zlock;
+use zenoh_keyexpr::keyexpr;
+use zenoh_protocol::{
+    core::{key_expr::OwnedKeyExpr, Reliability, WireExpr, ZenohIdProto},
+    network::{push, Push},
+    zenoh::{PushBody, Put},
+};
+use zenoh_result::{bail, ZResult};
+
+use crate::net::{
+    primitives::{DummyPrimitives, Primitives},
+    routing::interceptor::*,
+};
+
+const CAPTURE_MAGIC: &[u8; 4] = b"ZCAP";
+const CAPTURE_VERSION: u8 = 1;
+const FORWARDED: &str = "forwarded";
+// Records are dropped rather than slowing down the routing when the sink lags behind
+const RECORDS_CAPACITY: usize = 1024;
+
+pub(crate) fn mirror_interceptor_factories(
+    config: &Vec<MirrorItemConf>,
+) -> ZResult<Vec<InterceptorFactory>> {
+    let mut res: Vec<InterceptorFactory> = vec![];
+
+    for mirror in config {
+        if mirror.key_space.is_none() && mirror.file.is_none() {
+            bail!(
+                "Mirror of {:?} has neither a key_space nor a file",
+                mirror.key_exprs
+            );
+        }
+        if let Some(key_space) = &mirror.key_space {
+            let key_space = key_space.join("**")?;
+            if let Some(ke) = mirror.key_exprs.iter().find(|ke| ke.intersects(&key_space)) {
+                bail!(
+                    "Mirrored key expression {} intersects the mirror key_space {}",
+                    ke,
+                    key_space
+                );
+            }
+        }
+        res.push(Box::new(MirrorInterceptorFactory::new(mirror.clone())));
+    }
+
+    Ok(res)
+}
+
+struct MirrorRecord {
+    timestamp: u64,
+    flow: InterceptorFlow,
+    zid: ZenohIdProto,
+    reason: &'static str,
+    msg: NetworkMessage,
+}
+
+impl MirrorRecord {
+    fn flow_name(&self) -> &'static str {
+        match self.flow {
+            InterceptorFlow::Ingress => "ingress",
+            InterceptorFlow::Egress => "egress",
+        }
+    }
+
+    fn encode(&self) -> ZResult<Vec<u8>> {
+        let mut msg = vec![];
+        let codec = Zenoh080::new();
+        if codec.write(&mut &mut msg, &self.msg).is_err() {
+            bail!("Unable to encode mirrored message");
+        }
+        let zid = self.zid.to_le_bytes();
+        let zid = &zid[..self.zid.size()];
+
+        let mut bytes = Vec::with_capacity(15 + zid.len() + self.reason.len() + msg.len());
+        bytes.extend_from_slice(&self.timestamp.to_le_bytes());
+        bytes.push(match self.flow {
+            InterceptorFlow::Ingress => 0,
+            InterceptorFlow::Egress => 1,
+        });
+        bytes.push(zid.len() as u8);
+        bytes.extend_from_slice(zid);
+        bytes.push(self.reason.len() as u8);
+        bytes.extend_from_slice(self.reason.as_bytes());
+        bytes.extend_from_slice(&(msg.len() as u32).to_le_bytes());
+        bytes.extend_from_slice(&msg);
+        Ok(bytes)
+    }
+}
+
+pub struct MirrorInterceptorFactory {
+    interfaces: Option<Vec<String>>,
+    flows: Vec<InterceptorFlow>,
+    key_exprs: Arc<Vec<OwnedKeyExpr>>,
+    dropped: bool,
+    key_space: Option<OwnedKeyExpr>,
+    file: Option<String>,
+    sender: flume::Sender<MirrorRecord>,
+    receiver: Mutex<Option<flume::Receiver<MirrorRecord>>>,
+}
+
+impl MirrorInterceptorFactory {
+    pub fn new(conf: MirrorItemConf) -> Self {
+        let (sender, receiver) = flume::bounded(RECORDS_CAPACITY);
+        Self {
+            interfaces: conf.interfaces,
+            flows: conf
+                .flows
+                .unwrap_or_else(|| vec![InterceptorFlow::Ingress, InterceptorFlow::Egress]),
+            key_exprs: Arc::new(conf.key_exprs),
+            dropped: conf.dropped.unwrap_or(true),
+            key_space: conf.key_space,
+            file: conf.file,
+            sender,
+            receiver: Mutex::new(Some(receiver)),
+        }
+    }
+
+    fn interceptor(&self, flow: InterceptorFlow, zid: ZenohIdProto) -> Option<Interceptor> {
+        self.flows.contains(&flow).then(|| {
+            Box::new(ComputeOnMiss::new(MirrorInterceptor {
+                flow,
+                zid,
+                key_exprs: self.key_exprs.clone(),
+                dropped: self.dropped,
+                sender: self.sender.clone(),
+            })) as Interceptor
+        })
+    }
+}
+
+impl InterceptorFactoryTrait for MirrorInterceptorFactory {
+    fn new_transport_unicast(
+        &self,
+        transport: &TransportUnicast,
+    ) -> (Option<IngressInterceptor>, Option<EgressInterceptor>) {
+        tracing::debug!("New mirror transport unicast {:?}", transport);
+        if let Some(interfaces) = &self.interfaces {
+            if let Ok(links) = transport.get_links() {
+                for link in links {
+                    if !link.interfaces.iter().any(|x| interfaces.contains(x)) {
+                        return (None, None);
+                    }
+                }
+            }
+        };
+        let Ok(zid) = transport.get_zid() else {
+            return (None, None);
+        };
+        (
+            self.interceptor(InterceptorFlow::Ingress, zid),
+            self.interceptor(InterceptorFlow::Egress, zid),
+        )
+    }
+
+    fn new_transport_multicast(
+        &self,
+        _transport: &TransportMulticast,
+    ) -> Option<EgressInterceptor> {
+        None
+    }
+
+    fn new_peer_multicast(&self, _transport: &TransportMulticast) -> Option<IngressInterceptor> {
+        None
+    }
+
+    fn start(&self, runtime: &Runtime) {
+        let Some(receiver) = zlock!(self.receiver).take() else {
+            return;
+        };
+        let key_space = self.key_space.clone();
+        // The capture file is written by a blocking task so as not to block the runtime
+        let mut file = self.file.clone().map(|path| {
+            let (sender, receiver) = flume::bounded(RECORDS_CAPACITY);
+            zenoh_runtime::ZRuntime::Net
+                .spawn_blocking(move || write_capture_file(&path, receiver));
+            sender
+        });
+        let r = runtime.clone();
+        runtime.spawn_abortable(async move {
+            let face = key_space
+                .as_ref()
+                .map(|_| r.router().new_primitives(Arc::new(DummyPrimitives)));
+            while let Ok(record) = receiver.recv_async().await {
+                let mut bytes = match record.encode() {
+                    Ok(bytes) => bytes,
+                    Err(e) => {
+                        tracing::warn!("{}", e);
+                        continue;
+                    }
+                };
+                if let Some(f) = file.as_ref() {
+                    let bytes = if key_space.is_some() {
+                        bytes.clone()
+                    } else {
+                        std::mem::take(&mut bytes)
+                    };
+                    if f.send_async(bytes).await.is_err() {
+                        // The capture file could not be written
+                        file = None;
+                    }
+                }
+                if let (Some(face), Some(key_space)) = (face.as_ref(), key_space.as_ref()) {
+                    let key_expr = format!(
+                        "{}/{}/{}/{}",
+                        key_space,
+                        record.flow_name(),
+                        record.zid,
+                        record.reason
+                    );
+                    face.send_push(mirror_push(&key_expr, bytes), Reliability::Reliable);
+                }
+            }
+        });
+    }
+}
+
+// Writes the records received from `receiver` to the capture file at `path` until the mirror stops
+fn write_capture_file(path: &str, receiver: flume::Receiver<Vec<u8>>) {
+    let mut file = match open_capture_file(path) {
+        Ok(file) => file,
+        Err(e) => {
+            tracing::error!("Unable to open mirror capture file {}: {}", path, e);
+            return;
+        }
+    };
+    while let Ok(bytes) = receiver.recv() {
+        let res = file.write_all(&bytes).and_then(|_| {
+            if receiver.is_empty() {
+                file.flush()
+            } else {
+                Ok(())
+            }
+        });
+        if let Err(e) = res {
+            tracing::error!("Unable to write mirror capture file {}: {}", path, e);
+            return;
+        }
+    }
+}
+
+fn open_capture_file(path: &str) -> std::io::Result<std::io::BufWriter<std::fs::File>> {
+    let file = OpenOptions::new().create(true).append(true).open(path)?;
+    let mut file = std::io::BufWriter::new(file);
+    if file.get_ref().metadata()?.len() == 0 {
+        file.write_all(CAPTURE_MAGIC)?;
+        file.write_all(&[CAPTURE_VERSION])?;
+    }
+    Ok(file)
+}
+
+fn mirror_push(key_expr: &str, bytes: Vec<u8>) -> Push {
+    Push {
+        wire_expr: WireExpr::from(key_expr).to_owned(),
+        ext_qos: push::ext::QoSType::DEFAULT,
+        ext_tstamp: None,
+        ext_nodeid: push::ext::NodeIdType::DEFAULT,
+        ext_redundancy: None,
+        payload: PushBody::Put(Put {
+            timestamp: None,
+            encoding: Default::default(),
+            ext_sinfo: None,
+            ext_attachment: None,
+            #[cfg(feature = "shared-memory")]
+            ext_shm: None,
+            ext_unknown: vec![],
+            payload: bytes.into(),
+        }),
+    }
+}
+
+pub(crate) struct MirrorInterceptor {
+    flow: InterceptorFlow,
+    zid: ZenohIdProto,
+    key_exprs: Arc<Vec<OwnedKeyExpr>>,
+    dropped: bool,
+    sender: flume::Sender<MirrorRecord>,
+}
+
+impl MirrorInterceptor {
+    fn record(
+        &self,
+        ctx: &RoutingContext<NetworkMessage>,
+        cache: Option<&Box<dyn Any + Send + Sync>>,
+        reason: &'static str,
+    ) {
+        if !cache
+            .and_then(|c| c.downcast_ref::<bool>())
+            .copied()
+            .unwrap_or(false)
+        {
+            return;
+        }
+        let record = MirrorRecord {
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|t| t.as_nanos() as u64)
+                .unwrap_or_default(),
+            flow: self.flow,
+            zid: self.zid,
+            reason,
+            msg: ctx.msg.clone(),
+        };
+        if self.sender.try_send(record).is_err() {
+            tracing::trace!("Mirror sink lagging behind: record dropped");
+        }
+    }
+}
+
+impl InterceptorTrait for MirrorInterceptor {
+    fn compute_keyexpr_cache(&self, key_expr: &KeyExpr<'_>) -> Option<Box<dyn Any + Send + Sync>> {
+        let ke: &keyexpr = key_expr;
+        Some(Box::new(
+            self.key_exprs
+                .iter()
+                .any(|mirrored| mirrored.intersects(ke)),
+        ))
+    }
+
+    fn intercept(
+        &self,
+        ctx: RoutingContext<NetworkMessage>,
+        cache: Option<&Box<dyn Any + Send + Sync>>,
+    ) -> Option<RoutingContext<NetworkMessage>> {
+        self.record(&ctx, cache, FORWARDED);
+        Some(ctx)
+    }
+
+    fn check(
+        &self,
+        ctx: RoutingContext<NetworkMessage>,
+        _cache: Option<&Box<dyn Any + Send + Sync>>,
+    ) -> Option<RoutingContext<NetworkMessage>> {
+        Some(ctx)
+    }
+
+    fn name(&self) -> &'static str {
+        "mirror"
+    }
+
+    fn observes_drops(&self) -> bool {
+        self.dropped
+    }
+
+    fn dropped(
+        &self,
+        ctx: &RoutingContext<NetworkMessage>,
+        cache: Option<&Box<dyn Any + Send + Sync>>,
+        reason: &'static str,
+    ) {
+        self.record(ctx, cache, reason);
+    }
+}
@@ -24,17 +24,26 @@ use access_control::acl_interceptor_factories;
 mod authorization;
 use std::any::Any;
 
-use zenoh_config::Config;
+use zenoh_config::{Config, InterceptorFlow};
 use zenoh_protocol::network::NetworkMessage;
 use zenoh_result::ZResult;
 use zenoh_transport::{multicast::TransportMulticast, unicast::TransportUnicast};
 
 use super::RoutingContext;
-use crate::api::key_expr::KeyExpr;
+use crate::{api::key_expr::KeyExpr, net::runtime::Runtime};
 
 pub mod downsampling;
 use crate::net::routing::interceptor::downsampling::downsampling_interceptor_factories;
 
+mod mirror;
+use mirror::mirror_interceptor_factories;
+
+mod namespace;
+use namespace::namespace_interceptor_factories;
+
+mod remapping;
+use remapping::remapping_interceptor_factories;
+
 pub(crate) trait InterceptorTrait {
     fn compute_keyexpr_cache(&self, key_expr: &KeyExpr<'_>) -> Option<Box<dyn Any + Send + Sync>>;
 
@@ -43,6 +52,34 @@ pub(crate) trait InterceptorTrait {
         ctx: RoutingContext<NetworkMessage>,
         cache: Option<&Box<dyn Any + Send + Sync>>,
     ) -> Option<RoutingContext<NetworkMessage>>;
+
+    /// Evaluates whether `ctx` would go through this interceptor without updating its state.
+    fn check(
+        &self,
+        ctx: RoutingContext<NetworkMessage>,
+        cache: Option<&Box<dyn Any + Send + Sync>>,
+    ) -> Option<RoutingContext<NetworkMessage>> {
+        self.intercept(ctx, cache)
+    }
+
+    fn name(&self) -> &'static str {
+        "interceptor"
+    }
+
+    /// Whether this interceptor wants to be notified of the messages dropped by the other
+    /// interceptors of its chain.
+    fn observes_drops(&self) -> bool {
+        false
+    }
+
+    /// Notifies this interceptor that `ctx` was dropped by the interceptor named `reason`.
+    fn dropped(
+        &self,
+        _ctx: &RoutingContext<NetworkMessage>,
+        _cache: Option<&Box<dyn Any + Send + Sync>>,
+        _reason: &'static str,
+    ) {
+    }
 }
 
 pub(crate) type Interceptor = Box<dyn InterceptorTrait + Send + Sync>;
@@ -56,6 +93,9 @@ pub(crate) trait InterceptorFactoryTrait {
     ) -> (Option<IngressInterceptor>, Option<EgressInterceptor>);
     fn new_transport_multicast(&self, transport: &TransportMulticast) -> Option<EgressInterceptor>;
     fn new_peer_multicast(&self, transport: &TransportMulticast) -> Option<IngressInterceptor>;
+
+    /// Starts the background tasks of this factory once the runtime is built.
+    fn start(&self, _runtime: &Runtime) {}
 }
 
 pub(crate) type InterceptorFactory = Box<dyn InterceptorFactoryTrait + Send + Sync>;
@@ -64,13 +104,29 @@ pub(crate) fn interceptor_factories(config: &Config) -> ZResult<Vec<InterceptorF
     let mut res: Vec<InterceptorFactory> = vec![];
     // Uncomment to log the interceptors initialisation
     // res.push(Box::new(LoggerInterceptor {}));
+    // The remapping is applied first on ingress and last on egress so that
+    // the other interceptors only see the local key expressions
+    res.extend(remapping_interceptor_factories(
+        config.remapping(),
+        InterceptorFlow::Ingress,
+    )?);
     res.extend(downsampling_interceptor_factories(config.downsampling())?);
     res.extend(acl_interceptor_factories(config.access_control())?);
+    res.extend(namespace_interceptor_factories(
+        config.namespace_bindings(),
+    )?);
+    // After the others so that it sees the messages dropped by all of them
+    res.extend(mirror_interceptor_factories(config.mirror())?);
+    res.extend(remapping_interceptor_factories(
+        config.remapping(),
+        InterceptorFlow::Egress,
+    )?);
     Ok(res)
 }
 
 pub(crate) struct InterceptorsChain {
     pub(crate) interceptors: Vec<Interceptor>,
+    observes_drops: bool,
 }
 
 impl InterceptorsChain {
@@ -78,13 +134,62 @@ impl InterceptorsChain {
     pub(crate) fn empty() -> Self {
         Self {
             interceptors: vec![],
+            observes_drops: false,
         }
     }
+
+    // Notifies the interceptors observing drops that the interceptor at `idx` dropped `ctx`
+    fn notify_dropped(
+        &self,
+        ctx: &RoutingContext<NetworkMessage>,
+        caches: Option<&Vec<Option<Box<dyn Any + Send + Sync>>>>,
+        idx: usize,
+    ) {
+        let reason = self.interceptors[idx].name();
+        for (i, interceptor) in self.interceptors.iter().enumerate() {
+            if i != idx && interceptor.observes_drops() {
+                let cache = caches
+                    .and_then(|caches| caches.get(i).map(|k| k.as_ref()))
+                    .flatten();
+                interceptor.dropped(ctx, cache, reason);
+            }
+        }
+    }
+}
+
+impl InterceptorsChain {
+    // Returns the verdict of each interceptor of the chain on `msg`, without updating their state.
+    // The interceptors are evaluated in order, each on the context returned by the previous one,
+    // and the ones following the first drop are not reached (`None`).
+    pub(crate) fn verdicts(
+        &self,
+        msg: &NetworkMessage,
+        key_expr: &KeyExpr<'_>,
+    ) -> Vec<(&'static str, Option<bool>)> {
+        let mut ctx = Some(RoutingContext::with_expr(msg.clone(), key_expr.to_string()));
+        self.interceptors
+            .iter()
+            .map(|interceptor| {
+                let verdict = ctx.take().map(|current| {
+                    let cache = current
+                        .full_key_expr()
+                        .and_then(|key_expr| interceptor.compute_keyexpr_cache(&key_expr.into()));
+                    ctx = interceptor.check(current, cache.as_ref());
+                    ctx.is_some()
+                });
+                (interceptor.name(), verdict)
+            })
+            .collect()
+    }
 }
 
 impl From<Vec<Interceptor>> for InterceptorsChain {
     fn from(interceptors: Vec<Interceptor>) -> Self {
-        InterceptorsChain { interceptors }
+        let observes_drops = interceptors.iter().any(|i| i.observes_drops());
+        InterceptorsChain {
+            interceptors,
+            observes_drops,
+        }
     }
 }
 
@@ -105,6 +210,9 @@ impl InterceptorTrait for InterceptorsChain {
     ) -> Option<RoutingContext<NetworkMessage>> {
         let caches =
             caches.and_then(|i| i.downcast_ref::<Vec<Option<Box<dyn Any + Send + Sync>>>>());
+        // The interceptors observing drops are notified of the message as it entered the chain,
+        // matching the key expression the caches were computed for
+        let copy = self.observes_drops.then(|| ctx.clone());
         for (idx, interceptor) in self.interceptors.iter().enumerate() {
             let cache = caches
                 .and_then(|caches| caches.get(idx).map(|k| k.as_ref()))
@@ -113,12 +221,31 @@ impl InterceptorTrait for InterceptorsChain {
                 Some(newctx) => ctx = newctx,
                 None => {
                     tracing::trace!("Msg intercepted!");
+                    if let Some(copy) = &copy {
+                        self.notify_dropped(copy, caches, idx);
+                    }
                     return None;
                 }
             }
         }
         Some(ctx)
     }
+
+    fn check(
+        &self,
+        mut ctx: RoutingContext<NetworkMessage>,
+        caches: Option<&Box<dyn Any + Send + Sync>>,
+    ) -> Option<RoutingContext<NetworkMessage>> {
+        let caches =
+            caches.and_then(|i| i.downcast_ref::<Vec<Option<Box<dyn Any + Send + Sync>>>>());
+        for (idx, interceptor) in self.interceptors.iter().enumerate() {
+            let cache = caches
+                .and_then(|caches| caches.get(idx).map(|k| k.as_ref()))
+                .flatten();
+            ctx = interceptor.check(ctx, cache)?;
+        }
+        Some(ctx)
+    }
 }
 
 pub(crate) struct ComputeOnMiss<T: InterceptorTrait> {
@@ -157,6 +284,58 @@ impl<T: InterceptorTrait> InterceptorTrait for ComputeOnMiss<T> {
             self.interceptor.intercept(ctx, cache)
         }
     }
+
+    #[inline]
+    fn check(
+        &self,
+        ctx: RoutingContext<NetworkMessage>,
+        cache: Option<&Box<dyn Any + Send + Sync>>,
+    ) -> Option<RoutingContext<NetworkMessage>> {
+        if cache.is_some() {
+            self.interceptor.check(ctx, cache)
+        } else if let Some(key_expr) = ctx.full_key_expr() {
+            self.interceptor.check(
+                ctx,
+                self.interceptor
+                    .compute_keyexpr_cache(&key_expr.into())
+                    .as_ref(),
+            )
+        } else {
+            self.interceptor.check(ctx, cache)
+        }
+    }
+
+    #[inline]
+    fn name(&self) -> &'static str {
+        self.interceptor.name()
+    }
+
+    #[inline]
+    fn observes_drops(&self) -> bool {
+        self.interceptor.observes_drops()
+    }
+
+    #[inline]
+    fn dropped(
+        &self,
+        ctx: &RoutingContext<NetworkMessage>,
+        cache: Option<&Box<dyn Any + Send + Sync>>,
+        reason: &'static str,
+    ) {
+        if cache.is_some() {
+            self.interceptor.dropped(ctx, cache, reason)
+        } else if let Some(key_expr) = ctx.full_key_expr() {
+            self.interceptor.dropped(
+                ctx,
+                self.interceptor
+                    .compute_keyexpr_cache(&key_expr.into())
+                    .as_ref(),
+                reason,
+            )
+        } else {
+            self.interceptor.dropped(ctx, cache, reason)
+        }
+    }
 }
 
 #[allow(dead_code)]
@@ -0,0 +1,144 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+
+//! ⚠️ WARNING ⚠️
+//!
+//! This module is intended for Zenoh's internal use.
+//!
+//! [Click here for Zenoh's documentation](https://docs.rs/zenoh/latest/zenoh)
+//!
+//! Confines the authenticated sessions to the namespace they are bound to.
+
+use std::sync::Arc;
+
+use zenoh_config::NamespaceBindingConf;
+use zenoh_keyexpr::keyexpr;
+use zenoh_protocol::core::key_expr::OwnedKeyExpr;
+use zenoh_result::{bail, ZResult};
+use zenoh_transport::unicast::authentication::AuthId;
+
+use crate::net::routing::interceptor::*;
+
+pub(crate) fn namespace_interceptor_factories(
+    config: &Vec<NamespaceBindingConf>,
+) -> ZResult<Vec<InterceptorFactory>> {
+    let mut res: Vec<InterceptorFactory> = vec![];
+
+    if !config.is_empty() {
+        for binding in config {
+            if binding.namespace.is_wild() {
+                bail!("Namespace {} must not contain wildcards", binding.namespace);
+            }
+        }
+        res.push(Box::new(NamespaceEnforcerFactory {
+            bindings: config.clone(),
+        }));
+    }
+
+    Ok(res)
+}
+
+pub struct NamespaceEnforcerFactory {
+    bindings: Vec<NamespaceBindingConf>,
+}
+
+impl NamespaceEnforcerFactory {
+    fn binding(&self, auth_ids: &[AuthId]) -> Option<&NamespaceBindingConf> {
+        self.bindings.iter().find(|binding| {
+            auth_ids.iter().any(|auth_id| match auth_id {
+                AuthId::Username(value) => binding
+                    .usernames
+                    .iter()
+                    .flatten()
+                    .any(|username| username.0 == *value),
+                AuthId::CertCommonName(value) => binding
+                    .cert_common_names
+                    .iter()
+                    .flatten()
+                    .any(|name| name.0 == *value),
+                AuthId::None => false,
+            })
+        })
+    }
+}
+
+impl InterceptorFactoryTrait for NamespaceEnforcerFactory {
+    fn new_transport_unicast(
+        &self,
+        transport: &TransportUnicast,
+    ) -> (Option<IngressInterceptor>, Option<EgressInterceptor>) {
+        let auth_ids = match transport.get_auth_ids() {
+            Ok(auth_ids) => auth_ids,
+            Err(err) => {
+                tracing::error!("Couldn't get Transport Auth IDs: {}", err);
+                return (None, None);
+            }
+        };
+        let Some(binding) = self.binding(&auth_ids) else {
+            return (None, None);
+        };
+        tracing::debug!(
+            "Transport {:?} confined to namespace {}",
+            transport,
+            binding.namespace
+        );
+        let namespace = Arc::new(&binding.namespace / keyexpr::new("**").unwrap());
+        (
+            Some(Box::new(ComputeOnMiss::new(NamespaceEnforcer {
+                namespace: namespace.clone(),
+            }))),
+            Some(Box::new(ComputeOnMiss::new(NamespaceEnforcer {
+                namespace,
+            }))),
+        )
+    }
+
+    fn new_transport_multicast(
+        &self,
+        _transport: &TransportMulticast,
+    ) -> Option<EgressInterceptor> {
+        None
+    }
+
+    fn new_peer_multicast(&self, _transport: &TransportMulticast) -> Option<IngressInterceptor> {
+        None
+    }
+}
+
+pub(crate) struct NamespaceEnforcer {
+    // The namespace followed by `/**`
+    namespace: Arc<OwnedKeyExpr>,
+}
+
+impl InterceptorTrait for NamespaceEnforcer {
+    fn compute_keyexpr_cache(&self, key_expr: &KeyExpr<'_>) -> Option<Box<dyn Any + Send + Sync>> {
+        Some(Box::new(self.namespace.includes(key_expr)))
+    }
+
+    fn intercept(
+        &self,
+        ctx: RoutingContext<NetworkMessage>,
+        cache: Option<&Box<dyn Any + Send + Sync>>,
+    ) -> Option<RoutingContext<NetworkMessage>> {
+        // The messages without key expression are let through
+        match cache.and_then(|c| c.downcast_ref::<bool>()) {
+            Some(false) => None,
+            _ => Some(ctx),
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        "namespace"
+    }
+}
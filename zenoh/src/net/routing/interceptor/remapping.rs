@@ -0,0 +1,209 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+
+//! ⚠️ WARNING ⚠️
+//!
+//! This module is intended for Zenoh's internal use.
+//!
+//! [Click here for Zenoh's documentation](https://docs.rs/zenoh/latest/zenoh)
+//!
+//! Remaps the key expressions exchanged with some remotes: the messages received from them are
+//! remapped before any other interceptor and the messages sent to them after all the others,
+//! so that the routing and the other interceptors only deal with the local key expressions.
+//!
+//! Remapped key expressions are sent as full key expressions: as the declared key expressions
+//! are remapped as well, the key expressions relative to them keep resolving on both sides.
+//!
+//! The wildcard key expressions intersecting the prefix of a rule without starting with it are
+//! remapped to the part of them under the prefix, unless they already match all the remapped
+//! key expressions.
+
+use std::sync::Arc;
+
+use zenoh_config::{InterceptorFlow, RemappingItemConf};
+use zenoh_protocol::core::{
+    key_expr::{keyexpr, OwnedKeyExpr},
+    ZenohIdProto,
+};
+use zenoh_result::{bail, ZResult};
+
+use crate::net::routing::{interceptor::*, strip_wild_prefix};
+
+pub(crate) fn remapping_interceptor_factories(
+    config: &Vec<RemappingItemConf>,
+    flow: InterceptorFlow,
+) -> ZResult<Vec<InterceptorFactory>> {
+    let mut res: Vec<InterceptorFactory> = vec![];
+
+    for item in config {
+        for rule in &item.rules {
+            if rule.local.is_wild() || rule.remote.is_wild() {
+                bail!(
+                    "Remapping rule {} -> {} must not contain wildcards",
+                    rule.local,
+                    rule.remote
+                );
+            }
+        }
+        res.push(Box::new(RemappingInterceptorFactory::new(
+            item.clone(),
+            flow,
+        )));
+    }
+
+    Ok(res)
+}
+
+pub struct RemappingInterceptorFactory {
+    interfaces: Option<Vec<String>>,
+    zids: Option<Vec<ZenohIdProto>>,
+    // The (from, to) prefixes of the rules in the direction of the flow
+    rules: Arc<Vec<(OwnedKeyExpr, OwnedKeyExpr)>>,
+    flow: InterceptorFlow,
+}
+
+impl RemappingInterceptorFactory {
+    pub fn new(conf: RemappingItemConf, flow: InterceptorFlow) -> Self {
+        let rules = conf
+            .rules
+            .into_iter()
+            .map(|rule| match flow {
+                InterceptorFlow::Ingress => (rule.remote, rule.local),
+                InterceptorFlow::Egress => (rule.local, rule.remote),
+            })
+            .collect();
+        Self {
+            interfaces: conf.interfaces,
+            zids: conf
+                .zids
+                .map(|zids| zids.into_iter().map(Into::into).collect()),
+            rules: Arc::new(rules),
+            flow,
+        }
+    }
+}
+
+impl InterceptorFactoryTrait for RemappingInterceptorFactory {
+    fn new_transport_unicast(
+        &self,
+        transport: &TransportUnicast,
+    ) -> (Option<IngressInterceptor>, Option<EgressInterceptor>) {
+        tracing::debug!("New remapping transport unicast {:?}", transport);
+        if let Some(interfaces) = &self.interfaces {
+            if let Ok(links) = transport.get_links() {
+                for link in links {
+                    if !link.interfaces.iter().any(|x| interfaces.contains(x)) {
+                        return (None, None);
+                    }
+                }
+            }
+        };
+        if let Some(zids) = &self.zids {
+            match transport.get_zid() {
+                Ok(zid) if zids.contains(&zid) => (),
+                _ => return (None, None),
+            }
+        }
+
+        let interceptor: Interceptor = Box::new(ComputeOnMiss::new(RemappingInterceptor {
+            rules: self.rules.clone(),
+        }));
+        match self.flow {
+            InterceptorFlow::Ingress => (Some(interceptor), None),
+            InterceptorFlow::Egress => (None, Some(interceptor)),
+        }
+    }
+
+    fn new_transport_multicast(
+        &self,
+        _transport: &TransportMulticast,
+    ) -> Option<EgressInterceptor> {
+        None
+    }
+
+    fn new_peer_multicast(&self, _transport: &TransportMulticast) -> Option<IngressInterceptor> {
+        None
+    }
+}
+
+pub(crate) struct RemappingInterceptor {
+    rules: Arc<Vec<(OwnedKeyExpr, OwnedKeyExpr)>>,
+}
+
+impl RemappingInterceptor {
+    // Returns the remapped key expression if it starts with the prefix of one of the rules,
+    // or if it is a wildcard key expression intersecting it
+    fn remap(&self, key_expr: &keyexpr) -> Option<String> {
+        self.rules.iter().find_map(|(from, to)| {
+            match key_expr.as_str().strip_prefix(from.as_str()) {
+                Some("") => return Some(to.to_string()),
+                Some(suffix) if suffix.starts_with('/') => return Some(format!("{to}{suffix}")),
+                _ => {}
+            }
+            if !key_expr.is_wild() {
+                return None;
+            }
+            let exact = key_expr.includes(from);
+            let suffixes = strip_wild_prefix(key_expr, from);
+            if !exact && suffixes.is_empty() {
+                return None;
+            }
+            // An expression already matching all the remapped key expressions is kept as is,
+            // so that it still matches the key expressions out of the rule
+            if to
+                .join("**")
+                .is_ok_and(|remapped| key_expr.includes(&remapped))
+            {
+                return Some(key_expr.to_string());
+            }
+            // Otherwise only the key expressions matched under the prefix of the rule are kept
+            match suffixes.as_slice() {
+                [] => Some(to.to_string()),
+                [suffix] if !exact || suffix.as_str().starts_with("**") => {
+                    Some(format!("{to}/{suffix}"))
+                }
+                _ => {
+                    tracing::warn!(
+                        "Unable to remap {} with rule {} -> {} as a single key expression",
+                        key_expr,
+                        from,
+                        to
+                    );
+                    None
+                }
+            }
+        })
+    }
+}
+
+impl InterceptorTrait for RemappingInterceptor {
+    fn compute_keyexpr_cache(&self, key_expr: &KeyExpr<'_>) -> Option<Box<dyn Any + Send + Sync>> {
+        Some(Box::new(self.remap(key_expr)))
+    }
+
+    fn intercept(
+        &self,
+        mut ctx: RoutingContext<NetworkMessage>,
+        cache: Option<&Box<dyn Any + Send + Sync>>,
+    ) -> Option<RoutingContext<NetworkMessage>> {
+        if let Some(Some(expr)) = cache.and_then(|c| c.downcast_ref::<Option<String>>()) {
+            ctx.rewrite_expr(expr.clone());
+        }
+        Some(ctx)
+    }
+
+    fn name(&self) -> &'static str {
+        "remapping"
+    }
+}
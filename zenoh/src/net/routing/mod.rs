@@ -25,13 +25,17 @@ pub mod router;
 use std::{cell::OnceCell, sync::Arc};
 
 use zenoh_protocol::{
-    core::{key_expr::OwnedKeyExpr, WireExpr},
+    core::{
+        key_expr::{keyexpr, OwnedKeyExpr},
+        WireExpr,
+    },
     network::NetworkMessage,
 };
 
 use self::{dispatcher::face::Face, router::Resource};
 use super::runtime;
 
+#[derive(Clone)]
 pub(crate) struct RoutingContext<Msg> {
     pub(crate) msg: Msg,
     pub(crate) inface: OnceCell<Face>,
@@ -174,4 +178,81 @@ impl RoutingContext<NetworkMessage> {
         let full_expr = self.full_expr()?;
         OwnedKeyExpr::new(full_expr).ok()
     }
+
+    /// Replaces the key expression of the message with the given full key expression,
+    /// not relative to any declared key expression.
+    pub(crate) fn rewrite_expr(&mut self, expr: String) {
+        use zenoh_protocol::network::{DeclareBody, NetworkBody};
+        let wire_expr = match &mut self.msg.body {
+            NetworkBody::Push(m) => Some(&mut m.wire_expr),
+            NetworkBody::Request(m) => Some(&mut m.wire_expr),
+            NetworkBody::Response(m) => Some(&mut m.wire_expr),
+            NetworkBody::ResponseFinal(_) => None,
+            NetworkBody::Interest(m) => m.wire_expr.as_mut(),
+            NetworkBody::Declare(m) => match &mut m.body {
+                DeclareBody::DeclareKeyExpr(m) => Some(&mut m.wire_expr),
+                DeclareBody::UndeclareKeyExpr(_) => None,
+                DeclareBody::DeclareSubscriber(m) => Some(&mut m.wire_expr),
+                DeclareBody::UndeclareSubscriber(m) => Some(&mut m.ext_wire_expr.wire_expr),
+                DeclareBody::DeclareQueryable(m) => Some(&mut m.wire_expr),
+                DeclareBody::UndeclareQueryable(m) => Some(&mut m.ext_wire_expr.wire_expr),
+                DeclareBody::DeclareToken(m) => Some(&mut m.wire_expr),
+                DeclareBody::UndeclareToken(m) => Some(&mut m.ext_wire_expr.wire_expr),
+                DeclareBody::DeclareFinal(_) => None,
+            },
+            NetworkBody::OAM(_) => None,
+        };
+        if let Some(wire_expr) = wire_expr {
+            *wire_expr = WireExpr::from(expr.as_str()).to_owned();
+            self.prefix = OnceCell::new();
+            self.full_expr = OnceCell::from(expr);
+        }
+    }
+}
+
+/// Returns the suffixes `s` such that `prefix/s` is included in the wildcard key expression
+/// `key_expr`, omitting the suffixes included in another one. There may be several of them as
+/// a `**` of `key_expr` can match several chunks of `prefix`, e.g. `**/b/c` with prefix `a/b`
+/// gives `c` and `**/b/c`.
+///
+/// `key_expr` matching `prefix` itself is not reported, which can be checked with
+/// [`keyexpr::includes`] as `prefix` is not a wildcard key expression.
+pub(crate) fn strip_wild_prefix(key_expr: &keyexpr, prefix: &keyexpr) -> Vec<OwnedKeyExpr> {
+    fn strip<'a>(chunks: &[&'a str], prefix: &[&str], suffixes: &mut Vec<Vec<&'a str>>) {
+        match (chunks.split_first(), prefix.split_first()) {
+            (_, None) => suffixes.push(chunks.to_vec()),
+            (None, Some(_)) => {}
+            (Some((&"**", rest)), Some((_, prefix_rest))) => {
+                // `**` matches none of the remaining chunks of the prefix, or at least the first one
+                strip(rest, prefix, suffixes);
+                strip(chunks, prefix_rest, suffixes);
+            }
+            (Some((chunk, rest)), Some((prefix_chunk, prefix_rest))) => {
+                if keyexpr::new(*chunk).is_ok_and(|chunk| {
+                    keyexpr::new(*prefix_chunk)
+                        .is_ok_and(|prefix_chunk| chunk.includes(prefix_chunk))
+                }) {
+                    strip(rest, prefix_rest, suffixes);
+                }
+            }
+        }
+    }
+
+    let chunks: Vec<&str> = key_expr.as_str().split('/').collect();
+    let prefix: Vec<&str> = prefix.as_str().split('/').collect();
+    let mut suffixes = vec![];
+    strip(&chunks, &prefix, &mut suffixes);
+
+    let mut result: Vec<OwnedKeyExpr> = vec![];
+    for suffix in suffixes {
+        let Ok(suffix) = OwnedKeyExpr::new(suffix.join("/")) else {
+            continue;
+        };
+        if result.iter().any(|other| other.includes(&suffix)) {
+            continue;
+        }
+        result.retain(|other| !suffix.includes(other));
+        result.push(suffix);
+    }
+    result
 }
@@ -18,7 +18,7 @@ use std::{
 
 use uhlc::HLC;
 use zenoh_config::Config;
-use zenoh_protocol::core::{WhatAmI, ZenohIdProto};
+use zenoh_protocol::core::{key_expr::keyexpr, WhatAmI, ZenohIdProto};
 // use zenoh_collections::Timer;
 use zenoh_result::ZResult;
 use zenoh_transport::{multicast::TransportMulticast, unicast::TransportUnicast, TransportPeer};
@@ -27,6 +27,7 @@ pub(crate) use super::dispatcher::token::*;
 pub use super::dispatcher::{pubsub::*, queries::*, resource::*};
 use super::{
     dispatcher::{
+        explain::{explain_route, RouteExplanation},
         face::{Face, FaceState},
         tables::{Tables, TablesLock},
     },
@@ -68,6 +69,50 @@ impl Router {
         ctrl_lock.init(&mut tables, runtime)
     }
 
+    pub(crate) fn start_interceptors(&self, runtime: &Runtime) {
+        let tables = zread!(self.tables.tables);
+        for factory in &tables.interceptors {
+            factory.start(runtime);
+        }
+    }
+
+    pub(crate) fn update_locators(&self) {
+        let ctrl_lock = zlock!(self.tables.ctrl_lock);
+        let mut tables = zwrite!(self.tables.tables);
+        ctrl_lock.update_locators(&mut tables)
+    }
+
+    pub(crate) fn set_link_weight(&self, zid: &ZenohIdProto, weight: u16) {
+        let ctrl_lock = zlock!(self.tables.ctrl_lock);
+        let mut tables = zwrite!(self.tables.tables);
+        ctrl_lock.set_link_weight(&mut tables, &self.tables, zid, weight)
+    }
+
+    pub(crate) fn update_rtt_weights(&self) {
+        let ctrl_lock = zlock!(self.tables.ctrl_lock);
+        let mut tables = zwrite!(self.tables.tables);
+        ctrl_lock.update_rtt_weights(&mut tables, &self.tables)
+    }
+
+    pub(crate) async fn save_snapshot(&self) {
+        let snapshot = {
+            let ctrl_lock = zlock!(self.tables.ctrl_lock);
+            let tables = zread!(self.tables.tables);
+            ctrl_lock.snapshot(&tables)
+        };
+        if let Some(snapshot) = snapshot {
+            snapshot.save().await;
+        }
+    }
+
+    pub(crate) fn explain_route(
+        &self,
+        key_expr: &keyexpr,
+        source: Option<ZenohIdProto>,
+    ) -> ZResult<RouteExplanation> {
+        explain_route(&self.tables, key_expr, source.as_ref())
+    }
+
     pub(crate) fn new_primitives(
         &self,
         primitives: Arc<dyn EPrimitives + Send + Sync>,
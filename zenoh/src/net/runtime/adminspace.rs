@@ -13,6 +13,7 @@
 use std::{
     collections::HashMap,
     convert::{TryFrom, TryInto},
+    str::FromStr,
     sync::{Arc, Mutex},
 };
 
@@ -178,6 +179,12 @@ impl AdminSpace {
                 Arc::new(peers_linkstate_data),
             );
         }
+        handlers.insert(
+            format!("@/{zid_str}/{whatami_str}/route")
+                .try_into()
+                .unwrap(),
+            Arc::new(route_data),
+        );
         handlers.insert(
             format!("@/{zid_str}/{whatami_str}/subscriber/**")
                 .try_into()
@@ -560,14 +567,19 @@ fn local_data(context: &AdminContext, query: Query) {
 
     // transports info
     let transport_to_json = |transport: &TransportUnicast| {
+        let links = transport.get_links().unwrap_or_default();
         #[allow(unused_mut)]
         let mut json = json!({
             "peer": transport.get_zid().map_or_else(|_| "unknown".to_string(), |p| p.to_string()),
             "whatami": transport.get_whatami().map_or_else(|_| "unknown".to_string(), |p| p.to_string()),
-            "links": transport.get_links().map_or_else(
-                |_| Vec::new(),
-                |links| links.iter().map(|link| link.dst.to_string()).collect()
-            ),
+            "links": links.iter().map(|link| link.dst.to_string()).collect::<Vec<_>>(),
+            "links_info": links.iter().map(|link| json!({
+                "src": link.src.to_string(),
+                "dst": link.dst.to_string(),
+                "interfaces": link.interfaces,
+                "mtu": link.mtu,
+                "tcp": link.tcp_info(),
+            })).collect::<Vec<_>>(),
         });
         #[cfg(feature = "stats")]
         {
@@ -706,6 +718,50 @@ fn peers_linkstate_data(context: &AdminContext, query: Query) {
     }
 }
 
+fn route_data(context: &AdminContext, query: Query) {
+    let reply_key: OwnedKeyExpr = format!(
+        "@/{}/{}/route",
+        context.runtime.state.zid, context.runtime.state.whatami
+    )
+    .try_into()
+    .unwrap();
+
+    let explanation = query
+        .parameters()
+        .get("keyexpr")
+        .ok_or_else(|| "Missing keyexpr parameter".to_string())
+        .and_then(|ke| OwnedKeyExpr::autocanonize(ke.to_string()).map_err(|e| e.to_string()))
+        .and_then(|ke| {
+            let source = query
+                .parameters()
+                .get("source")
+                .map(ZenohId::from_str)
+                .transpose()
+                .map_err(|e| e.to_string())?;
+            context
+                .runtime
+                .explain_route(&ke, source)
+                .map_err(|e| e.to_string())
+        });
+
+    let reply = match explanation {
+        Ok(explanation) => match serde_json::to_vec(&explanation) {
+            Ok(bytes) => query
+                .reply(reply_key, ZBytes::from(bytes))
+                .encoding(Encoding::APPLICATION_JSON)
+                .wait(),
+            Err(e) => {
+                tracing::error!("Error serializing AdminSpace reply: {:?}", e);
+                return;
+            }
+        },
+        Err(e) => query.reply_err(e).wait(),
+    };
+    if let Err(e) = reply {
+        tracing::error!("Error sending AdminSpace reply: {:?}", e);
+    }
+}
+
 fn subscribers_data(context: &AdminContext, query: Query) {
     let tables = zread!(context.runtime.state.router.tables.tables);
     for sub in tables.hat_code.get_subscriptions(&tables) {
@@ -24,7 +24,7 @@ pub mod orchestrator;
 use std::sync::{Mutex, MutexGuard};
 use std::{
     any::Any,
-    collections::HashSet,
+    collections::{HashMap, HashSet},
     sync::{
         atomic::{AtomicU32, Ordering},
         Arc, Weak,
@@ -41,7 +41,7 @@ use zenoh_config::{unwrap_or_default, ModeDependent, ZenohId};
 use zenoh_link::{EndPoint, Link};
 use zenoh_plugin_trait::{PluginStartArgs, StructVersion};
 use zenoh_protocol::{
-    core::{Locator, WhatAmI, ZenohIdProto},
+    core::{key_expr::keyexpr, Locator, WhatAmI, ZenohIdProto},
     network::NetworkMessage,
 };
 use zenoh_result::{bail, ZResult};
@@ -57,13 +57,23 @@ use zenoh_transport::{
 };
 
 use self::orchestrator::StartConditions;
-use super::{primitives::DeMux, routing, routing::router::Router};
+use super::{
+    primitives::DeMux,
+    routing,
+    routing::{dispatcher::explain::RouteExplanation, router::Router},
+};
 #[cfg(feature = "plugins")]
 use crate::api::loader::{load_plugins, start_plugins};
 #[cfg(feature = "plugins")]
 use crate::api::plugins::PluginsManager;
 use crate::{
-    api::config::{Config, Notifier},
+    api::{
+        config::{Config, Notifier},
+        connectivity::{ConnectivityState, ConnectivityStateChange, EndpointEvent},
+        handlers::Callback,
+        info::{self, LinkEvent, Transport, TransportEvent},
+        Id,
+    },
     GIT_VERSION, LONG_VERSION,
 };
 
@@ -82,6 +92,40 @@ pub(crate) struct RuntimeState {
     plugins_manager: Mutex<PluginsManager>,
     start_conditions: Arc<StartConditions>,
     pending_connections: tokio::sync::Mutex<HashSet<ZenohIdProto>>,
+    listen_endpoints: tokio::sync::Mutex<Vec<EndPoint>>,
+    // The locators the opened listen endpoints are bound to, which differ from the endpoints
+    // when their port is chosen by the system
+    bound_listeners: std::sync::Mutex<HashMap<EndPoint, Locator>>,
+    connect_endpoints: tokio::sync::Mutex<Vec<EndPoint>>,
+    endpoint_events_callbacks: std::sync::RwLock<Vec<(Id, Callback<EndpointEvent>)>>,
+    transport_events_callbacks: std::sync::RwLock<Vec<(Id, Callback<TransportEvent>)>>,
+    link_events_callbacks: std::sync::RwLock<Vec<(Id, Callback<LinkEvent>)>>,
+    connectivity: std::sync::Mutex<Connectivity>,
+    connectivity_state_callbacks: std::sync::RwLock<Vec<(Id, Callback<ConnectivityStateChange>)>>,
+}
+
+/// The unicast transports of the runtime, tracked to compute its [`ConnectivityState`].
+struct Connectivity {
+    // Whether each transport is degraded, i.e. was established through
+    // another endpoint than the first connect endpoint of a client
+    transports: HashMap<ZenohIdProto, bool>,
+    state: ConnectivityState,
+    // The connect endpoint a client is currently connecting to
+    connecting: Option<EndPoint>,
+    // The connect endpoint the last transport of a client was established through
+    last_endpoint: Option<EndPoint>,
+}
+
+impl Connectivity {
+    fn is_degraded(&self, runtime: &Runtime, endpoint: Option<&EndPoint>) -> bool {
+        runtime.whatami() == WhatAmI::Client
+            && endpoint.is_some_and(|endpoint| {
+                runtime
+                    .configured_endpoints(false)
+                    .first()
+                    .is_some_and(|first| first != endpoint)
+            })
+    }
 }
 
 pub struct WeakRuntime {
@@ -135,6 +179,11 @@ impl RuntimeBuilder {
         } = self;
 
         tracing::debug!("Zenoh Rust API {}", GIT_VERSION);
+        if let Some(namespace) = config.namespace() {
+            if namespace.is_wild() {
+                bail!("Namespace {} must not contain wildcards", namespace);
+            }
+        }
         let zid = (*config.id()).into();
         tracing::info!("Using ZID: {}", zid);
 
@@ -185,10 +234,24 @@ impl RuntimeBuilder {
                 plugins_manager: Mutex::new(plugins_manager),
                 start_conditions: Arc::new(StartConditions::default()),
                 pending_connections: tokio::sync::Mutex::new(HashSet::new()),
+                listen_endpoints: tokio::sync::Mutex::new(vec![]),
+                bound_listeners: std::sync::Mutex::new(HashMap::new()),
+                connect_endpoints: tokio::sync::Mutex::new(vec![]),
+                endpoint_events_callbacks: std::sync::RwLock::new(vec![]),
+                transport_events_callbacks: std::sync::RwLock::new(vec![]),
+                link_events_callbacks: std::sync::RwLock::new(vec![]),
+                connectivity: std::sync::Mutex::new(Connectivity {
+                    transports: HashMap::new(),
+                    state: ConnectivityState::Disconnected,
+                    connecting: None,
+                    last_endpoint: None,
+                }),
+                connectivity_state_callbacks: std::sync::RwLock::new(vec![]),
             }),
         };
         *handler.runtime.write().unwrap() = Runtime::downgrade(&runtime);
         get_mut_unchecked(&mut runtime.state.router.clone()).init_link_state(runtime.clone());
+        runtime.state.router.start_interceptors(&runtime);
 
         // Admin space
         if start_admin_space {
@@ -215,6 +278,10 @@ impl RuntimeBuilder {
                                         if let Err(e) = runtime2.update_peers().await {
                                             tracing::error!("Error updating peers: {}", e);
                                         }
+                                    } else if &*event == "listen/endpoints" {
+                                        if let Err(e) = runtime2.update_listeners().await {
+                                            tracing::error!("Error updating listeners: {}", e);
+                                        }
                                     }
                                 },
                                 None => { break; }
@@ -269,6 +336,8 @@ impl Runtime {
 
     pub async fn close(&self) -> ZResult<()> {
         tracing::trace!("Runtime::close())");
+        // Save the routing state before the faces are closed
+        self.router().save_snapshot().await;
         // TODO: Plugins should be stopped
         // TODO: Check this whether is able to terminate all spawned task by Runtime::spawn
         self.state
@@ -332,6 +401,18 @@ impl Runtime {
         self.state.router.clone()
     }
 
+    /// Explains how the routing tables would route a put or a query on `key_expr` received
+    /// from `source`, or from a local session if `source` is `None`.
+    pub fn explain_route(
+        &self,
+        key_expr: &keyexpr,
+        source: Option<ZenohId>,
+    ) -> ZResult<RouteExplanation> {
+        self.state
+            .router
+            .explain_route(key_expr, source.map(Into::into))
+    }
+
     pub fn config(&self) -> &Notifier<Config> {
         &self.state.config
     }
@@ -369,6 +450,178 @@ impl Runtime {
     pub(crate) async fn remove_pending_connection(&self, zid: &ZenohIdProto) -> bool {
         self.state.pending_connections.lock().await.remove(zid)
     }
+
+    #[cfg(feature = "unstable")]
+    pub(crate) fn add_endpoint_events_callback(&self, callback: Callback<EndpointEvent>) -> Id {
+        let id = self.next_id();
+        zwrite!(self.state.endpoint_events_callbacks).push((id, callback));
+        id
+    }
+
+    #[cfg(feature = "unstable")]
+    pub(crate) fn remove_endpoint_events_callback(&self, id: Id) {
+        zwrite!(self.state.endpoint_events_callbacks).retain(|(i, _)| *i != id);
+    }
+
+    pub(crate) fn endpoint_event(&self, event: EndpointEvent) {
+        tracing::debug!("{:?}", event);
+        let callbacks = zread!(self.state.endpoint_events_callbacks).clone();
+        for (_, callback) in callbacks {
+            callback.call(event.clone());
+        }
+    }
+
+    #[cfg(feature = "unstable")]
+    pub(crate) fn add_transport_events_callback(&self, callback: Callback<TransportEvent>) -> Id {
+        let id = self.next_id();
+        zwrite!(self.state.transport_events_callbacks).push((id, callback));
+        id
+    }
+
+    #[cfg(feature = "unstable")]
+    pub(crate) fn remove_transport_events_callback(&self, id: Id) {
+        zwrite!(self.state.transport_events_callbacks).retain(|(i, _)| *i != id);
+    }
+
+    pub(crate) fn transport_event(&self, event: TransportEvent) {
+        tracing::debug!("{:?}", event);
+        let callbacks = zread!(self.state.transport_events_callbacks).clone();
+        for (_, callback) in callbacks {
+            callback.call(event.clone());
+        }
+    }
+
+    #[cfg(feature = "unstable")]
+    pub(crate) fn add_link_events_callback(&self, callback: Callback<LinkEvent>) -> Id {
+        let id = self.next_id();
+        zwrite!(self.state.link_events_callbacks).push((id, callback));
+        id
+    }
+
+    #[cfg(feature = "unstable")]
+    pub(crate) fn remove_link_events_callback(&self, id: Id) {
+        zwrite!(self.state.link_events_callbacks).retain(|(i, _)| *i != id);
+    }
+
+    pub(crate) fn link_event(&self, event: LinkEvent) {
+        tracing::debug!("{:?}", event);
+        let callbacks = zread!(self.state.link_events_callbacks).clone();
+        for (_, callback) in callbacks {
+            callback.call(event.clone());
+        }
+    }
+
+    pub(crate) fn connectivity_state(&self) -> ConnectivityState {
+        zlock!(self.state.connectivity).state
+    }
+
+    pub(crate) fn add_connectivity_state_callback(
+        &self,
+        callback: Callback<ConnectivityStateChange>,
+    ) -> Id {
+        let id = self.next_id();
+        zwrite!(self.state.connectivity_state_callbacks).push((id, callback));
+        id
+    }
+
+    pub(crate) fn remove_connectivity_state_callback(&self, id: Id) {
+        zwrite!(self.state.connectivity_state_callbacks).retain(|(i, _)| *i != id);
+    }
+
+    fn update_connectivity<F: FnOnce(&mut Connectivity)>(&self, update: F) {
+        let change = {
+            let mut connectivity = zlock!(self.state.connectivity);
+            update(&mut connectivity);
+            let current = if connectivity.transports.is_empty() {
+                ConnectivityState::Disconnected
+            } else if connectivity.transports.values().all(|degraded| *degraded) {
+                ConnectivityState::Degraded
+            } else {
+                ConnectivityState::Connected
+            };
+            let previous = std::mem::replace(&mut connectivity.state, current);
+            (previous != current).then_some(ConnectivityStateChange { previous, current })
+        };
+        if let Some(change) = change {
+            tracing::debug!("{:?}", change);
+            let callbacks = zread!(self.state.connectivity_state_callbacks).clone();
+            for (_, callback) in callbacks {
+                callback.call(change);
+            }
+        }
+    }
+
+    pub(super) fn connectivity_opened(&self, zid: ZenohIdProto) {
+        self.update_connectivity(|connectivity| {
+            let degraded = connectivity.is_degraded(self, connectivity.connecting.as_ref());
+            connectivity.transports.insert(zid, degraded);
+        });
+    }
+
+    pub(super) fn connectivity_closed(&self, zid: &ZenohIdProto) {
+        self.update_connectivity(|connectivity| {
+            connectivity.transports.remove(zid);
+        });
+    }
+
+    /// Records the connect endpoint a client is connecting to, so that the transport
+    /// is known as degraded from its opening when it is not the first connect endpoint.
+    pub(super) fn connectivity_connecting(&self, endpoint: Option<EndPoint>) {
+        zlock!(self.state.connectivity).connecting = endpoint;
+    }
+
+    /// Records the connect endpoint a transport was established through.
+    pub(super) fn connectivity_endpoint(&self, zid: &ZenohIdProto, endpoint: &EndPoint) {
+        self.update_connectivity(|connectivity| {
+            let degraded = connectivity.is_degraded(self, Some(endpoint));
+            if let Some(transport) = connectivity.transports.get_mut(zid) {
+                *transport = degraded;
+            }
+            connectivity.last_endpoint = Some(endpoint.clone());
+        });
+    }
+}
+
+/// Reports the opening and closing of a transport and of its links
+/// to the [`SessionInfo`](crate::session::SessionInfo) listeners.
+pub(super) struct TransportEvents {
+    runtime: Runtime,
+    zid: ZenohId,
+    transport: Transport,
+    links: std::sync::Mutex<Vec<Link>>,
+}
+
+impl TransportEvents {
+    fn new(runtime: Runtime, zid: ZenohId, transport: Transport) -> Self {
+        runtime.transport_event(TransportEvent::Opened(transport.clone()));
+        Self {
+            runtime,
+            zid,
+            transport,
+            links: std::sync::Mutex::new(vec![]),
+        }
+    }
+
+    fn new_link(&self, link: &Link) {
+        zlock!(self.links).push(link.clone());
+        self.runtime
+            .link_event(LinkEvent::Added(info::Link::new(self.zid, link)));
+    }
+
+    fn del_link(&self, link: &Link) {
+        zlock!(self.links).retain(|l| l != link);
+        self.runtime
+            .link_event(LinkEvent::Removed(info::Link::new(self.zid, link)));
+    }
+
+    fn closed(&self) {
+        for link in std::mem::take(&mut *zlock!(self.links)) {
+            self.runtime
+                .link_event(LinkEvent::Removed(info::Link::new(self.zid, &link)));
+        }
+        self.runtime
+            .transport_event(TransportEvent::Closed(self.transport.clone()));
+    }
 }
 
 struct RuntimeTransportEventHandler {
@@ -390,8 +643,11 @@ impl TransportEventHandler for RuntimeTransportEventHandler {
                             handler.new_unicast(peer.clone(), transport.clone()).ok()
                         })
                         .collect();
-                Ok(Arc::new(RuntimeSession {
+                let events = Transport::from_unicast(&transport)
+                    .map(|info| TransportEvents::new(runtime.clone(), peer.zid.into(), info));
+                let session = Arc::new(RuntimeSession {
                     runtime: runtime.clone(),
+                    zid: peer.zid,
                     endpoint: std::sync::RwLock::new(None),
                     main_handler: runtime
                         .state
@@ -399,7 +655,10 @@ impl TransportEventHandler for RuntimeTransportEventHandler {
                         .new_transport_unicast(transport)
                         .unwrap(),
                     slave_handlers,
-                }))
+                    events,
+                });
+                runtime.connectivity_opened(peer.zid);
+                Ok(session)
             }
             None => bail!("Runtime not yet ready!"),
         }
@@ -433,9 +692,11 @@ impl TransportEventHandler for RuntimeTransportEventHandler {
 
 pub(super) struct RuntimeSession {
     pub(super) runtime: Runtime,
+    pub(super) zid: ZenohIdProto,
     pub(super) endpoint: std::sync::RwLock<Option<EndPoint>>,
     pub(super) main_handler: Arc<DeMux>,
     pub(super) slave_handlers: Vec<Arc<dyn TransportPeerEventHandler>>,
+    pub(super) events: Option<TransportEvents>,
 }
 
 impl TransportPeerEventHandler for RuntimeSession {
@@ -448,6 +709,9 @@ impl TransportPeerEventHandler for RuntimeSession {
         for handler in &self.slave_handlers {
             handler.new_link(link.clone());
         }
+        if let Some(events) = &self.events {
+            events.new_link(&link);
+        }
     }
 
     fn del_link(&self, link: Link) {
@@ -455,14 +719,21 @@ impl TransportPeerEventHandler for RuntimeSession {
         for handler in &self.slave_handlers {
             handler.del_link(link.clone());
         }
+        if let Some(events) = &self.events {
+            events.del_link(&link);
+        }
     }
 
     fn closed(&self) {
         self.main_handler.closed();
+        self.runtime.connectivity_closed(&self.zid);
         Runtime::closed_session(self);
         for handler in &self.slave_handlers {
             handler.closed();
         }
+        if let Some(events) = &self.events {
+            events.closed();
+        }
     }
 
     fn as_any(&self) -> &dyn Any {
@@ -483,6 +754,14 @@ impl TransportMulticastEventHandler for RuntimeMulticastGroup {
             .iter()
             .filter_map(|handler| handler.new_peer(peer.clone()).ok())
             .collect();
+        let events = TransportEvents::new(
+            self.runtime.clone(),
+            peer.zid.into(),
+            Transport::from_multicast_peer(&peer),
+        );
+        for link in &peer.links {
+            events.new_link(link);
+        }
         Ok(Arc::new(RuntimeMulticastSession {
             main_handler: self
                 .runtime
@@ -490,6 +769,7 @@ impl TransportMulticastEventHandler for RuntimeMulticastGroup {
                 .router
                 .new_peer_multicast(self.transport.clone(), peer)?,
             slave_handlers,
+            events: Some(events),
         }))
     }
 
@@ -507,6 +787,7 @@ impl TransportMulticastEventHandler for RuntimeMulticastGroup {
 pub(super) struct RuntimeMulticastSession {
     pub(super) main_handler: Arc<DeMux>,
     pub(super) slave_handlers: Vec<Arc<dyn TransportPeerEventHandler>>,
+    pub(super) events: Option<TransportEvents>,
 }
 
 impl TransportPeerEventHandler for RuntimeMulticastSession {
@@ -519,6 +800,9 @@ impl TransportPeerEventHandler for RuntimeMulticastSession {
         for handler in &self.slave_handlers {
             handler.new_link(link.clone());
         }
+        if let Some(events) = &self.events {
+            events.new_link(&link);
+        }
     }
 
     fn del_link(&self, link: Link) {
@@ -526,6 +810,9 @@ impl TransportPeerEventHandler for RuntimeMulticastSession {
         for handler in &self.slave_handlers {
             handler.del_link(link.clone());
         }
+        if let Some(events) = &self.events {
+            events.del_link(&link);
+        }
     }
 
     fn closed(&self) {
@@ -533,6 +820,9 @@ impl TransportPeerEventHandler for RuntimeMulticastSession {
         for handler in &self.slave_handlers {
             handler.closed();
         }
+        if let Some(events) = &self.events {
+            events.closed();
+        }
     }
 
     fn as_any(&self) -> &dyn Any {
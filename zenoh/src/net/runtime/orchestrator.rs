@@ -13,12 +13,13 @@
 //
 use std::{
     collections::HashSet,
-    net::{IpAddr, Ipv6Addr, SocketAddr},
+    net::{IpAddr, Ipv6Addr, SocketAddr, SocketAddrV6},
     str::FromStr,
     time::Duration,
 };
 
 use futures::prelude::*;
+use rand::seq::SliceRandom;
 use socket2::{Domain, Socket, Type};
 use tokio::{
     net::UdpSocket,
@@ -30,7 +31,8 @@ use zenoh_buffers::{
 };
 use zenoh_codec::{RCodec, WCodec, Zenoh080};
 use zenoh_config::{
-    get_global_connect_timeout, get_global_listener_timeout, unwrap_or_default, ModeDependent,
+    get_global_connect_timeout, get_global_listener_timeout, unwrap_or_default, ConnectFailover,
+    ModeDependent, ScoutingMulticastConf,
 };
 use zenoh_link::{Locator, LocatorInspector};
 use zenoh_protocol::{
@@ -38,8 +40,10 @@ use zenoh_protocol::{
     scouting::{HelloProto, Scout, ScoutingBody, ScoutingMessage},
 };
 use zenoh_result::{bail, zerror, ZResult};
+use zenoh_transport::unicast::TransportUnicast;
 
 use super::{Runtime, RuntimeSession};
+use crate::api::connectivity::EndpointEvent;
 
 const RCV_BUF_SIZE: usize = u16::MAX as usize;
 const SCOUT_INITIAL_PERIOD: Duration = Duration::from_millis(1_000);
@@ -120,14 +124,17 @@ impl StartConditions {
 impl Runtime {
     pub async fn start(&mut self) -> ZResult<()> {
         match self.whatami() {
-            WhatAmI::Client => self.start_client().await,
-            WhatAmI::Peer => self.start_peer().await,
-            WhatAmI::Router => self.start_router().await,
+            WhatAmI::Client => self.start_client().await?,
+            WhatAmI::Peer => self.start_peer().await?,
+            WhatAmI::Router => self.start_router().await?,
         }
+        self.start_interfaces_watcher();
+        self.start_rtt_sampler();
+        Ok(())
     }
 
     async fn start_client(&self) -> ZResult<()> {
-        let (peers, scouting, addr, ifaces, timeout, multicast_ttl) = {
+        let (peers, scouting, addr, ifaces, timeout, multicast) = {
             let guard = &self.state.config.lock().0;
             (
                 guard
@@ -140,9 +147,10 @@ impl Runtime {
                 unwrap_or_default!(guard.scouting().multicast().address()),
                 unwrap_or_default!(guard.scouting().multicast().interface()),
                 std::time::Duration::from_millis(unwrap_or_default!(guard.scouting().timeout())),
-                unwrap_or_default!(guard.scouting().multicast().ttl()),
+                guard.scouting().multicast().clone(),
             )
         };
+        self.state.connect_endpoints.lock().await.clone_from(&peers);
         match peers.len() {
             0 => {
                 if scouting {
@@ -153,7 +161,7 @@ impl Runtime {
                     } else {
                         let sockets: Vec<UdpSocket> = ifaces
                             .into_iter()
-                            .filter_map(|iface| Runtime::bind_ucast_port(iface, multicast_ttl).ok())
+                            .filter_map(|iface| Runtime::bind_ucast_port(iface, &multicast).ok())
                             .collect();
                         if sockets.is_empty() {
                             bail!("Unable to bind UDP port to any multicast interface!")
@@ -203,8 +211,14 @@ impl Runtime {
             )
         };
 
+        self.state
+            .listen_endpoints
+            .lock()
+            .await
+            .clone_from(&listeners);
         self.bind_listeners(&listeners).await?;
 
+        self.state.connect_endpoints.lock().await.clone_from(&peers);
         self.connect_peers(&peers, false).await?;
 
         if scouting {
@@ -250,8 +264,14 @@ impl Runtime {
             )
         };
 
+        self.state
+            .listen_endpoints
+            .lock()
+            .await
+            .clone_from(&listeners);
         self.bind_listeners(&listeners).await?;
 
+        self.state.connect_endpoints.lock().await.clone_from(&peers);
         self.connect_peers(&peers, false).await?;
 
         if scouting {
@@ -269,17 +289,13 @@ impl Runtime {
         addr: SocketAddr,
         ifaces: String,
     ) -> ZResult<()> {
-        let multicast_ttl = {
-            let config_guard = self.config().lock();
-            let config = &config_guard.0;
-            unwrap_or_default!(config.scouting().multicast().ttl())
-        };
+        let multicast = self.config().lock().0.scouting().multicast().clone();
         let ifaces = Runtime::get_interfaces(&ifaces);
-        let mcast_socket = Runtime::bind_mcast_port(&addr, &ifaces, multicast_ttl).await?;
+        let mcast_socket = Runtime::bind_mcast_port(&addr, &ifaces, &multicast).await?;
         if !ifaces.is_empty() {
             let sockets: Vec<UdpSocket> = ifaces
                 .into_iter()
-                .filter_map(|iface| Runtime::bind_ucast_port(iface, multicast_ttl).ok())
+                .filter_map(|iface| Runtime::bind_ucast_port(iface, &multicast).ok())
                 .collect();
             if !sockets.is_empty() {
                 let this = self.clone();
@@ -338,28 +354,44 @@ impl Runtime {
     }
 
     async fn connect_peers_single_link(&self, peers: &[EndPoint]) -> ZResult<()> {
-        for peer in peers {
-            let endpoint = peer.clone();
-            let retry_config = self.get_connect_retry_config(&endpoint);
-            tracing::debug!(
-                "Try to connect: {:?}: global timeout: {:?}, retry: {:?}",
-                endpoint,
-                self.get_global_connect_timeout(),
-                retry_config
-            );
-            if retry_config.timeout().is_zero() || self.get_global_connect_timeout().is_zero() {
-                // try to connect and exit immediately without retry
-                if self
-                    .peer_connector(endpoint, retry_config.timeout())
-                    .await
-                    .is_ok()
-                {
+        // Try the endpoints one after the other in the failover order, each endpoint being
+        // retried after its own retry period, increased after each failed attempt
+        let retry = !self.get_global_connect_timeout().is_zero();
+        let now = tokio::time::Instant::now();
+        let mut endpoints: Vec<_> = self
+            .failover_order(peers)
+            .into_iter()
+            .map(|endpoint| {
+                let retry_config = self.get_connect_retry_config(&endpoint);
+                let period =
+                    (retry && !retry_config.timeout().is_zero()).then(|| retry_config.period());
+                (endpoint, period, Some(now))
+            })
+            .collect();
+        let cancellation_token = self.get_cancellation_token();
+        loop {
+            for (endpoint, period, next) in endpoints.iter_mut() {
+                if next.map_or(true, |next| next > tokio::time::Instant::now()) {
+                    continue;
+                }
+                let connected = self
+                    .connect_peer_single_link(endpoint.clone(), period.is_some())
+                    .await;
+                self.connectivity_connecting(None);
+                if connected {
                     return Ok(());
                 }
-            } else {
-                // try to connect with retry waiting
-                let _ = self.peer_connector_retry(endpoint).await;
-                return Ok(());
+                // The endpoints without retry are only tried once
+                *next = period.as_mut().and_then(|period| {
+                    tokio::time::Instant::now().checked_add(period.next_duration())
+                });
+            }
+            let Some(next) = endpoints.iter().filter_map(|(_, _, next)| *next).min() else {
+                break;
+            };
+            tokio::select! {
+                _ = tokio::time::sleep_until(next) => {}
+                _ = cancellation_token.cancelled() => { break; }
             }
         }
         let e = zerror!("Unable to connect to any of {:?}! ", peers);
@@ -367,6 +399,86 @@ impl Runtime {
         Err(e.into())
     }
 
+    async fn connect_peer_single_link(&self, endpoint: EndPoint, retry: bool) -> bool {
+        self.connectivity_connecting(Some(endpoint.clone()));
+        let retry_config = self.get_connect_retry_config(&endpoint);
+        tracing::debug!(
+            "Try to connect: {:?}: global timeout: {:?}, retry: {:?}",
+            endpoint,
+            self.get_global_connect_timeout(),
+            retry_config
+        );
+        if !retry {
+            // try to connect and exit immediately without retry
+            return self
+                .peer_connector(endpoint, retry_config.timeout())
+                .await
+                .is_ok();
+        }
+        match tokio::time::timeout(
+            retry_config.timeout(),
+            self.manager().open_transport_unicast(endpoint.clone()),
+        )
+        .await
+        {
+            Ok(Ok(transport)) => {
+                tracing::debug!("Successfully connected to configured peer {}", endpoint);
+                self.connected_through(&transport, endpoint);
+                true
+            }
+            Ok(Err(e)) => {
+                tracing::debug!("Unable to connect to configured peer {}! {}", endpoint, e);
+                false
+            }
+            Err(e) => {
+                tracing::debug!("Unable to connect to configured peer {}! {}", endpoint, e);
+                false
+            }
+        }
+    }
+
+    /// Returns the connect endpoints in the order a client should try them.
+    fn failover_order(&self, peers: &[EndPoint]) -> Vec<EndPoint> {
+        let failover = self
+            .config()
+            .lock()
+            .0
+            .connect()
+            .failover()
+            .unwrap_or_default();
+        let mut peers = peers.to_vec();
+        match failover {
+            ConnectFailover::Ordered => (),
+            ConnectFailover::RoundRobin => {
+                let last = zlock!(self.state.connectivity).last_endpoint.clone();
+                if let Some(index) = last.and_then(|last| peers.iter().position(|p| *p == last)) {
+                    peers.rotate_left(index + 1);
+                }
+            }
+            ConnectFailover::Random => peers.shuffle(&mut rand::thread_rng()),
+        }
+        peers
+    }
+
+    /// Records the connect endpoint a transport was established through.
+    fn connected_through(&self, transport: &TransportUnicast, endpoint: EndPoint) {
+        let Ok(zid) = transport.get_zid() else {
+            return;
+        };
+        if let Some(cost) = Self::endpoint_cost(&endpoint) {
+            self.router().set_link_weight(&zid, cost);
+        }
+        self.connectivity_endpoint(&zid, &endpoint);
+        if let Ok(Some(orch_transport)) = transport.get_callback() {
+            if let Some(orch_transport) = orch_transport
+                .as_any()
+                .downcast_ref::<super::RuntimeSession>()
+            {
+                *zwrite!(orch_transport.endpoint) = Some(endpoint);
+            }
+        }
+    }
+
     async fn connect_peers_multiply_links(&self, peers: &[EndPoint]) -> ZResult<()> {
         for peer in peers {
             let endpoint = peer.clone();
@@ -402,7 +514,10 @@ impl Runtime {
         match tokio::time::timeout(timeout, self.manager().open_transport_unicast(peer.clone()))
             .await
         {
-            Ok(Ok(_)) => Ok(()),
+            Ok(Ok(transport)) => {
+                self.connected_through(&transport, peer);
+                Ok(())
+            }
             Ok(Err(e)) => {
                 tracing::warn!("Unable to connect to {}! {}", peer, e);
                 Err(e)
@@ -415,64 +530,220 @@ impl Runtime {
     }
 
     pub(crate) async fn update_peers(&self) -> ZResult<()> {
-        let peers = {
-            self.state
-                .config
-                .lock()
-                .0
-                .connect()
-                .endpoints()
-                .get(self.state.whatami)
-                .unwrap_or(&vec![])
-                .clone()
-        };
-        let transports = self.manager().get_transports_unicast().await;
+        let peers = self.configured_endpoints(false);
+        let mut current = self.state.connect_endpoints.lock().await;
+        self.apply_connect_endpoints(&mut current, peers.clone())
+            .await?;
+        drop(current);
 
         if self.state.whatami == WhatAmI::Client {
-            for transport in transports {
-                let should_close = if let Ok(Some(orch_transport)) = transport.get_callback() {
-                    if let Some(orch_transport) = orch_transport
+            for transport in self.manager().get_transports_unicast().await {
+                let should_close = match transport.get_callback() {
+                    Ok(Some(callback)) => callback
                         .as_any()
                         .downcast_ref::<super::RuntimeSession>()
-                    {
-                        if let Some(endpoint) = &*zread!(orch_transport.endpoint) {
-                            !peers.contains(endpoint)
-                        } else {
-                            true
-                        }
-                    } else {
-                        false
-                    }
-                } else {
-                    false
+                        .map_or(false, |orch_transport| {
+                            zread!(orch_transport.endpoint)
+                                .as_ref()
+                                .map_or(true, |endpoint| !peers.contains(endpoint))
+                        }),
+                    _ => false,
                 };
                 if should_close {
                     transport.close().await?;
                 }
             }
-        } else {
-            for peer in peers {
-                if !transports.iter().any(|transport| {
-                    if let Ok(Some(orch_transport)) = transport.get_callback() {
-                        if let Some(orch_transport) = orch_transport
-                            .as_any()
-                            .downcast_ref::<super::RuntimeSession>()
-                        {
-                            if let Some(endpoint) = &*zread!(orch_transport.endpoint) {
-                                return *endpoint == peer;
-                            }
-                        }
-                    }
-                    false
-                }) {
-                    self.spawn_peer_connector(peer).await?;
+        }
+
+        Ok(())
+    }
+
+    pub(crate) async fn update_listeners(&self) -> ZResult<()> {
+        let listeners = self.configured_endpoints(true);
+        let mut current = self.state.listen_endpoints.lock().await;
+        let removed: Vec<EndPoint> = current
+            .iter()
+            .filter(|e| !listeners.contains(e))
+            .cloned()
+            .collect();
+        for endpoint in removed {
+            if self.is_listener_bound(&endpoint) {
+                if let Err(e) = self.del_listener(&endpoint).await {
+                    tracing::warn!("Unable to close listener {}: {}", endpoint, e);
+                    continue;
                 }
             }
+            current.retain(|e| *e != endpoint);
         }
+        for endpoint in listeners.iter().cloned() {
+            if !current.contains(&endpoint) {
+                current.push(endpoint.clone());
+                if self.add_listener(endpoint.clone()).await.is_err() {
+                    let retry_config = self.get_listen_retry_config(&endpoint);
+                    self.spawn_add_listener(endpoint, retry_config).await;
+                }
+            }
+        }
+        // The listeners that could not be closed are still configured
+        if current.iter().any(|e| !listeners.contains(e)) {
+            self.set_configured_endpoints(true, current.clone());
+        }
+        self.print_locators();
+        Ok(())
+    }
+
+    #[cfg(feature = "unstable")]
+    /// Opens a listener on `endpoint` and adds it to the `listen/endpoints` configuration.
+    pub(crate) async fn add_listener_endpoint(&self, endpoint: EndPoint) -> ZResult<Locator> {
+        let mut current = self.state.listen_endpoints.lock().await;
+        if current.contains(&endpoint) {
+            bail!("Already listening on {}", endpoint);
+        }
+        let locator = self.add_listener(endpoint.clone()).await?;
+        current.push(endpoint);
+        self.set_configured_endpoints(true, current.clone());
+        self.print_locators();
+        Ok(locator)
+    }
+
+    #[cfg(feature = "unstable")]
+    /// Closes the listener on `endpoint` and removes it from the `listen/endpoints` configuration.
+    pub(crate) async fn remove_listener_endpoint(&self, endpoint: &EndPoint) -> ZResult<()> {
+        let mut current = self.state.listen_endpoints.lock().await;
+        let Some(idx) = current.iter().position(|e| e == endpoint) else {
+            bail!("Not listening on {}", endpoint);
+        };
+        // A listener that is not bound yet is still being retried and only needs to be forgotten
+        if self.is_listener_bound(endpoint) {
+            self.del_listener(endpoint).await?;
+        }
+        current.remove(idx);
+        self.set_configured_endpoints(true, current.clone());
+        self.print_locators();
+        Ok(())
+    }
+
+    #[cfg(feature = "unstable")]
+    /// Adds `endpoint` to the `connect/endpoints` configuration and connects to it in background.
+    pub(crate) async fn add_connect_endpoint(&self, endpoint: EndPoint) -> ZResult<()> {
+        if LocatorInspector::default()
+            .is_multicast(&endpoint.to_locator())
+            .await?
+        {
+            bail!("Forbidden multicast endpoint in connect list!")
+        }
+        let mut current = self.state.connect_endpoints.lock().await;
+        if current.contains(&endpoint) {
+            bail!("Already connecting to {}", endpoint);
+        }
+        let mut peers = current.clone();
+        peers.push(endpoint);
+        self.set_configured_endpoints(false, peers.clone());
+        self.apply_connect_endpoints(&mut current, peers).await
+    }
+
+    #[cfg(feature = "unstable")]
+    /// Removes `endpoint` from the `connect/endpoints` configuration and closes the transports
+    /// established through it.
+    pub(crate) async fn remove_connect_endpoint(&self, endpoint: &EndPoint) -> ZResult<()> {
+        let mut current = self.state.connect_endpoints.lock().await;
+        if !current.contains(endpoint) {
+            bail!("Not connecting to {}", endpoint);
+        }
+        let peers: Vec<EndPoint> = current.iter().filter(|e| *e != endpoint).cloned().collect();
+        self.set_configured_endpoints(false, peers.clone());
+        self.apply_connect_endpoints(&mut current, peers).await
+    }
+
+    async fn apply_connect_endpoints(
+        &self,
+        current: &mut Vec<EndPoint>,
+        peers: Vec<EndPoint>,
+    ) -> ZResult<()> {
+        let removed: Vec<EndPoint> = current
+            .iter()
+            .filter(|e| !peers.contains(e))
+            .cloned()
+            .collect();
+        let added: Vec<EndPoint> = peers
+            .iter()
+            .filter(|e| !current.contains(e))
+            .cloned()
+            .collect();
+        *current = peers;
 
+        if !removed.is_empty() {
+            for transport in self.manager().get_transports_unicast().await {
+                if Runtime::transport_endpoint(&transport).map_or(false, |e| removed.contains(&e)) {
+                    transport.close().await?;
+                }
+            }
+        }
+        for endpoint in removed {
+            self.endpoint_event(EndpointEvent::ConnectRemoved { endpoint });
+        }
+        for endpoint in added {
+            self.endpoint_event(EndpointEvent::ConnectAdded {
+                endpoint: endpoint.clone(),
+            });
+            // Clients connect to the configured endpoints when their current transport is closed
+            if self.state.whatami != WhatAmI::Client {
+                self.spawn_peer_connector(endpoint).await?;
+            }
+        }
         Ok(())
     }
 
+    fn transport_endpoint(transport: &TransportUnicast) -> Option<EndPoint> {
+        transport
+            .get_callback()
+            .ok()
+            .flatten()
+            .and_then(|callback| {
+                callback
+                    .as_any()
+                    .downcast_ref::<super::RuntimeSession>()
+                    .and_then(|orch_transport| zread!(orch_transport.endpoint).clone())
+            })
+    }
+
+    pub(super) fn configured_endpoints(&self, listen: bool) -> Vec<EndPoint> {
+        let guard = &self.state.config.lock().0;
+        let endpoints = if listen {
+            guard.listen().endpoints()
+        } else {
+            guard.connect().endpoints()
+        };
+        endpoints
+            .get(self.state.whatami)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    fn set_configured_endpoints(&self, listen: bool, endpoints: Vec<EndPoint>) {
+        {
+            let guard = &mut self.state.config.lock().0;
+            let value = if listen {
+                &mut guard.listen.endpoints
+            } else {
+                &mut guard.connect.endpoints
+            };
+            match value {
+                zenoh_config::ModeDependentValue::Unique(value) => *value = endpoints,
+                zenoh_config::ModeDependentValue::Dependent(values) => match self.state.whatami {
+                    WhatAmI::Router => values.router = Some(endpoints),
+                    WhatAmI::Peer => values.peer = Some(endpoints),
+                    WhatAmI::Client => values.client = Some(endpoints),
+                },
+            }
+        }
+        self.state.config.notify(if listen {
+            "listen/endpoints"
+        } else {
+            "connect/endpoints"
+        });
+    }
+
     fn get_listen_retry_config(&self, endpoint: &EndPoint) -> zenoh_config::ConnectionRetryConf {
         let guard = &self.state.config.lock().0;
         zenoh_config::get_retry_config(guard, Some(endpoint), true)
@@ -560,6 +831,10 @@ impl Runtime {
     ) {
         let mut period = retry_config.period();
         loop {
+            if !self.state.listen_endpoints.lock().await.contains(&listener) {
+                tracing::debug!("Listener {} removed, stop retrying", listener);
+                break;
+            }
             if self.add_listener(listener.clone()).await.is_ok() {
                 break;
             }
@@ -567,26 +842,196 @@ impl Runtime {
         }
     }
 
-    async fn add_listener(&self, listener: EndPoint) -> ZResult<()> {
+    async fn add_listener(&self, listener: EndPoint) -> ZResult<Locator> {
         let endpoint = listener.clone();
         match self.manager().add_listener(endpoint).await {
-            Ok(listener) => tracing::debug!("Listener added: {}", listener),
+            Ok(locator) => {
+                tracing::debug!("Listener added: {}", locator);
+                self.state
+                    .bound_listeners
+                    .lock()
+                    .unwrap()
+                    .insert(listener.clone(), locator.clone());
+                self.endpoint_event(EndpointEvent::ListenerAdded {
+                    endpoint: listener,
+                    locator: locator.clone(),
+                });
+                Ok(locator)
+            }
             Err(err) => {
                 tracing::warn!("Unable to open listener {}: {}", listener, err);
-                return Err(err);
+                self.endpoint_event(EndpointEvent::ListenerFailed {
+                    endpoint: listener,
+                    error: err.to_string(),
+                });
+                Err(err)
             }
         }
+    }
+
+    fn is_listener_bound(&self, listener: &EndPoint) -> bool {
+        self.state
+            .bound_listeners
+            .lock()
+            .unwrap()
+            .contains_key(listener)
+    }
+
+    async fn del_listener(&self, listener: &EndPoint) -> ZResult<()> {
+        // Listeners are closed through the address they are bound to, whose port differs from
+        // the one of the endpoint when it was chosen by the system
+        let bound = self
+            .state
+            .bound_listeners
+            .lock()
+            .unwrap()
+            .get(listener)
+            .map_or_else(|| listener.clone(), Locator::to_endpoint);
+        self.manager().del_listener(&bound).await?;
+        self.state.bound_listeners.lock().unwrap().remove(listener);
+        tracing::debug!("Listener removed: {}", listener);
+        self.endpoint_event(EndpointEvent::ListenerRemoved {
+            endpoint: listener.clone(),
+        });
         Ok(())
     }
 
     fn print_locators(&self) {
-        let mut locators = self.state.locators.write().unwrap();
-        *locators = self.manager().get_locators();
-        for locator in &*locators {
+        self.update_locators();
+        for locator in &*self.state.locators.read().unwrap() {
             tracing::info!("Zenoh can be reached at: {}", locator);
         }
     }
 
+    /// Updates the locators advertised in scouting and gossip messages.
+    /// Returns true if they changed.
+    fn update_locators(&self) -> bool {
+        let locators = self.manager().get_locators();
+        let changed = {
+            let mut current = self.state.locators.write().unwrap();
+            let changed =
+                locators.len() != current.len() || locators.iter().any(|l| !current.contains(l));
+            *current = locators;
+            changed
+        };
+        if changed {
+            self.router().update_locators();
+        }
+        changed
+    }
+
+    fn start_interfaces_watcher(&self) {
+        let period = {
+            let guard = &self.state.config.lock().0;
+            unwrap_or_default!(guard.listen().interfaces_check_period_ms())
+        };
+        if period == 0 {
+            return;
+        }
+        // The addresses are polled as their changes are not notified in a portable way
+        let this = self.clone();
+        self.spawn_abortable(async move {
+            let mut interval = tokio::time::interval(Duration::from_millis(period));
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            loop {
+                interval.tick().await;
+                this.check_interfaces().await;
+            }
+        });
+    }
+
+    /// Periodically samples the round-trip time of the links to update the link weights derived
+    /// from it.
+    fn start_rtt_sampler(&self) {
+        let (rtt_weights, period) = {
+            let guard = &self.state.config.lock().0;
+            (
+                unwrap_or_default!(guard.routing().linkstate().rtt_weights()),
+                unwrap_or_default!(guard.routing().linkstate().rtt_period()),
+            )
+        };
+        if !rtt_weights || period == 0 || self.whatami() == WhatAmI::Client {
+            return;
+        }
+        let this = self.clone();
+        self.spawn_abortable(async move {
+            let mut interval = tokio::time::interval(Duration::from_millis(period));
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            loop {
+                interval.tick().await;
+                this.router().update_rtt_weights();
+            }
+        });
+    }
+
+    /// Re-binds the listeners whose address is no longer available and re-advertises
+    /// the locators when the addresses of the network interfaces changed.
+    async fn check_interfaces(&self) {
+        let opened = self.manager().get_listeners().await;
+        let configured = self.state.listen_endpoints.lock().await.clone();
+        for endpoint in configured {
+            let Some(addr) = Runtime::endpoint_socket_addr(&endpoint) else {
+                continue;
+            };
+            // Loopback addresses can always be bound, while only some of them are listed
+            if addr.ip().is_unspecified() || addr.ip().is_multicast() || addr.ip().is_loopback() {
+                continue;
+            }
+            let config = endpoint.config();
+            let iface = config.get(zenoh_link::BIND_INTERFACE);
+            if zenoh_util::net::get_local_addresses(iface)
+                .map_or(true, |addrs| addrs.contains(&addr.ip()))
+            {
+                continue;
+            }
+            let is_opened = opened.iter().any(|e| {
+                e.protocol() == endpoint.protocol()
+                    && Runtime::endpoint_socket_addr(e).map_or(false, |a| {
+                        a.ip() == addr.ip() && (addr.port() == 0 || a.port() == addr.port())
+                    })
+            });
+            if is_opened {
+                tracing::warn!(
+                    "Address of listener {} is no longer available, re-binding",
+                    endpoint
+                );
+                if let Err(e) = self.del_listener(&endpoint).await {
+                    tracing::warn!("Unable to close listener {}: {}", endpoint, e);
+                }
+                let retry_config = self.get_listen_retry_config(&endpoint);
+                self.spawn_add_listener(endpoint, retry_config).await;
+            }
+        }
+
+        if self.update_locators() {
+            for locator in &*self.state.locators.read().unwrap() {
+                tracing::info!("Zenoh can be reached at: {}", locator);
+            }
+        }
+    }
+
+    fn endpoint_socket_addr(endpoint: &EndPoint) -> Option<SocketAddr> {
+        endpoint.address().as_str().parse().ok()
+    }
+
+    // The link weight configured with the `cost` endpoint config, if any
+    fn endpoint_cost(endpoint: &EndPoint) -> Option<u16> {
+        let config = endpoint.config();
+        let cost = config.get("cost")?;
+        match cost.parse::<u16>() {
+            Ok(cost) if cost > 0 => Some(cost),
+            _ => {
+                tracing::warn!(
+                    "Invalid cost '{}' for endpoint {}: expected an integer between 1 and {}",
+                    cost,
+                    endpoint,
+                    u16::MAX
+                );
+                None
+            }
+        }
+    }
+
     pub fn get_interfaces(names: &str) -> Vec<IpAddr> {
         if names == "auto" {
             let ifaces = zenoh_util::net::get_multicast_interfaces();
@@ -624,8 +1069,21 @@ impl Runtime {
     pub async fn bind_mcast_port(
         sockaddr: &SocketAddr,
         ifaces: &[IpAddr],
-        multicast_ttl: u32,
+        multicast: &ScoutingMulticastConf,
     ) -> ZResult<UdpSocket> {
+        let sources = multicast.sources().clone().unwrap_or_default();
+        if !sources.is_empty() && (sockaddr.is_ipv6() || sources.iter().any(|s| s.is_ipv6())) {
+            bail!(
+                "Source-specific multicast scouting is only supported with IPv4 addresses: {}",
+                sockaddr
+            );
+        }
+        let scope_id = match (multicast.scope_id(), sockaddr) {
+            (Some(scope_id), _) => *scope_id,
+            (None, SocketAddr::V6(sockaddr)) => sockaddr.scope_id(),
+            (None, SocketAddr::V4(_)) => 0,
+        };
+
         let socket = match Socket::new(Domain::for_address(*sockaddr), Type::DGRAM, None) {
             Ok(socket) => socket,
             Err(err) => {
@@ -647,7 +1105,12 @@ impl Runtime {
                 std::net::Ipv4Addr::UNSPECIFIED.into()
             }
         };
-        match socket.bind(&SocketAddr::new(addr, sockaddr.port()).into()) {
+        let bind_addr = match addr {
+            // Link-local groups can only be bound with their scope
+            IpAddr::V6(addr) => SocketAddrV6::new(addr, sockaddr.port(), 0, scope_id).into(),
+            IpAddr::V4(_) => SocketAddr::new(addr, sockaddr.port()),
+        };
+        match socket.bind(&bind_addr.into()) {
             Ok(()) => tracing::debug!("UDP port bound to {}", sockaddr),
             Err(err) => {
                 tracing::error!("Unable to bind UDP port {}: {}", sockaddr, err);
@@ -656,26 +1119,40 @@ impl Runtime {
         }
 
         match sockaddr.ip() {
-            IpAddr::V6(addr) => match socket.join_multicast_v6(&addr, 0) {
+            IpAddr::V6(addr) => match socket.join_multicast_v6(&addr, scope_id) {
                 Ok(()) => {
-                    tracing::debug!("Joined multicast group {} on interface 0", sockaddr.ip())
+                    tracing::debug!(
+                        "Joined multicast group {} on interface {}",
+                        sockaddr.ip(),
+                        scope_id
+                    )
                 }
                 Err(err) => {
                     tracing::error!(
-                        "Unable to join multicast group {} on interface 0: {}",
+                        "Unable to join multicast group {} on interface {}: {}",
                         sockaddr.ip(),
+                        scope_id,
                         err
                     );
                     bail!(err =>
-                        "Unable to join multicast group {} on interface 0",
-                        sockaddr.ip()
+                        "Unable to join multicast group {} on interface {}",
+                        sockaddr.ip(),
+                        scope_id
                     )
                 }
             },
             IpAddr::V4(addr) => {
                 for iface in ifaces {
                     if let IpAddr::V4(iface_addr) = iface {
-                        match socket.join_multicast_v4(&addr, iface_addr) {
+                        let res = if sources.is_empty() {
+                            socket.join_multicast_v4(&addr, iface_addr)
+                        } else {
+                            sources.iter().try_for_each(|source| match source {
+                                IpAddr::V4(source) => socket.join_ssm_v4(source, &addr, iface_addr),
+                                IpAddr::V6(_) => unreachable!(),
+                            })
+                        };
+                        match res {
                             Ok(()) => tracing::debug!(
                                 "Joined multicast group {} on interface {}",
                                 sockaddr.ip(),
@@ -703,11 +1180,7 @@ impl Runtime {
         // Must set to nonblocking according to the doc of tokio
         // https://docs.rs/tokio/latest/tokio/net/struct.UdpSocket.html#notes
         socket.set_nonblocking(true)?;
-        socket.set_multicast_ttl_v4(multicast_ttl)?;
-
-        if sockaddr.is_ipv6() && multicast_ttl > 1 {
-            tracing::warn!("UDP Multicast TTL has been set to a value greater than 1 on a socket bound to an IPv6 address. This might not have the desired effect");
-        }
+        Runtime::set_multicast_options(&socket, sockaddr.ip(), multicast)?;
 
         // UdpSocket::from_std requires a runtime even though it's a sync function
         let udp_socket = zenoh_runtime::ZRuntime::Net
@@ -715,7 +1188,7 @@ impl Runtime {
         Ok(udp_socket)
     }
 
-    pub fn bind_ucast_port(addr: IpAddr, multicast_ttl: u32) -> ZResult<UdpSocket> {
+    pub fn bind_ucast_port(addr: IpAddr, multicast: &ScoutingMulticastConf) -> ZResult<UdpSocket> {
         let sockaddr = || SocketAddr::new(addr, 0);
         let socket = match Socket::new(Domain::for_address(sockaddr()), Type::DGRAM, None) {
             Ok(socket) => socket,
@@ -743,7 +1216,10 @@ impl Runtime {
         // Must set to nonblocking according to the doc of tokio
         // https://docs.rs/tokio/latest/tokio/net/struct.UdpSocket.html#notes
         socket.set_nonblocking(true)?;
-        socket.set_multicast_ttl_v4(multicast_ttl)?;
+        Runtime::set_multicast_options(&socket, addr, multicast)?;
+        if let (IpAddr::V6(_), Some(scope_id)) = (addr, multicast.scope_id()) {
+            socket.set_multicast_if_v6(*scope_id)?;
+        }
 
         // UdpSocket::from_std requires a runtime even though it's a sync function
         let udp_socket = zenoh_runtime::ZRuntime::Net
@@ -751,6 +1227,27 @@ impl Runtime {
         Ok(udp_socket)
     }
 
+    fn set_multicast_options(
+        socket: &Socket,
+        addr: IpAddr,
+        multicast: &ScoutingMulticastConf,
+    ) -> ZResult<()> {
+        let multicast_ttl = multicast
+            .ttl()
+            .unwrap_or(zenoh_config::defaults::scouting::multicast::ttl);
+        match addr {
+            IpAddr::V4(_) => socket.set_multicast_ttl_v4(multicast_ttl)?,
+            IpAddr::V6(_) => socket.set_multicast_hops_v6(multicast_ttl)?,
+        }
+        if let Some(loopback) = multicast.loopback() {
+            match addr {
+                IpAddr::V4(_) => socket.set_multicast_loop_v4(*loopback)?,
+                IpAddr::V6(_) => socket.set_multicast_loop_v6(*loopback)?,
+            }
+        }
+        Ok(())
+    }
+
     async fn spawn_peer_connector(&self, peer: EndPoint) -> ZResult<()> {
         if !LocatorInspector::default()
             .is_multicast(&peer.to_locator())
@@ -788,6 +1285,9 @@ impl Runtime {
         let mut period = retry_config.period();
         let cancellation_token = self.get_cancellation_token();
         loop {
+            if !self.state.connect_endpoints.lock().await.contains(&peer) {
+                bail!("Peer {} removed from the connect endpoints", peer);
+            }
             tracing::trace!("Trying to connect to configured peer {}", peer);
             let endpoint = peer.clone();
             tokio::select! {
@@ -795,14 +1295,7 @@ impl Runtime {
                     match res {
                         Ok(Ok(transport)) => {
                             tracing::debug!("Successfully connected to configured peer {}", peer);
-                            if let Ok(Some(orch_transport)) = transport.get_callback() {
-                                if let Some(orch_transport) = orch_transport
-                                    .as_any()
-                                    .downcast_ref::<super::RuntimeSession>()
-                                {
-                                    *zwrite!(orch_transport.endpoint) = Some(peer);
-                                }
-                            }
+                            self.connected_through(&transport, peer);
                             return transport.get_zid();
                         }
                         Ok(Err(e)) => {
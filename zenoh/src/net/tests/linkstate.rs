@@ -0,0 +1,147 @@
+//
+// Copyright (c) 2023 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+use std::convert::TryFrom;
+
+use zenoh_buffers::{
+    reader::{DidntRead, HasReader, Reader},
+    writer::HasWriter,
+    ZBuf,
+};
+use zenoh_codec::{RCodec, WCodec, Zenoh080};
+use zenoh_protocol::{
+    common::imsg,
+    core::{Locator, WhatAmI, ZenohIdProto},
+};
+
+use crate::net::{
+    codec::Zenoh080Routing,
+    protocol::{
+        linkstate,
+        linkstate::{LinkState, LinkStateList},
+    },
+};
+
+fn link_state(psid: u64, link_weights: Option<Vec<u16>>, region: Option<&str>) -> LinkState {
+    LinkState {
+        psid,
+        sn: psid + 10,
+        zid: Some(ZenohIdProto::default()),
+        whatami: Some(WhatAmI::Router),
+        locators: Some(vec!["tcp/127.0.0.1:7447".parse().unwrap()]),
+        links: vec![1, 2, 3],
+        link_weights,
+        region: region.map(String::from),
+    }
+}
+
+fn encode(list: &LinkStateList) -> ZBuf {
+    let mut buf = ZBuf::empty();
+    Zenoh080Routing::new()
+        .write(&mut buf.writer(), list)
+        .unwrap();
+    buf
+}
+
+// The decoder of the nodes which don't know the link weights and region extensions
+fn legacy_read<R: Reader>(reader: &mut R) -> Result<Vec<LinkState>, DidntRead> {
+    let codec = Zenoh080::new();
+    let len: usize = codec.read(&mut *reader)?;
+    let mut link_states = vec![];
+    for _ in 0..len {
+        let options: u64 = codec.read(&mut *reader)?;
+        let psid: u64 = codec.read(&mut *reader)?;
+        let sn: u64 = codec.read(&mut *reader)?;
+        let zid = if imsg::has_option(options, linkstate::PID) {
+            Some(codec.read(&mut *reader)?)
+        } else {
+            None
+        };
+        let whatami = if imsg::has_option(options, linkstate::WAI) {
+            let wai: u8 = codec.read(&mut *reader)?;
+            Some(WhatAmI::try_from(wai).map_err(|_| DidntRead)?)
+        } else {
+            None
+        };
+        let locators = if imsg::has_option(options, linkstate::LOC) {
+            let locators: Vec<Locator> = codec.read(&mut *reader)?;
+            Some(locators)
+        } else {
+            None
+        };
+        let len: usize = codec.read(&mut *reader)?;
+        let mut links = vec![];
+        for _ in 0..len {
+            let link: u64 = codec.read(&mut *reader)?;
+            links.push(link);
+        }
+        link_states.push(LinkState {
+            psid,
+            sn,
+            zid,
+            whatami,
+            locators,
+            links,
+            link_weights: None,
+            region: None,
+        });
+    }
+    Ok(link_states)
+}
+
+#[test]
+fn linkstate_codec() {
+    for list in [
+        LinkStateList {
+            link_states: vec![link_state(0, None, None), link_state(1, None, None)],
+        },
+        LinkStateList {
+            link_states: vec![
+                link_state(0, Some(vec![10, 0, 30]), None),
+                link_state(1, None, Some("paris")),
+                link_state(2, Some(vec![1, 2, 3]), Some("london")),
+            ],
+        },
+        LinkStateList {
+            link_states: vec![link_state(0, None, Some("paris"))],
+        },
+    ] {
+        let buf = encode(&list);
+        let decoded: LinkStateList = Zenoh080Routing::new().read(&mut buf.reader()).unwrap();
+        assert_eq!(decoded, list);
+    }
+}
+
+#[test]
+fn linkstate_codec_legacy_decoder() {
+    // The link weights and regions are ignored by the nodes which don't know them
+    let list = LinkStateList {
+        link_states: vec![
+            link_state(0, Some(vec![10, 0, 30]), Some("paris")),
+            link_state(1, None, Some("london")),
+            link_state(2, Some(vec![1, 2, 3]), None),
+        ],
+    };
+    let buf = encode(&list);
+    let decoded = legacy_read(&mut buf.reader()).unwrap();
+    let expected: Vec<LinkState> = list
+        .link_states
+        .into_iter()
+        .map(|ls| LinkState {
+            link_weights: None,
+            region: None,
+            ..ls
+        })
+        .collect();
+    assert_eq!(decoded, expected);
+}
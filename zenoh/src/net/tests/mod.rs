@@ -1 +1,2 @@
+pub(crate) mod linkstate;
 pub(crate) mod tables;
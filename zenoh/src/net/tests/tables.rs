@@ -34,7 +34,7 @@ use crate::net::{
     routing::{
         dispatcher::{pubsub::SubscriberInfo, tables::Tables},
         router::*,
-        RoutingContext,
+        strip_wild_prefix, RoutingContext,
     },
 };
 
@@ -731,6 +731,7 @@ fn client_test() {
             ext_qos: ext::QoSType::DEFAULT,
             ext_tstamp: None,
             ext_nodeid: ext::NodeIdType { node_id: 0 },
+            ext_redundancy: None,
             payload: PushBody::Put(Put {
                 timestamp: None,
                 encoding: Encoding::empty(),
@@ -768,6 +769,7 @@ fn client_test() {
             ext_qos: ext::QoSType::DEFAULT,
             ext_tstamp: None,
             ext_nodeid: ext::NodeIdType { node_id: 0 },
+            ext_redundancy: None,
             payload: PushBody::Put(Put {
                 timestamp: None,
                 encoding: Encoding::empty(),
@@ -805,6 +807,7 @@ fn client_test() {
             ext_qos: ext::QoSType::DEFAULT,
             ext_tstamp: None,
             ext_nodeid: ext::NodeIdType { node_id: 0 },
+            ext_redundancy: None,
             payload: PushBody::Put(Put {
                 timestamp: None,
                 encoding: Encoding::empty(),
@@ -842,6 +845,7 @@ fn client_test() {
             ext_qos: ext::QoSType::DEFAULT,
             ext_tstamp: None,
             ext_nodeid: ext::NodeIdType { node_id: 0 },
+            ext_redundancy: None,
             payload: PushBody::Put(Put {
                 timestamp: None,
                 encoding: Encoding::empty(),
@@ -879,6 +883,7 @@ fn client_test() {
             ext_qos: ext::QoSType::DEFAULT,
             ext_tstamp: None,
             ext_nodeid: ext::NodeIdType { node_id: 0 },
+            ext_redundancy: None,
             payload: PushBody::Put(Put {
                 timestamp: None,
                 encoding: Encoding::empty(),
@@ -905,3 +910,24 @@ fn client_test() {
     // mapping strategy check
     // assert_eq!(primitives2.get_last_key().unwrap(), KeyExpr::IdWithSuffix(31, "/z2_pub1".to_string()));
 }
+
+#[test]
+fn strip_wild_prefix_test() {
+    let strip = |key_expr: &str, prefix: &str| -> Vec<String> {
+        strip_wild_prefix(
+            keyexpr::new(key_expr).unwrap(),
+            keyexpr::new(prefix).unwrap(),
+        )
+        .into_iter()
+        .map(|suffix| suffix.to_string())
+        .collect()
+    };
+    assert_eq!(strip("a/**", "a/b"), ["**"]);
+    assert_eq!(strip("**", "a/b"), ["**"]);
+    assert_eq!(strip("a/*/c/**", "a/b"), ["c/**"]);
+    assert_eq!(strip("a/b$*/c", "a/bc"), ["c"]);
+    assert_eq!(strip("**/c", "a/b"), ["**/c"]);
+    assert_eq!(strip("**/b/c", "a/b"), ["c", "**/b/c"]);
+    assert!(strip("a/*", "a/b").is_empty());
+    assert!(strip("a/*/c", "x/b").is_empty());
+}
@@ -0,0 +1,127 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+#![cfg(feature = "unstable")]
+
+use std::time::Duration;
+
+use zenoh::{
+    bytes::Encoding,
+    config::WhatAmI,
+    qos::{CongestionControl, Priority},
+    Config, Session,
+};
+use zenoh_core::ztimeout;
+
+const TIMEOUT: Duration = Duration::from_secs(60);
+const SLEEP: Duration = Duration::from_secs(1);
+const N: usize = 200;
+
+async fn open_peers(locator: &str) -> (Session, Session) {
+    let mut config = Config::default();
+    config.set_mode(Some(WhatAmI::Peer)).unwrap();
+    config
+        .listen
+        .endpoints
+        .set(vec![locator.parse().unwrap()])
+        .unwrap();
+    config.scouting.multicast.set_enabled(Some(false)).unwrap();
+    let peer1 = ztimeout!(zenoh::open(config)).unwrap();
+
+    let mut config = Config::default();
+    config.set_mode(Some(WhatAmI::Peer)).unwrap();
+    config
+        .connect
+        .endpoints
+        .set(vec![locator.parse().unwrap()])
+        .unwrap();
+    config.scouting.multicast.set_enabled(Some(false)).unwrap();
+    let peer2 = ztimeout!(zenoh::open(config)).unwrap();
+    (peer1, peer2)
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn zenoh_put_batch() {
+    zenoh_util::init_log_from_env_or("error");
+    let (peer1, peer2) = open_peers("tcp/127.0.0.1:17630").await;
+
+    let remote_sub = ztimeout!(peer2.declare_subscriber("test/batch/**")).unwrap();
+    let local_sub = ztimeout!(peer1.declare_subscriber("test/batch/**")).unwrap();
+    tokio::time::sleep(SLEEP).await;
+
+    // The publications of a batch are received in order, with their own key, encoding and attachment
+    ztimeout!(peer1
+        .put_batch((0..N).map(|i| {
+            if i % 2 == 0 {
+                (format!("test/batch/{i}"), i.to_string()).into()
+            } else {
+                (
+                    format!("test/batch/{i}"),
+                    i.to_string(),
+                    Encoding::TEXT_PLAIN,
+                    Some(format!("attachment {i}")),
+                )
+                    .into()
+            }
+        }))
+        .congestion_control(CongestionControl::Block)
+        .priority(Priority::DataHigh))
+    .unwrap();
+    for sub in [&remote_sub, &local_sub] {
+        for i in 0..N {
+            let sample = ztimeout!(sub.recv_async()).unwrap();
+            assert_eq!(sample.key_expr().as_str(), format!("test/batch/{i}"));
+            assert_eq!(sample.payload().try_to_string().unwrap(), i.to_string());
+            assert_eq!(sample.priority(), Priority::DataHigh);
+            if i % 2 == 0 {
+                assert_eq!(sample.encoding(), &Encoding::default());
+                assert!(sample.attachment().is_none());
+            } else {
+                assert_eq!(sample.encoding(), &Encoding::TEXT_PLAIN);
+                assert_eq!(
+                    sample.attachment().unwrap().try_to_string().unwrap(),
+                    format!("attachment {i}")
+                );
+            }
+        }
+    }
+
+    // An invalid key expression fails the whole batch
+    assert!(peer1
+        .put_batch([
+            ("test/batch/valid", "valid").into(),
+            ("test/batch/*/invalid/**/**", "invalid").into(),
+        ])
+        .await
+        .is_err());
+
+    // Publishers put many payloads with their own key expression and qos
+    let publisher = ztimeout!(peer1
+        .declare_publisher("test/batch/publisher")
+        .encoding(Encoding::ZENOH_STRING)
+        .congestion_control(CongestionControl::Block))
+    .unwrap();
+    ztimeout!(publisher.put_many((0..N).map(|i| i.to_string()))).unwrap();
+    for i in 0..N {
+        let sample = ztimeout!(remote_sub.recv_async()).unwrap();
+        assert_eq!(sample.key_expr().as_str(), "test/batch/publisher");
+        assert_eq!(sample.payload().try_to_string().unwrap(), i.to_string());
+        assert_eq!(sample.encoding(), &Encoding::ZENOH_STRING);
+        assert!(sample.timestamp().is_none());
+    }
+    tokio::time::sleep(SLEEP).await;
+    assert!(remote_sub.try_recv().unwrap().is_none());
+
+    ztimeout!(peer1.close()).unwrap();
+    ztimeout!(peer2.close()).unwrap();
+}
@@ -11,13 +11,87 @@
 // Contributors:
 //   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
 //
-#![cfg(all(feature = "shared-memory", feature = "unstable"))]
+#![cfg(feature = "unstable")]
+use std::io::{Read, Seek, SeekFrom};
+
+use bytes::Buf;
+use zenoh::bytes::ZBytes;
+#[cfg(feature = "shared-memory")]
 use zenoh::{
-    bytes::ZBytes,
     shm::{zshmmut, PosixShmProviderBackend, ShmProviderBuilder, ZShm, ZShmMut, POSIX_PROTOCOL_ID},
     Wait,
 };
 
+fn fragmented() -> ZBytes {
+    let mut writer = ZBytes::writer();
+    writer.append(ZBytes::from(vec![1u8, 2, 3]));
+    writer.append(ZBytes::from(vec![4u8, 5]));
+    writer.finish()
+}
+
+#[test]
+fn bytes_reader_borrowed() {
+    let zbytes = fragmented();
+    let mut reader = zbytes.reader();
+    assert_eq!(reader.read_borrowed(2), Some(&[1u8, 2][..]));
+    // Reading across slices is not possible without copy
+    assert_eq!(reader.read_borrowed(2), None);
+    assert_eq!(reader.read_borrowed(1), Some(&[3u8][..]));
+    assert_eq!(reader.read_borrowed(2), Some(&[4u8, 5][..]));
+    assert!(reader.is_empty());
+
+    let mut zbytes = fragmented();
+    assert_eq!(zbytes.contiguous(), &[1, 2, 3, 4, 5]);
+    let slice = zbytes.slices().next().unwrap().as_ptr();
+    // The defragmented buffer is kept
+    assert_eq!(zbytes.contiguous().as_ptr(), slice);
+    assert_eq!(
+        zbytes.reader().read_borrowed(5),
+        Some(&[1u8, 2, 3, 4, 5][..])
+    );
+}
+
+#[test]
+fn bytes_reader_buf() {
+    let zbytes = fragmented();
+    let mut reader = zbytes.reader();
+    assert_eq!(Buf::remaining(&reader), 5);
+    assert_eq!(reader.chunk(), &[1, 2, 3]);
+    reader.advance(2);
+    assert_eq!(reader.chunk(), &[3]);
+    assert_eq!(reader.get_u16(), 0x0304);
+    assert_eq!(reader.chunk(), &[5]);
+    assert_eq!(reader.copy_to_bytes(1).as_ref(), &[5]);
+    assert!(!reader.has_remaining());
+
+    let mut reader = zbytes.reader();
+    reader.seek(SeekFrom::End(-3)).unwrap();
+    let mut buf = Vec::new();
+    reader.read_to_end(&mut buf).unwrap();
+    assert_eq!(buf, [3, 4, 5]);
+}
+
+#[test]
+fn bytes_reader_seek_buf() {
+    let zbytes = fragmented();
+    let mut reader = zbytes.reader();
+    // Seeking back onto the boundary between the two slices
+    reader.seek(SeekFrom::End(0)).unwrap();
+    reader.seek(SeekFrom::Current(-2)).unwrap();
+    assert_eq!(reader.chunk(), &[4, 5]);
+    assert_eq!(reader.get_u16(), 0x0405);
+    assert!(!reader.has_remaining());
+
+    reader.seek(SeekFrom::Current(-2)).unwrap();
+    let mut buf = [0u8; 2];
+    reader.copy_to_slice(&mut buf);
+    assert_eq!(buf, [4, 5]);
+
+    reader.seek(SeekFrom::Current(-2)).unwrap();
+    assert_eq!(reader.read_borrowed(2), Some(&[4u8, 5][..]));
+}
+
+#[cfg(feature = "shared-memory")]
 #[test]
 fn shm_bytes_single_buf() {
     // create an SHM backend...
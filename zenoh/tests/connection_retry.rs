@@ -56,6 +56,7 @@ fn retry_config_overriding() {
             period_init_ms: 3000,
             period_max_ms: 6000,
             period_increase_factor: 1.5,
+            period_jitter: 0.,
             exit_on_failure: false,
         },
         // override one key
@@ -63,6 +64,7 @@ fn retry_config_overriding() {
             period_init_ms: 30000,
             period_max_ms: 6000,
             period_increase_factor: 1.5,
+            period_jitter: 0.,
             exit_on_failure: false,
         },
         // override all keys
@@ -70,6 +72,7 @@ fn retry_config_overriding() {
             period_init_ms: 30000,
             period_max_ms: 60000,
             period_increase_factor: 15.,
+            period_jitter: 0.,
             exit_on_failure: true,
         },
     ];
@@ -115,6 +118,40 @@ fn retry_config_parsing() {
     }
 }
 
+#[test]
+fn retry_config_jitter() {
+    let mut config = Config::default();
+    config
+        .insert_json5(
+            "connect/retry",
+            r#"
+            {
+                period_init_ms: 1000,
+                period_max_ms: 4000,
+                period_increase_factor: 2,
+                period_jitter: 0.1,
+            }
+            "#,
+        )
+        .unwrap();
+
+    let endpoint: EndPoint = "tcp/[::]:0".parse().unwrap();
+    let retry_config = zenoh_config::get_retry_config(&config, Some(&endpoint), false);
+    assert_eq!(retry_config.period_jitter, 0.1);
+
+    let mut period = retry_config.period();
+    let expected = vec![1000, 2000, 4000, 4000];
+
+    for v in expected {
+        let duration = period.next_duration().as_millis();
+        assert!((v * 9 / 10..=v * 11 / 10).contains(&duration));
+    }
+
+    let endpoint: EndPoint = "tcp/[::]:0#retry_period_jitter=0.5".parse().unwrap();
+    let retry_config = zenoh_config::get_retry_config(&config, Some(&endpoint), false);
+    assert_eq!(retry_config.period_jitter, 0.5);
+}
+
 #[test]
 fn retry_config_const_period() {
     let mut config = Config::default();
@@ -0,0 +1,130 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+#![cfg(feature = "unstable")]
+
+use std::time::Duration;
+
+use zenoh::{
+    config::WhatAmI,
+    qos::CongestionControl,
+    session::{ConnectivityState, ConnectivityStateChange},
+    Config, Session,
+};
+use zenoh_core::ztimeout;
+
+const TIMEOUT: Duration = Duration::from_secs(30);
+const SLEEP: Duration = Duration::from_millis(500);
+
+async fn open_router(locator: &str) -> Session {
+    let mut config = Config::default();
+    config.set_mode(Some(WhatAmI::Router)).unwrap();
+    config
+        .listen
+        .endpoints
+        .set(vec![locator.parse().unwrap()])
+        .unwrap();
+    config.scouting.multicast.set_enabled(Some(false)).unwrap();
+    ztimeout!(zenoh::open(config)).unwrap()
+}
+
+fn client_config(locators: &[&str]) -> Config {
+    let mut config = Config::default();
+    config.set_mode(Some(WhatAmI::Client)).unwrap();
+    config
+        .connect
+        .endpoints
+        .set(locators.iter().map(|l| l.parse().unwrap()).collect())
+        .unwrap();
+    config.scouting.multicast.set_enabled(Some(false)).unwrap();
+    config
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn zenoh_connectivity_state_failover() {
+    zenoh_util::init_log_from_env_or("error");
+    let primary = "tcp/127.0.0.1:17620";
+    let secondary = "tcp/127.0.0.1:17621";
+
+    let router1 = open_router(primary).await;
+    let router2 = open_router(secondary).await;
+    let client = ztimeout!(zenoh::open(client_config(&[primary, secondary]))).unwrap();
+    assert_eq!(client.connectivity_state(), ConnectivityState::Connected);
+    let changes = ztimeout!(client.declare_connectivity_state_listener()).unwrap();
+
+    // The client fails over to the secondary router when the primary one is gone
+    ztimeout!(router1.close()).unwrap();
+    assert_eq!(
+        ztimeout!(changes.recv_async()).unwrap(),
+        ConnectivityStateChange {
+            previous: ConnectivityState::Connected,
+            current: ConnectivityState::Disconnected,
+        }
+    );
+    assert_eq!(
+        ztimeout!(changes.recv_async()).unwrap(),
+        ConnectivityStateChange {
+            previous: ConnectivityState::Disconnected,
+            current: ConnectivityState::Degraded,
+        }
+    );
+    assert_eq!(client.connectivity_state(), ConnectivityState::Degraded);
+    let zids: Vec<_> = client.info().routers_zid().await.collect();
+    assert_eq!(zids, vec![router2.zid()]);
+
+    ztimeout!(client.close()).unwrap();
+    ztimeout!(router2.close()).unwrap();
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn zenoh_disconnected_buffer() {
+    zenoh_util::init_log_from_env_or("error");
+    let locator = "tcp/127.0.0.1:17622";
+
+    let router = open_router(locator).await;
+    let mut config = client_config(&[locator]);
+    config
+        .connect
+        .set_disconnected_buffer_size(Some(2))
+        .unwrap();
+    let client = ztimeout!(zenoh::open(config)).unwrap();
+    let changes = ztimeout!(client.declare_connectivity_state_listener()).unwrap();
+
+    ztimeout!(router.close()).unwrap();
+    let change = ztimeout!(changes.recv_async()).unwrap();
+    assert_eq!(change.current, ConnectivityState::Disconnected);
+
+    // Only the latest publications with a blocking congestion control are buffered
+    for value in ["0", "1", "2"] {
+        ztimeout!(client
+            .put("test/buffer", value)
+            .congestion_control(CongestionControl::Block))
+        .unwrap();
+    }
+    ztimeout!(client.put("test/buffer", "dropped")).unwrap();
+
+    let router = open_router(locator).await;
+    let subscriber = ztimeout!(router.declare_subscriber("test/buffer")).unwrap();
+    let change = ztimeout!(changes.recv_async()).unwrap();
+    assert_eq!(change.current, ConnectivityState::Connected);
+
+    for value in ["1", "2"] {
+        let sample = ztimeout!(subscriber.recv_async()).unwrap();
+        assert_eq!(sample.payload().try_to_string().unwrap(), value);
+    }
+    tokio::time::sleep(SLEEP).await;
+    assert!(subscriber.try_recv().unwrap().is_none());
+
+    ztimeout!(client.close()).unwrap();
+    ztimeout!(router.close()).unwrap();
+}
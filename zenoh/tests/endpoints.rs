@@ -0,0 +1,260 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+#![cfg(feature = "unstable")]
+
+use std::time::Duration;
+
+use zenoh::{config::EndPoint, session::EndpointEvent, Session};
+use zenoh_core::ztimeout;
+
+const TIMEOUT: Duration = Duration::from_secs(10);
+const SLEEP: Duration = Duration::from_millis(100);
+
+async fn open_session() -> Session {
+    let mut config = zenoh::Config::default();
+    config.listen.endpoints.set(vec![]).unwrap();
+    config.scouting.multicast.set_enabled(Some(false)).unwrap();
+    ztimeout!(zenoh::open(config)).unwrap()
+}
+
+async fn wait_peers(session: &Session, count: usize) {
+    ztimeout!(async {
+        while session.info().peers_zid().await.count() != count {
+            tokio::time::sleep(SLEEP).await;
+        }
+    });
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn zenoh_endpoints_api() {
+    let listen: EndPoint = "tcp/127.0.0.1:17490".parse().unwrap();
+
+    let session1 = open_session().await;
+    let events = ztimeout!(session1.declare_endpoint_events_listener()).unwrap();
+
+    let locator = ztimeout!(session1.add_listener(listen.clone())).unwrap();
+    assert_eq!(locator.to_string(), listen.to_string());
+    assert_eq!(
+        ztimeout!(events.recv_async()).unwrap(),
+        EndpointEvent::ListenerAdded {
+            endpoint: listen.clone(),
+            locator,
+        }
+    );
+    assert!(ztimeout!(session1.add_listener(listen.clone())).is_err());
+    assert_eq!(
+        session1
+            .config()
+            .lock()
+            .get_json("listen/endpoints")
+            .unwrap(),
+        format!("[\"{listen}\"]")
+    );
+
+    let session2 = open_session().await;
+    ztimeout!(session2.add_connect_endpoint(listen.clone())).unwrap();
+    wait_peers(&session1, 1).await;
+    wait_peers(&session2, 1).await;
+
+    ztimeout!(session2.remove_connect_endpoint(listen.clone())).unwrap();
+    wait_peers(&session1, 0).await;
+    wait_peers(&session2, 0).await;
+    assert!(ztimeout!(session2.remove_connect_endpoint(listen.clone())).is_err());
+
+    ztimeout!(session1.remove_listener(listen.clone())).unwrap();
+    assert_eq!(
+        ztimeout!(events.recv_async()).unwrap(),
+        EndpointEvent::ListenerRemoved {
+            endpoint: listen.clone(),
+        }
+    );
+    assert!(ztimeout!(session1.remove_listener(listen.clone())).is_err());
+
+    ztimeout!(session1.close()).unwrap();
+    ztimeout!(session2.close()).unwrap();
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn zenoh_endpoints_system_port() {
+    let listen: EndPoint = "tcp/127.0.0.1:0".parse().unwrap();
+
+    let session1 = open_session().await;
+    let events = ztimeout!(session1.declare_endpoint_events_listener()).unwrap();
+
+    // The listener is bound to a port chosen by the system
+    let locator = ztimeout!(session1.add_listener(listen.clone())).unwrap();
+    assert_ne!(locator.to_string(), listen.to_string());
+    assert!(matches!(
+        ztimeout!(events.recv_async()).unwrap(),
+        EndpointEvent::ListenerAdded { endpoint, .. } if endpoint == listen
+    ));
+
+    let session2 = open_session().await;
+    ztimeout!(session2.add_connect_endpoint(locator.to_endpoint())).unwrap();
+    wait_peers(&session1, 1).await;
+    ztimeout!(session2.remove_connect_endpoint(locator.to_endpoint())).unwrap();
+    wait_peers(&session1, 0).await;
+
+    // And is closed through the endpoint it was added with
+    ztimeout!(session1.remove_listener(listen.clone())).unwrap();
+    assert_eq!(
+        ztimeout!(events.recv_async()).unwrap(),
+        EndpointEvent::ListenerRemoved {
+            endpoint: listen.clone(),
+        }
+    );
+    assert_eq!(
+        session1
+            .config()
+            .lock()
+            .get_json("listen/endpoints")
+            .unwrap(),
+        "[]"
+    );
+    assert!(std::net::TcpStream::connect(locator.address().as_str()).is_err());
+
+    ztimeout!(session1.close()).unwrap();
+    ztimeout!(session2.close()).unwrap();
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn zenoh_endpoints_config() {
+    let listen: EndPoint = "tcp/127.0.0.1:17491".parse().unwrap();
+
+    let session1 = open_session().await;
+    let events = ztimeout!(session1.declare_endpoint_events_listener()).unwrap();
+
+    // Updating the configuration, e.g. through the admin space, opens and closes listeners
+    session1
+        .config()
+        .insert_json5("listen/endpoints", &format!("[\"{listen}\"]"))
+        .unwrap();
+    assert!(matches!(
+        ztimeout!(events.recv_async()).unwrap(),
+        EndpointEvent::ListenerAdded { endpoint, .. } if endpoint == listen
+    ));
+
+    let session2 = open_session().await;
+    let events2 = ztimeout!(session2.declare_endpoint_events_listener()).unwrap();
+    session2
+        .config()
+        .insert_json5("connect/endpoints", &format!("[\"{listen}\"]"))
+        .unwrap();
+    assert_eq!(
+        ztimeout!(events2.recv_async()).unwrap(),
+        EndpointEvent::ConnectAdded {
+            endpoint: listen.clone(),
+        }
+    );
+    wait_peers(&session2, 1).await;
+
+    session2
+        .config()
+        .insert_json5("connect/endpoints", "[]")
+        .unwrap();
+    assert_eq!(
+        ztimeout!(events2.recv_async()).unwrap(),
+        EndpointEvent::ConnectRemoved {
+            endpoint: listen.clone(),
+        }
+    );
+    wait_peers(&session2, 0).await;
+
+    session1
+        .config()
+        .insert_json5("listen/endpoints", "[]")
+        .unwrap();
+    assert_eq!(
+        ztimeout!(events.recv_async()).unwrap(),
+        EndpointEvent::ListenerRemoved {
+            endpoint: listen.clone(),
+        }
+    );
+
+    ztimeout!(session1.close()).unwrap();
+    ztimeout!(session2.close()).unwrap();
+}
+
+// Adds or removes an address of the loopback interface, returning false if not permitted
+#[cfg(target_os = "linux")]
+fn ip_addr(action: &str, addr: &str) -> bool {
+    std::process::Command::new("ip")
+        .args(["addr", action, &format!("{addr}/32"), "dev", "lo"])
+        .output()
+        .map_or(false, |output| output.status.success())
+}
+
+// An address added to the loopback interface, removed when dropped even if the test panics
+#[cfg(target_os = "linux")]
+struct LoopbackAddr(&'static str);
+
+#[cfg(target_os = "linux")]
+impl Drop for LoopbackAddr {
+    fn drop(&mut self) {
+        ip_addr("del", self.0);
+    }
+}
+
+#[cfg(target_os = "linux")]
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+#[ignore = "changes the addresses of the loopback interface, which requires the CAP_NET_ADMIN capability"]
+async fn zenoh_endpoints_interfaces_change() {
+    const ADDR: &str = "192.0.2.47";
+    assert!(ip_addr("add", ADDR), "Unable to add address {ADDR}");
+    let _addr = LoopbackAddr(ADDR);
+    let listen: EndPoint = format!("tcp/{ADDR}:17492").parse().unwrap();
+
+    let mut config = zenoh::Config::default();
+    config.listen.endpoints.set(vec![listen.clone()]).unwrap();
+    config
+        .insert_json5("listen/interfaces_check_period_ms", "100")
+        .unwrap();
+    config
+        .insert_json5("listen/retry", "{period_init_ms: 100, period_max_ms: 100}")
+        .unwrap();
+    config.scouting.multicast.set_enabled(Some(false)).unwrap();
+    let session = ztimeout!(zenoh::open(config)).unwrap();
+    let events = ztimeout!(session.declare_endpoint_events_listener()).unwrap();
+
+    // The listener is closed when its address disappears, and re-bound once it is back
+    assert!(ip_addr("del", ADDR));
+    assert_eq!(
+        ztimeout!(events.recv_async()).unwrap(),
+        EndpointEvent::ListenerRemoved {
+            endpoint: listen.clone(),
+        }
+    );
+    assert!(ip_addr("add", ADDR));
+    ztimeout!(async {
+        loop {
+            match events.recv_async().await.unwrap() {
+                EndpointEvent::ListenerFailed { endpoint, .. } => assert_eq!(endpoint, listen),
+                event => {
+                    assert!(matches!(
+                        event,
+                        EndpointEvent::ListenerAdded { endpoint, .. } if endpoint == listen
+                    ));
+                    break;
+                }
+            }
+        }
+    });
+
+    let peer = open_session().await;
+    ztimeout!(peer.add_connect_endpoint(listen.clone())).unwrap();
+    wait_peers(&session, 1).await;
+
+    ztimeout!(peer.close()).unwrap();
+    ztimeout!(session.close()).unwrap();
+}
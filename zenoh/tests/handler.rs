@@ -11,9 +11,20 @@
 // Contributors:
 //   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
 //
-use std::{thread, time::Duration};
+use std::{
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    thread,
+    time::Duration,
+};
 
-use zenoh::{handlers::RingChannel, Config, Wait};
+use futures::{stream, SinkExt, StreamExt};
+use zenoh::{
+    handlers::{FifoChannel, RingChannel},
+    Config, Wait,
+};
 
 #[test]
 fn pubsub_with_ringbuffer() {
@@ -64,3 +75,231 @@ fn query_with_ringbuffer() {
     // Only receive the latest query
     assert_eq!(query.payload().unwrap().try_to_string().unwrap(), "query2");
 }
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn stream_with_fifo_channel() {
+    let zenoh = zenoh::open(Config::default()).await.unwrap();
+    let mut sub = zenoh
+        .declare_subscriber("test/stream_fifo")
+        .with(FifoChannel::new(1))
+        .await
+        .unwrap();
+
+    // A full fifo channel blocks the publication until the stream is polled
+    let published = Arc::new(AtomicUsize::new(0));
+    let publisher = thread::spawn({
+        let zenoh = zenoh.clone();
+        let published = published.clone();
+        move || {
+            for i in 0..3 {
+                zenoh
+                    .put("test/stream_fifo", format!("put{i}"))
+                    .wait()
+                    .unwrap();
+                published.fetch_add(1, Ordering::SeqCst);
+            }
+        }
+    });
+    tokio::time::sleep(Duration::from_millis(500)).await;
+    assert_eq!(published.load(Ordering::SeqCst), 1);
+    for i in 0..3 {
+        let sample = sub.next().await.unwrap();
+        assert_eq!(sample.payload().try_to_string().unwrap(), format!("put{i}"));
+    }
+    publisher.join().unwrap();
+    assert_eq!(published.load(Ordering::SeqCst), 3);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn stream_with_ringbuffer() {
+    let zenoh = zenoh::open(Config::default()).await.unwrap();
+    let mut sub = zenoh
+        .declare_subscriber("test/stream_ringbuffer")
+        .with(RingChannel::new(3))
+        .await
+        .unwrap();
+    // A full ring channel never blocks the publication
+    for i in 0..10 {
+        zenoh
+            .put("test/stream_ringbuffer", format!("put{i}"))
+            .await
+            .unwrap();
+    }
+    for i in 7..10 {
+        let sample = sub.next().await.unwrap();
+        assert_eq!(sample.payload().try_to_string().unwrap(), format!("put{i}"));
+    }
+    assert!(tokio::time::timeout(Duration::from_millis(100), sub.next())
+        .await
+        .is_err());
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn stream_replies() {
+    let zenoh = zenoh::open(Config::default()).await.unwrap();
+    let _queryable = zenoh
+        .declare_queryable("test/stream_replies/*")
+        .callback(|query| {
+            for i in 0..3 {
+                query
+                    .reply(format!("test/stream_replies/{i}"), i.to_string())
+                    .wait()
+                    .unwrap();
+            }
+        })
+        .await
+        .unwrap();
+
+    // The stream finishes with the query
+    let replies = zenoh.get("test/stream_replies/*").await.unwrap();
+    assert_eq!(replies.collect::<Vec<_>>().await.len(), 3);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn sink_publisher() {
+    let zenoh = zenoh::open(Config::default()).await.unwrap();
+    let sub = zenoh.declare_subscriber("test/sink").await.unwrap();
+    let mut publisher = zenoh.declare_publisher("test/sink").await.unwrap();
+    publisher.send("put0").await.unwrap();
+    stream::iter([vec![1u8, 2], vec![3]])
+        .map(Ok)
+        .forward(&mut publisher)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        sub.recv().unwrap().payload().try_to_string().unwrap(),
+        "put0"
+    );
+    assert_eq!(sub.recv().unwrap().payload().to_bytes(), [1, 2].as_slice());
+    assert_eq!(sub.recv().unwrap().payload().to_bytes(), [3].as_slice());
+}
+
+#[cfg(feature = "unstable")]
+#[test]
+fn pubsub_with_priority_channel() {
+    use zenoh::{handlers::PriorityChannel, qos::Priority};
+
+    let zenoh = zenoh::open(Config::default()).wait().unwrap();
+    let sub = zenoh
+        .declare_subscriber("test/priority")
+        .with(PriorityChannel::new(3))
+        .wait()
+        .unwrap();
+    for (payload, priority) in [
+        ("data1", Priority::Data),
+        ("realtime", Priority::RealTime),
+        ("background", Priority::Background),
+        ("data2", Priority::Data),
+        ("datalow", Priority::DataLow),
+    ] {
+        zenoh
+            .put("test/priority", payload)
+            .priority(priority)
+            .wait()
+            .unwrap();
+    }
+    // The lowest priority samples are dropped when the channel is full
+    for payload in ["realtime", "data1", "data2"] {
+        assert_eq!(
+            sub.recv().unwrap().payload().try_to_string().unwrap(),
+            payload
+        );
+    }
+    assert!(sub.try_recv().unwrap().is_none());
+}
+
+#[cfg(feature = "unstable")]
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn pubsub_with_latest_channel() {
+    use zenoh::handlers::LatestChannel;
+
+    let zenoh = zenoh::open(Config::default()).await.unwrap();
+    let mut sub = zenoh
+        .declare_subscriber("test/latest/*")
+        .with(LatestChannel::new(2))
+        .await
+        .unwrap();
+    for (key_expr, payload) in [
+        ("test/latest/a", "a0"),
+        ("test/latest/b", "b0"),
+        ("test/latest/a", "a1"),
+    ] {
+        zenoh.put(key_expr, payload).await.unwrap();
+    }
+    // Samples on the same key expression are coalesced, keeping their place in the channel
+    for payload in ["a1", "b0"] {
+        let sample = sub.next().await.unwrap();
+        assert_eq!(sample.payload().try_to_string().unwrap(), payload);
+    }
+    // The oldest key expression is dropped when the channel is full
+    for (key_expr, payload) in [
+        ("test/latest/a", "a2"),
+        ("test/latest/b", "b1"),
+        ("test/latest/c", "c0"),
+    ] {
+        zenoh.put(key_expr, payload).await.unwrap();
+    }
+    for payload in ["b1", "c0"] {
+        let sample = sub.recv_async().await.unwrap();
+        assert_eq!(sample.payload().try_to_string().unwrap(), payload);
+    }
+    assert!(sub.try_recv().unwrap().is_none());
+}
+
+#[cfg(feature = "unstable")]
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn pubsub_with_dedup_channel() {
+    use zenoh::{
+        config::{EndPoint, WhatAmI},
+        handlers::DedupChannel,
+        sample::SourceInfo,
+    };
+
+    // Source infos are only sent to remote subscribers
+    let endpoint: EndPoint = "tcp/127.0.0.1:17650".parse().unwrap();
+    let mut config = Config::default();
+    config.listen.endpoints.set(vec![endpoint.clone()]).unwrap();
+    config.scouting.multicast.set_enabled(Some(false)).unwrap();
+    let _ = config.set_mode(Some(WhatAmI::Peer));
+    let session1 = zenoh::open(config).await.unwrap();
+    let mut config = Config::default();
+    config.connect.endpoints.set(vec![endpoint]).unwrap();
+    config.scouting.multicast.set_enabled(Some(false)).unwrap();
+    let _ = config.set_mode(Some(WhatAmI::Peer));
+    let session2 = zenoh::open(config).await.unwrap();
+
+    let sub = session1
+        .declare_subscriber("test/dedup")
+        .with(DedupChannel::new(FifoChannel::new(16), 2))
+        .await
+        .unwrap();
+    let publisher = session2.declare_publisher("test/dedup").await.unwrap();
+    tokio::time::sleep(Duration::from_secs(1)).await;
+    for (payload, sn) in [
+        ("put0", 0),
+        ("put1", 1),
+        ("put0", 0),
+        ("put2", 2),
+        ("put0", 0),
+    ] {
+        publisher
+            .put(payload)
+            .source_info(SourceInfo::new(Some(publisher.id()), Some(sn)))
+            .await
+            .unwrap();
+    }
+    // Samples without source info are never deduplicated
+    publisher.put("put3").await.unwrap();
+    publisher.put("put3").await.unwrap();
+
+    // The first sn is forgotten once 2 newer ones have been received
+    for payload in ["put0", "put1", "put2", "put0", "put3", "put3"] {
+        let sample = tokio::time::timeout(Duration::from_secs(10), sub.recv_async())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(sample.payload().try_to_string().unwrap(), payload);
+    }
+    assert!(sub.try_recv().unwrap().is_none());
+}
@@ -0,0 +1,97 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+#![cfg(feature = "unstable")]
+
+use std::time::Duration;
+
+use zenoh::{
+    config::WhatAmI,
+    session::{LinkEvent, TransportEvent},
+};
+use zenoh_core::ztimeout;
+
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn zenoh_transports_and_links() {
+    zenoh_util::init_log_from_env_or("error");
+    let locator = "tcp/127.0.0.1:17610";
+
+    let mut config = zenoh::Config::default();
+    config.set_mode(Some(WhatAmI::Router)).unwrap();
+    config
+        .listen
+        .endpoints
+        .set(vec![locator.parse().unwrap()])
+        .unwrap();
+    config.scouting.multicast.set_enabled(Some(false)).unwrap();
+    let router = ztimeout!(zenoh::open(config)).unwrap();
+    let router_zid = router.info().zid().await;
+
+    let mut config = zenoh::Config::default();
+    config.set_mode(Some(WhatAmI::Client)).unwrap();
+    config
+        .connect
+        .endpoints
+        .set(vec![locator.parse().unwrap()])
+        .unwrap();
+    config.scouting.multicast.set_enabled(Some(false)).unwrap();
+    let client = ztimeout!(zenoh::open(config)).unwrap();
+    let client_zid = client.info().zid().await;
+
+    let transports: Vec<_> = client.info().transports().await.collect();
+    assert_eq!(transports.len(), 1);
+    assert_eq!(transports[0].zid(), router_zid);
+    assert_eq!(transports[0].whatami(), WhatAmI::Router);
+    assert!(!transports[0].is_multicast());
+    let links: Vec<_> = client.info().links().await.collect();
+    assert_eq!(links.len(), 1);
+    assert_eq!(links[0].zid(), router_zid);
+    assert_eq!(links[0].dst().to_string(), locator);
+    assert!(links[0].is_streamed());
+
+    // The router is notified of the transport and link of a new client
+    let transport_events = ztimeout!(router.info().declare_transport_events_listener()).unwrap();
+    let link_events = ztimeout!(router.info().declare_link_events_listener()).unwrap();
+    ztimeout!(client.close()).unwrap();
+    let mut config = zenoh::Config::default();
+    config.set_mode(Some(WhatAmI::Client)).unwrap();
+    config
+        .connect
+        .endpoints
+        .set(vec![locator.parse().unwrap()])
+        .unwrap();
+    config.scouting.multicast.set_enabled(Some(false)).unwrap();
+    let client = ztimeout!(zenoh::open(config)).unwrap();
+    let new_client_zid = client.info().zid().await;
+
+    let event = ztimeout!(transport_events.recv_async()).unwrap();
+    assert!(matches!(&event, TransportEvent::Closed(t) if t.zid() == client_zid));
+    let event = ztimeout!(link_events.recv_async()).unwrap();
+    assert!(matches!(&event, LinkEvent::Removed(l) if l.zid() == client_zid));
+    let event = ztimeout!(transport_events.recv_async()).unwrap();
+    assert!(matches!(&event, TransportEvent::Opened(t) if t.zid() == new_client_zid));
+    assert_eq!(event.transport().whatami(), WhatAmI::Client);
+    let event = ztimeout!(link_events.recv_async()).unwrap();
+    assert!(matches!(&event, LinkEvent::Added(l) if l.zid() == new_client_zid));
+    assert_eq!(event.link().src().to_string(), locator);
+
+    // And the client when it loses its router
+    let transport_events = ztimeout!(client.info().declare_transport_events_listener()).unwrap();
+    ztimeout!(router.close()).unwrap();
+    let event = ztimeout!(transport_events.recv_async()).unwrap();
+    assert!(matches!(&event, TransportEvent::Closed(t) if t.zid() == router_zid));
+
+    ztimeout!(client.close()).unwrap();
+}
@@ -0,0 +1,222 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+use std::time::Duration;
+
+use zenoh::{config::WhatAmI, Session};
+use zenoh_core::ztimeout;
+
+const TIMEOUT: Duration = Duration::from_secs(10);
+const SLEEP: Duration = Duration::from_millis(100);
+
+async fn open_router(listen: &[&str], connect: &[&str], rtt_weights: bool) -> Session {
+    let mut config = zenoh::Config::default();
+    config.set_mode(Some(WhatAmI::Router)).unwrap();
+    config
+        .listen
+        .endpoints
+        .set(listen.iter().map(|e| e.parse().unwrap()).collect())
+        .unwrap();
+    config
+        .connect
+        .endpoints
+        .set(connect.iter().map(|e| e.parse().unwrap()).collect())
+        .unwrap();
+    config.scouting.multicast.set_enabled(Some(false)).unwrap();
+    config.adminspace.set_enabled(true).unwrap();
+    config
+        .routing
+        .linkstate
+        .set_rtt_weights(Some(rtt_weights))
+        .unwrap();
+    ztimeout!(zenoh::open(config)).unwrap()
+}
+
+// Waits until the routers linkstate graph seen by `session` matches `f`
+async fn wait_linkstate<F: Fn(&str) -> bool>(session: &Session, f: F) {
+    let zid = session.zid();
+    ztimeout!(async {
+        loop {
+            let replies = session
+                .get(format!("@/{zid}/router/linkstate/routers"))
+                .await
+                .unwrap();
+            while let Ok(reply) = replies.recv_async().await {
+                let dot = reply
+                    .result()
+                    .unwrap()
+                    .payload()
+                    .try_to_string()
+                    .unwrap()
+                    .into_owned();
+                if f(&dot) {
+                    return;
+                }
+            }
+            tokio::time::sleep(SLEEP).await;
+        }
+    });
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn zenoh_link_weights_cost() {
+    zenoh_util::init_log_from_env_or("error");
+
+    let router1 = open_router(&["tcp/127.0.0.1:17510"], &[], false).await;
+    let router2 = open_router(&[], &["tcp/127.0.0.1:17510#cost=10000"], false).await;
+
+    // The cost configured on the connecting side is gossiped to the other router
+    wait_linkstate(&router1, |dot| dot.contains("label = \"10000\"")).await;
+    wait_linkstate(&router2, |dot| dot.contains("label = \"10000\"")).await;
+
+    ztimeout!(router2.close()).unwrap();
+    ztimeout!(router1.close()).unwrap();
+}
+
+// Returns the faces `router` forwards the data published on `key_expr` to
+async fn next_hops(router: &Session, key_expr: &str) -> Vec<serde_json::Value> {
+    let replies = ztimeout!(router.get(format!(
+        "@/{}/router/route?keyexpr={key_expr}",
+        router.zid()
+    )))
+    .unwrap();
+    let reply = ztimeout!(replies.recv_async()).unwrap();
+    let payload = reply.result().unwrap().payload().to_bytes().into_owned();
+    let explanation: serde_json::Value = serde_json::from_slice(&payload).unwrap();
+    explanation["data_route"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|hop| hop["face"]["zid"].clone())
+        .collect()
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn zenoh_link_weights_route() {
+    zenoh_util::init_log_from_env_or("error");
+
+    // Routers in a triangle 1 - 2 - 3 - 1, the direct link between 1 and 3 being costly
+    let router1 = open_router(&["tcp/127.0.0.1:17512"], &[], false).await;
+    let router2 = open_router(&["tcp/127.0.0.1:17513"], &["tcp/127.0.0.1:17512"], false).await;
+    let router3 = open_router(
+        &[],
+        &["tcp/127.0.0.1:17513", "tcp/127.0.0.1:17512#cost=10000"],
+        false,
+    )
+    .await;
+    let sub = ztimeout!(router3.declare_subscriber("test/link_weights/**")).unwrap();
+
+    // The data published on router 1 goes through router 2 rather than the costly direct link
+    let router2_zid = serde_json::json!(router2.zid());
+    ztimeout!(async {
+        while next_hops(&router1, "test/link_weights/data").await != [router2_zid.clone()] {
+            tokio::time::sleep(SLEEP).await;
+        }
+    });
+    ztimeout!(router1.put("test/link_weights/data", "data")).unwrap();
+    let sample = ztimeout!(sub.recv_async()).unwrap();
+    assert_eq!(sample.key_expr().as_str(), "test/link_weights/data");
+
+    ztimeout!(router3.close()).unwrap();
+    ztimeout!(router2.close()).unwrap();
+    ztimeout!(router1.close()).unwrap();
+}
+
+#[cfg(target_os = "linux")]
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn zenoh_link_weights_rtt() {
+    zenoh_util::init_log_from_env_or("error");
+
+    let router1 = open_router(&["tcp/127.0.0.1:17511"], &[], false).await;
+    let router2 = open_router(&[], &["tcp/127.0.0.1:17511"], true).await;
+
+    // A local link has a round-trip time well below the default weight of 1 ms
+    wait_linkstate(&router1, |dot| {
+        dot.contains("label") && !dot.contains("label = \"100\"")
+    })
+    .await;
+
+    ztimeout!(router2.close()).unwrap();
+    ztimeout!(router1.close()).unwrap();
+}
+
+// Returns the highest link weight of the routers linkstate graph seen by `session`
+async fn max_weight(session: &Session) -> Option<u16> {
+    let replies =
+        ztimeout!(session.get(format!("@/{}/router/linkstate/routers", session.zid()))).unwrap();
+    let reply = ztimeout!(replies.recv_async()).unwrap();
+    let dot = reply
+        .result()
+        .unwrap()
+        .payload()
+        .try_to_string()
+        .unwrap()
+        .into_owned();
+    dot.split("label = \"")
+        .skip(1)
+        .filter_map(|s| s.split('"').next()?.parse().ok())
+        .max()
+}
+
+#[cfg(target_os = "linux")]
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn zenoh_link_weights_rtt_update() {
+    zenoh_util::init_log_from_env_or("error");
+
+    let router1 = open_router(&["tcp/127.0.0.1:17514"], &[], false).await;
+    // The writes of router 2 are shaped to 1 Mbps, a burst of publications delaying them
+    let mut config = zenoh::Config::default();
+    config.set_mode(Some(WhatAmI::Router)).unwrap();
+    config
+        .connect
+        .endpoints
+        .set(vec!["tcp/127.0.0.1:17514?sim_bw=1Mbps".parse().unwrap()])
+        .unwrap();
+    config.scouting.multicast.set_enabled(Some(false)).unwrap();
+    config.adminspace.set_enabled(true).unwrap();
+    config
+        .routing
+        .linkstate
+        .set_rtt_weights(Some(true))
+        .unwrap();
+    config.routing.linkstate.set_rtt_period(Some(100)).unwrap();
+    let router2 = ztimeout!(zenoh::open(config)).unwrap();
+    let _sub = ztimeout!(router1.declare_subscriber("test/link_weights/**")).unwrap();
+
+    // The weight derived from the round-trip time of the idle link is low
+    ztimeout!(async {
+        while !max_weight(&router2).await.is_some_and(|w| w < 1000) {
+            tokio::time::sleep(SLEEP).await;
+        }
+    });
+
+    // The time the publications wait for the bandwidth increases the round-trip time of the link
+    for _ in 0..5 {
+        ztimeout!(router2.put("test/link_weights/data", vec![0u8; 20_000])).unwrap();
+    }
+    ztimeout!(async {
+        while !max_weight(&router2).await.is_some_and(|w| w >= 1000) {
+            tokio::time::sleep(SLEEP).await;
+        }
+    });
+
+    // And the weight gets low again once the link is idle
+    ztimeout!(async {
+        while !max_weight(&router2).await.is_some_and(|w| w < 1000) {
+            tokio::time::sleep(SLEEP).await;
+        }
+    });
+
+    ztimeout!(router2.close()).unwrap();
+    ztimeout!(router1.close()).unwrap();
+}
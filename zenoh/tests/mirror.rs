@@ -0,0 +1,126 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+#![cfg(feature = "internal_config")]
+
+use std::time::Duration;
+
+use zenoh::{config::WhatAmI, Session};
+use zenoh_config::{DownsamplingItemConf, DownsamplingRuleConf, InterceptorFlow, MirrorItemConf};
+use zenoh_core::ztimeout;
+
+const TIMEOUT: Duration = Duration::from_secs(30);
+const SLEEP: Duration = Duration::from_millis(500);
+const PUT_COUNT: usize = 3;
+
+async fn open_client(locator: &str) -> Session {
+    let mut config = zenoh::Config::default();
+    config.set_mode(Some(WhatAmI::Client)).unwrap();
+    config
+        .connect
+        .endpoints
+        .set(vec![locator.parse().unwrap()])
+        .unwrap();
+    config.scouting.multicast.set_enabled(Some(false)).unwrap();
+    ztimeout!(zenoh::open(config)).unwrap()
+}
+
+// Returns the reason tag of a mirrored record
+fn record_reason(record: &[u8]) -> String {
+    let zid_len = record[9] as usize;
+    let reason_len = record[10 + zid_len] as usize;
+    let reason = &record[11 + zid_len..11 + zid_len + reason_len];
+    String::from_utf8(reason.to_vec()).unwrap()
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn zenoh_mirror() {
+    zenoh_util::init_log_from_env_or("error");
+    let locator = "tcp/127.0.0.1:17580";
+    let capture = std::env::temp_dir().join(format!("zenoh-mirror-{}.zcap", std::process::id()));
+    let _ = std::fs::remove_file(&capture);
+
+    let mut config = zenoh::Config::default();
+    config.set_mode(Some(WhatAmI::Router)).unwrap();
+    config
+        .listen
+        .endpoints
+        .set(vec![locator.parse().unwrap()])
+        .unwrap();
+    config.scouting.multicast.set_enabled(Some(false)).unwrap();
+    // Let a single message go through every 100s
+    config
+        .set_downsampling(vec![DownsamplingItemConf {
+            flow: InterceptorFlow::Egress,
+            interfaces: None,
+            rules: vec![DownsamplingRuleConf {
+                key_expr: "test/mirror/**".parse().unwrap(),
+                freq: 0.01,
+            }],
+        }])
+        .unwrap();
+    config
+        .set_mirror(vec![MirrorItemConf {
+            interfaces: None,
+            flows: None,
+            key_exprs: vec!["test/mirror/**".parse().unwrap()],
+            dropped: None,
+            key_space: Some("debug/mirror".parse().unwrap()),
+            file: Some(capture.to_str().unwrap().to_string()),
+        }])
+        .unwrap();
+    let router = ztimeout!(zenoh::open(config)).unwrap();
+
+    let publisher = open_client(locator).await;
+    let subscriber = open_client(locator).await;
+    let debugger = open_client(locator).await;
+    let sub = ztimeout!(subscriber.declare_subscriber("test/mirror/**")).unwrap();
+    let records = ztimeout!(debugger.declare_subscriber("debug/mirror/**")).unwrap();
+    tokio::time::sleep(SLEEP).await;
+
+    for _ in 0..PUT_COUNT {
+        ztimeout!(publisher.put("test/mirror/data", "data")).unwrap();
+    }
+    tokio::time::sleep(SLEEP).await;
+    assert!(ztimeout!(sub.recv_async()).is_ok());
+    assert!(sub.try_recv().unwrap().is_none());
+
+    let mut ingress = 0;
+    let mut dropped = 0;
+    while let Some(record) = records.try_recv().unwrap() {
+        let key_expr = record.key_expr().as_str();
+        let payload = record.payload().to_bytes();
+        let reason = record_reason(&payload);
+        assert!(key_expr.ends_with(&format!("/{reason}")));
+        if key_expr == format!("debug/mirror/ingress/{}/forwarded", publisher.zid()) {
+            ingress += 1;
+        }
+        if key_expr == format!("debug/mirror/egress/{}/downsampling", subscriber.zid()) {
+            dropped += 1;
+        }
+    }
+    // All the publications are mirrored when received, the ones dropped
+    // by the downsampler are mirrored with the "downsampling" reason
+    assert!(ingress >= PUT_COUNT);
+    assert_eq!(dropped, PUT_COUNT - 1);
+
+    ztimeout!(publisher.close()).unwrap();
+    ztimeout!(subscriber.close()).unwrap();
+    ztimeout!(debugger.close()).unwrap();
+    ztimeout!(router.close()).unwrap();
+
+    let content = std::fs::read(&capture).unwrap();
+    assert!(content.starts_with(b"ZCAP"));
+    assert!(content.len() > 5);
+    let _ = std::fs::remove_file(&capture);
+}
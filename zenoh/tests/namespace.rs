@@ -0,0 +1,202 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+#![cfg(feature = "internal_config")]
+
+use std::time::Duration;
+
+use zenoh::{config::WhatAmI, Config};
+use zenoh_config::NamespaceBindingConf;
+use zenoh_core::ztimeout;
+
+const TIMEOUT: Duration = Duration::from_secs(30);
+const SLEEP: Duration = Duration::from_millis(500);
+
+fn router_config(port: u16) -> Config {
+    let mut config = zenoh::Config::default();
+    config.set_mode(Some(WhatAmI::Router)).unwrap();
+    config
+        .listen
+        .endpoints
+        .set(vec![format!("tcp/127.0.0.1:{port}").parse().unwrap()])
+        .unwrap();
+    config.scouting.multicast.set_enabled(Some(false)).unwrap();
+    config
+}
+
+fn client_config(port: u16, namespace: Option<&str>) -> Config {
+    let mut config = zenoh::Config::default();
+    config.set_mode(Some(WhatAmI::Client)).unwrap();
+    config
+        .connect
+        .endpoints
+        .set(vec![format!("tcp/127.0.0.1:{port}").parse().unwrap()])
+        .unwrap();
+    config.scouting.multicast.set_enabled(Some(false)).unwrap();
+    config
+        .set_namespace(namespace.map(|ns| ns.parse().unwrap()))
+        .unwrap();
+    config
+}
+
+fn set_credentials(config: &mut Config, user: &str, password: &str) {
+    config
+        .insert_json5(
+            "transport/auth/usrpwd",
+            &format!(r#"{{ user: "{user}", password: "{password}" }}"#),
+        )
+        .unwrap();
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn zenoh_session_namespace() {
+    zenoh_util::init_log_from_env_or("error");
+    let port = 17590;
+    let router = ztimeout!(zenoh::open(router_config(port))).unwrap();
+    let tenant = ztimeout!(zenoh::open(client_config(port, Some("tenant-a")))).unwrap();
+    let other = ztimeout!(zenoh::open(client_config(port, None))).unwrap();
+
+    let other_sub = ztimeout!(other.declare_subscriber("**")).unwrap();
+    let tenant_sub = ztimeout!(tenant.declare_subscriber("data/**")).unwrap();
+    let _queryable = ztimeout!(tenant.declare_queryable("query").callback(|query| {
+        tokio::spawn(async move {
+            query.reply("query", "reply").await.unwrap();
+        });
+    }))
+    .unwrap();
+    let _wild_queryable = ztimeout!(tenant.declare_queryable("wild/**").callback(|query| {
+        tokio::spawn(async move {
+            let key_expr = query.key_expr().to_string();
+            query.reply("wild/temp", key_expr).await.unwrap();
+        });
+    }))
+    .unwrap();
+    let _token = ztimeout!(tenant.liveliness().declare_token("token")).unwrap();
+    tokio::time::sleep(SLEEP).await;
+
+    // The key expressions sent by the tenant are prefixed with its namespace
+    ztimeout!(tenant.put("data/x", "x")).unwrap();
+    let sample = ztimeout!(other_sub.recv_async()).unwrap();
+    assert_eq!(sample.key_expr().as_str(), "tenant-a/data/x");
+    let sample = ztimeout!(tenant_sub.recv_async()).unwrap();
+    assert_eq!(sample.key_expr().as_str(), "data/x");
+
+    // And the namespace is stripped from the ones it receives
+    ztimeout!(other.put("other/data/y", "y")).unwrap();
+    ztimeout!(other.put("tenant-a/data/y", "y")).unwrap();
+    let sample = ztimeout!(tenant_sub.recv_async()).unwrap();
+    assert_eq!(sample.key_expr().as_str(), "data/y");
+    tokio::time::sleep(SLEEP).await;
+    assert!(tenant_sub.try_recv().unwrap().is_none());
+
+    let replies = ztimeout!(other.get("tenant-a/query")).unwrap();
+    let reply = ztimeout!(replies.recv_async()).unwrap();
+    assert_eq!(
+        reply.result().unwrap().key_expr().as_str(),
+        "tenant-a/query"
+    );
+
+    // The wildcard key expressions are replaced by their intersection with the namespace
+    for (key_expr, stripped) in [("**/temp", "**/temp"), ("*/wild/temp", "wild/temp")] {
+        let replies = ztimeout!(other.get(key_expr)).unwrap();
+        let reply = ztimeout!(replies.recv_async()).unwrap();
+        let sample = reply.result().unwrap();
+        assert_eq!(sample.key_expr().as_str(), "tenant-a/wild/temp");
+        assert_eq!(sample.payload().try_to_string().unwrap(), stripped);
+    }
+
+    let replies = ztimeout!(other.liveliness().get("tenant-a/**")).unwrap();
+    let reply = ztimeout!(replies.recv_async()).unwrap();
+    assert_eq!(
+        reply.result().unwrap().key_expr().as_str(),
+        "tenant-a/token"
+    );
+
+    ztimeout!(tenant.close()).unwrap();
+    ztimeout!(other.close()).unwrap();
+    ztimeout!(router.close()).unwrap();
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn zenoh_namespace_binding() {
+    zenoh_util::init_log_from_env_or("error");
+    let port = 17591;
+    let credentials = std::env::temp_dir().join(format!(
+        "zenoh-namespace-credentials-{}.txt",
+        std::process::id()
+    ));
+    std::fs::write(&credentials, "alice:alicepasswd\nbob:bobpasswd\n").unwrap();
+
+    let mut config = router_config(port);
+    set_credentials(&mut config, "router", "routerpasswd");
+    config
+        .transport
+        .auth
+        .usrpwd
+        .set_dictionary_file(Some(credentials.to_str().unwrap().to_string()))
+        .unwrap();
+    config
+        .set_namespace_bindings(vec![NamespaceBindingConf {
+            namespace: "tenant-a".parse().unwrap(),
+            usernames: Some(vec![zenoh_config::Username("alice".to_string())]),
+            cert_common_names: None,
+        }])
+        .unwrap();
+    let router = ztimeout!(zenoh::open(config)).unwrap();
+
+    // Alice is confined to "tenant-a" without configuring a namespace herself
+    let mut alice = vec![];
+    for _ in 0..2 {
+        let mut config = client_config(port, None);
+        set_credentials(&mut config, "alice", "alicepasswd");
+        alice.push(ztimeout!(zenoh::open(config)).unwrap());
+    }
+    let mut config = client_config(port, None);
+    set_credentials(&mut config, "bob", "bobpasswd");
+    let bob = ztimeout!(zenoh::open(config)).unwrap();
+
+    let bob_sub = ztimeout!(bob.declare_subscriber("**")).unwrap();
+    let alice_all = ztimeout!(alice[1].declare_subscriber("**")).unwrap();
+    let alice_sub = ztimeout!(alice[1].declare_subscriber("tenant-a/**")).unwrap();
+    tokio::time::sleep(SLEEP).await;
+
+    // The publications of alice outside of her namespace are dropped
+    ztimeout!(alice[0].put("other/x", "x")).unwrap();
+    ztimeout!(alice[0].put("tenant-a/x", "x")).unwrap();
+    let sample = ztimeout!(bob_sub.recv_async()).unwrap();
+    assert_eq!(sample.key_expr().as_str(), "tenant-a/x");
+    let sample = ztimeout!(alice_sub.recv_async()).unwrap();
+    assert_eq!(sample.key_expr().as_str(), "tenant-a/x");
+    tokio::time::sleep(SLEEP).await;
+    assert!(bob_sub.try_recv().unwrap().is_none());
+
+    // As well as the publications she would receive outside of her namespace,
+    // and the subscriptions she declares outside of her namespace
+    ztimeout!(bob.put("other/y", "y")).unwrap();
+    ztimeout!(bob.put("tenant-a/y", "y")).unwrap();
+    let sample = ztimeout!(alice_sub.recv_async()).unwrap();
+    assert_eq!(sample.key_expr().as_str(), "tenant-a/y");
+    tokio::time::sleep(SLEEP).await;
+    assert!(alice_sub.try_recv().unwrap().is_none());
+    // Only the samples received for her other subscriber are delivered locally
+    while let Some(sample) = alice_all.try_recv().unwrap() {
+        assert!(sample.key_expr().as_str().starts_with("tenant-a/"));
+    }
+
+    for session in alice {
+        ztimeout!(session.close()).unwrap();
+    }
+    ztimeout!(bob.close()).unwrap();
+    ztimeout!(router.close()).unwrap();
+    let _ = std::fs::remove_file(&credentials);
+}
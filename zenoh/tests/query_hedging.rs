@@ -0,0 +1,221 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+use std::{
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use zenoh::{config::WhatAmI, query::Query, Session};
+use zenoh_core::ztimeout;
+
+const TIMEOUT: Duration = Duration::from_secs(30);
+const SLEEP: Duration = Duration::from_millis(100);
+const QUERY_COUNT: usize = 10;
+const KEY_EXPR: &str = "test/query_hedging";
+
+async fn open_router(port: u16, hedge_delay: u64) -> Session {
+    let mut config = zenoh::Config::default();
+    config.set_mode(Some(WhatAmI::Router)).unwrap();
+    config
+        .listen
+        .endpoints
+        .set(vec![format!("tcp/127.0.0.1:{port}").parse().unwrap()])
+        .unwrap();
+    config.scouting.multicast.set_enabled(Some(false)).unwrap();
+    config
+        .routing
+        .queries
+        .set_load_balancing(Some("round_robin".to_string()))
+        .unwrap();
+    config
+        .routing
+        .queries
+        .set_hedge_delay(Some(hedge_delay))
+        .unwrap();
+    ztimeout!(zenoh::open(config)).unwrap()
+}
+
+async fn open_client(port: u16) -> Session {
+    let mut config = zenoh::Config::default();
+    config.set_mode(Some(WhatAmI::Client)).unwrap();
+    config
+        .connect
+        .endpoints
+        .set(vec![format!("tcp/127.0.0.1:{port}").parse().unwrap()])
+        .unwrap();
+    config.scouting.multicast.set_enabled(Some(false)).unwrap();
+    ztimeout!(zenoh::open(config)).unwrap()
+}
+
+// Opens two sessions with a complete queryable: "replier" that replies to the queries
+// and "holder" that keeps them pending without replying
+async fn open_queryables(port: u16) -> (Session, Session, Arc<Mutex<Vec<Query>>>) {
+    let replier = open_client(port).await;
+    ztimeout!(replier
+        .declare_queryable(KEY_EXPR)
+        .complete(true)
+        .callback(|query| {
+            tokio::spawn(async move {
+                query.reply(KEY_EXPR, "replier").await.unwrap();
+            });
+        })
+        .background())
+    .unwrap();
+
+    let held = Arc::new(Mutex::new(vec![]));
+    let holder = open_client(port).await;
+    ztimeout!(holder
+        .declare_queryable(KEY_EXPR)
+        .complete(true)
+        .callback({
+            let held = held.clone();
+            move |query| held.lock().unwrap().push(query)
+        })
+        .background())
+    .unwrap();
+    tokio::time::sleep(SLEEP).await;
+    (replier, holder, held)
+}
+
+// Sends a query and returns the replies it got
+async fn query(session: &Session, timeout: Duration) -> Vec<String> {
+    let replies = ztimeout!(session.get(KEY_EXPR).timeout(timeout)).unwrap();
+    let mut payloads = vec![];
+    while let Ok(reply) = replies.recv_async().await {
+        if let Ok(sample) = reply.result() {
+            payloads.push(sample.payload().try_to_string().unwrap().into_owned());
+        }
+    }
+    payloads
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn zenoh_query_hedging() {
+    zenoh_util::init_log_from_env_or("error");
+    let port = 17560;
+    let router = open_router(port, 200).await;
+    let (replier, holder, held) = open_queryables(port).await;
+    let querier = open_client(port).await;
+
+    // The queries sent to the holder are also sent to the replier after the hedge delay,
+    // the querier getting a single reply long before the query timeout
+    for _ in 0..QUERY_COUNT {
+        let start = Instant::now();
+        let replies = query(&querier, Duration::from_secs(5)).await;
+        assert_eq!(replies, vec!["replier".to_string()]);
+        assert!(start.elapsed() < Duration::from_secs(2));
+    }
+    assert!(!held.lock().unwrap().is_empty());
+
+    // The late replies of the holder are not forwarded to the querier
+    let mut tasks = vec![];
+    for _ in 0..2 {
+        let querier = querier.clone();
+        tasks.push(tokio::spawn(async move {
+            query(&querier, Duration::from_secs(2)).await
+        }));
+    }
+    tokio::time::sleep(Duration::from_secs(1)).await;
+    for query in held.lock().unwrap().drain(..) {
+        tokio::spawn(async move {
+            let _ = query.reply(KEY_EXPR, "holder").await;
+        });
+    }
+    for task in tasks {
+        assert_eq!(ztimeout!(task).unwrap(), vec!["replier".to_string()]);
+    }
+
+    ztimeout!(querier.close()).unwrap();
+    ztimeout!(holder.close()).unwrap();
+    ztimeout!(replier.close()).unwrap();
+    ztimeout!(router.close()).unwrap();
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn zenoh_query_retry_on_close() {
+    zenoh_util::init_log_from_env_or("error");
+    let port = 17561;
+    let router = open_router(port, 10_000).await;
+    let (replier, holder, held) = open_queryables(port).await;
+    let querier = open_client(port).await;
+
+    // One of the two queries is sent to the holder
+    let start = Instant::now();
+    let mut tasks = vec![];
+    for _ in 0..2 {
+        let querier = querier.clone();
+        tasks.push(tokio::spawn(async move {
+            query(&querier, Duration::from_secs(5)).await
+        }));
+    }
+    tokio::time::sleep(SLEEP).await;
+    assert_eq!(held.lock().unwrap().len(), 1);
+
+    // Closing the holder forwards its pending query to the replier
+    ztimeout!(holder.close()).unwrap();
+    held.lock().unwrap().clear();
+    for task in tasks {
+        assert_eq!(ztimeout!(task).unwrap(), vec!["replier".to_string()]);
+    }
+    assert!(start.elapsed() < Duration::from_secs(5));
+
+    ztimeout!(querier.close()).unwrap();
+    ztimeout!(replier.close()).unwrap();
+    ztimeout!(router.close()).unwrap();
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn zenoh_query_hedging_empty_reply() {
+    zenoh_util::init_log_from_env_or("error");
+    let port = 17562;
+    let router = open_router(port, 200).await;
+
+    // A complete queryable that replies to the queries without any data
+    let empty = open_client(port).await;
+    ztimeout!(empty
+        .declare_queryable(KEY_EXPR)
+        .complete(true)
+        .callback(drop)
+        .background())
+    .unwrap();
+    // A complete queryable that replies to the queries after the hedge delay
+    let slow = open_client(port).await;
+    ztimeout!(slow
+        .declare_queryable(KEY_EXPR)
+        .complete(true)
+        .callback(move |query| {
+            tokio::spawn(async move {
+                tokio::time::sleep(Duration::from_millis(500)).await;
+                query.reply(KEY_EXPR, "slow").await.unwrap();
+            });
+        })
+        .background())
+    .unwrap();
+    tokio::time::sleep(SLEEP).await;
+    let querier = open_client(port).await;
+
+    // The empty final reply answers the query, which is not forwarded to the other queryable,
+    // and ends it even when the query was hedged to the empty queryable
+    for _ in 0..4 {
+        let start = Instant::now();
+        let replies = query(&querier, Duration::from_secs(5)).await;
+        assert!(replies.is_empty());
+        assert!(start.elapsed() < Duration::from_secs(2));
+    }
+
+    ztimeout!(querier.close()).unwrap();
+    ztimeout!(slow.close()).unwrap();
+    ztimeout!(empty.close()).unwrap();
+    ztimeout!(router.close()).unwrap();
+}
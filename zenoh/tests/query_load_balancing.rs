@@ -0,0 +1,179 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use zenoh::{config::WhatAmI, query::Query, Session};
+use zenoh_core::ztimeout;
+
+const TIMEOUT: Duration = Duration::from_secs(30);
+const SLEEP: Duration = Duration::from_millis(100);
+const QUERY_COUNT: usize = 10;
+const KEY_EXPR: &str = "test/query_load_balancing";
+
+async fn open_router(port: u16, load_balancing: &str, failover_timeout: Option<u64>) -> Session {
+    let mut config = zenoh::Config::default();
+    config.set_mode(Some(WhatAmI::Router)).unwrap();
+    config
+        .listen
+        .endpoints
+        .set(vec![format!("tcp/127.0.0.1:{port}").parse().unwrap()])
+        .unwrap();
+    config.scouting.multicast.set_enabled(Some(false)).unwrap();
+    config
+        .routing
+        .queries
+        .set_load_balancing(Some(load_balancing.to_string()))
+        .unwrap();
+    config
+        .routing
+        .queries
+        .set_failover_timeout(failover_timeout)
+        .unwrap();
+    ztimeout!(zenoh::open(config)).unwrap()
+}
+
+async fn open_client(port: u16) -> Session {
+    let mut config = zenoh::Config::default();
+    config.set_mode(Some(WhatAmI::Client)).unwrap();
+    config
+        .connect
+        .endpoints
+        .set(vec![format!("tcp/127.0.0.1:{port}").parse().unwrap()])
+        .unwrap();
+    config.scouting.multicast.set_enabled(Some(false)).unwrap();
+    ztimeout!(zenoh::open(config)).unwrap()
+}
+
+// Opens two sessions with a complete queryable: "replier" that replies to the queries
+// and "holder" that keeps them pending without replying
+async fn open_queryables(port: u16) -> (Session, Session, Arc<Mutex<Vec<Query>>>) {
+    let replier = open_client(port).await;
+    ztimeout!(replier
+        .declare_queryable(KEY_EXPR)
+        .complete(true)
+        .callback(|query| {
+            tokio::spawn(async move {
+                query.reply(KEY_EXPR, "replier").await.unwrap();
+            });
+        })
+        .background())
+    .unwrap();
+
+    let held = Arc::new(Mutex::new(vec![]));
+    let holder = open_client(port).await;
+    ztimeout!(holder
+        .declare_queryable(KEY_EXPR)
+        .complete(true)
+        .callback({
+            let held = held.clone();
+            move |query| held.lock().unwrap().push(query)
+        })
+        .background())
+    .unwrap();
+    tokio::time::sleep(SLEEP).await;
+    (replier, holder, held)
+}
+
+// Sends a query and returns the replies it got
+async fn query(session: &Session, timeout: Duration) -> Vec<String> {
+    let replies = ztimeout!(session.get(KEY_EXPR).timeout(timeout)).unwrap();
+    let mut payloads = vec![];
+    while let Ok(reply) = replies.recv_async().await {
+        if let Ok(sample) = reply.result() {
+            payloads.push(sample.payload().try_to_string().unwrap().into_owned());
+        }
+    }
+    payloads
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn zenoh_query_round_robin() {
+    zenoh_util::init_log_from_env_or("error");
+    let port = 17550;
+    let router = open_router(port, "round_robin", None).await;
+    let (replier, holder, held) = open_queryables(port).await;
+    let querier = open_client(port).await;
+
+    // The queries alternate between the two queryables
+    let mut replied = 0;
+    for _ in 0..QUERY_COUNT {
+        let replies = query(&querier, Duration::from_millis(300)).await;
+        assert!(replies.len() <= 1);
+        replied += replies.len();
+    }
+    assert_eq!(replied, QUERY_COUNT / 2);
+    assert_eq!(held.lock().unwrap().len(), QUERY_COUNT / 2);
+
+    held.lock().unwrap().clear();
+    ztimeout!(querier.close()).unwrap();
+    ztimeout!(holder.close()).unwrap();
+    ztimeout!(replier.close()).unwrap();
+    ztimeout!(router.close()).unwrap();
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn zenoh_query_least_outstanding() {
+    zenoh_util::init_log_from_env_or("error");
+    let port = 17551;
+    let router = open_router(port, "least_outstanding", None).await;
+    let (replier, holder, held) = open_queryables(port).await;
+    let querier = open_client(port).await;
+
+    // Once the holder has a pending query, all the queries go to the replier
+    let mut tasks = vec![];
+    for _ in 0..QUERY_COUNT {
+        let querier = querier.clone();
+        tasks.push(tokio::spawn(async move {
+            query(&querier, Duration::from_secs(2)).await
+        }));
+        tokio::time::sleep(SLEEP).await;
+    }
+    let mut replied = 0;
+    for task in tasks {
+        replied += ztimeout!(task).unwrap().len();
+    }
+    assert!(held.lock().unwrap().len() <= 1);
+    assert!(replied >= QUERY_COUNT - 1);
+
+    held.lock().unwrap().clear();
+    ztimeout!(querier.close()).unwrap();
+    ztimeout!(holder.close()).unwrap();
+    ztimeout!(replier.close()).unwrap();
+    ztimeout!(router.close()).unwrap();
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn zenoh_query_failover() {
+    zenoh_util::init_log_from_env_or("error");
+    let port = 17552;
+    let router = open_router(port, "round_robin", Some(200)).await;
+    let (replier, holder, held) = open_queryables(port).await;
+    let querier = open_client(port).await;
+
+    // The queries sent to the holder are forwarded to the replier when they time out
+    for _ in 0..QUERY_COUNT {
+        let replies = query(&querier, Duration::from_secs(5)).await;
+        assert_eq!(replies, vec!["replier".to_string()]);
+    }
+    assert!(!held.lock().unwrap().is_empty());
+
+    held.lock().unwrap().clear();
+    ztimeout!(querier.close()).unwrap();
+    ztimeout!(holder.close()).unwrap();
+    ztimeout!(replier.close()).unwrap();
+    ztimeout!(router.close()).unwrap();
+}
@@ -0,0 +1,132 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+use std::time::Duration;
+
+use zenoh::{
+    config::WhatAmI, handlers::FifoChannelHandler, pubsub::Subscriber, sample::Sample, Session,
+};
+use zenoh_core::ztimeout;
+
+const TIMEOUT: Duration = Duration::from_secs(30);
+const SLEEP: Duration = Duration::from_millis(100);
+const MSG_COUNT: usize = 50;
+
+async fn open(mode: WhatAmI, listen: &[String], connect: &[String]) -> Session {
+    let mut config = zenoh::Config::default();
+    config.set_mode(Some(mode)).unwrap();
+    config
+        .listen
+        .endpoints
+        .set(listen.iter().map(|e| e.parse().unwrap()).collect())
+        .unwrap();
+    config
+        .connect
+        .endpoints
+        .set(connect.iter().map(|e| e.parse().unwrap()).collect())
+        .unwrap();
+    config.scouting.multicast.set_enabled(Some(false)).unwrap();
+    config.scouting.gossip.set_enabled(Some(false)).unwrap();
+    config
+        .routing
+        .router
+        .set_redundant_key_exprs(vec!["test/redundancy/critical/**".parse().unwrap()])
+        .unwrap();
+    ztimeout!(zenoh::open(config)).unwrap()
+}
+
+// Checks that each message is received once. The copies following the two routes may overtake
+// each other, so the messages are not necessarily received in order.
+async fn assert_received(subscriber: &Subscriber<FifoChannelHandler<Sample>>) {
+    let mut received = vec![];
+    for _ in 0..MSG_COUNT {
+        let sample = ztimeout!(subscriber.recv_async()).unwrap();
+        received.push(
+            sample
+                .payload()
+                .try_to_string()
+                .unwrap()
+                .parse::<usize>()
+                .unwrap(),
+        );
+    }
+    received.sort_unstable();
+    assert_eq!(received, (0..MSG_COUNT).collect::<Vec<_>>());
+    tokio::time::sleep(SLEEP).await;
+    assert!(subscriber.try_recv().unwrap().is_none());
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn zenoh_redundant_routes() {
+    zenoh_util::init_log_from_env_or("error");
+
+    let locator = |port: u16| format!("tcp/127.0.0.1:{port}");
+
+    // Routers in a ring: A - B - D - C - A
+    let router_a = open(WhatAmI::Router, &[locator(17520)], &[]).await;
+    let router_b = open(WhatAmI::Router, &[locator(17521)], &[locator(17520)]).await;
+    let router_c = open(WhatAmI::Router, &[locator(17522)], &[locator(17520)]).await;
+    let router_d = open(
+        WhatAmI::Router,
+        &[locator(17523)],
+        &[locator(17521), locator(17522)],
+    )
+    .await;
+
+    let publisher_session = open(WhatAmI::Client, &[], &[locator(17520)]).await;
+    let subscriber_session = open(WhatAmI::Client, &[], &[locator(17523)]).await;
+
+    let subscriber =
+        ztimeout!(subscriber_session.declare_subscriber("test/redundancy/**")).unwrap();
+    let publisher =
+        ztimeout!(publisher_session.declare_publisher("test/redundancy/critical/stop")).unwrap();
+
+    // Wait for the routes to be established
+    ztimeout!(async {
+        loop {
+            publisher.put("warmup").await.unwrap();
+            tokio::time::sleep(SLEEP).await;
+            if subscriber.try_recv().unwrap().is_some() {
+                break;
+            }
+        }
+    });
+    tokio::time::sleep(SLEEP).await;
+    while subscriber.try_recv().unwrap().is_some() {}
+
+    // Each message is delivered once although it follows two routes
+    for i in 0..MSG_COUNT {
+        ztimeout!(publisher.put(i.to_string())).unwrap();
+    }
+    assert_received(&subscriber).await;
+
+    // No message is lost when a router of the ring fails
+    let failure = tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        ztimeout!(router_b.close()).unwrap();
+    });
+    for i in 0..MSG_COUNT {
+        ztimeout!(publisher.put(i.to_string())).unwrap();
+        tokio::time::sleep(Duration::from_millis(2)).await;
+    }
+    failure.await.unwrap();
+    assert_received(&subscriber).await;
+
+    ztimeout!(publisher.undeclare()).unwrap();
+    ztimeout!(subscriber.undeclare()).unwrap();
+    ztimeout!(publisher_session.close()).unwrap();
+    ztimeout!(subscriber_session.close()).unwrap();
+    ztimeout!(router_d.close()).unwrap();
+    ztimeout!(router_c.close()).unwrap();
+    ztimeout!(router_a.close()).unwrap();
+}
@@ -0,0 +1,202 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+use std::time::Duration;
+
+use zenoh::{config::WhatAmI, Session};
+use zenoh_core::ztimeout;
+
+const TIMEOUT: Duration = Duration::from_secs(30);
+const SLEEP: Duration = Duration::from_millis(100);
+const MSG_COUNT: usize = 10;
+
+async fn open(
+    mode: WhatAmI,
+    region: Option<&str>,
+    listen: &[String],
+    connect: &[String],
+) -> Session {
+    let mut config = zenoh::Config::default();
+    config.set_mode(Some(mode)).unwrap();
+    config
+        .listen
+        .endpoints
+        .set(listen.iter().map(|e| e.parse().unwrap()).collect())
+        .unwrap();
+    config
+        .connect
+        .endpoints
+        .set(connect.iter().map(|e| e.parse().unwrap()).collect())
+        .unwrap();
+    config.scouting.multicast.set_enabled(Some(false)).unwrap();
+    config.scouting.gossip.set_enabled(Some(false)).unwrap();
+    config.adminspace.set_enabled(true).unwrap();
+    config
+        .routing
+        .router
+        .set_region(region.map(str::to_string))
+        .unwrap();
+    config
+        .aggregation
+        .set_subscribers(vec!["test/regions/pubsub/**".parse().unwrap()])
+        .unwrap();
+    ztimeout!(zenoh::open(config)).unwrap()
+}
+
+// Returns the routers linkstate graph seen by `session`
+async fn linkstate(session: &Session) -> String {
+    let replies = session
+        .get(format!("@/{}/router/linkstate/routers", session.zid()))
+        .await
+        .unwrap();
+    let reply = replies.recv_async().await.unwrap();
+    reply
+        .result()
+        .unwrap()
+        .payload()
+        .try_to_string()
+        .unwrap()
+        .into_owned()
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn zenoh_regions() {
+    zenoh_util::init_log_from_env_or("error");
+
+    let locator = |port: u16| format!("tcp/127.0.0.1:{port}");
+
+    // Region a: A1 - A2, region b: B1 - B2 - B3, with two border links A2 - B1 and A1 - B2
+    let router_a1 = open(WhatAmI::Router, Some("a"), &[locator(17530)], &[]).await;
+    let router_a2 = open(
+        WhatAmI::Router,
+        Some("a"),
+        &[locator(17531)],
+        &[locator(17530)],
+    )
+    .await;
+    let router_b1 = open(
+        WhatAmI::Router,
+        Some("b"),
+        &[locator(17532)],
+        &[locator(17531)],
+    )
+    .await;
+    let router_b2 = open(
+        WhatAmI::Router,
+        Some("b"),
+        &[locator(17533)],
+        &[locator(17532), locator(17530)],
+    )
+    .await;
+    let router_b3 = open(
+        WhatAmI::Router,
+        Some("b"),
+        &[locator(17534)],
+        &[locator(17533)],
+    )
+    .await;
+
+    let client_a = open(WhatAmI::Client, None, &[], &[locator(17530)]).await;
+    let client_b = open(WhatAmI::Client, None, &[], &[locator(17534)]).await;
+
+    // The routers of a region only know the border routers of the other region
+    ztimeout!(async {
+        loop {
+            let dot = linkstate(&router_a1).await;
+            if dot.contains(&router_b1.zid().to_string())
+                && dot.contains(&router_b2.zid().to_string())
+            {
+                break;
+            }
+            tokio::time::sleep(SLEEP).await;
+        }
+    });
+
+    // Data crosses the regions once although the region has two border routers
+    let subscriber = ztimeout!(client_b.declare_subscriber("test/regions/pubsub/1")).unwrap();
+    let publisher = ztimeout!(client_a.declare_publisher("test/regions/pubsub/1")).unwrap();
+    ztimeout!(async {
+        loop {
+            publisher.put("warmup").await.unwrap();
+            tokio::time::sleep(SLEEP).await;
+            if subscriber.try_recv().unwrap().is_some() {
+                break;
+            }
+        }
+    });
+    tokio::time::sleep(SLEEP).await;
+    while subscriber.try_recv().unwrap().is_some() {}
+
+    for i in 0..MSG_COUNT {
+        ztimeout!(publisher.put(i.to_string())).unwrap();
+    }
+    for i in 0..MSG_COUNT {
+        let sample = ztimeout!(subscriber.recv_async()).unwrap();
+        assert_eq!(sample.payload().try_to_string().unwrap(), i.to_string());
+    }
+    tokio::time::sleep(SLEEP).await;
+    assert!(subscriber.try_recv().unwrap().is_none());
+
+    // The routers behind the border routers are not known to the other region
+    assert!(!linkstate(&router_a1)
+        .await
+        .contains(&router_b3.zid().to_string()));
+    assert!(!linkstate(&router_a2)
+        .await
+        .contains(&router_b3.zid().to_string()));
+
+    // Queries and liveliness tokens cross the regions
+    let _queryable =
+        ztimeout!(client_b
+            .declare_queryable("test/regions/query")
+            .callback(|query| {
+                tokio::spawn(async move {
+                    query.reply(query.key_expr(), "reply").await.unwrap();
+                });
+            }))
+        .unwrap();
+    let _token = ztimeout!(client_b.liveliness().declare_token("test/regions/token")).unwrap();
+    ztimeout!(async {
+        loop {
+            let replies = client_a.get("test/regions/query").await.unwrap();
+            let mut count = 0;
+            while let Ok(reply) = replies.recv_async().await {
+                assert_eq!(
+                    reply.result().unwrap().payload().try_to_string().unwrap(),
+                    "reply"
+                );
+                count += 1;
+            }
+            assert!(count <= 1);
+            let tokens = client_a
+                .liveliness()
+                .get("test/regions/token")
+                .await
+                .unwrap();
+            if count == 1 && tokens.recv_async().await.is_ok() {
+                break;
+            }
+            tokio::time::sleep(SLEEP).await;
+        }
+    });
+
+    ztimeout!(publisher.undeclare()).unwrap();
+    ztimeout!(subscriber.undeclare()).unwrap();
+    ztimeout!(client_a.close()).unwrap();
+    ztimeout!(client_b.close()).unwrap();
+    ztimeout!(router_b3.close()).unwrap();
+    ztimeout!(router_b2.close()).unwrap();
+    ztimeout!(router_b1.close()).unwrap();
+    ztimeout!(router_a2.close()).unwrap();
+    ztimeout!(router_a1.close()).unwrap();
+}
@@ -0,0 +1,193 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+#![cfg(feature = "internal_config")]
+
+use std::{str::FromStr, time::Duration};
+
+use zenoh::{config::WhatAmI, session::ZenohId, Config, Session};
+use zenoh_config::{RemappingItemConf, RemappingRuleConf};
+use zenoh_core::ztimeout;
+
+const TIMEOUT: Duration = Duration::from_secs(30);
+const SLEEP: Duration = Duration::from_secs(1);
+
+fn router_config(listen: &str, connect: Option<&str>) -> Config {
+    let mut config = zenoh::Config::default();
+    config.set_mode(Some(WhatAmI::Router)).unwrap();
+    config
+        .listen
+        .endpoints
+        .set(vec![listen.parse().unwrap()])
+        .unwrap();
+    if let Some(connect) = connect {
+        config
+            .connect
+            .endpoints
+            .set(vec![connect.parse().unwrap()])
+            .unwrap();
+    }
+    config.scouting.multicast.set_enabled(Some(false)).unwrap();
+    config
+}
+
+async fn open_client(locator: &str) -> Session {
+    let mut config = zenoh::Config::default();
+    config.set_mode(Some(WhatAmI::Client)).unwrap();
+    config
+        .connect
+        .endpoints
+        .set(vec![locator.parse().unwrap()])
+        .unwrap();
+    config.scouting.multicast.set_enabled(Some(false)).unwrap();
+    ztimeout!(zenoh::open(config)).unwrap()
+}
+
+// Opens a factory router remapping `factory/line1` to `site/paris/line1` for the site router
+async fn open_routers(factory_locator: &str, site_locator: &str) -> (Session, Session) {
+    let site_zid = ZenohId::from_str("5173").unwrap();
+
+    // The factory router remaps its key expressions for the site router only
+    let mut config = router_config(factory_locator, None);
+    config
+        .set_remapping(vec![RemappingItemConf {
+            interfaces: None,
+            zids: Some(vec![site_zid]),
+            rules: vec![RemappingRuleConf {
+                local: "factory/line1".parse().unwrap(),
+                remote: "site/paris/line1".parse().unwrap(),
+            }],
+        }])
+        .unwrap();
+    let factory_router = ztimeout!(zenoh::open(config)).unwrap();
+    let mut config = router_config(site_locator, Some(factory_locator));
+    config.set_id(site_zid).unwrap();
+    let site_router = ztimeout!(zenoh::open(config)).unwrap();
+    tokio::time::sleep(SLEEP).await;
+    (factory_router, site_router)
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn zenoh_remapping() {
+    zenoh_util::init_log_from_env_or("error");
+    let factory_locator = "tcp/127.0.0.1:17600";
+    let site_locator = "tcp/127.0.0.1:17601";
+    let (factory_router, site_router) = open_routers(factory_locator, site_locator).await;
+
+    let factory = open_client(factory_locator).await;
+    let site = open_client(site_locator).await;
+
+    // Only the key expressions starting with a prefix of the rules are remapped
+    let factory_sub = ztimeout!(factory.declare_subscriber("factory/line1/**")).unwrap();
+    let site_sub = ztimeout!(site.declare_subscriber("site/paris/line1/**")).unwrap();
+    let site_other_sub = ztimeout!(site.declare_subscriber("factory/**")).unwrap();
+    let _queryable =
+        ztimeout!(factory
+            .declare_queryable("factory/line1/status")
+            .callback(|query| {
+                tokio::spawn(async move {
+                    query
+                        .reply("factory/line1/status", "running")
+                        .await
+                        .unwrap();
+                });
+            }))
+        .unwrap();
+    tokio::time::sleep(SLEEP).await;
+
+    // Publications cross the router with remapped key expressions in both directions
+    ztimeout!(factory.put("factory/line1/temp", "21")).unwrap();
+    let sample = ztimeout!(site_sub.recv_async()).unwrap();
+    assert_eq!(sample.key_expr().as_str(), "site/paris/line1/temp");
+    let sample = ztimeout!(factory_sub.recv_async()).unwrap();
+    assert_eq!(sample.key_expr().as_str(), "factory/line1/temp");
+
+    ztimeout!(site.put("site/paris/line1/cmd", "stop")).unwrap();
+    let sample = ztimeout!(factory_sub.recv_async()).unwrap();
+    assert_eq!(sample.key_expr().as_str(), "factory/line1/cmd");
+    let sample = ztimeout!(site_sub.recv_async()).unwrap();
+    assert_eq!(sample.key_expr().as_str(), "site/paris/line1/cmd");
+
+    // Key expressions outside of the rules are not remapped
+    ztimeout!(factory.put("factory/line2/temp", "22")).unwrap();
+    let sample = ztimeout!(site_other_sub.recv_async()).unwrap();
+    assert_eq!(sample.key_expr().as_str(), "factory/line2/temp");
+    tokio::time::sleep(SLEEP).await;
+    assert!(site_sub.try_recv().unwrap().is_none());
+    assert!(site_other_sub.try_recv().unwrap().is_none());
+
+    // As well as queries and replies
+    let replies = ztimeout!(site.get("site/paris/line1/status")).unwrap();
+    let reply = ztimeout!(replies.recv_async()).unwrap();
+    let sample = reply.result().unwrap();
+    assert_eq!(sample.key_expr().as_str(), "site/paris/line1/status");
+    assert_eq!(sample.payload().try_to_string().unwrap(), "running");
+
+    ztimeout!(factory.close()).unwrap();
+    ztimeout!(site.close()).unwrap();
+    ztimeout!(site_router.close()).unwrap();
+    ztimeout!(factory_router.close()).unwrap();
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn zenoh_remapping_wildcards() {
+    zenoh_util::init_log_from_env_or("error");
+    let factory_locator = "tcp/127.0.0.1:17602";
+    let site_locator = "tcp/127.0.0.1:17603";
+    let (factory_router, site_router) = open_routers(factory_locator, site_locator).await;
+
+    let factory = open_client(factory_locator).await;
+    let site = open_client(site_locator).await;
+
+    // The wildcard key expressions intersecting a prefix without starting with it are remapped
+    let site_sub = ztimeout!(site.declare_subscriber("site/**")).unwrap();
+    let site_line_sub = ztimeout!(site.declare_subscriber("site/*/line1/**")).unwrap();
+    let factory_sub = ztimeout!(factory.declare_subscriber("factory/*/cmd")).unwrap();
+    let _queryable =
+        ztimeout!(factory
+            .declare_queryable("factory/line1/status")
+            .callback(|query| {
+                tokio::spawn(async move {
+                    query
+                        .reply("factory/line1/status", "running")
+                        .await
+                        .unwrap();
+                });
+            }))
+        .unwrap();
+    tokio::time::sleep(SLEEP).await;
+
+    ztimeout!(factory.put("factory/line1/temp", "21")).unwrap();
+    for sub in [&site_sub, &site_line_sub] {
+        let sample = ztimeout!(sub.recv_async()).unwrap();
+        assert_eq!(sample.key_expr().as_str(), "site/paris/line1/temp");
+    }
+
+    ztimeout!(site.put("site/paris/line1/cmd", "stop")).unwrap();
+    let sample = ztimeout!(factory_sub.recv_async()).unwrap();
+    assert_eq!(sample.key_expr().as_str(), "factory/line1/cmd");
+
+    // As well as the ones of queries
+    for selector in ["site/**", "site/*/line1/status"] {
+        let replies = ztimeout!(site.get(selector)).unwrap();
+        let reply = ztimeout!(replies.recv_async()).unwrap();
+        let sample = reply.result().unwrap();
+        assert_eq!(sample.key_expr().as_str(), "site/paris/line1/status");
+        assert_eq!(sample.payload().try_to_string().unwrap(), "running");
+    }
+
+    ztimeout!(factory.close()).unwrap();
+    ztimeout!(site.close()).unwrap();
+    ztimeout!(site_router.close()).unwrap();
+    ztimeout!(factory_router.close()).unwrap();
+}
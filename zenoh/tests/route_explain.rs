@@ -0,0 +1,245 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+#![cfg(feature = "internal_config")]
+
+use std::{str::FromStr, time::Duration};
+
+use zenoh::{config::WhatAmI, session::ZenohId, Session};
+use zenoh_config::{
+    DownsamplingItemConf, DownsamplingRuleConf, InterceptorFlow, RemappingItemConf,
+    RemappingRuleConf,
+};
+use zenoh_core::ztimeout;
+
+const TIMEOUT: Duration = Duration::from_secs(30);
+const SLEEP: Duration = Duration::from_millis(100);
+
+async fn open_client(locator: &str, zid: Option<ZenohId>) -> Session {
+    let mut config = zenoh::Config::default();
+    config.set_mode(Some(WhatAmI::Client)).unwrap();
+    if let Some(zid) = zid {
+        config.set_id(zid).unwrap();
+    }
+    config
+        .connect
+        .endpoints
+        .set(vec![locator.parse().unwrap()])
+        .unwrap();
+    config.scouting.multicast.set_enabled(Some(false)).unwrap();
+    ztimeout!(zenoh::open(config)).unwrap()
+}
+
+async fn explain(session: &Session, router: &Session, params: &str) -> serde_json::Value {
+    let replies =
+        ztimeout!(session.get(format!("@/{}/router/route?{params}", router.zid()))).unwrap();
+    let reply = ztimeout!(replies.recv_async()).unwrap();
+    let payload = reply.result().unwrap().payload().to_bytes().into_owned();
+    serde_json::from_slice(&payload).unwrap()
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn zenoh_route_explain() {
+    zenoh_util::init_log_from_env_or("error");
+    let locator = "tcp/127.0.0.1:17540";
+
+    let mut config = zenoh::Config::default();
+    config.set_mode(Some(WhatAmI::Router)).unwrap();
+    config
+        .listen
+        .endpoints
+        .set(vec![locator.parse().unwrap()])
+        .unwrap();
+    config.scouting.multicast.set_enabled(Some(false)).unwrap();
+    config.adminspace.set_enabled(true).unwrap();
+    // Let a single message go through every 100s
+    config
+        .set_downsampling(vec![DownsamplingItemConf {
+            flow: InterceptorFlow::Egress,
+            interfaces: None,
+            rules: vec![DownsamplingRuleConf {
+                key_expr: "test/explain/**".parse().unwrap(),
+                freq: 0.01,
+            }],
+        }])
+        .unwrap();
+    let router = ztimeout!(zenoh::open(config)).unwrap();
+
+    let publisher = open_client(locator, None).await;
+    let subscriber = open_client(locator, None).await;
+    let sub = ztimeout!(subscriber.declare_subscriber("test/explain/**")).unwrap();
+    let _queryable = ztimeout!(subscriber.declare_queryable("test/explain/query")).unwrap();
+    tokio::time::sleep(SLEEP).await;
+
+    let params = format!("keyexpr=test/explain/data;source={}", publisher.zid());
+    // Explaining a route twice does not consume the downsampling budget
+    for _ in 0..2 {
+        let explanation = explain(&publisher, &router, &params).await;
+        assert_eq!(
+            explanation["source"]["zid"],
+            serde_json::json!(publisher.zid())
+        );
+        assert_eq!(explanation["ingress_filter"], true);
+        let data_route = explanation["data_route"].as_array().unwrap();
+        assert_eq!(data_route.len(), 1);
+        assert_eq!(
+            data_route[0]["face"]["zid"],
+            serde_json::json!(subscriber.zid())
+        );
+        assert_eq!(data_route[0]["egress_filter"], true);
+        assert_eq!(
+            data_route[0]["interceptors"],
+            serde_json::json!([{ "interceptor": "downsampling", "allowed": true }])
+        );
+        assert!(explanation["subscribers"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .any(|sub| sub["key_expr"] == "test/explain/**"
+                && sub["clients"] == serde_json::json!([subscriber.zid()])));
+        assert!(explanation["query_route"].as_array().unwrap().is_empty());
+    }
+
+    ztimeout!(publisher.put("test/explain/data", "data")).unwrap();
+    let sample = ztimeout!(sub.recv_async()).unwrap();
+    assert_eq!(sample.key_expr().as_str(), "test/explain/data");
+
+    // The downsampler now drops the data sent to the subscriber
+    let explanation = explain(&publisher, &router, &params).await;
+    assert_eq!(
+        explanation["data_route"][0]["interceptors"][0]["allowed"],
+        false
+    );
+
+    let explanation = explain(
+        &publisher,
+        &router,
+        &format!("keyexpr=test/explain/query;source={}", publisher.zid()),
+    )
+    .await;
+    let query_route = explanation["query_route"].as_array().unwrap();
+    assert_eq!(query_route.len(), 1);
+    assert_eq!(
+        query_route[0]["face"]["zid"],
+        serde_json::json!(subscriber.zid())
+    );
+    assert_eq!(query_route[0]["complete"], false);
+
+    // Unknown sources and missing key expressions are reported as errors
+    for params in ["source=1234;keyexpr=test/explain/data", "source=1234"] {
+        let replies =
+            ztimeout!(publisher.get(format!("@/{}/router/route?{params}", router.zid()))).unwrap();
+        assert!(ztimeout!(replies.recv_async()).unwrap().result().is_err());
+    }
+
+    ztimeout!(publisher.close()).unwrap();
+    ztimeout!(subscriber.close()).unwrap();
+    ztimeout!(router.close()).unwrap();
+}
+
+#[cfg(target_family = "unix")]
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn zenoh_route_explain_remapping_acl() {
+    zenoh_util::init_log_from_env_or("error");
+    let locator = "tcp/127.0.0.1:17541";
+    let publisher_zid = ZenohId::from_str("5175").unwrap();
+    let subscriber_zid = ZenohId::from_str("5176").unwrap();
+
+    let mut config = zenoh::Config::default();
+    config.set_mode(Some(WhatAmI::Router)).unwrap();
+    config
+        .listen
+        .endpoints
+        .set(vec![locator.parse().unwrap()])
+        .unwrap();
+    config.scouting.multicast.set_enabled(Some(false)).unwrap();
+    config.adminspace.set_enabled(true).unwrap();
+    // The clients see `test/remote/**` while the router sees `test/local/**`
+    config
+        .set_remapping(vec![RemappingItemConf {
+            interfaces: None,
+            zids: Some(vec![publisher_zid, subscriber_zid]),
+            rules: vec![RemappingRuleConf {
+                local: "test/local".parse().unwrap(),
+                remote: "test/remote".parse().unwrap(),
+            }],
+        }])
+        .unwrap();
+    // The puts on the local key expressions are denied in both directions
+    config
+        .insert_json5(
+            "access_control",
+            r#"{
+                "enabled": true,
+                "default_permission": "allow",
+                "rules": [
+                    {
+                        "id": "r1",
+                        "permission": "deny",
+                        "flows": ["egress", "ingress"],
+                        "messages": ["put"],
+                        "key_exprs": ["test/local/**"],
+                    },
+                ],
+                "subjects": [{ "id": "s1", "interfaces": ["lo", "lo0"] }],
+                "policies": [{ "rules": ["r1"], "subjects": ["s1"] }],
+            }"#,
+        )
+        .unwrap();
+    let router = ztimeout!(zenoh::open(config)).unwrap();
+
+    let publisher = open_client(locator, Some(publisher_zid)).await;
+    let subscriber = open_client(locator, Some(subscriber_zid)).await;
+    let _sub = ztimeout!(subscriber.declare_subscriber("test/remote/**")).unwrap();
+    tokio::time::sleep(SLEEP).await;
+
+    // The access control checks the key expression remapped on ingress
+    let explanation = explain(
+        &publisher,
+        &router,
+        &format!("keyexpr=test/remote/data;source={}", publisher.zid()),
+    )
+    .await;
+    assert_eq!(
+        explanation["ingress_interceptors"],
+        serde_json::json!([
+            { "interceptor": "remapping", "allowed": true },
+            { "interceptor": "access_control", "allowed": false },
+        ])
+    );
+
+    // The egress remapping is not reached once the access control drops the message
+    let explanation = explain(
+        &publisher,
+        &router,
+        &format!("keyexpr=test/local/data;source={}", publisher.zid()),
+    )
+    .await;
+    let data_route = explanation["data_route"].as_array().unwrap();
+    assert_eq!(data_route.len(), 1);
+    assert_eq!(
+        data_route[0]["face"]["zid"],
+        serde_json::json!(subscriber.zid())
+    );
+    assert_eq!(
+        data_route[0]["interceptors"],
+        serde_json::json!([
+            { "interceptor": "access_control", "allowed": false },
+            { "interceptor": "remapping", "allowed": null },
+        ])
+    );
+
+    ztimeout!(publisher.close()).unwrap();
+    ztimeout!(subscriber.close()).unwrap();
+    ztimeout!(router.close()).unwrap();
+}
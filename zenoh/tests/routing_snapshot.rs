@@ -0,0 +1,209 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+use std::{path::Path, str::FromStr, time::Duration};
+
+use zenoh::{
+    config::{WhatAmI, ZenohId},
+    Session,
+};
+use zenoh_core::ztimeout;
+
+const TIMEOUT: Duration = Duration::from_secs(30);
+const SLEEP: Duration = Duration::from_millis(500);
+const RECONCILIATION_DELAY: u64 = 3000;
+
+async fn open_router_a(locator: &str, snapshot: &Path) -> Session {
+    let mut config = zenoh::Config::default();
+    config.set_mode(Some(WhatAmI::Router)).unwrap();
+    config.set_id(ZenohId::from_str("a").unwrap()).unwrap();
+    config
+        .listen
+        .endpoints
+        .set(vec![locator.parse().unwrap()])
+        .unwrap();
+    config.scouting.multicast.set_enabled(Some(false)).unwrap();
+    config.adminspace.set_enabled(true).unwrap();
+    let snapshot_conf = &mut config.routing.router.snapshot;
+    snapshot_conf
+        .set_path(Some(snapshot.to_str().unwrap().to_string()))
+        .unwrap();
+    snapshot_conf.set_period(Some(100)).unwrap();
+    snapshot_conf
+        .set_reconciliation_delay(Some(RECONCILIATION_DELAY))
+        .unwrap();
+    ztimeout!(zenoh::open(config)).unwrap()
+}
+
+async fn open_router(id: Option<&str>, listen: &str, connect: &str) -> Session {
+    let mut config = zenoh::Config::default();
+    config.set_mode(Some(WhatAmI::Router)).unwrap();
+    if let Some(id) = id {
+        config.set_id(ZenohId::from_str(id).unwrap()).unwrap();
+    }
+    config
+        .listen
+        .endpoints
+        .set(vec![listen.parse().unwrap()])
+        .unwrap();
+    config
+        .connect
+        .endpoints
+        .set(vec![connect.parse().unwrap()])
+        .unwrap();
+    config.scouting.multicast.set_enabled(Some(false)).unwrap();
+    ztimeout!(zenoh::open(config)).unwrap()
+}
+
+async fn open_client(locator: &str) -> Session {
+    let mut config = zenoh::Config::default();
+    config.set_mode(Some(WhatAmI::Client)).unwrap();
+    config
+        .connect
+        .endpoints
+        .set(vec![locator.parse().unwrap()])
+        .unwrap();
+    config.scouting.multicast.set_enabled(Some(false)).unwrap();
+    ztimeout!(zenoh::open(config)).unwrap()
+}
+
+// Returns the routers linkstate graph seen by `session`
+async fn linkstate(session: &Session) -> String {
+    let replies = session
+        .get(format!("@/{}/router/linkstate/routers", session.zid()))
+        .await
+        .unwrap();
+    let reply = replies.recv_async().await.unwrap();
+    reply
+        .result()
+        .unwrap()
+        .payload()
+        .try_to_string()
+        .unwrap()
+        .into_owned()
+}
+
+// Returns the key expressions of the subscribers declared by router "b" as known by `router`
+async fn remote_subscribers(router: &Session) -> Vec<String> {
+    let replies = ztimeout!(router.get(format!(
+        "@/{}/router/route?keyexpr=test/snapshot/**",
+        router.zid()
+    )))
+    .unwrap();
+    let reply = ztimeout!(replies.recv_async()).unwrap();
+    let payload = reply.result().unwrap().payload().to_bytes().into_owned();
+    let explanation: serde_json::Value = serde_json::from_slice(&payload).unwrap();
+    let mut subscribers: Vec<String> = explanation["subscribers"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter(|sub| sub["routers"] == serde_json::json!(["b"]))
+        .map(|sub| sub["key_expr"].as_str().unwrap().to_string())
+        .collect();
+    subscribers.sort();
+    subscribers
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn zenoh_routing_snapshot() {
+    zenoh_util::init_log_from_env_or("error");
+    const ROUTER_A: &str = "tcp/127.0.0.1:17570";
+    const ROUTER_B: &str = "tcp/127.0.0.1:17571";
+    let snapshot = std::env::temp_dir().join(format!("zenoh-routing-{}.json", std::process::id()));
+    let _ = std::fs::remove_file(&snapshot);
+
+    let router_a = open_router_a(ROUTER_A, &snapshot).await;
+    let router_b = open_router(Some("b"), ROUTER_B, ROUTER_A).await;
+    let client = open_client(ROUTER_B).await;
+    let sub = ztimeout!(client.declare_subscriber("test/snapshot/data")).unwrap();
+    let gone = ztimeout!(client.declare_subscriber("test/snapshot/gone")).unwrap();
+    tokio::time::sleep(SLEEP).await;
+    assert_eq!(
+        remote_subscribers(&router_a).await,
+        vec!["test/snapshot/data", "test/snapshot/gone"]
+    );
+
+    // The state of router "a" is saved when it closes
+    ztimeout!(router_a.close()).unwrap();
+    let content = std::fs::read_to_string(&snapshot).unwrap();
+    assert!(content.contains("test/snapshot/data"));
+    ztimeout!(gone.undeclare()).unwrap();
+
+    // The restored declarations are known as soon as router "a" restarts
+    let router_a = open_router_a(ROUTER_A, &snapshot).await;
+    assert_eq!(
+        remote_subscribers(&router_a).await,
+        vec!["test/snapshot/data", "test/snapshot/gone"]
+    );
+
+    // The data is routed once router "b" reconnects
+    ztimeout!(async {
+        loop {
+            router_a.put("test/snapshot/data", "data").await.unwrap();
+            if tokio::time::timeout(SLEEP, sub.recv_async()).await.is_ok() {
+                break;
+            }
+        }
+    });
+
+    // The restored subscriber that router "b" did not declare again is removed
+    tokio::time::sleep(Duration::from_millis(RECONCILIATION_DELAY)).await;
+    assert_eq!(
+        remote_subscribers(&router_a).await,
+        vec!["test/snapshot/data"]
+    );
+
+    ztimeout!(client.close()).unwrap();
+    ztimeout!(router_b.close()).unwrap();
+    ztimeout!(router_a.close()).unwrap();
+    let _ = std::fs::remove_file(&snapshot);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn zenoh_routing_snapshot_neighbour_restart() {
+    zenoh_util::init_log_from_env_or("error");
+    const ROUTER_A: &str = "tcp/127.0.0.1:17572";
+    const ROUTER_B: &str = "tcp/127.0.0.1:17573";
+    const ROUTER_C: &str = "tcp/127.0.0.1:17574";
+    let snapshot =
+        std::env::temp_dir().join(format!("zenoh-routing-restart-{}.json", std::process::id()));
+    let _ = std::fs::remove_file(&snapshot);
+
+    // Routers "a" - "b" - "c", router "a" saving the state of "b" with its links to "a" and "c"
+    let router_a = open_router_a(ROUTER_A, &snapshot).await;
+    let router_b = open_router(Some("b"), ROUTER_B, ROUTER_A).await;
+    let router_c = open_router(None, ROUTER_C, ROUTER_B).await;
+    let zid_c = router_c.zid().to_string();
+    ztimeout!(async {
+        while !linkstate(&router_a).await.contains(&zid_c) {
+            tokio::time::sleep(SLEEP).await;
+        }
+    });
+    ztimeout!(router_a.close()).unwrap();
+    ztimeout!(router_c.close()).unwrap();
+    ztimeout!(router_b.close()).unwrap();
+
+    // Router "b" restarts without snapshot, its sequence number starting over, and without "c".
+    // Its new state replaces the restored one, which leaves "c" detached and removed.
+    let router_a = open_router_a(ROUTER_A, &snapshot).await;
+    let router_b = open_router(Some("b"), ROUTER_B, ROUTER_A).await;
+    ztimeout!(async {
+        while linkstate(&router_a).await.contains(&zid_c) {
+            tokio::time::sleep(SLEEP).await;
+        }
+    });
+
+    ztimeout!(router_b.close()).unwrap();
+    ztimeout!(router_a.close()).unwrap();
+    let _ = std::fs::remove_file(&snapshot);
+}
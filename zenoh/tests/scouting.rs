@@ -0,0 +1,122 @@
+//
+// Copyright (c) 2024 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+#![cfg(all(target_os = "linux", feature = "internal"))]
+use std::{
+    net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr},
+    time::Duration,
+};
+
+use zenoh::{
+    config::{Config, WhatAmI},
+    internal::{runtime::Runtime, ztimeout},
+};
+
+const TIMEOUT: Duration = Duration::from_secs(60);
+const SCOUT: Duration = Duration::from_secs(2);
+const LOCALHOST: IpAddr = IpAddr::V4(Ipv4Addr::LOCALHOST);
+
+fn multicast_config(address: &str, options: &[(&str, &str)]) -> Config {
+    let mut config = Config::default();
+    config
+        .insert_json5("scouting/multicast/address", &format!("\"{address}\""))
+        .unwrap();
+    config
+        .insert_json5("scouting/multicast/interface", "\"lo\"")
+        .unwrap();
+    for (key, value) in options {
+        config
+            .insert_json5(&format!("scouting/multicast/{key}"), value)
+            .unwrap();
+    }
+    config
+}
+
+#[test]
+fn scouting_multicast_invalid_options() {
+    let mut config = Config::default();
+    for (key, value) in [
+        ("ttl", "\"often\""),
+        ("loopback", "\"yes\""),
+        ("scope_id", "\"lo\""),
+        ("sources", "[\"host\"]"),
+        ("sources", "\"127.0.0.1\""),
+    ] {
+        assert!(
+            config
+                .insert_json5(&format!("scouting/multicast/{key}"), value)
+                .is_err(),
+            "{key}: {value}"
+        );
+    }
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn scouting_multicast_bind() {
+    zenoh_util::init_log_from_env_or("error");
+    let config = multicast_config(
+        "224.0.0.226:17530",
+        &[("ttl", "4"), ("loopback", "false"), ("scope_id", "1")],
+    );
+    let multicast = &config.scouting.multicast;
+    let addr: SocketAddr = "224.0.0.226:17530".parse().unwrap();
+    ztimeout!(Runtime::bind_mcast_port(&addr, &[LOCALHOST], multicast)).unwrap();
+    Runtime::bind_ucast_port(LOCALHOST, multicast).unwrap();
+    // The scope id selects the interface IPv6 packets are sent on
+    Runtime::bind_ucast_port(IpAddr::V6(Ipv6Addr::LOCALHOST), multicast).unwrap();
+    let addr: SocketAddr = "[ff02::1:9]:17530".parse().unwrap();
+    ztimeout!(Runtime::bind_mcast_port(&addr, &[], multicast)).unwrap();
+
+    // Source-specific multicast is only supported on IPv4
+    let config = multicast_config("232.0.0.226:17531", &[("sources", "[\"127.0.0.1\"]")]);
+    let multicast = &config.scouting.multicast;
+    let addr: SocketAddr = "232.0.0.226:17531".parse().unwrap();
+    ztimeout!(Runtime::bind_mcast_port(&addr, &[LOCALHOST], multicast)).unwrap();
+    let addr: SocketAddr = "[ff3e::1:9]:17531".parse().unwrap();
+    assert!(ztimeout!(Runtime::bind_mcast_port(&addr, &[], multicast)).is_err());
+    let config = multicast_config("232.0.0.226:17531", &[("sources", "[\"::1\"]")]);
+    let addr: SocketAddr = "232.0.0.226:17531".parse().unwrap();
+    assert!(ztimeout!(Runtime::bind_mcast_port(
+        &addr,
+        &[LOCALHOST],
+        &config.scouting.multicast
+    ))
+    .is_err());
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn scouting_multicast_source_specific() {
+    zenoh_util::init_log_from_env_or("error");
+    // The scouted peer only answers the scouts sent from its sources
+    for (sources, found) in [("[\"127.0.0.1\"]", true), ("[\"127.0.0.2\"]", false)] {
+        let mut config = multicast_config("232.0.0.227:17532", &[("sources", sources)]);
+        config
+            .insert_json5("listen/endpoints", "[\"tcp/127.0.0.1:17533\"]")
+            .unwrap();
+        config.set_mode(Some(WhatAmI::Peer)).unwrap();
+        let session = ztimeout!(zenoh::open(config)).unwrap();
+
+        let scout = ztimeout!(zenoh::scout(
+            WhatAmI::Peer,
+            multicast_config("232.0.0.227:17532", &[])
+        ))
+        .unwrap();
+        let hello = scout.recv_timeout(SCOUT).unwrap();
+        assert_eq!(hello.is_some(), found);
+        if let Some(hello) = hello {
+            assert_eq!(hello.zid(), session.zid());
+        }
+        scout.stop();
+        ztimeout!(session.close()).unwrap();
+    }
+}